@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
@@ -20,6 +21,12 @@ pub trait API: Sync + Send {
     /// environment
     async fn get_tools(&self) -> anyhow::Result<ToolsOverview>;
 
+    /// Provides the built-in tools exposed when Forge runs as an MCP server
+    async fn get_server_tools(&self) -> Result<Vec<ToolDefinition>>;
+
+    /// Executes a built-in tool on behalf of an external MCP client
+    async fn call_server_tool(&self, call: ToolCallFull) -> Result<ToolResult>;
+
     /// Provides a list of models available in the current environment
     async fn get_models(&self) -> Result<Vec<Model>>;
     /// Provides a list of agents available in the current environment
@@ -87,6 +94,15 @@ pub trait API: Sync + Send {
         conversation_id: &ConversationId,
     ) -> Result<CompactionResult>;
 
+    /// Replaces the messages from `start` to `end` (inclusive) in the context
+    /// of the given conversation with a summary and persists it.
+    async fn compact_conversation_range(
+        &self,
+        conversation_id: &ConversationId,
+        start: usize,
+        end: usize,
+    ) -> Result<CompactionResult>;
+
     /// Executes a shell command using the shell tool infrastructure
     async fn execute_shell_command(
         &self,
@@ -155,12 +171,43 @@ pub trait API: Sync + Send {
     /// Refresh MCP caches by fetching fresh data
     async fn reload_mcp(&self) -> Result<()>;
 
+    /// Renders a prompt template published by an MCP server
+    async fn get_mcp_prompt(
+        &self,
+        server: &ServerName,
+        name: &str,
+        arguments: BTreeMap<String, String>,
+    ) -> Result<String>;
+
+    /// Reports connection state, latency and tool counts of MCP servers
+    async fn get_mcp_status(&self) -> Result<Vec<McpServerStatus>>;
+
+    /// Starts the OAuth authorization flow for an MCP server
+    async fn init_mcp_auth(&self, server: &ServerName) -> Result<CodeRequest>;
+
+    /// Completes the OAuth authorization of an MCP server and saves its
+    /// credentials
+    async fn complete_mcp_auth(
+        &self,
+        server: &ServerName,
+        context: AuthContextResponse,
+    ) -> Result<()>;
+
+    /// Removes the stored OAuth credentials of an MCP server
+    async fn logout_mcp(&self, server: &ServerName) -> Result<()>;
+
     /// List of commands defined in .md file(s)
     async fn get_commands(&self) -> Result<Vec<Command>>;
 
     /// List of available skills
     async fn get_skills(&self) -> Result<Vec<Skill>>;
 
+    /// Lists the plans of the working directory, most recent first
+    async fn get_plans(&self) -> Result<Vec<PlanFile>>;
+
+    /// Parses the plan file at `path` into its steps
+    async fn get_plan(&self, path: &Path) -> Result<PlanFile>;
+
     /// Generate a shell command from natural language prompt
     async fn generate_command(&self, prompt: UserPrompt) -> Result<String>;
 
@@ -226,4 +273,40 @@ pub trait API: Sync + Send {
         &self,
         data_parameters: DataGenerationParameters,
     ) -> Result<BoxStream<'static, Result<serde_json::Value, anyhow::Error>>>;
+
+    /// Resolves the provider and model for a model name received by
+    /// `forge proxy`. The name may be prefixed with a configured provider id
+    /// as in `anthropic/claude-sonnet-4-5`.
+    async fn resolve_proxy_model(&self, model: &str) -> Result<(ProviderId, ModelId)>;
+
+    /// Streams a completion of `context` from a provider without running an
+    /// agent, retrying failures before the stream starts
+    async fn proxy_chat(
+        &self,
+        provider: ProviderId,
+        model: &ModelId,
+        context: Context,
+    ) -> Result<BoxStream<'static, Result<ChatCompletionMessage>>>;
+
+    /// Appends a request served by `forge proxy` to the request log
+    async fn log_proxy_exchange(&self, exchange: ProxyExchange) -> Result<()>;
+
+    /// Stores a new scheduled job and returns it with its assigned id
+    async fn add_schedule(&self, schedule: Schedule) -> Result<Schedule>;
+
+    /// Lists all scheduled jobs, oldest first
+    async fn get_schedules(&self) -> Result<Vec<Schedule>>;
+
+    /// Removes a scheduled job and its run history, returning false if it did
+    /// not exist
+    async fn remove_schedule(&self, id: ScheduleId) -> Result<bool>;
+
+    /// Records the start of a run of a scheduled job
+    async fn start_schedule_run(&self, run: ScheduleRun) -> Result<ScheduleRun>;
+
+    /// Records the outcome of a run started with `start_schedule_run`
+    async fn finish_schedule_run(&self, run: &ScheduleRun) -> Result<()>;
+
+    /// Lists up to `limit` runs of a scheduled job, most recent first
+    async fn get_schedule_runs(&self, id: ScheduleId, limit: usize) -> Result<Vec<ScheduleRun>>;
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -8,8 +9,8 @@ use forge_app::{
     AgentProviderResolver, AgentRegistry, AppConfigService, AuthService, CommandInfra,
     CommandLoaderService, ContextEngineService, ConversationService, DataGenerationApp,
     EnvironmentInfra, EnvironmentService, FileDiscoveryService, ForgeApp, GitApp, GrpcInfra,
-    McpConfigManager, McpService, ProviderAuthService, ProviderService, Services, User, UserUsage,
-    Walker,
+    McpConfigManager, McpService, PlanService, ProviderAuthService, ProviderService, ProxyApp,
+    Services, ToolServer, User, UserUsage, Walker,
 };
 use forge_domain::{Agent, InitAuth, LoginInfo, *};
 use forge_infra::ForgeInfra;
@@ -42,7 +43,22 @@ impl<A, F> ForgeAPI<A, F> {
 
 impl ForgeAPI<ForgeServices<ForgeRepo<ForgeInfra>>, ForgeRepo<ForgeInfra>> {
     pub fn init(restricted: bool, cwd: PathBuf) -> Self {
-        let infra = Arc::new(ForgeInfra::new(restricted, cwd));
+        Self::from_infra(ForgeInfra::new(restricted, cwd))
+    }
+
+    /// Initializes an API whose agents all use `model` from `provider`,
+    /// regardless of the user's configuration.
+    pub fn init_with_model(
+        restricted: bool,
+        cwd: PathBuf,
+        provider: ProviderId,
+        model: ModelId,
+    ) -> Self {
+        Self::from_infra(ForgeInfra::with_model(restricted, cwd, provider, model))
+    }
+
+    fn from_infra(infra: ForgeInfra) -> Self {
+        let infra = Arc::new(infra);
         let repo = Arc::new(ForgeRepo::new(infra.clone()));
         let app = Arc::new(ForgeServices::new(repo.clone()));
         ForgeAPI::new(app, repo)
@@ -57,7 +73,13 @@ impl ForgeAPI<ForgeServices<ForgeRepo<ForgeInfra>>, ForgeRepo<ForgeInfra>> {
 #[async_trait::async_trait]
 impl<
     A: Services,
-    F: CommandInfra + EnvironmentInfra + SkillRepository + AppConfigRepository + GrpcInfra,
+    F: CommandInfra
+        + EnvironmentInfra
+        + SkillRepository
+        + AppConfigRepository
+        + ProxyLogRepository
+        + ScheduleRepository
+        + GrpcInfra,
 > API for ForgeAPI<A, F>
 {
     async fn discover(&self) -> Result<Vec<File>> {
@@ -70,6 +92,14 @@ impl<
         self.app().list_tools().await
     }
 
+    async fn get_server_tools(&self) -> Result<Vec<ToolDefinition>> {
+        ToolServer::new(self.services.clone()).list().await
+    }
+
+    async fn call_server_tool(&self, call: ToolCallFull) -> Result<ToolResult> {
+        Ok(ToolServer::new(self.services.clone()).call(call).await)
+    }
+
     async fn get_models(&self) -> Result<Vec<Model>> {
         self.app().get_models().await
     }
@@ -140,6 +170,22 @@ impl<
             .await
     }
 
+    async fn compact_conversation_range(
+        &self,
+        conversation_id: &ConversationId,
+        start: usize,
+        end: usize,
+    ) -> anyhow::Result<CompactionResult> {
+        let agent_id = self
+            .services
+            .get_active_agent_id()
+            .await?
+            .unwrap_or_default();
+        self.app()
+            .compact_conversation_range(agent_id, conversation_id, start, end)
+            .await
+    }
+
     fn environment(&self) -> Environment {
         self.services.get_environment().clone()
     }
@@ -286,6 +332,41 @@ impl<
     async fn reload_mcp(&self) -> Result<()> {
         self.services.mcp_service().reload_mcp().await
     }
+
+    async fn get_mcp_prompt(
+        &self,
+        server: &ServerName,
+        name: &str,
+        arguments: BTreeMap<String, String>,
+    ) -> Result<String> {
+        self.services
+            .mcp_service()
+            .get_mcp_prompt(server, name, arguments)
+            .await
+    }
+
+    async fn get_mcp_status(&self) -> Result<Vec<McpServerStatus>> {
+        self.services.mcp_service().get_mcp_status().await
+    }
+
+    async fn init_mcp_auth(&self, server: &ServerName) -> Result<CodeRequest> {
+        self.services.mcp_service().init_mcp_auth(server).await
+    }
+
+    async fn complete_mcp_auth(
+        &self,
+        server: &ServerName,
+        context: AuthContextResponse,
+    ) -> Result<()> {
+        self.services
+            .mcp_service()
+            .complete_mcp_auth(server, context)
+            .await
+    }
+
+    async fn logout_mcp(&self, server: &ServerName) -> Result<()> {
+        self.services.mcp_service().logout_mcp(server).await
+    }
     async fn get_commands(&self) -> Result<Vec<Command>> {
         self.services.get_commands().await
     }
@@ -294,6 +375,14 @@ impl<
         self.infra.load_skills().await
     }
 
+    async fn get_plans(&self) -> Result<Vec<PlanFile>> {
+        self.services.plans().await
+    }
+
+    async fn get_plan(&self, path: &Path) -> Result<PlanFile> {
+        self.services.plan(path).await
+    }
+
     async fn generate_command(&self, prompt: UserPrompt) -> Result<String> {
         use forge_app::CommandGenerator;
         let generator = CommandGenerator::new(self.services.clone());
@@ -382,6 +471,49 @@ impl<
         self.services.get_default_provider().await
     }
 
+    async fn resolve_proxy_model(&self, model: &str) -> Result<(ProviderId, ModelId)> {
+        ProxyApp::new(self.services.clone()).resolve(model).await
+    }
+
+    async fn proxy_chat(
+        &self,
+        provider: ProviderId,
+        model: &ModelId,
+        context: Context,
+    ) -> Result<BoxStream<'static, Result<ChatCompletionMessage>>> {
+        ProxyApp::new(self.services.clone())
+            .chat(provider, model, context)
+            .await
+    }
+
+    async fn log_proxy_exchange(&self, exchange: ProxyExchange) -> Result<()> {
+        self.infra.append_proxy_exchange(exchange).await
+    }
+
+    async fn add_schedule(&self, schedule: Schedule) -> Result<Schedule> {
+        self.infra.add_schedule(schedule).await
+    }
+
+    async fn get_schedules(&self) -> Result<Vec<Schedule>> {
+        self.infra.get_schedules().await
+    }
+
+    async fn remove_schedule(&self, id: ScheduleId) -> Result<bool> {
+        self.infra.remove_schedule(id).await
+    }
+
+    async fn start_schedule_run(&self, run: ScheduleRun) -> Result<ScheduleRun> {
+        self.infra.start_schedule_run(run).await
+    }
+
+    async fn finish_schedule_run(&self, run: &ScheduleRun) -> Result<()> {
+        self.infra.finish_schedule_run(run).await
+    }
+
+    async fn get_schedule_runs(&self, id: ScheduleId, limit: usize) -> Result<Vec<ScheduleRun>> {
+        self.infra.get_schedule_runs(id, limit).await
+    }
+
     fn hydrate_channel(&self) -> Result<()> {
         self.infra.hydrate();
         Ok(())
@@ -82,6 +82,8 @@ impl<S: Services> AgentExecutor<S> {
                 ChatResponse::ToolCallEnd(_) => ctx.send(message).await?,
                 ChatResponse::RetryAttempt { .. } => ctx.send(message).await?,
                 ChatResponse::Interrupt { .. } => ctx.send(message).await?,
+                // The task list belongs to the sub-agent's own conversation
+                ChatResponse::TodoUpdate { .. } => {}
             }
         }
 
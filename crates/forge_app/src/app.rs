@@ -16,13 +16,14 @@ use crate::services::{
     AgentRegistry, CustomInstructionsService, ProviderAuthService, TemplateService,
 };
 use crate::set_conversation_id::SetConversationId;
+use crate::summary_generator::SummaryGenerator;
 use crate::system_prompt::SystemPrompt;
 use crate::tool_registry::ToolRegistry;
 use crate::tool_resolver::ToolResolver;
 use crate::user_prompt::UserPromptGenerator;
 use crate::{
-    AgentProviderResolver, ConversationService, EnvironmentService, FileDiscoveryService,
-    ProviderService, Services, WorkflowService,
+    AgentProviderResolver, CommandResolver, ConversationService, EnvironmentService,
+    FileDiscoveryService, ProviderService, Services, WorkflowService,
 };
 
 /// ForgeApp handles the core chat functionality by orchestrating various
@@ -49,10 +50,27 @@ impl<S: Services> ForgeApp<S> {
     pub async fn chat(
         &self,
         agent_id: AgentId,
-        chat: ChatRequest,
+        mut chat: ChatRequest,
     ) -> Result<MpscStream<Result<ChatResponse, anyhow::Error>>> {
         let services = self.services.clone();
 
+        // Resolve custom commands into their rendered prompt and pinned settings
+        let command = match chat.event.value.as_ref().and_then(EventValue::as_command) {
+            Some(invocation) => {
+                CommandResolver::new(services.clone())
+                    .resolve(invocation)
+                    .await?
+            }
+            None => None,
+        };
+        let agent_id = command
+            .as_ref()
+            .and_then(|command| command.agent.clone())
+            .unwrap_or(agent_id);
+        if let Some(command) = &command {
+            chat.event.value = Some(EventValue::text(&command.prompt));
+        }
+
         // Get the conversation for the chat request
         let conversation = services
             .find_conversation(&chat.conversation_id)
@@ -82,7 +100,7 @@ impl<S: Services> ForgeApp<S> {
         let agent_provider_resolver = AgentProviderResolver::new(services.clone());
 
         // Get agent and apply workflow config
-        let agent = self
+        let mut agent = self
             .services
             .get_agent(&agent_id)
             .await?
@@ -90,6 +108,16 @@ impl<S: Services> ForgeApp<S> {
             .apply_workflow_config(&workflow)
             .set_compact_model_if_none();
 
+        // Apply the model and tools pinned by a custom command
+        if let Some(command) = command {
+            if let Some(model) = command.model {
+                agent.model = model;
+            }
+            if let Some(tools) = command.tools {
+                agent.tools = Some(tools);
+            }
+        }
+
         let agent_provider = agent_provider_resolver
             .get_provider(Some(agent.id.clone()))
             .await?;
@@ -99,10 +127,11 @@ impl<S: Services> ForgeApp<S> {
             .refresh_provider_credential(agent_provider)
             .await?;
 
+        let native_search_supported = agent_provider.response == Some(ProviderResponse::Anthropic);
         let models = services.models(agent_provider).await?;
 
         // Get system and mcp tool definitions and resolve them for the agent
-        let all_tool_definitions = self.tool_registry.list().await?;
+        let all_tool_definitions = self.tool_registry.list(native_search_supported).await?;
         let tool_resolver = ToolResolver::new(all_tool_definitions);
         let tool_definitions: Vec<ToolDefinition> =
             tool_resolver.resolve(&agent).into_iter().cloned().collect();
@@ -185,6 +214,34 @@ impl<S: Services> ForgeApp<S> {
         &self,
         active_agent_id: AgentId,
         conversation_id: &ConversationId,
+    ) -> Result<CompactionResult> {
+        self.summarize_conversation(active_agent_id, conversation_id, None)
+            .await
+    }
+
+    /// Replaces the messages from `start` to `end` (inclusive) in the context
+    /// of the conversation with a summary, using the compaction settings of
+    /// the agent.
+    pub async fn compact_conversation_range(
+        &self,
+        active_agent_id: AgentId,
+        conversation_id: &ConversationId,
+        start: usize,
+        end: usize,
+    ) -> Result<CompactionResult> {
+        self.summarize_conversation(active_agent_id, conversation_id, Some((start, end)))
+            .await
+    }
+
+    /// Compacts the context of the conversation and persists it. The given
+    /// `range` is summarized if present, otherwise the range is selected by
+    /// the compaction strategy. Summarizing a range fails if the agent can't
+    /// compact, instead of leaving the context unchanged.
+    async fn summarize_conversation(
+        &self,
+        active_agent_id: AgentId,
+        conversation_id: &ConversationId,
+        range: Option<(usize, usize)>,
     ) -> Result<CompactionResult> {
         use crate::compact::Compactor;
 
@@ -214,6 +271,9 @@ impl<S: Services> ForgeApp<S> {
         let agent = self.services.get_agent(&active_agent_id).await?;
 
         let Some(agent) = agent else {
+            if range.is_some() {
+                return Err(crate::Error::AgentNotFound(active_agent_id).into());
+            }
             return Ok(CompactionResult::new(
                 original_token_count,
                 0,
@@ -223,11 +283,13 @@ impl<S: Services> ForgeApp<S> {
         };
 
         // Get compact config from the agent
-        let Some(compact) = agent
+        let agent = agent
             .apply_workflow_config(&workflow)
-            .set_compact_model_if_none()
-            .compact
-        else {
+            .set_compact_model_if_none();
+        let Some(compact) = agent.compact.clone() else {
+            if range.is_some() {
+                return Err(crate::Error::CompactionNotConfigured(agent.id).into());
+            }
             return Ok(CompactionResult::new(
                 original_token_count,
                 0,
@@ -238,7 +300,21 @@ impl<S: Services> ForgeApp<S> {
 
         // Apply compaction using the Compactor
         let environment = self.services.get_environment();
-        let compacted_context = Compactor::new(compact, environment).compact(context, true)?;
+        let generator = SummaryGenerator::new(
+            self.services.clone(),
+            compact.model.clone().unwrap_or_else(|| agent.model.clone()),
+            Some(agent.provider.clone()),
+        );
+        let compactor = Compactor::new(compact, environment).todos(conversation.todos.clone());
+        let compacted_context = match range {
+            Some((start, end)) => {
+                context.check_summary_range(start, end)?;
+                compactor
+                    .compact_range(context, (start, end), &generator)
+                    .await?
+            }
+            None => compactor.compact_with(context, true, &generator).await?,
+        };
 
         let compacted_messages = compacted_context.messages.len();
         let compacted_tokens = *compacted_context.token_count();
@@ -258,7 +334,13 @@ impl<S: Services> ForgeApp<S> {
     }
 
     pub async fn list_tools(&self) -> Result<ToolsOverview> {
-        self.tool_registry.tools_overview().await
+        let native_search_supported = AgentProviderResolver::new(self.services.clone())
+            .get_provider(None)
+            .await
+            .is_ok_and(|provider| provider.response == Some(ProviderResponse::Anthropic));
+        self.tool_registry
+            .tools_overview(native_search_supported)
+            .await
     }
 
     /// Gets available models for the default provider with automatic credential
@@ -0,0 +1,303 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use forge_domain::{
+    AgentId, Command, ModelId, PermissionOperation, Template, ToolName, UserCommand,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::{
+    CommandLoaderService, EnvironmentService, PolicyService, ShellService, TemplateEngine,
+};
+
+/// A custom command invocation resolved into the prompt to send, along with
+/// the agent, model and tools it pins for the turn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedCommand {
+    pub prompt: String,
+    pub agent: Option<AgentId>,
+    pub model: Option<ModelId>,
+    pub tools: Option<Vec<ToolName>>,
+}
+
+/// Resolves custom command invocations against their definitions.
+pub struct CommandResolver<S> {
+    services: Arc<S>,
+}
+
+impl<S: CommandLoaderService + ShellService + EnvironmentService + PolicyService>
+    CommandResolver<S>
+{
+    pub fn new(services: Arc<S>) -> Self {
+        Self { services }
+    }
+
+    /// Binds the invocation's arguments, renders the command prompt and
+    /// replaces every `` !`cmd` `` with the output of running `cmd`.
+    ///
+    /// Returns `None` when no custom command with the invoked name exists.
+    pub async fn resolve(
+        &self,
+        invocation: &UserCommand,
+    ) -> anyhow::Result<Option<ResolvedCommand>> {
+        let Some(command) = self
+            .services
+            .get_commands()
+            .await?
+            .into_iter()
+            .find(|command| command.name == invocation.name)
+        else {
+            return Ok(None);
+        };
+
+        let env = self.services.get_environment();
+        let prompt = RenderedPrompt::new(&command, &invocation.parameters, &env.shell)?;
+        let mut outputs = Vec::with_capacity(prompt.commands.len());
+        for shell in &prompt.commands {
+            outputs.push(self.execute(shell, &env.cwd).await?);
+        }
+
+        Ok(Some(ResolvedCommand {
+            prompt: prompt.render(outputs)?,
+            agent: command.agent,
+            model: command.model,
+            tools: command.tools,
+        }))
+    }
+
+    /// Runs an interpolated command, subject to the same policies as the shell
+    /// tool
+    async fn execute(&self, shell: &str, cwd: &Path) -> anyhow::Result<String> {
+        let operation = PermissionOperation::Execute {
+            command: shell.to_string(),
+            cwd: cwd.to_path_buf(),
+            message: format!("Execute shell command: {shell}"),
+        };
+        let decision = self.services.check_operation_permission(&operation).await?;
+        if !decision.allowed {
+            anyhow::bail!("Command `{shell}` is not allowed by the permission policies");
+        }
+
+        let result = self
+            .services
+            .execute(shell.to_string(), cwd.to_path_buf(), false, true, None)
+            .await?;
+        if !result.output.success() {
+            anyhow::bail!(
+                "Command `{shell}` failed while rendering the prompt: {}",
+                result.output.stderr.trim()
+            );
+        }
+        Ok(result.output.stdout.trim_end().to_string())
+    }
+}
+
+lazy_static! {
+    /// Matches shell interpolations written as !`command`
+    static ref SHELL_PATTERN: Regex = Regex::new(r"!`([^`]+)`").unwrap();
+}
+
+/// A command prompt whose shell interpolations have been rendered, waiting
+/// for their output.
+///
+/// Interpolations are found in the template as written, so argument values
+/// can never introduce a command, and every value rendered into a command is
+/// quoted for the shell.
+#[derive(Debug, PartialEq)]
+struct RenderedPrompt {
+    /// The prompt template with each interpolation replaced by a placeholder
+    template: String,
+    /// The interpolated commands with their arguments bound
+    commands: Vec<String>,
+    data: Map<String, Value>,
+}
+
+impl RenderedPrompt {
+    fn new(command: &Command, parameters: &[String], shell: &str) -> anyhow::Result<Self> {
+        let mut data = command.bind_arguments(parameters)?;
+        data.insert(
+            "parameters".to_string(),
+            Value::String(parameters.join(" ")),
+        );
+        let quoted = data
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    Value::String(value) => Value::String(quote(shell, value)?),
+                    value => value.clone(),
+                };
+                Ok((name.clone(), value))
+            })
+            .collect::<anyhow::Result<Map<_, _>>>()?;
+
+        let engine = TemplateEngine::default();
+        let raw = command.prompt.clone().unwrap_or_default();
+        let mut template = String::with_capacity(raw.len());
+        let mut commands = Vec::new();
+        let mut last = 0;
+        for capture in SHELL_PATTERN.captures_iter(&raw) {
+            let (Some(matched), Some(shell)) = (capture.get(0), capture.get(1)) else {
+                continue;
+            };
+            commands.push(engine.render_template(
+                Template::new(shell.as_str()),
+                &Value::Object(quoted.clone()),
+            )?);
+            template.push_str(&raw[last..matched.start()]);
+            template.push_str(&format!("{{{{{}}}}}", placeholder(commands.len() - 1)));
+            last = matched.end();
+        }
+        template.push_str(&raw[last..]);
+
+        Ok(Self { template, commands, data })
+    }
+
+    /// Renders the prompt with the output of each command in place of its
+    /// interpolation
+    fn render(mut self, outputs: Vec<String>) -> anyhow::Result<String> {
+        for (index, output) in outputs.into_iter().enumerate() {
+            self.data.insert(placeholder(index), Value::String(output));
+        }
+        TemplateEngine::default()
+            .render_template(Template::new(self.template), &Value::Object(self.data))
+    }
+}
+
+fn placeholder(index: usize) -> String {
+    format!("__shell_{index}")
+}
+
+/// Quotes a value so that `shell` passes it to the command as a single
+/// argument
+fn quote(shell: &str, value: &str) -> anyhow::Result<String> {
+    let name = shell
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(shell)
+        .to_lowercase();
+    match name.trim_end_matches(".exe") {
+        "cmd" => {
+            // cmd has no escape that works inside quotes, so refuse the
+            // characters it would still interpret
+            if value.contains(['"', '%', '!', '^', '\n', '\r']) {
+                anyhow::bail!("Argument `{value}` cannot be passed safely to {shell}");
+            }
+            Ok(format!("\"{value}\""))
+        }
+        "powershell" | "pwsh" => Ok(format!("'{}'", value.replace('\'', "''"))),
+        _ => Ok(format!("'{}'", value.replace('\'', r"'\''"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::{CommandArgument, CommandArgumentType};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn review_pr() -> Command {
+        Command::default()
+            .name("review-pr")
+            .prompt(
+                "Review PR #{{pr}}{{#if focus}} focusing on {{focus}}{{/if}}. !`gh pr diff {{pr}} {{focus}}`",
+            )
+            .arguments(vec![
+                CommandArgument {
+                    kind: CommandArgumentType::Number,
+                    required: true,
+                    ..CommandArgument::new("pr")
+                },
+                CommandArgument::new("focus"),
+            ])
+    }
+
+    #[test]
+    fn test_render_prompt_with_arguments() {
+        let fixture = review_pr();
+
+        let prompt =
+            RenderedPrompt::new(&fixture, &["1234".to_string(), "tests".to_string()], "bash")
+                .unwrap();
+        let actual = (
+            prompt.commands.clone(),
+            prompt.render(vec!["diff".to_string()]).unwrap(),
+        );
+
+        let expected = (
+            vec!["gh pr diff 1234 'tests'".to_string()],
+            "Review PR #1234 focusing on tests. diff".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_prompt_exposes_raw_parameters() {
+        let fixture = Command::default()
+            .name("fix-test")
+            .prompt("Fix {{parameters}}");
+
+        let actual = RenderedPrompt::new(&fixture, &["path::to::test".to_string()], "bash")
+            .unwrap()
+            .render(vec![])
+            .unwrap();
+
+        let expected = "Fix path::to::test";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_prompt_quotes_malicious_arguments() {
+        let fixture = review_pr();
+
+        let actual = RenderedPrompt::new(
+            &fixture,
+            &["1".to_string(), "x'; rm -rf ~; echo '".to_string()],
+            "/bin/bash",
+        )
+        .unwrap()
+        .commands;
+
+        let expected = vec![r"gh pr diff 1 'x'\''; rm -rf ~; echo '\'''".to_string()];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_prompt_never_runs_commands_from_arguments() {
+        let fixture = review_pr();
+
+        let prompt = RenderedPrompt::new(
+            &fixture,
+            &["1".to_string(), "!`touch pwned`".to_string()],
+            "bash",
+        )
+        .unwrap();
+        let actual = (
+            prompt.commands.len(),
+            prompt.render(vec!["diff".to_string()]).unwrap(),
+        );
+
+        let expected = (
+            1,
+            "Review PR #1 focusing on !`touch pwned`. diff".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_prompt_rejects_unsafe_cmd_arguments() {
+        let fixture = review_pr();
+
+        let actual = RenderedPrompt::new(
+            &fixture,
+            &["1".to_string(), "%PATH%".to_string()],
+            r"C:\Windows\System32\cmd.exe",
+        )
+        .is_err();
+
+        let expected = true;
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,20 +1,30 @@
 use forge_domain::{
-    Compact, CompactionStrategy, Context, ContextMessage, ContextSummary, Environment, Transformer,
+    Compact, CompactionMode, CompactionStrategy, Context, ContextMessage, ContextSummary,
+    Environment, Todo, Transformer,
 };
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::TemplateEngine;
+use crate::agent::AgentService;
+use crate::summary_generator::SummaryGenerator;
 use crate::transformers::SummaryTransformer;
 
 /// A service dedicated to handling context compaction.
 pub struct Compactor {
     compact: Compact,
     environment: Environment,
+    todos: Vec<Todo>,
 }
 
 impl Compactor {
     pub fn new(compact: Compact, environment: Environment) -> Self {
-        Self { compact, environment }
+        Self { compact, environment, todos: Vec::new() }
+    }
+
+    /// Sets the task list of the conversation, which every summary repeats so
+    /// that it survives compaction
+    pub fn todos(self, todos: Vec<Todo>) -> Self {
+        Self { todos, ..self }
     }
 
     /// Applies the standard compaction transformer pipeline to a context
@@ -36,8 +46,9 @@ impl Compactor {
 }
 
 impl Compactor {
-    /// Apply compaction to the context if requested.
-    pub fn compact(&self, context: Context, max: bool) -> anyhow::Result<Context> {
+    /// Returns the ranges of messages to compact, which are separated by
+    /// pinned messages
+    fn eviction_ranges(&self, context: &Context, max: bool) -> Vec<(usize, usize)> {
         let eviction = CompactionStrategy::evict(self.compact.eviction_window);
         let retention = CompactionStrategy::retain(self.compact.retention_window);
 
@@ -48,17 +59,96 @@ impl Compactor {
             eviction.min(retention)
         };
 
-        match strategy.eviction_range(&context) {
-            Some(sequence) => self.compress_single_sequence(context, sequence),
-            None => Ok(context),
+        strategy.eviction_ranges(context)
+    }
+
+    /// Apply compaction to the context if requested, summarizing the
+    /// compacted messages structurally.
+    pub fn compact(&self, mut context: Context, max: bool) -> anyhow::Result<Context> {
+        let sequences = self.eviction_ranges(&context, max);
+        if sequences.is_empty() {
+            return Ok(context);
+        }
+        // Later sequences go first so that the earlier ones keep their indices
+        for sequence in sequences.into_iter().rev() {
+            context = self.compress_single_sequence(context, sequence, None)?;
+        }
+        Ok(remove_droppable(context))
+    }
+
+    /// Apply compaction to the context if requested, asking `generator` for a
+    /// summary of the compacted messages when the configured mode uses the
+    /// model.
+    pub async fn compact_with<S: AgentService>(
+        &self,
+        mut context: Context,
+        max: bool,
+        generator: &SummaryGenerator<S>,
+    ) -> anyhow::Result<Context> {
+        if !self.compact.mode.unwrap_or_default().uses_model() {
+            return self.compact(context, max);
+        }
+        let sequences = self.eviction_ranges(&context, max);
+        if sequences.is_empty() {
+            return Ok(context);
         }
+        for sequence in sequences.into_iter().rev() {
+            let summary = self.summarize(&context, sequence, generator).await;
+            context = self.compress_single_sequence(context, sequence, summary)?;
+        }
+        Ok(remove_droppable(context))
+    }
+
+    /// Replaces the messages in `sequence` with a summary. The summary comes
+    /// from `generator` when the configured mode uses the model, falling back
+    /// to the structural summary if the model fails.
+    pub async fn compact_range<S: AgentService>(
+        &self,
+        context: Context,
+        sequence: (usize, usize),
+        generator: &SummaryGenerator<S>,
+    ) -> anyhow::Result<Context> {
+        let summary = match self.compact.mode.unwrap_or_default().uses_model() {
+            true => self.summarize(&context, sequence, generator).await,
+            false => None,
+        };
+        let context = self.compress_single_sequence(context, sequence, summary)?;
+        Ok(remove_droppable(context))
     }
 
-    /// Compress a single identified sequence of assistant messages.
+    /// Asks `generator` to summarize the messages in `sequence`, returning
+    /// `None` if the model fails
+    async fn summarize<S: AgentService>(
+        &self,
+        context: &Context,
+        sequence: (usize, usize),
+        generator: &SummaryGenerator<S>,
+    ) -> Option<String> {
+        let (start, end) = sequence;
+        let sequence_context = Context::default().messages(
+            context.messages[start..=end]
+                .iter()
+                .filter(|msg| !msg.is_droppable())
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+        generator
+            .generate(&sequence_context)
+            .await
+            .inspect_err(
+                |error| warn!(error = ?error, "Falling back to structural compaction summary"),
+            )
+            .ok()
+    }
+
+    /// Compress a single identified sequence of assistant messages. The
+    /// model's `summary` replaces the structural one in the `llm` mode and
+    /// precedes it in the `hybrid` mode.
     fn compress_single_sequence(
         &self,
         mut context: Context,
         sequence: (usize, usize),
+        summary: Option<String>,
     ) -> anyhow::Result<Context> {
         let (start, end) = sequence;
 
@@ -86,9 +176,13 @@ impl Compactor {
             "Created context compaction summary"
         );
 
+        let messages = match (&summary, self.compact.mode.unwrap_or_default()) {
+            (Some(_), CompactionMode::Llm) => Vec::new(),
+            _ => context_summary.messages,
+        };
         let summary = TemplateEngine::default().render(
             "forge-partial-summary-frame.md",
-            &serde_json::json!({"messages": context_summary.messages}),
+            &serde_json::json!({"summary": summary, "messages": messages, "todos": self.todos}),
         )?;
 
         // Extended thinking reasoning chain preservation
@@ -124,9 +218,6 @@ impl Compactor {
             std::iter::once(ContextMessage::user(summary, None).into()),
         );
 
-        // Remove all droppable messages from the context
-        context.messages.retain(|msg| !msg.is_droppable());
-
         // Inject preserved reasoning into first assistant message (if empty)
         if let Some(reasoning) = reasoning_details
             && let Some(ContextMessage::Text(msg)) = context
@@ -146,9 +237,18 @@ impl Compactor {
     }
 }
 
+/// Removes all droppable messages from the context unless pinned
+fn remove_droppable(mut context: Context) -> Context {
+    context
+        .messages
+        .retain(|msg| msg.pinned || !msg.is_droppable());
+    context
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
+    use std::sync::Arc;
 
     use forge_domain::MessageEntry;
     use pretty_assertions::assert_eq;
@@ -196,7 +296,9 @@ mod tests {
             .add_message(ContextMessage::user("M3", None))
             .add_message(ContextMessage::assistant("R3", None, None));
 
-        let actual = compactor.compress_single_sequence(context, (0, 3)).unwrap();
+        let actual = compactor
+            .compress_single_sequence(context, (0, 3), None)
+            .unwrap();
 
         // Verify only LAST reasoning_details were preserved
         let assistant_msg = actual
@@ -240,7 +342,9 @@ mod tests {
             .add_message(ContextMessage::user("M2", None))
             .add_message(ContextMessage::assistant("R2", None, None));
 
-        let context = compactor.compress_single_sequence(context, (0, 1)).unwrap();
+        let context = compactor
+            .compress_single_sequence(context, (0, 1), None)
+            .unwrap();
 
         // Verify first assistant has the reasoning
         let first_assistant = context
@@ -258,7 +362,9 @@ mod tests {
             .add_message(ContextMessage::user("M3", None))
             .add_message(ContextMessage::assistant("R3", None, None));
 
-        let context = compactor.compress_single_sequence(context, (0, 2)).unwrap();
+        let context = compactor
+            .compress_single_sequence(context, (0, 2), None)
+            .unwrap();
 
         // Verify reasoning didn't accumulate - should still be just 1 reasoning block
         let first_assistant = context
@@ -302,7 +408,9 @@ mod tests {
             .add_message(ContextMessage::user("M3", None))
             .add_message(ContextMessage::assistant("R3", None, None)); // Outside range
 
-        let actual = compactor.compress_single_sequence(context, (0, 3)).unwrap();
+        let actual = compactor
+            .compress_single_sequence(context, (0, 3), None)
+            .unwrap();
 
         // After compression: [U-summary, U3, A3]
         // The reasoning from R1 (non-empty) should be injected into A3
@@ -444,6 +552,188 @@ mod tests {
         insta::assert_yaml_snapshot!(compacted_context);
     }
 
+    /// Agent service answering every chat with a fixed response
+    struct MockAgentService {
+        response: anyhow::Result<String, String>,
+    }
+
+    #[async_trait::async_trait]
+    impl AgentService for MockAgentService {
+        async fn chat_agent(
+            &self,
+            _id: &forge_domain::ModelId,
+            _context: Context,
+            _provider_id: Option<forge_domain::ProviderId>,
+        ) -> forge_domain::ResultStream<forge_domain::ChatCompletionMessage, anyhow::Error>
+        {
+            let content = self.response.clone().map_err(anyhow::Error::msg)?;
+            let message = forge_domain::ChatCompletionMessage::assistant(
+                forge_domain::Content::full(content),
+            )
+            .finish_reason(forge_domain::FinishReason::Stop);
+            Ok(Box::pin(tokio_stream::iter(std::iter::once(Ok(message)))))
+        }
+
+        async fn call(
+            &self,
+            _agent: &forge_domain::Agent,
+            _context: &forge_domain::ToolCallContext,
+            _call: forge_domain::ToolCallFull,
+        ) -> forge_domain::ToolResult {
+            unimplemented!()
+        }
+
+        async fn update(&self, _conversation: forge_domain::Conversation) -> anyhow::Result<()> {
+            unimplemented!()
+        }
+    }
+
+    fn summary_generator(response: Result<&str, &str>) -> SummaryGenerator<MockAgentService> {
+        let service = MockAgentService {
+            response: response.map(str::to_string).map_err(str::to_string),
+        };
+        SummaryGenerator::new(Arc::new(service), "compact-model".into(), None)
+    }
+
+    fn summary_message(context: &Context) -> String {
+        context.messages[1]
+            .content()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn compaction_fixture() -> Context {
+        Context::default()
+            .add_message(ContextMessage::user("Use tokio, not async-std", None))
+            .add_message(ContextMessage::assistant(
+                "Switching the runtime",
+                None,
+                None,
+            ))
+            .add_message(ContextMessage::user("Now fix the tests", None))
+            .add_message(ContextMessage::assistant("Fixing", None, None))
+    }
+
+    #[tokio::test]
+    async fn test_compact_with_llm_mode_uses_model_summary() {
+        let compactor = Compactor::new(
+            Compact::new()
+                .mode(CompactionMode::Llm)
+                .retention_window(1_usize),
+            test_environment(),
+        );
+        let generator = summary_generator(Ok(
+            "<summary>### Constraints\n- Use tokio, not async-std</summary>",
+        ));
+
+        let actual = compactor
+            .compact_with(compaction_fixture(), true, &generator)
+            .await
+            .unwrap();
+
+        let summary = summary_message(&actual);
+        assert_eq!(actual.messages.len(), 3);
+        assert!(summary.contains("### Constraints\n- Use tokio, not async-std"));
+        assert!(!summary.contains("### 1."));
+    }
+
+    #[tokio::test]
+    async fn test_compact_with_keeps_task_list() {
+        use forge_domain::TodoStatus;
+
+        let compactor = Compactor::new(
+            Compact::new()
+                .mode(CompactionMode::Llm)
+                .retention_window(1_usize),
+            test_environment(),
+        )
+        .todos(vec![
+            Todo::new("Switch the runtime", TodoStatus::Done),
+            Todo::new("Fix the tests", TodoStatus::InProgress),
+        ]);
+        let generator = summary_generator(Ok("<summary>### Goals\n- Migrate runtime</summary>"));
+
+        let actual = compactor
+            .compact_with(compaction_fixture(), true, &generator)
+            .await
+            .unwrap();
+
+        let summary = summary_message(&actual);
+        assert!(summary.contains(
+            "## Task List\n\nThe task list kept with the `todo` tool, as of this summary:\n\n- [done] Switch the runtime\n- [in_progress] Fix the tests\n"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_compact_with_hybrid_mode_keeps_structural_summary() {
+        let compactor = Compactor::new(
+            Compact::new()
+                .mode(CompactionMode::Hybrid)
+                .retention_window(1_usize),
+            test_environment(),
+        );
+        let generator = summary_generator(Ok("<summary>### Goals\n- Migrate runtime</summary>"));
+
+        let actual = compactor
+            .compact_with(compaction_fixture(), true, &generator)
+            .await
+            .unwrap();
+
+        let summary = summary_message(&actual);
+        assert!(summary.contains("### Goals\n- Migrate runtime"));
+        assert!(summary.contains("### 1. Assistant\n\n````\nSwitching the runtime"));
+    }
+
+    #[tokio::test]
+    async fn test_compact_with_falls_back_to_structural_summary() {
+        let compactor = Compactor::new(
+            Compact::new()
+                .mode(CompactionMode::Llm)
+                .retention_window(1_usize),
+            test_environment(),
+        );
+        let fixture = compaction_fixture();
+        let expected = compactor.compact(fixture.clone(), true).unwrap();
+
+        let actual = compactor
+            .compact_with(fixture, true, &summary_generator(Err("rate limited")))
+            .await
+            .unwrap();
+
+        assert_eq!(summary_message(&actual), summary_message(&expected));
+    }
+
+    #[test]
+    fn test_compact_keeps_pinned_messages_between_summaries() {
+        let compactor =
+            Compactor::new(Compact::new().retention_window(1_usize), test_environment());
+        let mut fixture = compaction_fixture()
+            .add_message(ContextMessage::user("Run clippy too", None))
+            .add_message(ContextMessage::assistant("Running", None, None));
+        fixture.pin_message(2, true).unwrap();
+
+        let actual = compactor.compact(fixture, true).unwrap();
+
+        let actual = actual
+            .messages
+            .iter()
+            .map(|entry| {
+                (
+                    entry.pinned,
+                    entry.content().unwrap_or_default().contains("### 1."),
+                )
+            })
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (false, false),
+            (false, true),
+            (true, false),
+            (false, true),
+            (false, false),
+        ];
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_compaction_removes_droppable_messages() {
         use forge_domain::{ContextMessage, Role, TextMessage};
@@ -469,7 +759,11 @@ mod tests {
                 None,
             ));
 
-        let actual = compactor.compress_single_sequence(context, (0, 1)).unwrap();
+        let actual = remove_droppable(
+            compactor
+                .compress_single_sequence(context, (0, 1), None)
+                .unwrap(),
+        );
 
         // The compaction should remove the droppable message
         // Expected: [U-summary, U2, A2]
@@ -530,7 +824,9 @@ mod tests {
         assert_eq!(context.token_count(), TokenCount::Actual(50000));
 
         // Compact the sequence (first 4 messages, indices 0-3)
-        let compacted = compactor.compress_single_sequence(context, (0, 3)).unwrap();
+        let compacted = compactor
+            .compress_single_sequence(context, (0, 3), None)
+            .unwrap();
 
         // Verify we have exactly 3 messages after compaction
         assert_eq!(
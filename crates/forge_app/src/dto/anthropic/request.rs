@@ -1,5 +1,5 @@
 use derive_setters::Setters;
-use forge_domain::{ContextMessage, Image};
+use forge_domain::{ContextMessage, Document, Image};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Default, Setters)]
@@ -197,6 +197,9 @@ impl TryFrom<ContextMessage> for Message {
             ContextMessage::Image(img) => {
                 Message { content: vec![Content::from(img)], role: Role::User }
             }
+            ContextMessage::Document(document) => {
+                Message { content: vec![Content::from(document)], role: Role::User }
+            }
         })
     }
 }
@@ -218,6 +221,7 @@ impl Message {
                     .find_map(|(idx, content)| match content {
                         Content::Text { .. }
                         | Content::Image { .. }
+                        | Content::Document { .. }
                         | Content::ToolUse { .. }
                         | Content::ToolResult { .. } => Some(idx),
                         _ => None,
@@ -255,6 +259,28 @@ impl From<Image> for Content {
     }
 }
 
+impl From<Document> for Content {
+    fn from(value: Document) -> Self {
+        Content::Document {
+            source: DocumentSource {
+                type_: "base64".to_string(),
+                media_type: value.mime_type().to_string(),
+                data: value.data().to_string(),
+            },
+            title: Some(value.title().to_string()),
+            cache_control: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DocumentSource {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub media_type: String,
+    pub data: String,
+}
+
 #[derive(Serialize)]
 pub struct ImageSource {
     #[serde(rename = "type")]
@@ -275,6 +301,13 @@ pub enum Content {
         #[serde(skip_serializing_if = "Option::is_none")]
         cache_control: Option<CacheControl>,
     },
+    Document {
+        source: DocumentSource,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
     Text {
         text: String,
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -323,6 +356,9 @@ impl Content {
                 Content::ToolResult { tool_use_id, content, is_error, cache_control }
             }
             Content::Image { source, .. } => Content::Image { source, cache_control },
+            Content::Document { source, title, .. } => {
+                Content::Document { source, title, cache_control }
+            }
             // TODO: verify this Thinking variants don't support cache control
             Content::Thinking { signature, thinking } => Content::Thinking { signature, thinking },
         }
@@ -334,6 +370,7 @@ impl Content {
             Content::ToolUse { cache_control, .. } => cache_control.is_some(),
             Content::ToolResult { cache_control, .. } => cache_control.is_some(),
             Content::Image { cache_control, .. } => cache_control.is_some(),
+            Content::Document { cache_control, .. } => cache_control.is_some(),
             Content::Thinking { .. } => false,
         }
     }
@@ -426,22 +463,45 @@ impl From<forge_domain::ToolChoice> for ToolChoice {
 
 #[derive(Serialize)]
 pub struct ToolDefinition {
+    /// Type of a server tool run by Anthropic, unset for client tools
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_control: Option<CacheControl>,
-    pub input_schema: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_schema: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u32>,
+}
+
+impl ToolDefinition {
+    /// Anthropic's server-side web search tool, allowing up to `max_uses`
+    /// searches per request
+    pub fn web_search(max_uses: u32) -> Self {
+        ToolDefinition {
+            r#type: Some("web_search_20250305".to_string()),
+            name: "web_search".to_string(),
+            description: None,
+            cache_control: None,
+            input_schema: None,
+            max_uses: Some(max_uses),
+        }
+    }
 }
 
 impl TryFrom<forge_domain::ToolDefinition> for ToolDefinition {
     type Error = anyhow::Error;
     fn try_from(value: forge_domain::ToolDefinition) -> std::result::Result<Self, Self::Error> {
         Ok(ToolDefinition {
+            r#type: None,
             name: value.name.to_string(),
             description: Some(value.description),
             cache_control: None,
-            input_schema: serde_json::to_value(value.input_schema)?,
+            input_schema: Some(serde_json::to_value(value.input_schema)?),
+            max_uses: None,
         })
     }
 }
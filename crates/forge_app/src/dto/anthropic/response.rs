@@ -244,6 +244,10 @@ pub enum ContentBlock {
     InputJsonDelta {
         partial_json: String,
     },
+    /// Call to a tool run by Anthropic, such as the native web search
+    ServerToolUse {
+        id: String,
+    },
     Thinking {
         thinking: Option<String>,
         signature: Option<String>,
@@ -357,6 +361,16 @@ impl TryFrom<ContentBlock> for ChatCompletionMessage {
                     },
                 })
             }
+            ContentBlock::ServerToolUse { id } => {
+                // The call is executed by Anthropic. Starting a tool call without a name
+                // keeps the input deltas that follow from being appended to another
+                // call, and the call itself is dropped when tool calls are assembled.
+                ChatCompletionMessage::assistant(Content::part("")).add_tool_call(ToolCallPart {
+                    call_id: Some(ToolCallId::new(id)),
+                    name: None,
+                    arguments_part: "".to_string(),
+                })
+            }
             ContentBlock::InputJsonDelta { partial_json } => {
                 ChatCompletionMessage::assistant(Content::part("")).add_tool_call(ToolCallPart {
                     call_id: None,
@@ -467,6 +481,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_server_tool_use_is_dropped_from_tool_calls() {
+        let fixture = [
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"server_tool_use","id":"srvtoolu_01","name":"web_search","input":{}}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"query\": \"rust\"}"}}"#,
+            r#"{"type":"content_block_start","index":1,"content_block":{"type":"web_search_tool_result","tool_use_id":"srvtoolu_01","content":[]}}"#,
+            r#"{"type":"content_block_start","index":2,"content_block":{"type":"tool_use","id":"toolu_01","name":"read","input":{}}}"#,
+            r#"{"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":"{\"path\": \"a.rs\"}"}}"#,
+        ];
+
+        let parts = fixture
+            .iter()
+            .map(|event| serde_json::from_str::<EventData>(event).unwrap())
+            .map(|event| ChatCompletionMessage::try_from(event).unwrap())
+            .flat_map(|message| message.tool_calls)
+            .filter_map(|call| match call {
+                forge_domain::ToolCall::Part(part) => Some(part),
+                forge_domain::ToolCall::Full(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let actual = forge_domain::ToolCallFull::try_from_parts(&parts).unwrap();
+
+        let expected = vec![
+            forge_domain::ToolCallFull::new("read")
+                .call_id("toolu_01")
+                .arguments(forge_domain::ToolCallArguments::from_json(
+                    r#"{"path": "a.rs"}"#,
+                )),
+        ];
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_model_deser() {
         let input = r#"{
@@ -1,9 +1,11 @@
 mod auth_system_message;
 mod drop_invalid_toolcalls;
+mod native_web_search;
 mod reasoning_transform;
 mod set_cache;
 
 pub use auth_system_message::AuthSystemMessage;
 pub use drop_invalid_toolcalls::DropInvalidToolUse;
+pub use native_web_search::NativeWebSearch;
 pub use reasoning_transform::ReasoningTransform;
 pub use set_cache::SetCache;
@@ -0,0 +1,56 @@
+use forge_domain::Transformer;
+
+use crate::dto::anthropic::{Request, ToolDefinition};
+
+/// Number of searches Anthropic may run for a single request
+const MAX_USES: u32 = 5;
+
+/// Transformer that replaces the `web_search` client tool with Anthropic's
+/// server-side web search tool, so that searches are run by the provider.
+pub struct NativeWebSearch;
+
+impl Transformer for NativeWebSearch {
+    type Value = Request;
+
+    fn transform(&mut self, mut request: Self::Value) -> Self::Value {
+        for tool in request.tools.iter_mut() {
+            // The server tool shares the name of the client tool it replaces
+            if tool.r#type.is_none() && tool.name == "web_search" {
+                *tool = ToolDefinition::web_search(MAX_USES);
+            }
+        }
+
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::{Context, ToolCatalog, ToolDefinition as DomainToolDefinition};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    fn tool(name: &str) -> DomainToolDefinition {
+        ToolCatalog::iter()
+            .find(|tool| tool.to_string() == name)
+            .unwrap()
+            .definition()
+    }
+
+    #[test]
+    fn test_replaces_web_search_with_server_tool() {
+        let fixture = Context::default().tools(vec![tool("fetch"), tool("web_search")]);
+
+        let actual = NativeWebSearch.transform(Request::try_from(fixture).unwrap());
+
+        let actual = serde_json::to_value(&actual.tools).unwrap();
+        assert_eq!(actual[0]["name"], json!("fetch"));
+        assert_eq!(
+            actual[1],
+            json!({"type": "web_search_20250305", "name": "web_search", "max_uses": 5})
+        );
+    }
+}
@@ -445,6 +445,18 @@ impl From<ContextMessage> for Message {
                     reasoning_opaque: None,
                 }
             }
+            // Documents are sent as their text rendering since native document
+            // input is not part of the chat completions API
+            ContextMessage::Document(document) => Message {
+                role: Role::User,
+                content: Some(MessageContent::Text(document.text().clone())),
+                name: None,
+                tool_call_id: None,
+                tool_calls: None,
+                reasoning_details: None,
+                reasoning_text: None,
+                reasoning_opaque: None,
+            },
         }
     }
 }
@@ -19,6 +19,14 @@ pub enum Error {
         supported_tools: String,
     },
 
+    #[error("Tool '{0}' was denied by the configured permission policies")]
+    PermissionDenied(ToolName),
+
+    #[error(
+        "Tool '{0}' needs confirmation under the configured permission policies, which external clients cannot give. Allow it in the permissions file to use it."
+    )]
+    ConfirmationRequired(ToolName),
+
     #[error("Empty tool response")]
     EmptyToolResponse,
 
@@ -28,6 +36,9 @@ pub enum Error {
     #[error("Agent '{0}' not found")]
     AgentNotFound(forge_domain::AgentId),
 
+    #[error("Agent '{0}' has no compaction settings to summarize messages with")]
+    CompactionNotConfigured(forge_domain::AgentId),
+
     #[error("No active provider configured")]
     NoActiveProvider,
 
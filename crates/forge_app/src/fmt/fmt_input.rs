@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use forge_domain::{ChatResponseContent, Environment, TitleFormat, ToolCatalog};
+use forge_domain::{ChatResponseContent, Environment, SymbolQuery, TitleFormat, ToolCatalog};
 
 use crate::fmt::content::FormatContent;
 use crate::utils::format_display_path;
@@ -60,6 +60,30 @@ impl FormatContent for ToolCatalog {
                 };
                 Some(TitleFormat::debug(title).into())
             }
+            ToolCatalog::AstSearch(input) => {
+                let formatted_dir = display_path_for(&input.path);
+                let title = match &input.file_pattern {
+                    Some(pattern) => format!("AST Search in '{pattern}' files at {formatted_dir}"),
+                    None => format!("AST Search at {formatted_dir}"),
+                };
+                Some(TitleFormat::debug(title).sub_title(&input.query).into())
+            }
+            ToolCatalog::Symbols(input) => {
+                let target = match input.query {
+                    SymbolQuery::Outline => display_path_for(&input.target),
+                    _ => input.target.clone(),
+                };
+                Some(
+                    TitleFormat::debug(format!("Symbols [{}]", input.query))
+                        .sub_title(target)
+                        .into(),
+                )
+            }
+            ToolCatalog::Diagnostics(input) => Some(
+                TitleFormat::debug("Diagnostics")
+                    .sub_title(display_path_for(&input.path))
+                    .into(),
+            ),
             ToolCatalog::SemSearch(input) => {
                 let pairs: Vec<_> = input
                     .queries
@@ -96,12 +120,29 @@ impl FormatContent for ToolCatalog {
             ToolCatalog::Fetch(input) => {
                 Some(TitleFormat::debug("GET").sub_title(&input.url).into())
             }
+            ToolCatalog::WebSearch(input) => Some(
+                TitleFormat::debug("Web Search")
+                    .sub_title(&input.query)
+                    .into(),
+            ),
             ToolCatalog::Followup(input) => Some(
                 TitleFormat::debug("Follow-up")
                     .sub_title(&input.question)
                     .into(),
             ),
             ToolCatalog::Plan(_) => None,
+            ToolCatalog::PlanStatus(input) => Some(
+                TitleFormat::debug("Plan Status")
+                    .sub_title(display_path_for(&input.path))
+                    .into(),
+            ),
+            ToolCatalog::PlanUpdateStep(input) => Some(
+                TitleFormat::debug(format!("Plan Step {} {}", input.step, input.status))
+                    .sub_title(display_path_for(&input.path))
+                    .into(),
+            ),
+            // Rendered as a live checklist by the UI
+            ToolCatalog::Todo(_) => None,
             ToolCatalog::Skill(input) => Some(
                 TitleFormat::debug("Skill")
                     .sub_title(input.name.to_lowercase())
@@ -1,9 +1,9 @@
-use forge_display::DiffFormat;
+use forge_display::{DiffFormat, GrepFormat};
 use forge_domain::{ChatResponseContent, Environment, TitleFormat};
 
 use crate::fmt::content::FormatContent;
 use crate::operation::ToolOperation;
-use crate::utils::format_display_path;
+use crate::utils::{format_display_path, format_match};
 
 impl FormatContent for ToolOperation {
     fn to_content(&self, env: &Environment) -> Option<ChatResponseContent> {
@@ -22,6 +22,16 @@ impl FormatContent for ToolOperation {
             }
             ToolOperation::FsRemove { input: _, output: _ } => None,
             ToolOperation::FsSearch { input: _, output: _ } => None,
+            ToolOperation::AstSearch { input: _, output } => {
+                let lines = output
+                    .iter()
+                    .map(|matched| format_match(&matched.to_match(), &env.cwd))
+                    .collect::<Vec<_>>();
+                (!lines.is_empty())
+                    .then(|| ChatResponseContent::PlainText(GrepFormat::new(lines).format()))
+            }
+            ToolOperation::Symbols { input: _, output: _ } => None,
+            ToolOperation::Diagnostics { input: _, output: _ } => None,
             ToolOperation::CodebaseSearch { output: _ } => None,
             ToolOperation::FsPatch { input: _, output } => Some(ChatResponseContent::PlainText(
                 DiffFormat::format(&output.before, &output.after)
@@ -30,6 +40,7 @@ impl FormatContent for ToolOperation {
             )),
             ToolOperation::FsUndo { input: _, output: _ } => None,
             ToolOperation::NetFetch { input: _, output: _ } => None,
+            ToolOperation::WebSearch { input: _, output: _ } => None,
             ToolOperation::Shell { output: _ } => None,
             ToolOperation::FollowUp { output: _ } => None,
             ToolOperation::PlanCreate { input: _, output } => Some({
@@ -39,6 +50,9 @@ impl FormatContent for ToolOperation {
                 ));
                 title.into()
             }),
+            ToolOperation::PlanStatus { input: _, output: _ } => None,
+            ToolOperation::PlanUpdateStep { input: _, output: _ } => None,
+            ToolOperation::Todo { input: _ } => None,
             ToolOperation::Skill { input: _, output: _ } => None,
         }
     }
@@ -58,8 +72,8 @@ mod tests {
     // ContentFormat is now ChatResponseContent
     use crate::operation::ToolOperation;
     use crate::{
-        Content, FsCreateOutput, FsRemoveOutput, FsUndoOutput, HttpResponse, Match, MatchResult,
-        PatchOutput, ReadOutput, ResponseContext, SearchResult, ShellOutput,
+        AstMatch, Content, FsCreateOutput, FsRemoveOutput, FsUndoOutput, HttpResponse, Match,
+        MatchResult, PatchOutput, ReadOutput, ResponseContext, SearchResult, ShellOutput,
     };
 
     // ContentFormat methods are now implemented in ChatResponseContent
@@ -302,6 +316,30 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_ast_search_with_matches() {
+        let fixture = ToolOperation::AstSearch {
+            input: forge_domain::AstSearch {
+                path: "/home/user/project".to_string(),
+                query: "(impl_item) @match".to_string(),
+                ..Default::default()
+            },
+            output: vec![AstMatch {
+                path: "/home/user/project/src/lib.rs".to_string(),
+                line_number: 7,
+                symbol: Some("mod client".to_string()),
+                line: "impl Display for Client {".to_string(),
+            }],
+        };
+        let env = fixture_environment().cwd(PathBuf::from("/home/user/project"));
+
+        let actual = fixture.to_content(&env).unwrap();
+        let actual = strip_ansi_codes(actual.as_str()).to_string();
+        let expected = "src/lib.rs\n7: [mod client] impl Display for Client {\n";
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_fs_patch_success() {
         let after_content = "Hello universe\nThis is a test\nNew line";
@@ -376,6 +414,7 @@ mod tests {
             input: forge_domain::NetFetch {
                 url: "https://example.com".to_string(),
                 raw: Some(false),
+                ..Default::default()
             },
             output: HttpResponse {
                 content: "# Example Website\n\nThis is content.".to_string(),
@@ -398,6 +437,7 @@ mod tests {
             input: forge_domain::NetFetch {
                 url: "https://example.com/notfound".to_string(),
                 raw: Some(true),
+                ..Default::default()
             },
             output: HttpResponse {
                 content: "Not Found".to_string(),
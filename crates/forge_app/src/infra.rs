@@ -1,15 +1,17 @@
 use std::collections::BTreeMap;
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use anyhow::Result;
 use bytes::Bytes;
 use forge_domain::{
-    AuthCodeParams, CommandOutput, Environment, FileInfo, McpServerConfig, OAuthConfig,
-    OAuthTokenResponse, ToolDefinition, ToolName, ToolOutput,
+    AuthCodeParams, CommandOutput, Diagnostic, Environment, FileInfo, LspServerConfig, McpPrompt,
+    McpResource, McpResourceContent, McpServerConfig, OAuthConfig, OAuthTokenResponse,
+    ToolDefinition, ToolName, ToolOutput,
 };
-use reqwest::Response;
 use reqwest::header::HeaderMap;
+use reqwest::{Method, Response};
 use reqwest_eventsource::EventSource;
 use serde::de::DeserializeOwned;
 use url::Url;
@@ -97,6 +99,7 @@ pub trait FileInfoInfra: Send + Sync {
     async fn is_file(&self, path: &Path) -> anyhow::Result<bool>;
     async fn exists(&self, path: &Path) -> anyhow::Result<bool>;
     async fn file_size(&self, path: &Path) -> anyhow::Result<u64>;
+    async fn modified(&self, path: &Path) -> anyhow::Result<SystemTime>;
 }
 
 #[async_trait::async_trait]
@@ -123,6 +126,15 @@ pub trait CommandInfra: Send + Sync {
         working_dir: PathBuf,
         env_vars: Option<Vec<String>>,
     ) -> anyhow::Result<std::process::ExitStatus>;
+
+    /// Runs a program silently without a shell, so that every argument
+    /// reaches it verbatim on every platform
+    async fn execute_program(
+        &self,
+        program: &str,
+        args: Vec<String>,
+        working_dir: PathBuf,
+    ) -> anyhow::Result<CommandOutput>;
 }
 
 #[async_trait::async_trait]
@@ -168,6 +180,24 @@ pub trait McpClientInfra: Clone + Send + Sync + 'static {
         tool_name: &ToolName,
         input: serde_json::Value,
     ) -> anyhow::Result<ToolOutput>;
+
+    /// Lists the resources published by the server. Servers that do not
+    /// support resources return an empty list.
+    async fn list_resources(&self) -> anyhow::Result<Vec<McpResource>>;
+
+    /// Reads the contents of a resource
+    async fn read_resource(&self, uri: &str) -> anyhow::Result<Vec<McpResourceContent>>;
+
+    /// Lists the prompt templates published by the server. Servers that do not
+    /// support prompts return an empty list.
+    async fn list_prompts(&self) -> anyhow::Result<Vec<McpPrompt>>;
+
+    /// Renders a prompt template into the text of its messages
+    async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: BTreeMap<String, String>,
+    ) -> anyhow::Result<String>;
 }
 
 #[async_trait::async_trait]
@@ -179,6 +209,38 @@ pub trait McpServerInfra: Send + Sync + 'static {
         env_vars: &BTreeMap<String, String>,
     ) -> anyhow::Result<Self::Client>;
 }
+
+/// Connection to a running language server
+#[async_trait::async_trait]
+pub trait LspClientInfra: Send + Sync + 'static {
+    /// Opens or updates the document at `path` with `content` and waits for
+    /// the diagnostics the server publishes for it. Returns the last known
+    /// diagnostics if none are published within `timeout`.
+    async fn diagnostics(
+        &self,
+        path: &Path,
+        content: &str,
+        timeout: std::time::Duration,
+    ) -> anyhow::Result<Vec<Diagnostic>>;
+
+    /// Returns false once the server process has exited
+    fn is_running(&self) -> bool;
+}
+
+#[async_trait::async_trait]
+pub trait LspServerInfra: Send + Sync + 'static {
+    type Client: LspClientInfra;
+
+    /// Spawns the language server and initializes it for the workspace at
+    /// `root`
+    async fn start(
+        &self,
+        language_id: &str,
+        config: &LspServerConfig,
+        root: &Path,
+    ) -> anyhow::Result<Self::Client>;
+}
+
 /// Service for walking filesystem directories
 #[async_trait::async_trait]
 pub trait WalkerInfra: Send + Sync {
@@ -194,6 +256,18 @@ pub trait HttpInfra: Send + Sync + 'static {
     async fn http_post(&self, url: &Url, body: bytes::Bytes) -> anyhow::Result<Response>;
     async fn http_delete(&self, url: &Url) -> anyhow::Result<Response>;
 
+    /// Sends a request with an arbitrary method using only the given headers.
+    /// Unlike the other methods, non-success statuses are returned rather
+    /// than treated as errors, and redirects are returned rather than
+    /// followed, so that callers decide which headers the new location gets.
+    async fn http_request(
+        &self,
+        method: Method,
+        url: &Url,
+        headers: HeaderMap,
+        body: Option<Bytes>,
+    ) -> anyhow::Result<Response>;
+
     /// Posts JSON data and returns a server-sent events stream
     async fn http_eventsource(
         &self,
@@ -6,6 +6,7 @@ mod apply_tunable_parameters;
 mod authenticator;
 mod changed_files;
 mod command_generator;
+mod command_resolver;
 mod compact;
 mod data_gen;
 pub mod dto;
@@ -20,16 +21,19 @@ mod operation;
 mod orch;
 #[cfg(test)]
 mod orch_spec;
+mod proxy;
 mod retry;
 mod search_dedup;
 mod services;
 mod set_conversation_id;
+mod summary_generator;
 pub mod system_prompt;
 mod template_engine;
 mod title_generator;
 mod tool_executor;
 mod tool_registry;
 mod tool_resolver;
+mod tool_server;
 mod transformers;
 mod truncation;
 mod user;
@@ -41,13 +45,16 @@ pub use agent::*;
 pub use agent_provider_resolver::*;
 pub use app::*;
 pub use command_generator::*;
+pub use command_resolver::*;
 pub use data_gen::*;
 pub use error::*;
 pub use git_app::*;
 pub use infra::*;
+pub use proxy::*;
 pub use services::*;
 pub use template_engine::*;
 pub use tool_resolver::*;
+pub use tool_server::*;
 pub use user::*;
 pub use utils::compute_hash;
 pub use walker::*;
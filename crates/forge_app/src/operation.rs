@@ -6,19 +6,21 @@ use console::strip_ansi_codes;
 use derive_setters::Setters;
 use forge_display::DiffFormat;
 use forge_domain::{
-    CodebaseSearchResults, Environment, FSPatch, FSRead, FSRemove, FSSearch, FSUndo, FSWrite,
-    FileOperation, LineNumbers, Metrics, NetFetch, PlanCreate, ToolKind,
+    AstSearch, CodebaseSearchResults, Diagnostic, Diagnostics, Environment, FSPatch, FSRead,
+    FSRemove, FSSearch, FSUndo, FSWrite, FileOperation, LineNumbers, Metrics, NetFetch, PlanCreate,
+    PlanFile, PlanStatus, PlanUpdateStep, SymbolQuery, Symbols, TodoStatus, TodoWrite, ToolKind,
+    WebSearch, WebSearchResult,
 };
 use forge_template::Element;
 
 use crate::truncation::{
-    Stderr, Stdout, TruncationMode, truncate_fetch_content, truncate_search_output,
-    truncate_shell_output,
+    Stderr, Stdout, TruncatedSearchOutput, TruncationMode, truncate_fetch_content,
+    truncate_search_output, truncate_shell_output,
 };
 use crate::utils::{compute_hash, format_display_path};
 use crate::{
-    FsCreateOutput, FsRemoveOutput, FsUndoOutput, HttpResponse, PatchOutput, PlanCreateOutput,
-    ReadOutput, ResponseContext, SearchResult, ShellOutput,
+    AstMatch, FsCreateOutput, FsRemoveOutput, FsUndoOutput, HttpResponse, Match, PatchOutput,
+    PlanCreateOutput, ReadOutput, ResponseContext, SearchResult, ShellOutput, SymbolsOutput,
 };
 
 #[derive(Debug, Default, Setters)]
@@ -49,6 +51,18 @@ pub enum ToolOperation {
         input: FSSearch,
         output: Option<SearchResult>,
     },
+    AstSearch {
+        input: AstSearch,
+        output: Vec<AstMatch>,
+    },
+    Symbols {
+        input: Symbols,
+        output: SymbolsOutput,
+    },
+    Diagnostics {
+        input: Diagnostics,
+        output: Option<Vec<Diagnostic>>,
+    },
     CodebaseSearch {
         output: CodebaseSearchResults,
     },
@@ -64,6 +78,10 @@ pub enum ToolOperation {
         input: NetFetch,
         output: HttpResponse,
     },
+    WebSearch {
+        input: WebSearch,
+        output: Vec<WebSearchResult>,
+    },
     Shell {
         output: ShellOutput,
     },
@@ -74,6 +92,18 @@ pub enum ToolOperation {
         input: PlanCreate,
         output: PlanCreateOutput,
     },
+    PlanStatus {
+        #[allow(dead_code)]
+        input: PlanStatus,
+        output: PlanFile,
+    },
+    PlanUpdateStep {
+        input: PlanUpdateStep,
+        output: PlanFile,
+    },
+    Todo {
+        input: TodoWrite,
+    },
     Skill {
         #[allow(dead_code)]
         input: forge_domain::SkillFetch,
@@ -189,6 +219,87 @@ fn create_stream_element<T: StreamElement>(
 
     Some(elem)
 }
+/// Formats the 1-based range of search result lines being displayed
+fn display_lines(output: &TruncatedSearchOutput) -> String {
+    if output.start < output.end {
+        format!("{}-{}", output.start.saturating_add(1), output.end)
+    } else {
+        format!("{}-{}", output.start, output.end)
+    }
+}
+
+/// Applies the search tools' pagination and size limits to `matches`,
+/// describing the displayed range on `elm`. Returns the element along with
+/// the displayed lines.
+fn paginate_matches(
+    elm: Element,
+    matches: &[Match],
+    start_index: Option<i32>,
+    max_search_lines: Option<i32>,
+    search_dir: &Path,
+    env: &Environment,
+) -> (Element, String) {
+    let max_lines = min(
+        env.max_search_lines,
+        max_search_lines.unwrap_or(i32::MAX) as usize,
+    );
+    let start_index = start_index.unwrap_or(1).max(1) - 1;
+    let truncated_output = truncate_search_output(
+        matches,
+        start_index as usize,
+        max_lines,
+        env.max_search_result_bytes,
+        search_dir,
+    );
+
+    let elm = elm
+        .attr("max_bytes_allowed", env.max_search_result_bytes)
+        .attr("total_lines", truncated_output.total)
+        .attr("display_lines", display_lines(&truncated_output))
+        .attr_if_some(
+            "reason",
+            truncation_reason(&truncated_output.strategy, max_lines, env),
+        );
+    (elm, truncated_output.data.join("\n"))
+}
+
+/// Explains why search results were truncated, if they were
+fn truncation_reason(mode: &TruncationMode, max_lines: usize, env: &Environment) -> Option<String> {
+    match mode {
+        TruncationMode::Byte => Some(format!(
+            "Results truncated due to exceeding the {} bytes size limit. Please use a more specific search pattern",
+            env.max_search_result_bytes
+        )),
+        TruncationMode::Line => Some(format!(
+            "Results truncated due to exceeding the {max_lines} lines limit. Please use a more specific search pattern"
+        )),
+        TruncationMode::Full => None,
+    }
+}
+
+/// Lists the steps of a plan along with its progress and the step to work on
+fn plan_element(plan: &PlanFile) -> Element {
+    let progress = plan.progress();
+    let mut elm = Element::new("plan")
+        .attr("path", plan.path.display().to_string())
+        .attr("done", progress.done)
+        .attr("total", progress.total);
+    elm = match plan.next_step() {
+        Some(step) => elm.attr("next_step", step.number),
+        None if plan.is_complete() => elm.attr("complete", true),
+        None => elm,
+    };
+    if plan.steps.is_empty() {
+        return elm.text("The plan has no `- [ ]` checkboxes");
+    }
+    elm.append(plan.steps.iter().map(|step| {
+        Element::new("step")
+            .attr("number", step.number)
+            .attr("status", step.status)
+            .text(&step.text)
+    }))
+}
+
 impl ToolOperation {
     pub fn into_tool_output(
         self,
@@ -291,39 +402,18 @@ impl ToolOperation {
                         search_dir,
                     );
 
-                    let display_lines = if truncated_output.start < truncated_output.end {
-                        // 1 Line based indexing
-                        let new_start = truncated_output.start.saturating_add(1);
-                        format!("{}-{}", new_start, truncated_output.end)
-                    } else {
-                        format!("{}-{}", truncated_output.start, truncated_output.end)
-                    };
-
                     let mut elm = Element::new("search_results")
                         .attr("path", &input.path)
                         .attr("max_bytes_allowed", env.max_search_result_bytes)
                         .attr("total_lines", truncated_output.total)
-                        .attr("display_lines", display_lines);
+                        .attr("display_lines", display_lines(&truncated_output));
 
                     elm = elm.attr_if_some("regex", input.regex);
                     elm = elm.attr_if_some("file_pattern", input.file_pattern);
-
-                    match truncated_output.strategy {
-                        TruncationMode::Byte => {
-                            let reason = format!(
-                                "Results truncated due to exceeding the {} bytes size limit. Please use a more specific search pattern",
-                                env.max_search_result_bytes
-                            );
-                            elm = elm.attr("reason", reason);
-                        }
-                        TruncationMode::Line => {
-                            let reason = format!(
-                                "Results truncated due to exceeding the {max_lines} lines limit. Please use a more specific search pattern"
-                            );
-                            elm = elm.attr("reason", reason);
-                        }
-                        TruncationMode::Full => {}
-                    };
+                    elm = elm.attr_if_some(
+                        "reason",
+                        truncation_reason(&truncated_output.strategy, max_lines, env),
+                    );
                     elm = elm.cdata(truncated_output.data.join("\n"));
 
                     forge_domain::ToolOutput::text(elm)
@@ -336,6 +426,80 @@ impl ToolOperation {
                     forge_domain::ToolOutput::text(elm)
                 }
             },
+            ToolOperation::AstSearch { input, output } => {
+                let mut elm = Element::new("ast_search_results").attr("path", &input.path);
+                let mut data = None;
+
+                if !output.is_empty() {
+                    let matches = output.iter().map(AstMatch::to_match).collect::<Vec<_>>();
+                    let (paginated, lines) = paginate_matches(
+                        elm,
+                        &matches,
+                        input.start_index,
+                        input.max_search_lines,
+                        Path::new(&input.path),
+                        env,
+                    );
+                    elm = paginated;
+                    data = Some(lines);
+                }
+
+                elm = elm
+                    .attr("query", &input.query)
+                    .attr_if_some("inside", input.inside)
+                    .attr_if_some("file_pattern", input.file_pattern);
+                if let Some(data) = data {
+                    elm = elm.cdata(data);
+                }
+
+                forge_domain::ToolOutput::text(elm)
+            }
+            ToolOperation::Symbols { input, output } => {
+                let mut elm = Element::new("symbols")
+                    .attr("query", input.query)
+                    .attr("target", &input.target);
+
+                if output.is_empty() {
+                    elm = elm.text("No symbols found");
+                } else {
+                    let base_dir = match input.query {
+                        SymbolQuery::Outline => Path::new(&input.target)
+                            .parent()
+                            .map(Path::to_path_buf)
+                            .unwrap_or_default(),
+                        _ => input.path.as_ref().map_or(env.cwd.clone(), PathBuf::from),
+                    };
+                    let (paginated, lines) = paginate_matches(
+                        elm,
+                        &output.to_matches(),
+                        input.start_index,
+                        input.max_search_lines,
+                        &base_dir,
+                        env,
+                    );
+                    elm = paginated.cdata(lines);
+                }
+
+                forge_domain::ToolOutput::text(elm)
+            }
+            ToolOperation::Diagnostics { input, output } => {
+                let elm = Element::new("diagnostics").attr("path", &input.path);
+                let elm = match output {
+                    None => elm.text("No language server is configured for this file"),
+                    Some(diagnostics) if diagnostics.is_empty() => {
+                        elm.text("No diagnostics reported")
+                    }
+                    Some(diagnostics) => elm.attr("total", diagnostics.len()).cdata(
+                        diagnostics
+                            .iter()
+                            .map(Diagnostic::to_string)
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    ),
+                };
+
+                forge_domain::ToolOutput::text(elm)
+            }
             ToolOperation::CodebaseSearch { output } => {
                 let total_results: usize = output.queries.iter().map(|q| q.results.len()).sum();
                 let mut root = Element::new("sem_search_results");
@@ -494,6 +658,19 @@ impl ToolOperation {
 
                 forge_domain::ToolOutput::text(elm)
             }
+            ToolOperation::WebSearch { input, output } => {
+                let elm = Element::new("web_search_results")
+                    .attr("query", &input.query)
+                    .attr("total_results", output.len())
+                    .append(output.into_iter().map(|result| {
+                        Element::new("result")
+                            .attr("title", result.title)
+                            .attr("url", result.url)
+                            .cdata(result.snippet)
+                    }));
+
+                forge_domain::ToolOutput::text(elm)
+            }
             ToolOperation::Shell { output } => {
                 let mut parent_elem = Element::new("shell_output")
                     .attr("command", &output.output.command)
@@ -542,8 +719,38 @@ impl ToolOperation {
                     .attr("plan_name", input.plan_name)
                     .attr("version", input.version);
 
+                // Link the plan to the conversation
+                metrics.plan = Some(output.path);
+
+                forge_domain::ToolOutput::text(elm)
+            }
+            ToolOperation::PlanStatus { input: _, output } => {
+                let elm = plan_element(&output);
+                metrics.plan = Some(output.path);
+                forge_domain::ToolOutput::text(elm)
+            }
+            ToolOperation::PlanUpdateStep { input, output } => {
+                let elm = plan_element(&output)
+                    .attr("updated_step", input.step)
+                    .attr("status", input.status);
+                metrics.plan = Some(output.path);
                 forge_domain::ToolOutput::text(elm)
             }
+            ToolOperation::Todo { input } => {
+                let count = |status: TodoStatus| {
+                    input
+                        .todos
+                        .iter()
+                        .filter(|todo| todo.status == status)
+                        .count()
+                };
+                forge_domain::ToolOutput::text(
+                    Element::new("todo_list_updated")
+                        .attr("pending", count(TodoStatus::Pending))
+                        .attr("in_progress", count(TodoStatus::InProgress))
+                        .attr("done", count(TodoStatus::Done)),
+                )
+            }
             ToolOperation::Skill { input: _, output } => {
                 let mut elm = Element::new("skill_details");
 
@@ -1146,6 +1353,126 @@ mod tests {
         insta::assert_snapshot!(to_value(actual));
     }
 
+    #[test]
+    fn test_ast_search_output() {
+        let fixture = ToolOperation::AstSearch {
+            input: forge_domain::AstSearch {
+                path: "/home/user/project".to_string(),
+                query: "(call_expression function: (field_expression field: (field_identifier) @method) (#eq? @method \"unwrap\")) @match".to_string(),
+                inside: Some("(function_item (function_modifiers \"async\")) @match".to_string()),
+                file_pattern: Some("*.rs".to_string()),
+                ..Default::default()
+            },
+            output: vec![
+                AstMatch {
+                    path: "/home/user/project/src/main.rs".to_string(),
+                    line_number: 12,
+                    symbol: Some("run".to_string()),
+                    line: "let config = load().await.unwrap();".to_string(),
+                },
+                AstMatch {
+                    path: "/home/user/project/src/lib.rs".to_string(),
+                    line_number: 40,
+                    symbol: Some("impl Service for Client > call".to_string()),
+                    line: "self.inner.lock().unwrap()".to_string(),
+                },
+            ],
+        };
+
+        let env = fixture_environment();
+
+        let actual = fixture.into_tool_output(
+            ToolKind::AstSearch,
+            TempContentFiles::default(),
+            &env,
+            &mut Metrics::default(),
+        );
+
+        insta::assert_snapshot!(to_value(actual));
+    }
+
+    #[test]
+    fn test_symbols_outline() {
+        let fixture = ToolOperation::Symbols {
+            input: forge_domain::Symbols {
+                query: forge_domain::SymbolQuery::Outline,
+                target: "/home/user/project/src/lib.rs".to_string(),
+                ..Default::default()
+            },
+            output: SymbolsOutput::Definitions(vec![
+                forge_domain::SymbolDefinition {
+                    name: "Client".to_string(),
+                    kind: forge_domain::SymbolKind::Struct,
+                    qualified_name: "Client".to_string(),
+                    path: "/home/user/project/src/lib.rs".to_string(),
+                    start_line: 3,
+                    end_line: 5,
+                    signature: "pub struct Client {".to_string(),
+                    implements: vec![],
+                },
+                forge_domain::SymbolDefinition {
+                    name: "call".to_string(),
+                    kind: forge_domain::SymbolKind::Method,
+                    qualified_name: "impl Service for Client > call".to_string(),
+                    path: "/home/user/project/src/lib.rs".to_string(),
+                    start_line: 8,
+                    end_line: 10,
+                    signature: "fn call(&self) -> Result<()> {".to_string(),
+                    implements: vec![],
+                },
+            ]),
+        };
+
+        let env = fixture_environment();
+
+        let actual = fixture.into_tool_output(
+            ToolKind::Symbols,
+            TempContentFiles::default(),
+            &env,
+            &mut Metrics::default(),
+        );
+
+        insta::assert_snapshot!(to_value(actual));
+    }
+
+    #[test]
+    fn test_diagnostics_output() {
+        let fixture = ToolOperation::Diagnostics {
+            input: forge_domain::Diagnostics { path: "/home/user/project/src/lib.rs".to_string() },
+            output: Some(vec![
+                Diagnostic {
+                    path: "/home/user/project/src/lib.rs".to_string(),
+                    line: 12,
+                    column: 9,
+                    severity: forge_domain::DiagnosticSeverity::Error,
+                    message: "cannot find value `client` in this scope".to_string(),
+                    source: Some("rustc".to_string()),
+                    code: Some("E0425".to_string()),
+                },
+                Diagnostic {
+                    path: "/home/user/project/src/lib.rs".to_string(),
+                    line: 3,
+                    column: 5,
+                    severity: forge_domain::DiagnosticSeverity::Warning,
+                    message: "unused import: `std::fmt`".to_string(),
+                    source: Some("rustc".to_string()),
+                    code: None,
+                },
+            ]),
+        };
+
+        let env = fixture_environment();
+
+        let actual = fixture.into_tool_output(
+            ToolKind::Diagnostics,
+            TempContentFiles::default(),
+            &env,
+            &mut Metrics::default(),
+        );
+
+        insta::assert_snapshot!(to_value(actual));
+    }
+
     #[test]
     fn test_fs_search_max_output() {
         // Create a large number of search matches to trigger truncation
@@ -1588,6 +1915,7 @@ mod tests {
             input: forge_domain::NetFetch {
                 url: "https://example.com".to_string(),
                 raw: Some(false),
+                ..Default::default()
             },
             output: HttpResponse {
                 content: "# Example Website\n\nThis is some content from a website.".to_string(),
@@ -1622,6 +1950,7 @@ mod tests {
             input: forge_domain::NetFetch {
                 url: "https://example.com/large-page".to_string(),
                 raw: Some(false),
+                ..Default::default()
             },
             output: HttpResponse {
                 content: long_content,
@@ -1654,6 +1983,32 @@ mod tests {
         insta::assert_snapshot!(to_value(actual));
     }
 
+    #[test]
+    fn test_web_search_results() {
+        let fixture = ToolOperation::WebSearch {
+            input: forge_domain::WebSearch {
+                query: "rust async runtime".to_string(),
+                max_results: None,
+            },
+            output: vec![forge_domain::WebSearchResult {
+                title: "Tokio".to_string(),
+                url: "https://tokio.rs/".to_string(),
+                snippet: "An asynchronous runtime for Rust".to_string(),
+            }],
+        };
+
+        let env = fixture_environment();
+
+        let actual = fixture.into_tool_output(
+            ToolKind::WebSearch,
+            TempContentFiles::default(),
+            &env,
+            &mut Metrics::default(),
+        );
+
+        insta::assert_snapshot!(to_value(actual));
+    }
+
     #[test]
     fn test_shell_success() {
         let fixture = ToolOperation::Shell {
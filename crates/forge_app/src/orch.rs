@@ -13,6 +13,7 @@ use tracing::{debug, info, warn};
 use crate::TemplateEngine;
 use crate::agent::AgentService;
 use crate::compact::Compactor;
+use crate::summary_generator::SummaryGenerator;
 use crate::title_generator::TitleGenerator;
 
 #[derive(Clone, Setters)]
@@ -169,7 +170,7 @@ impl<S: AgentService> Orchestrator<S> {
             .await
     }
     /// Checks if compaction is needed and performs it if necessary
-    fn check_and_compact(&self, context: &Context) -> anyhow::Result<Option<Context>> {
+    async fn check_and_compact(&self, context: &Context) -> anyhow::Result<Option<Context>> {
         let agent = &self.agent;
         // Estimate token count for compaction decision
         let token_count = context.token_count();
@@ -177,8 +178,15 @@ impl<S: AgentService> Orchestrator<S> {
             && let Some(compact) = agent.compact.clone()
         {
             info!(agent_id = %agent.id, "Compaction needed");
+            let generator = SummaryGenerator::new(
+                self.services.clone(),
+                compact.model.clone().unwrap_or_else(|| agent.model.clone()),
+                Some(agent.provider.clone()),
+            );
             Compactor::new(compact, self.environment.clone())
-                .compact(context.clone(), false)
+                .todos(self.conversation.todos.clone())
+                .compact_with(context.clone(), false, &generator)
+                .await
                 .map(Some)
         } else {
             debug!(agent_id = %agent.id, "Compaction not needed");
@@ -221,8 +229,9 @@ impl<S: AgentService> Orchestrator<S> {
         // Retrieve the number of requests allowed per tick.
         let max_requests_per_turn = agent.max_requests_per_turn;
 
-        let tool_context =
-            ToolCallContext::new(self.conversation.metrics.clone()).sender(self.sender.clone());
+        let tool_context = ToolCallContext::new(self.conversation.metrics.clone())
+            .sender(self.sender.clone())
+            .todos(self.conversation.todos.clone());
 
         // Asynchronously generate a title for the provided task
         // TODO: Move into app.rs
@@ -256,7 +265,7 @@ impl<S: AgentService> Orchestrator<S> {
             // triggered after receiving the response Trigger compaction after
             // making a request NOTE: Ideally compaction should be implemented
             // as a transformer
-            if let Some(c_context) = self.check_and_compact(&context)? {
+            if let Some(c_context) = self.check_and_compact(&context).await? {
                 info!(agent_id = %agent.id, "Using compacted context from execution");
                 context = c_context;
             } else {
@@ -375,6 +384,9 @@ impl<S: AgentService> Orchestrator<S> {
             tool_context.with_metrics(|metrics| {
                 self.conversation.metrics = metrics.clone();
             })?;
+            tool_context.with_todos(|todos| {
+                self.conversation.todos = todos.clone();
+            })?;
         }
 
         // Set conversation title
@@ -16,7 +16,9 @@ use crate::orch::Orchestrator;
 use crate::set_conversation_id::SetConversationId;
 use crate::system_prompt::SystemPrompt;
 use crate::user_prompt::UserPromptGenerator;
-use crate::{AgentService, AttachmentService, SkillFetchService, TemplateService};
+use crate::{
+    AgentService, AttachmentService, GitContextService, SkillFetchService, TemplateService,
+};
 
 #[derive(Embed)]
 #[folder = "../../templates/"]
@@ -213,3 +215,13 @@ impl SkillFetchService for Runner {
         Ok(vec![])
     }
 }
+
+#[async_trait::async_trait]
+impl GitContextService for Runner {
+    async fn git_context(
+        &self,
+        _config: &forge_domain::GitContextConfig,
+    ) -> anyhow::Result<Option<forge_domain::GitContext>> {
+        Ok(None)
+    }
+}
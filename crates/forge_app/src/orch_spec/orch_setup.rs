@@ -89,6 +89,9 @@ impl Default for TestContext {
                 workspace_server_url: Url::parse("http://localhost:8080").unwrap(),
                 override_model: None,
                 override_provider: None,
+                web_search: None,
+                record_cassette: None,
+                replay_mismatch: Default::default(),
             },
             title: Some("test-conversation".into()),
             agent: Agent::new(
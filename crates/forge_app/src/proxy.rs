@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use forge_domain::{BoxStream, ChatCompletionMessage, Context, ModelId, ProviderId};
+
+use crate::retry::retry_with_config;
+use crate::{AppConfigService, EnvironmentService, ProviderAuthService, ProviderService, Services};
+
+/// Routes requests received by `forge proxy` through the configured
+/// providers.
+pub struct ProxyApp<S> {
+    services: Arc<S>,
+}
+
+impl<S: Services> ProxyApp<S> {
+    pub fn new(services: Arc<S>) -> Self {
+        Self { services }
+    }
+
+    /// Resolves the provider and model for a model name sent by a client.
+    ///
+    /// `<provider>/<model>` selects a configured provider by its id. Any other
+    /// name, including one whose prefix is not a configured provider such as
+    /// `openai/gpt-5` on OpenRouter, falls back to the default provider.
+    pub async fn resolve(&self, model: &str) -> anyhow::Result<(ProviderId, ModelId)> {
+        if let Some((id, name)) = model.split_once('/') {
+            let id = ProviderId::from(id.to_string());
+            let configured = self
+                .services
+                .get_all_providers()
+                .await?
+                .iter()
+                .any(|provider| provider.id() == id && provider.is_configured());
+            if configured {
+                return Ok((id, ModelId::new(name)));
+            }
+        }
+        let provider = self.services.get_default_provider().await?;
+        Ok((provider.id, ModelId::new(model)))
+    }
+
+    /// Streams a completion of `context` from `provider`, refreshing its
+    /// credentials first. Failures before the stream starts are retried with
+    /// the configured retry policy.
+    pub async fn chat(
+        &self,
+        provider: ProviderId,
+        model: &ModelId,
+        context: Context,
+    ) -> anyhow::Result<BoxStream<ChatCompletionMessage, anyhow::Error>> {
+        let provider = self.services.get_provider(provider).await?;
+        let provider = self
+            .services
+            .provider_auth_service()
+            .refresh_provider_credential(provider)
+            .await?;
+        let retry_config = self.services.get_environment().retry_config;
+        let provider_id = provider.id.clone();
+        retry_with_config(
+            &retry_config,
+            || self.services.chat(model, context.clone(), provider.clone()),
+            Some(move |error: &anyhow::Error, duration: Duration| {
+                tracing::warn!(provider = %provider_id, error = ?error.root_cause(), retry_in = ?duration, "Proxy retry attempt");
+            }),
+        )
+        .await
+        .with_context(|| format!("Request to {} failed", provider.id))
+    }
+}
@@ -1,14 +1,18 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use bytes::Bytes;
 use derive_setters::Setters;
 use forge_domain::{
-    AgentId, AnyProvider, Attachment, AuthContextRequest, AuthContextResponse, AuthMethod,
-    ChatCompletionMessage, CommandOutput, Context, Conversation, ConversationId, Environment, File,
-    Image, InitAuth, LoginInfo, McpConfig, McpServers, Model, ModelId, Node, PatchOperation,
-    Provider, ProviderId, ResultStream, Scope, SearchParams, SyncProgress, Template, ToolCallFull,
-    ToolOutput, Workflow, WorkspaceAuth, WorkspaceId, WorkspaceInfo,
+    AgentId, AnyProvider, AstSearch, Attachment, AuthContextRequest, AuthContextResponse,
+    AuthMethod, ChatCompletionMessage, CodeRequest, CommandOutput, Context, Conversation,
+    ConversationId, Diagnostic, Environment, File, GitContext, GitContextConfig, Image, InitAuth,
+    LoginInfo, McpConfig, McpResourceContent, McpServerStatus, McpServers, Model, ModelId,
+    NetFetch, Node, PatchOperation, PlanFile, Provider, ProviderId, ResultStream, Scope,
+    SearchParams, ServerName, StepStatus, SymbolDefinition, Symbols, SyncProgress, Template,
+    ToolCallFull, ToolOutput, WebSearch, WebSearchResult, Workflow, WorkspaceAuth, WorkspaceId,
+    WorkspaceInfo,
 };
 use merge::Merge;
 use reqwest::Response;
@@ -77,6 +81,66 @@ pub enum MatchResult {
     Found { line_number: usize, line: String },
 }
 
+/// A structural search match, reported at the line where the matched node
+/// starts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AstMatch {
+    pub path: String,
+    pub line_number: usize,
+    /// Name of the innermost function, type or impl containing the match
+    pub symbol: Option<String>,
+    /// First line of the matched node
+    pub line: String,
+}
+
+impl AstMatch {
+    /// Converts the match into a line match, prefixing the line with the
+    /// enclosing symbol so it survives search output formatting
+    pub fn to_match(&self) -> Match {
+        let line = match &self.symbol {
+            Some(symbol) => format!("[{symbol}] {}", self.line),
+            None => self.line.clone(),
+        };
+        Match {
+            path: self.path.clone(),
+            result: Some(MatchResult::Found { line_number: self.line_number, line }),
+        }
+    }
+}
+
+/// Result of a `symbols` lookup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolsOutput {
+    Definitions(Vec<SymbolDefinition>),
+    References(Vec<AstMatch>),
+}
+
+impl SymbolsOutput {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Definitions(definitions) => definitions.is_empty(),
+            Self::References(references) => references.is_empty(),
+        }
+    }
+
+    /// Converts the result into line matches for search output formatting
+    pub fn to_matches(&self) -> Vec<Match> {
+        match self {
+            Self::Definitions(definitions) => definitions
+                .iter()
+                .map(|definition| Match {
+                    path: definition.path.clone(),
+                    result: Some(MatchResult::Found {
+                        line_number: definition.start_line,
+                        line: definition.summary(),
+                    }),
+                })
+                .collect(),
+            Self::References(references) => references.iter().map(AstMatch::to_match).collect(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct HttpResponse {
     pub content: String,
@@ -199,6 +263,31 @@ pub trait McpService: Send + Sync {
     async fn execute_mcp(&self, call: ToolCallFull) -> anyhow::Result<ToolOutput>;
     /// Refresh the MCP cache by fetching fresh data
     async fn reload_mcp(&self) -> anyhow::Result<()>;
+    /// Reads a resource published by an MCP server
+    async fn read_mcp_resource(
+        &self,
+        server: &ServerName,
+        uri: &str,
+    ) -> anyhow::Result<Vec<McpResourceContent>>;
+    /// Renders a prompt template published by an MCP server
+    async fn get_mcp_prompt(
+        &self,
+        server: &ServerName,
+        name: &str,
+        arguments: BTreeMap<String, String>,
+    ) -> anyhow::Result<String>;
+    /// Reports the connection health of every configured MCP server
+    async fn get_mcp_status(&self) -> anyhow::Result<Vec<McpServerStatus>>;
+    /// Starts the OAuth authorization code flow for an MCP server
+    async fn init_mcp_auth(&self, server: &ServerName) -> anyhow::Result<CodeRequest>;
+    /// Exchanges the authorization code and stores the server's credential
+    async fn complete_mcp_auth(
+        &self,
+        server: &ServerName,
+        response: AuthContextResponse,
+    ) -> anyhow::Result<()>;
+    /// Removes the stored OAuth credential of an MCP server
+    async fn logout_mcp(&self, server: &ServerName) -> anyhow::Result<()>;
 }
 
 #[async_trait::async_trait]
@@ -339,6 +428,24 @@ pub trait PlanCreateService: Send + Sync {
     ) -> anyhow::Result<PlanCreateOutput>;
 }
 
+#[async_trait::async_trait]
+pub trait PlanService: Send + Sync {
+    /// Parses the plan file at `path` into its steps.
+    async fn plan(&self, path: &Path) -> anyhow::Result<PlanFile>;
+
+    /// Lists the plans of the working directory, most recent first.
+    async fn plans(&self) -> anyhow::Result<Vec<PlanFile>>;
+
+    /// Sets the status of a step in the plan file and returns the updated
+    /// plan. Concurrent updates of the same file are applied one at a time.
+    async fn update_plan_step(
+        &self,
+        path: &Path,
+        step: usize,
+        status: StepStatus,
+    ) -> anyhow::Result<PlanFile>;
+}
+
 #[async_trait::async_trait]
 pub trait FsPatchService: Send + Sync {
     /// Patches a file at the specified path with the given content.
@@ -385,6 +492,28 @@ pub trait FsSearchService: Send + Sync {
     ) -> anyhow::Result<Option<SearchResult>>;
 }
 
+#[async_trait::async_trait]
+pub trait AstSearchService: Send + Sync {
+    /// Searches files under the input path for nodes matching a tree-sitter
+    /// query, optionally nested inside nodes matching `inside`.
+    async fn ast_search(&self, input: AstSearch) -> anyhow::Result<Vec<AstMatch>>;
+}
+
+#[async_trait::async_trait]
+pub trait SymbolService: Send + Sync {
+    /// Answers a symbol lookup from the local symbol index, re-indexing files
+    /// that changed since the last lookup.
+    async fn symbols(&self, input: Symbols) -> anyhow::Result<SymbolsOutput>;
+}
+
+#[async_trait::async_trait]
+pub trait LspService: Send + Sync {
+    /// Syncs the file with the language server configured for its language
+    /// and returns the diagnostics it reports. Returns `None` when no server
+    /// handles the file.
+    async fn diagnostics(&self, path: &Path) -> anyhow::Result<Option<Vec<Diagnostic>>>;
+}
+
 #[async_trait::async_trait]
 pub trait FollowUpService: Send + Sync {
     /// Follows up on a tool call with the given context.
@@ -407,8 +536,15 @@ pub trait FsUndoService: Send + Sync {
 
 #[async_trait::async_trait]
 pub trait NetFetchService: Send + Sync {
-    /// Fetches content from a URL and returns it as a string.
-    async fn fetch(&self, url: String, raw: Option<bool>) -> anyhow::Result<HttpResponse>;
+    /// Sends the request and returns the response content as a string.
+    async fn fetch(&self, request: NetFetch) -> anyhow::Result<HttpResponse>;
+}
+
+#[async_trait::async_trait]
+pub trait WebSearchService: Send + Sync {
+    /// Searches the web using the configured backend and returns at most
+    /// `max_results` results.
+    async fn web_search(&self, request: WebSearch) -> anyhow::Result<Vec<WebSearchResult>>;
 }
 
 #[async_trait::async_trait]
@@ -467,6 +603,13 @@ pub trait PolicyService: Send + Sync {
         &self,
         operation: &forge_domain::PermissionOperation,
     ) -> anyhow::Result<PolicyDecision>;
+
+    /// Evaluates the policies for an operation without asking the user, for
+    /// callers that have no terminal to ask on
+    async fn permission(
+        &self,
+        operation: &forge_domain::PermissionOperation,
+    ) -> anyhow::Result<forge_domain::Permission>;
 }
 
 /// Skill fetch service
@@ -487,6 +630,15 @@ pub trait SkillFetchService: Send + Sync {
     async fn list_skills(&self) -> anyhow::Result<Vec<forge_domain::Skill>>;
 }
 
+/// Collects the state of the git repository the agent works in
+#[async_trait::async_trait]
+pub trait GitContextService: Send + Sync {
+    /// Returns the branch, upstream divergence, changed files, recent commits
+    /// and diffstat of the repository at the current working directory,
+    /// capped to the configured size. Returns `None` outside a repository.
+    async fn git_context(&self, config: &GitContextConfig) -> anyhow::Result<Option<GitContext>>;
+}
+
 /// Provider authentication service
 #[async_trait::async_trait]
 pub trait ProviderAuthService: Send + Sync {
@@ -529,14 +681,19 @@ pub trait Services: Send + Sync + 'static + Clone {
     type McpConfigManager: McpConfigManager;
     type FsCreateService: FsCreateService;
     type PlanCreateService: PlanCreateService;
+    type PlanService: PlanService;
     type FsPatchService: FsPatchService;
     type FsReadService: FsReadService;
     type ImageReadService: ImageReadService;
     type FsRemoveService: FsRemoveService;
     type FsSearchService: FsSearchService;
+    type AstSearchService: AstSearchService;
+    type SymbolService: SymbolService;
+    type LspService: LspService;
     type FollowUpService: FollowUpService;
     type FsUndoService: FsUndoService;
     type NetFetchService: NetFetchService;
+    type WebSearchService: WebSearchService;
     type ShellService: ShellService;
     type McpService: McpService;
     type AuthService: AuthService;
@@ -546,6 +703,7 @@ pub trait Services: Send + Sync + 'static + Clone {
     type ProviderAuthService: ProviderAuthService;
     type CodebaseService: ContextEngineService;
     type SkillFetchService: SkillFetchService;
+    type GitContextService: GitContextService;
 
     fn provider_service(&self) -> &Self::ProviderService;
     fn config_service(&self) -> &Self::AppConfigService;
@@ -557,14 +715,19 @@ pub trait Services: Send + Sync + 'static + Clone {
     fn mcp_config_manager(&self) -> &Self::McpConfigManager;
     fn fs_create_service(&self) -> &Self::FsCreateService;
     fn plan_create_service(&self) -> &Self::PlanCreateService;
+    fn plan_service(&self) -> &Self::PlanService;
     fn fs_patch_service(&self) -> &Self::FsPatchService;
     fn fs_read_service(&self) -> &Self::FsReadService;
     fn image_read_service(&self) -> &Self::ImageReadService;
     fn fs_remove_service(&self) -> &Self::FsRemoveService;
     fn fs_search_service(&self) -> &Self::FsSearchService;
+    fn ast_search_service(&self) -> &Self::AstSearchService;
+    fn symbol_service(&self) -> &Self::SymbolService;
+    fn lsp_service(&self) -> &Self::LspService;
     fn follow_up_service(&self) -> &Self::FollowUpService;
     fn fs_undo_service(&self) -> &Self::FsUndoService;
     fn net_fetch_service(&self) -> &Self::NetFetchService;
+    fn web_search_service(&self) -> &Self::WebSearchService;
     fn shell_service(&self) -> &Self::ShellService;
     fn mcp_service(&self) -> &Self::McpService;
     fn environment_service(&self) -> &Self::EnvironmentService;
@@ -576,6 +739,7 @@ pub trait Services: Send + Sync + 'static + Clone {
     fn provider_auth_service(&self) -> &Self::ProviderAuthService;
     fn context_engine_service(&self) -> &Self::CodebaseService;
     fn skill_fetch_service(&self) -> &Self::SkillFetchService;
+    fn git_context_service(&self) -> &Self::GitContextService;
 }
 
 #[async_trait::async_trait]
@@ -684,6 +848,45 @@ impl<I: Services> McpService for I {
     async fn reload_mcp(&self) -> anyhow::Result<()> {
         self.mcp_service().reload_mcp().await
     }
+
+    async fn read_mcp_resource(
+        &self,
+        server: &ServerName,
+        uri: &str,
+    ) -> anyhow::Result<Vec<McpResourceContent>> {
+        self.mcp_service().read_mcp_resource(server, uri).await
+    }
+
+    async fn get_mcp_prompt(
+        &self,
+        server: &ServerName,
+        name: &str,
+        arguments: BTreeMap<String, String>,
+    ) -> anyhow::Result<String> {
+        self.mcp_service()
+            .get_mcp_prompt(server, name, arguments)
+            .await
+    }
+
+    async fn get_mcp_status(&self) -> anyhow::Result<Vec<McpServerStatus>> {
+        self.mcp_service().get_mcp_status().await
+    }
+
+    async fn init_mcp_auth(&self, server: &ServerName) -> anyhow::Result<CodeRequest> {
+        self.mcp_service().init_mcp_auth(server).await
+    }
+
+    async fn complete_mcp_auth(
+        &self,
+        server: &ServerName,
+        response: AuthContextResponse,
+    ) -> anyhow::Result<()> {
+        self.mcp_service().complete_mcp_auth(server, response).await
+    }
+
+    async fn logout_mcp(&self, server: &ServerName) -> anyhow::Result<()> {
+        self.mcp_service().logout_mcp(server).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -760,6 +963,28 @@ impl<I: Services> PlanCreateService for I {
     }
 }
 
+#[async_trait::async_trait]
+impl<I: Services> PlanService for I {
+    async fn plan(&self, path: &Path) -> anyhow::Result<PlanFile> {
+        self.plan_service().plan(path).await
+    }
+
+    async fn plans(&self) -> anyhow::Result<Vec<PlanFile>> {
+        self.plan_service().plans().await
+    }
+
+    async fn update_plan_step(
+        &self,
+        path: &Path,
+        step: usize,
+        status: StepStatus,
+    ) -> anyhow::Result<PlanFile> {
+        self.plan_service()
+            .update_plan_step(path, step, status)
+            .await
+    }
+}
+
 #[async_trait::async_trait]
 impl<I: Services> FsPatchService for I {
     async fn patch(
@@ -816,6 +1041,27 @@ impl<I: Services> FsSearchService for I {
     }
 }
 
+#[async_trait::async_trait]
+impl<I: Services> AstSearchService for I {
+    async fn ast_search(&self, input: AstSearch) -> anyhow::Result<Vec<AstMatch>> {
+        self.ast_search_service().ast_search(input).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: Services> SymbolService for I {
+    async fn symbols(&self, input: Symbols) -> anyhow::Result<SymbolsOutput> {
+        self.symbol_service().symbols(input).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: Services> LspService for I {
+    async fn diagnostics(&self, path: &Path) -> anyhow::Result<Option<Vec<Diagnostic>>> {
+        self.lsp_service().diagnostics(path).await
+    }
+}
+
 #[async_trait::async_trait]
 impl<I: Services> FollowUpService for I {
     async fn follow_up(
@@ -839,8 +1085,15 @@ impl<I: Services> FsUndoService for I {
 
 #[async_trait::async_trait]
 impl<I: Services> NetFetchService for I {
-    async fn fetch(&self, url: String, raw: Option<bool>) -> anyhow::Result<HttpResponse> {
-        self.net_fetch_service().fetch(url, raw).await
+    async fn fetch(&self, request: NetFetch) -> anyhow::Result<HttpResponse> {
+        self.net_fetch_service().fetch(request).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: Services> WebSearchService for I {
+    async fn web_search(&self, request: WebSearch) -> anyhow::Result<Vec<WebSearchResult>> {
+        self.web_search_service().web_search(request).await
     }
 }
 
@@ -958,6 +1211,13 @@ impl<I: Services> PolicyService for I {
             .check_operation_permission(operation)
             .await
     }
+
+    async fn permission(
+        &self,
+        operation: &forge_domain::PermissionOperation,
+    ) -> anyhow::Result<forge_domain::Permission> {
+        self.policy_service().permission(operation).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -998,6 +1258,13 @@ impl<I: Services> SkillFetchService for I {
     }
 }
 
+#[async_trait::async_trait]
+impl<I: Services> GitContextService for I {
+    async fn git_context(&self, config: &GitContextConfig) -> anyhow::Result<Option<GitContext>> {
+        self.git_context_service().git_context(config).await
+    }
+}
+
 #[async_trait::async_trait]
 impl<I: Services> ProviderAuthService for I {
     async fn init_provider_auth(
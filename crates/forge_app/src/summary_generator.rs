@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use forge_domain::{
+    ChatCompletionMessageFull, Context, ContextMessage, ConversationId, ModelId, ProviderId,
+    ResultStreamExt, extract_tag_content,
+};
+
+use crate::TemplateEngine;
+use crate::agent::AgentService as AS;
+
+/// Service for generating abstractive summaries of compacted messages
+pub struct SummaryGenerator<S> {
+    /// Shared reference to the agent services used for AI interactions
+    services: Arc<S>,
+    /// The model ID to use for summarization
+    model_id: ModelId,
+    /// The provider ID to use for summarization
+    provider_id: Option<ProviderId>,
+}
+
+impl<S: AS> SummaryGenerator<S> {
+    pub fn new(services: Arc<S>, model_id: ModelId, provider_id: Option<ProviderId>) -> Self {
+        Self { services, model_id, provider_id }
+    }
+
+    /// Asks the model for a summary of the goals, constraints, decisions, open
+    /// TODOs and touched files in `context`
+    pub async fn generate(&self, context: &Context) -> anyhow::Result<String> {
+        let template = TemplateEngine::default().render(
+            "forge-system-prompt-context-summary.md",
+            &Default::default(),
+        )?;
+
+        let ctx = Context::default()
+            .conversation_id(ConversationId::generate())
+            .add_message(ContextMessage::system(template))
+            .add_message(ContextMessage::user(
+                context.to_text(),
+                Some(self.model_id.clone()),
+            ));
+
+        let stream = self
+            .services
+            .chat_agent(&self.model_id, ctx, self.provider_id.clone())
+            .await?;
+        let ChatCompletionMessageFull { content, .. } = stream.into_full(false, None).await?;
+        extract_tag_content(&content, "summary")
+            .map(str::trim)
+            .filter(|summary| !summary.is_empty())
+            .map(str::to_string)
+            .context("The compaction model did not return a summary")
+    }
+}
@@ -2,12 +2,12 @@ use std::sync::Arc;
 
 use derive_setters::Setters;
 use forge_domain::{
-    Agent, Conversation, Environment, File, Model, SystemContext, Template, ToolDefinition,
-    ToolUsagePrompt,
+    Agent, Conversation, Environment, File, GitContext, Model, SystemContext, Template,
+    ToolDefinition, ToolUsagePrompt,
 };
-use tracing::debug;
+use tracing::{debug, warn};
 
-use crate::{SkillFetchService, TemplateEngine};
+use crate::{GitContextService, SkillFetchService, TemplateEngine};
 
 #[derive(Setters)]
 pub struct SystemPrompt<S> {
@@ -20,7 +20,7 @@ pub struct SystemPrompt<S> {
     custom_instructions: Vec<String>,
 }
 
-impl<S: SkillFetchService> SystemPrompt<S> {
+impl<S: SkillFetchService + GitContextService> SystemPrompt<S> {
     pub fn new(services: Arc<S>, environment: Environment, agent: Agent) -> Self {
         Self {
             services,
@@ -61,6 +61,7 @@ impl<S: SkillFetchService> SystemPrompt<S> {
             });
 
             let skills = self.services.list_skills().await?;
+            let git = self.git_context().await;
 
             let ctx = SystemContext {
                 env: Some(env),
@@ -70,6 +71,7 @@ impl<S: SkillFetchService> SystemPrompt<S> {
                 custom_rules: custom_rules.join("\n\n"),
                 supports_parallel_tool_calls,
                 skills,
+                git,
             };
 
             let static_block = TemplateEngine::default()
@@ -85,6 +87,24 @@ impl<S: SkillFetchService> SystemPrompt<S> {
         Ok(conversation.context(context))
     }
 
+    /// Collects the repository state when the agent opts into it. Failures
+    /// are logged and leave the git section out of the prompt.
+    async fn git_context(&self) -> Option<GitContext> {
+        let config = self
+            .agent
+            .git_context
+            .as_ref()
+            .filter(|config| config.enabled)?;
+        self.services
+            .git_context(config)
+            .await
+            .inspect_err(
+                |error| warn!(agent_id = %self.agent.id, %error, "Failed to collect git context"),
+            )
+            .ok()
+            .flatten()
+    }
+
     // Returns if agent supports tool or not.
     fn is_tool_supported(&self) -> anyhow::Result<bool> {
         let agent = &self.agent;
@@ -148,6 +168,16 @@ mod tests {
         }
     }
 
+    #[async_trait::async_trait]
+    impl GitContextService for MockSkillFetchService {
+        async fn git_context(
+            &self,
+            _config: &forge_domain::GitContextConfig,
+        ) -> anyhow::Result<Option<GitContext>> {
+            Ok(Some(GitContext::default().branch("feature/git-context")))
+        }
+    }
+
     fn create_test_environment() -> Environment {
         use fake::Faker;
         Faker.fake()
@@ -179,4 +209,23 @@ mod tests {
         let conversation = result.unwrap();
         assert!(conversation.context.is_some());
     }
+
+    #[tokio::test]
+    async fn test_system_prompt_renders_git_context_when_enabled() {
+        let services = Arc::new(MockSkillFetchService);
+        let env = create_test_environment();
+        let agent = create_test_agent()
+            .system_prompt(Template::new("{{> forge-partial-system-info.md }}"))
+            .git_context(forge_domain::GitContextConfig::default());
+        let system_prompt = SystemPrompt::new(services, env, agent);
+
+        let conversation = system_prompt
+            .add_system_message(forge_domain::Conversation::generate())
+            .await
+            .unwrap();
+
+        let context = conversation.context.unwrap();
+        let actual = context.system_prompt().unwrap();
+        assert!(actual.contains("<branch>feature/git-context</branch>"));
+    }
 }
@@ -1,8 +1,9 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use forge_domain::{
-    CodebaseQueryResult, TitleFormat, ToolCallContext, ToolCallFull, ToolCatalog, ToolOutput,
+    ChatResponse, CodebaseQueryResult, PermissionOperation, SymbolQuery, TitleFormat,
+    ToolCallContext, ToolCallFull, ToolCatalog, ToolOutput,
 };
 
 use crate::fmt::content::FormatContent;
@@ -10,10 +11,11 @@ use crate::operation::{TempContentFiles, ToolOperation};
 use crate::services::ShellService;
 use crate::utils::format_display_path;
 use crate::{
-    ContextEngineService, ConversationService, EnvironmentService, FollowUpService,
-    FsCreateService, FsPatchService, FsReadService, FsRemoveService, FsSearchService,
-    FsUndoService, ImageReadService, NetFetchService, PlanCreateService, PolicyService,
-    SkillFetchService,
+    AstSearchService, ContextEngineService, ConversationService, EnvironmentService,
+    FollowUpService, FsCreateService, FsPatchService, FsReadService, FsRemoveService,
+    FsSearchService, FsUndoService, ImageReadService, LspService, NetFetchService,
+    PlanCreateService, PlanService, PolicyService, SkillFetchService, SymbolService,
+    WebSearchService,
 };
 
 pub struct ToolExecutor<S> {
@@ -25,8 +27,12 @@ impl<
         + ImageReadService
         + FsCreateService
         + FsSearchService
+        + AstSearchService
+        + SymbolService
+        + LspService
         + ContextEngineService
         + NetFetchService
+        + WebSearchService
         + FsRemoveService
         + FsPatchService
         + FsUndoService
@@ -35,6 +41,7 @@ impl<
         + ConversationService
         + EnvironmentService
         + PlanCreateService
+        + PlanService
         + PolicyService
         + SkillFetchService,
 > ToolExecutor<S>
@@ -43,6 +50,18 @@ impl<
         Self { services }
     }
 
+    /// The operation a tool call performs, as checked against the policies
+    pub(crate) fn tool_operation(&self, tool_input: &ToolCatalog) -> Option<PermissionOperation> {
+        let env = self.services.get_environment();
+        match tool_input {
+            ToolCatalog::WebSearch(input) => env
+                .web_search
+                .as_ref()
+                .map(|backend| input.to_policy_operation(backend, env.cwd.clone())),
+            _ => tool_input.to_policy_operation(env.cwd),
+        }
+    }
+
     /// Check if a tool operation is allowed based on the workflow policies
     #[allow(unused)]
     async fn check_tool_permission(
@@ -51,8 +70,7 @@ impl<
         context: &ToolCallContext,
     ) -> anyhow::Result<bool> {
         let cwd = self.services.get_environment().cwd;
-        let operation = tool_input.to_policy_operation(cwd.clone());
-        if let Some(operation) = operation {
+        if let Some(operation) = self.tool_operation(tool_input) {
             let decision = self.services.check_operation_permission(&operation).await?;
 
             // Send custom policy message to the user when a policy file was created
@@ -131,6 +149,33 @@ impl<
         }
     }
 
+    /// Appends the errors and warnings the language server reports for an
+    /// edited file to the warning of the edit. Diagnostics are best effort, so
+    /// failing servers leave the warning unchanged.
+    async fn with_diagnostics(&self, path: &str, warning: Option<String>) -> Option<String> {
+        let diagnostics = match self.services.diagnostics(Path::new(path)).await {
+            Ok(diagnostics) => diagnostics.unwrap_or_default(),
+            Err(error) => {
+                tracing::warn!(path, error = ?error, "Failed to collect diagnostics");
+                Vec::new()
+            }
+        };
+        let problems = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_problem())
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        if problems.is_empty() {
+            return warning;
+        }
+
+        let report = format!("Language server diagnostics:\n{}", problems.join("\n"));
+        Some(match warning {
+            Some(warning) => format!("{warning}\n{report}"),
+            None => report,
+        })
+    }
+
     async fn create_temp_file(
         &self,
         prefix: &str,
@@ -154,7 +199,11 @@ impl<
         Ok(path)
     }
 
-    async fn call_internal(&self, input: ToolCatalog) -> anyhow::Result<ToolOperation> {
+    async fn call_internal(
+        &self,
+        input: ToolCatalog,
+        context: &ToolCallContext,
+    ) -> anyhow::Result<ToolOperation> {
         Ok(match input {
             ToolCatalog::Read(input) => {
                 let normalized_path = self.normalize_path(input.path.clone());
@@ -176,10 +225,17 @@ impl<
             }
             ToolCatalog::Write(input) => {
                 let normalized_path = self.normalize_path(input.path.clone());
-                let output = self
+                let mut output = self
                     .services
-                    .create(normalized_path, input.content.clone(), input.overwrite)
+                    .create(
+                        normalized_path.clone(),
+                        input.content.clone(),
+                        input.overwrite,
+                    )
                     .await?;
+                output.warning = self
+                    .with_diagnostics(&normalized_path, output.warning)
+                    .await;
                 (input, output).into()
             }
             ToolCatalog::Search(input) => {
@@ -194,6 +250,39 @@ impl<
                     .await?;
                 (input, output).into()
             }
+            ToolCatalog::AstSearch(input) => {
+                let normalized = forge_domain::AstSearch {
+                    path: self.normalize_path(input.path.clone()),
+                    ..input.clone()
+                };
+                let output = self.services.ast_search(normalized).await?;
+                (input, output).into()
+            }
+            ToolCatalog::Symbols(input) => {
+                let input = match input.query {
+                    SymbolQuery::Outline => forge_domain::Symbols {
+                        target: self.normalize_path(input.target.clone()),
+                        ..input.clone()
+                    },
+                    _ => forge_domain::Symbols {
+                        path: Some(match &input.path {
+                            Some(path) => self.normalize_path(path.clone()),
+                            None => self.services.get_environment().cwd.display().to_string(),
+                        }),
+                        ..input.clone()
+                    },
+                };
+                let output = self.services.symbols(input.clone()).await?;
+                (input, output).into()
+            }
+            ToolCatalog::Diagnostics(input) => {
+                let normalized_path = self.normalize_path(input.path.clone());
+                let output = self
+                    .services
+                    .diagnostics(Path::new(&normalized_path))
+                    .await?;
+                (input, output).into()
+            }
             ToolCatalog::SemSearch(input) => {
                 let env = self.services.get_environment();
                 let services = self.services.clone();
@@ -249,15 +338,18 @@ impl<
             }
             ToolCatalog::Patch(input) => {
                 let normalized_path = self.normalize_path(input.path.clone());
-                let output = self
+                let mut output = self
                     .services
                     .patch(
-                        normalized_path,
+                        normalized_path.clone(),
                         input.search.clone(),
                         input.operation.clone(),
                         input.content.clone(),
                     )
                     .await?;
+                output.warning = self
+                    .with_diagnostics(&normalized_path, output.warning)
+                    .await;
                 (input, output).into()
             }
             ToolCatalog::Undo(input) => {
@@ -280,7 +372,11 @@ impl<
                 output.into()
             }
             ToolCatalog::Fetch(input) => {
-                let output = self.services.fetch(input.url.clone(), input.raw).await?;
+                let output = self.services.fetch(input.clone()).await?;
+                (input, output).into()
+            }
+            ToolCatalog::WebSearch(input) => {
+                let output = self.services.web_search(input.clone()).await?;
                 (input, output).into()
             }
             ToolCatalog::Followup(input) => {
@@ -313,6 +409,26 @@ impl<
                     .await?;
                 (input, output).into()
             }
+            ToolCatalog::PlanStatus(input) => {
+                let normalized_path = self.normalize_path(input.path.clone());
+                let output = self.services.plan(Path::new(&normalized_path)).await?;
+                (input, output).into()
+            }
+            ToolCatalog::PlanUpdateStep(input) => {
+                let normalized_path = self.normalize_path(input.path.clone());
+                let output = self
+                    .services
+                    .update_plan_step(Path::new(&normalized_path), input.step, input.status)
+                    .await?;
+                (input, output).into()
+            }
+            ToolCatalog::Todo(input) => {
+                context.with_todos(|todos| *todos = input.todos.clone())?;
+                context
+                    .send(ChatResponse::TodoUpdate { todos: input.todos.clone() })
+                    .await?;
+                input.into()
+            }
             ToolCatalog::Skill(input) => {
                 let skill = self.services.fetch_skill(input.name.clone()).await?;
                 (input, skill).into()
@@ -346,7 +462,7 @@ impl<
         //     ));
         // }
 
-        let execution_result = self.call_internal(tool_input.clone()).await;
+        let execution_result = self.call_internal(tool_input.clone(), context).await;
 
         if let Err(ref error) = execution_result {
             tracing::error!(error = ?error, "Tool execution failed");
@@ -5,7 +5,7 @@ use anyhow::Context;
 use console::style;
 use forge_domain::{
     Agent, AgentId, AgentInput, ChatResponse, ChatResponseContent, ToolCallContext, ToolCallFull,
-    ToolCatalog, ToolDefinition, ToolName, ToolOutput, ToolResult,
+    ToolCatalog, ToolDefinition, ToolName, ToolOutput, ToolResult, WebSearchBackend,
 };
 use futures::future::join_all;
 use strum::IntoEnumIterator;
@@ -122,35 +122,49 @@ impl<S: Services> ToolRegistry<S> {
         ToolResult::new(tool_name).call_id(call_id).output(output)
     }
 
-    pub async fn list(&self) -> anyhow::Result<Vec<ToolDefinition>> {
-        Ok(self.tools_overview().await?.into())
+    /// Lists the tool definitions. `native_search_supported` tells whether the
+    /// provider in use can run native web searches.
+    pub async fn list(&self, native_search_supported: bool) -> anyhow::Result<Vec<ToolDefinition>> {
+        Ok(self.tools_overview(native_search_supported).await?.into())
     }
-    pub async fn tools_overview(&self) -> anyhow::Result<ToolsOverview> {
+    pub async fn tools_overview(
+        &self,
+        native_search_supported: bool,
+    ) -> anyhow::Result<ToolsOverview> {
         let mcp_tools = self.services.get_mcp_servers().await?;
         let agent_tools = self.agent_executor.agent_definitions().await?;
 
         // Check if current working directory is indexed
-        let cwd = self.services.get_environment().cwd.clone();
-        let is_indexed = self.services.is_indexed(&cwd).await.unwrap_or(false);
+        let env = self.services.get_environment();
+        let is_indexed = self.services.is_indexed(&env.cwd).await.unwrap_or(false);
         let is_authenticated = self.services.is_authenticated().await.unwrap_or(false);
+        // Web search needs a backend, and native searches need a provider that runs them
+        let web_search_supported = env
+            .web_search
+            .is_some_and(|backend| backend != WebSearchBackend::Native || native_search_supported);
 
         Ok(ToolsOverview::new()
-            .system(Self::get_system_tools(is_indexed && is_authenticated))
+            .system(Self::get_system_tools(
+                is_indexed && is_authenticated,
+                web_search_supported,
+            ))
             .agents(agent_tools)
             .mcp(mcp_tools))
     }
 }
 
 impl<S> ToolRegistry<S> {
-    fn get_system_tools(sem_search_supported: bool) -> Vec<ToolDefinition> {
+    fn get_system_tools(
+        sem_search_supported: bool,
+        web_search_supported: bool,
+    ) -> Vec<ToolDefinition> {
         ToolCatalog::iter()
-            .filter(|tool| {
+            .filter(|tool| match tool {
                 // Filter out sem_search if cwd is not indexed
-                if matches!(tool, ToolCatalog::SemSearch(_)) {
-                    sem_search_supported
-                } else {
-                    true
-                }
+                ToolCatalog::SemSearch(_) => sem_search_supported,
+                // Filter out web_search if no usable backend is configured
+                ToolCatalog::WebSearch(_) => web_search_supported,
+                _ => true,
             })
             .map(|tool| tool.definition())
             .collect::<Vec<_>>()
@@ -407,13 +421,22 @@ mod tests {
 
     #[test]
     fn test_sem_search_included_when_supported() {
-        let actual = ToolRegistry::<()>::get_system_tools(true);
+        let actual = ToolRegistry::<()>::get_system_tools(true, true);
         assert!(actual.iter().any(|t| t.name.as_str() == "sem_search"));
     }
 
     #[test]
     fn test_sem_search_filtered_when_not_supported() {
-        let actual = ToolRegistry::<()>::get_system_tools(false);
+        let actual = ToolRegistry::<()>::get_system_tools(false, true);
         assert!(actual.iter().all(|t| t.name.as_str() != "sem_search"));
     }
+
+    #[test]
+    fn test_web_search_filtered_when_not_supported() {
+        let actual = ToolRegistry::<()>::get_system_tools(true, false);
+        assert!(actual.iter().all(|t| t.name.as_str() != "web_search"));
+
+        let actual = ToolRegistry::<()>::get_system_tools(true, true);
+        assert!(actual.iter().any(|t| t.name.as_str() == "web_search"));
+    }
 }
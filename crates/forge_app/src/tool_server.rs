@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use forge_domain::{
+    Metrics, Permission, ToolCallContext, ToolCallFull, ToolCatalog, ToolDefinition, ToolName,
+    ToolOutput, ToolResult, WebSearchBackend,
+};
+use strum::IntoEnumIterator;
+
+use crate::error::Error;
+use crate::tool_executor::ToolExecutor;
+use crate::{ContextEngineService, EnvironmentService, PolicyService, Services};
+
+/// Serves Forge's built-in tools to external clients, such as other agents or
+/// IDEs connected through `forge mcp serve`.
+///
+/// Calls go through the same executor used for agent tool calls, so file
+/// edits are snapshotted and can be reverted with `undo`. Every call is
+/// checked against the configured permission policies before it runs, and
+/// calls that would need confirmation are denied.
+pub struct ToolServer<S> {
+    services: Arc<S>,
+    executor: ToolExecutor<S>,
+}
+
+impl<S: Services> ToolServer<S> {
+    pub fn new(services: Arc<S>) -> Self {
+        Self { executor: ToolExecutor::new(services.clone()), services }
+    }
+
+    /// Lists the tools exposed to external clients.
+    pub async fn list(&self) -> anyhow::Result<Vec<ToolDefinition>> {
+        Ok(self
+            .available_tools()
+            .await
+            .map(|tool| tool.definition())
+            .collect())
+    }
+
+    /// Executes a tool call on behalf of an external client.
+    pub async fn call(&self, call: ToolCallFull) -> ToolResult {
+        let call_id = call.call_id.clone();
+        let tool_name = call.name.clone();
+        let output = self.call_inner(call).await;
+
+        ToolResult::new(tool_name).call_id(call_id).output(output)
+    }
+
+    /// The exposed tools that can run in the current environment
+    async fn available_tools(&self) -> impl Iterator<Item = ToolCatalog> {
+        let env = self.services.get_environment();
+        let is_indexed = self.services.is_indexed(&env.cwd).await.unwrap_or(false);
+        let is_authenticated = self.services.is_authenticated().await.unwrap_or(false);
+        // Native searches run inside a provider, so they can't serve clients
+        let web_search_supported = env
+            .web_search
+            .is_some_and(|backend| backend != WebSearchBackend::Native);
+
+        Self::exposed_tools(is_indexed && is_authenticated, web_search_supported)
+    }
+
+    async fn call_inner(&self, call: ToolCallFull) -> anyhow::Result<ToolOutput> {
+        let is_available = self
+            .available_tools()
+            .await
+            .any(|tool| call.name.as_str() == tool.to_string());
+        if !is_available {
+            return Err(Error::NotFound(call.name).into());
+        }
+
+        // The client owns stdin and stdout, so there is nobody to confirm with
+        let input = ToolCatalog::try_from(call.clone())?;
+        if let Some(operation) = self.executor.tool_operation(&input) {
+            let permission = self.services.permission(&operation).await?;
+            authorize(&call.name, permission)?;
+        }
+
+        let context = ToolCallContext::new(Metrics::default());
+        self.executor.execute(call, &context).await
+    }
+}
+
+/// Allows a call only when the policies allow it without confirmation
+fn authorize(name: &ToolName, permission: Permission) -> Result<(), Error> {
+    match permission {
+        Permission::Allow => Ok(()),
+        Permission::Deny => Err(Error::PermissionDenied(name.clone())),
+        Permission::Confirm => Err(Error::ConfirmationRequired(name.clone())),
+    }
+}
+
+impl<S> ToolServer<S> {
+    /// Built-in tools that can run outside of an agent session. Tools that
+    /// need an interactive user or an active agent are left out.
+    fn exposed_tools(
+        sem_search_supported: bool,
+        web_search_supported: bool,
+    ) -> impl Iterator<Item = ToolCatalog> {
+        ToolCatalog::iter().filter(move |tool| match tool {
+            ToolCatalog::Followup(_)
+            | ToolCatalog::Plan(_)
+            | ToolCatalog::Todo(_)
+            | ToolCatalog::Skill(_) => false,
+            ToolCatalog::SemSearch(_) => sem_search_supported,
+            ToolCatalog::WebSearch(_) => web_search_supported,
+            _ => true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn names(sem_search_supported: bool, web_search_supported: bool) -> Vec<String> {
+        ToolServer::<()>::exposed_tools(sem_search_supported, web_search_supported)
+            .map(|tool| tool.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_exposed_tools_exclude_session_tools() {
+        let actual = names(true, true);
+        let expected = vec![
+            "read",
+            "read_image",
+            "write",
+            "search",
+            "ast_search",
+            "symbols",
+            "diagnostics",
+            "sem_search",
+            "remove",
+            "patch",
+            "undo",
+            "shell",
+            "fetch",
+            "web_search",
+            "plan_status",
+            "plan_update_step",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_exposed_tools_hide_sem_search_when_unsupported() {
+        let actual = names(false, true).contains(&"sem_search".to_string());
+        assert_eq!(actual, false);
+    }
+
+    #[test]
+    fn test_exposed_tools_hide_web_search_when_unconfigured() {
+        let actual = names(true, false).contains(&"web_search".to_string());
+        assert_eq!(actual, false);
+    }
+
+    #[test]
+    fn test_authorize_denies_calls_that_need_confirmation() {
+        let name = ToolName::new("shell");
+
+        let actual = [Permission::Allow, Permission::Deny, Permission::Confirm]
+            .map(|permission| authorize(&name, permission).map_err(|error| error.to_string()));
+
+        let expected = [
+            Ok(()),
+            Err("Tool 'shell' was denied by the configured permission policies".to_string()),
+            Err("Tool 'shell' needs confirmation under the configured permission policies, which external clients cannot give. Allow it in the permissions file to use it.".to_string()),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
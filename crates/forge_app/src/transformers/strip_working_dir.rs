@@ -81,6 +81,7 @@ impl Transformer for StripWorkingDir {
                         | SummaryTool::Search { .. }
                         | SummaryTool::SemSearch { .. }
                         | SummaryTool::Fetch { .. }
+                        | SummaryTool::WebSearch { .. }
                         | SummaryTool::Followup { .. }
                         | SummaryTool::Plan { .. }
                         | SummaryTool::Skill { .. } => {
@@ -28,6 +28,8 @@ enum Operation<'a> {
     },
     /// Fetch operation for a specific URL
     Fetch(&'a str),
+    /// Web search with a specific query
+    WebSearch(&'a str),
     /// Follow-up question
     Followup(&'a str),
     /// Plan creation with a specific name
@@ -52,6 +54,7 @@ fn to_op(tool: &SummaryTool) -> Operation<'_> {
             Operation::CodebaseSearch { queries, file_extension: file_extension.as_deref() }
         }
         SummaryTool::Fetch { url } => Operation::Fetch(url),
+        SummaryTool::WebSearch { query } => Operation::WebSearch(query),
         SummaryTool::Followup { question } => Operation::Followup(question),
         SummaryTool::Plan { plan_name } => Operation::Plan(plan_name),
         SummaryTool::Skill { name } => Operation::Skill(name),
@@ -34,7 +34,7 @@ impl<'a> ParsedLine<'a> {
     /// * `Some(ParsedLine)` if the line matches the expected format
     /// * `None` if the line is malformed
     fn parse(line: &'a str) -> Option<Self> {
-        let parts: Vec<_> = line.split(':').collect();
+        let parts: Vec<_> = line.splitn(3, ':').collect();
         if parts.len() != 3 {
             return None;
         }
@@ -286,6 +286,12 @@ mod tests {
             Some("fn"),
         );
 
+        suite.add(
+            "Content containing the separator",
+            vec!["src/lib.rs:7:impl fmt::Display for Error {"],
+            Some("impl"),
+        );
+
         suite.add(
             "Content with full-width unicode characters",
             vec![
@@ -2,9 +2,9 @@ use derive_setters::Setters;
 use merge::Merge;
 
 use crate::{
-    AgentDefinition, AgentId, Compact, Context, Error, EventContext, MaxTokens, ModelId,
-    ProviderId, ReasoningConfig, Result, SystemContext, Temperature, Template, ToolDefinition,
-    ToolName, TopK, TopP, Workflow,
+    AgentDefinition, AgentId, Compact, Context, Error, EventContext, GitContextConfig, MaxTokens,
+    ModelId, ProviderId, ReasoningConfig, Result, SystemContext, Temperature, Template,
+    ToolDefinition, ToolName, TopK, TopP, Workflow,
 };
 
 /// Runtime agent representation with required model and provider
@@ -71,6 +71,9 @@ pub struct Agent {
 
     /// Maximum number of requests that can be made in a single turn
     pub max_requests_per_turn: Option<usize>,
+
+    /// Repository state rendered into the system prompt
+    pub git_context: Option<GitContextConfig>,
 }
 
 impl Agent {
@@ -96,6 +99,7 @@ impl Agent {
             reasoning: Default::default(),
             max_tool_failure_per_turn: Default::default(),
             max_requests_per_turn: Default::default(),
+            git_context: Default::default(),
             path: Default::default(),
         }
     }
@@ -226,6 +230,7 @@ impl Agent {
             custom_rules: def.custom_rules,
             max_tool_failure_per_turn: def.max_tool_failure_per_turn,
             max_requests_per_turn: def.max_requests_per_turn,
+            git_context: def.git_context,
             path: def.path,
         }
     }
@@ -1,7 +1,7 @@
 use nom::Parser;
 use nom::bytes::complete::tag;
 
-use crate::Image;
+use crate::{Document, Image};
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq, Eq)]
 pub struct Attachment {
@@ -21,6 +21,59 @@ pub enum AttachmentContent {
     DirectoryListing {
         entries: Vec<DirectoryEntry>,
     },
+    McpResource {
+        server: String,
+        uri: String,
+        mime_type: Option<String>,
+        content: String,
+    },
+    GitContent {
+        content: String,
+        start_line: u64,
+        end_line: u64,
+        total_lines: u64,
+    },
+    UrlContent {
+        content: String,
+        content_type: String,
+        total_chars: usize,
+        /// Temporary file holding the complete content when it was truncated
+        full_content_path: Option<String>,
+    },
+    /// A document passed natively to providers that support it, such as a
+    /// PDF restricted to the selected pages
+    Document {
+        document: Document,
+        start_page: u64,
+        end_page: u64,
+        total_pages: u64,
+    },
+    /// Text extracted from a document, with the range counted in `unit`
+    DocumentText {
+        content: String,
+        unit: DocumentUnit,
+        start: u64,
+        end: u64,
+        total: u64,
+    },
+    /// Note that `count` files matching a glob were left out once the
+    /// attachment limits were reached
+    OmittedFiles {
+        count: usize,
+    },
+}
+
+/// Unit in which a range of an extracted document is selected
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DocumentUnit {
+    Page,
+    Paragraph,
+    Row,
+    Sheet,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq, Eq)]
@@ -42,6 +95,12 @@ impl AttachmentContent {
             AttachmentContent::Image(_) => false,
             AttachmentContent::FileContent { content, .. } => content.contains(text),
             AttachmentContent::DirectoryListing { .. } => false,
+            AttachmentContent::McpResource { content, .. } => content.contains(text),
+            AttachmentContent::GitContent { content, .. } => content.contains(text),
+            AttachmentContent::UrlContent { content, .. } => content.contains(text),
+            AttachmentContent::Document { document, .. } => document.text().contains(text),
+            AttachmentContent::DocumentText { content, .. } => content.contains(text),
+            AttachmentContent::OmittedFiles { .. } => false,
         }
     }
 
@@ -54,7 +113,8 @@ impl AttachmentContent {
 
     pub fn range_info(&self) -> Option<(u64, u64, u64)> {
         match self {
-            AttachmentContent::FileContent { start_line, end_line, total_lines, .. } => {
+            AttachmentContent::FileContent { start_line, end_line, total_lines, .. }
+            | AttachmentContent::GitContent { start_line, end_line, total_lines, .. } => {
                 Some((*start_line, *end_line, *total_lines))
             }
             _ => None,
@@ -98,6 +158,195 @@ impl Attachment {
 
         tags
     }
+
+    /// Parses a string and extracts every attachment tag, like
+    /// [`Attachment::parse_all`] does for files. Resources published by MCP servers are referenced as
+    /// @[mcp:server/uri], git diffs, commits, file revisions and blame as
+    /// @[git:...], web pages as @[https://...] and sets of files as globs
+    /// such as @[src/**/*.sql]. For documents the range selects pages of a
+    /// PDF, sheets of a spreadsheet, rows of a CSV file or paragraphs of a
+    /// DOCX/ODT file, e.g. @[spec.pdf:3:5].
+    pub fn parse_tags<T: ToString>(text: T) -> Vec<AttachmentTag> {
+        let input = text.to_string();
+        let mut remaining = input.as_str();
+        let mut tags = Vec::new();
+
+        while !remaining.is_empty() {
+            // Find the next "@[" pattern
+            if let Some(start_pos) = remaining.find("@[") {
+                // Move to the position where "@[" starts
+                remaining = &remaining[start_pos..];
+                match AttachmentTag::parse(remaining) {
+                    Ok((next_remaining, tag)) => {
+                        tags.push(tag);
+                        remaining = next_remaining;
+                    }
+                    Err(_e) => {
+                        // Skip the "@[" since we couldn't parse it
+                        remaining = &remaining[2..];
+                    }
+                }
+            } else {
+                // No more "@[" patterns found
+                break;
+            }
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        tags.retain(|tag| seen.insert(tag.clone()));
+
+        tags
+    }
+}
+
+/// A reference to attach, as written in a message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AttachmentTag {
+    File(FileTag),
+    McpResource(McpResourceTag),
+    Git(GitTag),
+    Url(String),
+    Glob(String),
+}
+
+impl AttachmentTag {
+    pub fn parse(input: &str) -> nom::IResult<&str, AttachmentTag> {
+        nom::branch::alt((
+            nom::combinator::map(McpResourceTag::parse, AttachmentTag::McpResource),
+            nom::combinator::map(GitTag::parse, AttachmentTag::Git),
+            nom::combinator::map(Self::parse_url, |url| AttachmentTag::Url(url.to_string())),
+            nom::combinator::map(FileTag::parse, |tag| {
+                if tag.path.contains(['*', '?']) {
+                    AttachmentTag::Glob(tag.path)
+                } else {
+                    AttachmentTag::File(tag)
+                }
+            }),
+        ))
+        .parse(input)
+    }
+
+    fn parse_url(input: &str) -> nom::IResult<&str, &str> {
+        use nom::bytes::complete::take_while1;
+        use nom::character::complete::char;
+        use nom::sequence::delimited;
+
+        delimited(
+            tag("@["),
+            nom::combinator::recognize((
+                nom::branch::alt((tag("https://"), tag("http://"))),
+                take_while1(|c: char| c != ']'),
+            )),
+            char(']'),
+        )
+        .parse(input)
+    }
+}
+
+/// A resource published by an MCP server, written as @[mcp:server/uri].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct McpResourceTag {
+    pub server: String,
+    pub uri: String,
+}
+
+impl McpResourceTag {
+    pub fn parse(input: &str) -> nom::IResult<&str, McpResourceTag> {
+        use nom::bytes::complete::take_while1;
+        use nom::character::complete::char;
+        use nom::sequence::{delimited, preceded};
+
+        let mut parser = delimited(
+            tag("@[mcp:"),
+            (
+                take_while1(|c: char| c != '/' && c != ']'),
+                preceded(char('/'), take_while1(|c: char| c != ']')),
+            ),
+            char(']'),
+        );
+
+        let (remaining, (server, uri)) = parser.parse(input)?;
+        Ok((
+            remaining,
+            McpResourceTag { server: server.to_string(), uri: uri.to_string() },
+        ))
+    }
+}
+
+/// A git object, written as one of:
+/// - @[git:diff] for uncommitted changes against HEAD
+/// - @[git:diff:<ref>] for the working tree against a ref
+/// - @[git:commit:<sha>] for a commit with its patch
+/// - @[git:show:<rev>:<path>] for a file at a revision
+/// - @[git:blame:<path>] or @[git:blame:<path>:L<start>-<end>]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GitTag {
+    Diff { base: Option<String> },
+    Commit { sha: String },
+    Show { rev: String, path: String },
+    Blame { path: String, loc: Option<Location> },
+}
+
+impl GitTag {
+    pub fn parse(input: &str) -> nom::IResult<&str, GitTag> {
+        use nom::bytes::complete::take_while1;
+        use nom::character::complete::char;
+        use nom::sequence::delimited;
+
+        let mut parser = nom::combinator::map_opt(
+            delimited(tag("@[git:"), take_while1(|c: char| c != ']'), char(']')),
+            Self::from_reference,
+        );
+        parser.parse(input)
+    }
+
+    fn from_reference(reference: &str) -> Option<GitTag> {
+        let (kind, rest) = reference.split_once(':').unwrap_or((reference, ""));
+        match kind {
+            "diff" => Some(GitTag::Diff { base: (!rest.is_empty()).then(|| rest.to_string()) }),
+            "commit" if !rest.is_empty() => Some(GitTag::Commit { sha: rest.to_string() }),
+            "show" => {
+                let (rev, path) = rest.split_once(':')?;
+                (!rev.is_empty() && !path.is_empty())
+                    .then(|| GitTag::Show { rev: rev.to_string(), path: path.to_string() })
+            }
+            "blame" if !rest.is_empty() => {
+                let Some((path, range)) = rest.rsplit_once(":L") else {
+                    return Some(GitTag::Blame { path: rest.to_string(), loc: None });
+                };
+                let (start, end) = match range.split_once('-') {
+                    Some((start, end)) => (start.parse().ok()?, Some(end.parse().ok()?)),
+                    None => (range.parse().ok()?, None),
+                };
+                Some(GitTag::Blame {
+                    path: path.to_string(),
+                    loc: Some(Location { start: Some(start), end }),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for GitTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitTag::Diff { base: None } => write!(f, "git:diff"),
+            GitTag::Diff { base: Some(base) } => write!(f, "git:diff:{base}"),
+            GitTag::Commit { sha } => write!(f, "git:commit:{sha}"),
+            GitTag::Show { rev, path } => write!(f, "git:show:{rev}:{path}"),
+            GitTag::Blame { path, loc } => {
+                write!(f, "git:blame:{path}")?;
+                match loc {
+                    Some(Location { start: Some(start), end: Some(end) }) => {
+                        write!(f, ":L{start}-{end}")
+                    }
+                    Some(Location { start: Some(start), end: None }) => write!(f, ":L{start}"),
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -548,4 +797,109 @@ mod tests {
         assert!(paths.contains(&expected_unix));
         assert!(paths.contains(&expected_windows));
     }
+
+    #[test]
+    fn test_attachment_parse_mcp_resource() {
+        let text = String::from("Use @[mcp:docs/file:///guides/setup.md] for context");
+        let actual = Attachment::parse_tags(text);
+        let expected = vec![AttachmentTag::McpResource(McpResourceTag {
+            server: "docs".to_string(),
+            uri: "file:///guides/setup.md".to_string(),
+        })];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_attachment_parse_mcp_resource_with_files() {
+        let text = String::from("Compare @[mcp:db/postgres://main/schema] with @[/src/schema.sql]");
+        let actual = Attachment::parse_tags(text);
+        let expected = vec![
+            AttachmentTag::McpResource(McpResourceTag {
+                server: "db".to_string(),
+                uri: "postgres://main/schema".to_string(),
+            }),
+            AttachmentTag::File(FileTag {
+                path: "/src/schema.sql".to_string(),
+                loc: None,
+                symbol: None,
+            }),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_attachment_parse_mcp_resource_without_uri() {
+        let text = String::from("Broken @[mcp:docs]");
+        let actual = Attachment::parse_tags(text);
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn test_attachment_parse_git_tags() {
+        let text = String::from(
+            "Review @[git:diff] and @[git:diff:main], then @[git:commit:abc1234], @[git:show:HEAD~1:src/lib.rs] and @[git:blame:src/main.rs:L10-40]",
+        );
+        let actual = Attachment::parse_tags(text);
+        let expected = vec![
+            AttachmentTag::Git(GitTag::Diff { base: None }),
+            AttachmentTag::Git(GitTag::Diff { base: Some("main".to_string()) }),
+            AttachmentTag::Git(GitTag::Commit { sha: "abc1234".to_string() }),
+            AttachmentTag::Git(GitTag::Show {
+                rev: "HEAD~1".to_string(),
+                path: "src/lib.rs".to_string(),
+            }),
+            AttachmentTag::Git(GitTag::Blame {
+                path: "src/main.rs".to_string(),
+                loc: Some(Location { start: Some(10), end: Some(40) }),
+            }),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_attachment_parse_git_invalid() {
+        let text = String::from("Broken @[git:log] and @[git:show:HEAD]");
+        let actual = Attachment::parse_tags(text);
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn test_git_tag_display_round_trip() {
+        let fixture = [
+            "git:diff",
+            "git:diff:origin/main",
+            "git:commit:abc1234",
+            "git:show:v1.0:README.md",
+            "git:blame:src/lib.rs",
+            "git:blame:src/lib.rs:L5",
+            "git:blame:src/lib.rs:L5-9",
+        ];
+        let actual = fixture
+            .iter()
+            .map(|reference| {
+                let (_, tag) = GitTag::parse(&format!("@[{reference}]")).unwrap();
+                tag.to_string()
+            })
+            .collect::<Vec<_>>();
+        let expected = fixture.map(str::to_string).to_vec();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_attachment_parse_url_and_glob() {
+        let text = String::from(
+            "Follow @[https://docs.rs/nom/latest/nom/] for @[migrations/**/*.sql] and @[src/lib.rs]",
+        );
+        let actual = Attachment::parse_tags(text);
+        let expected = vec![
+            AttachmentTag::Url("https://docs.rs/nom/latest/nom/".to_string()),
+            AttachmentTag::Glob("migrations/**/*.sql".to_string()),
+            AttachmentTag::File(FileTag {
+                path: "src/lib.rs".to_string(),
+                loc: None,
+                symbol: None,
+            }),
+        ];
+        assert_eq!(actual, expected);
+    }
 }
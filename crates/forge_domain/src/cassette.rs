@@ -0,0 +1,249 @@
+use std::hash::Hasher;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+use crate::{ChatCompletionMessage, Context, Model, ModelId};
+
+/// The date agents put in user prompts, which changes between recording and
+/// replaying
+static SYSTEM_DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<system_date>[^<]*</system_date>").unwrap());
+
+/// The parts of the system prompt that describe the machine and the working
+/// directory, which differ between recording and replaying
+static SYSTEM_INFO: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?s)<(operating_system|current_working_directory|default_shell|home_directory|file_list|git_status)>.*?</(operating_system|current_working_directory|default_shell|home_directory|file_list|git_status)>",
+    )
+    .unwrap()
+});
+
+/// What the replay provider does when a request matches none of the recorded
+/// interactions
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+pub enum ReplayMismatch {
+    /// Fail the request
+    #[default]
+    Strict,
+    /// Serve the first unused interaction in recording order. Useful when
+    /// requests differ between runs, e.g. by the date or working directory in
+    /// the prompt.
+    Lenient,
+}
+
+/// A request sent to a provider and the response it streamed back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub model: ModelId,
+    pub request_hash: String,
+    pub response: Vec<ChatCompletionMessage>,
+}
+
+impl Interaction {
+    pub fn new(model: &ModelId, context: &Context, response: Vec<ChatCompletionMessage>) -> Self {
+        Self {
+            model: model.clone(),
+            request_hash: Self::request_hash(model, context),
+            response,
+        }
+    }
+
+    /// Returns a hash identifying a request to `model` with `context`. Only
+    /// the messages and the tools are hashed, as the conversation id is
+    /// generated for every session. The date and the environment described
+    /// in the system prompt, such as the working directory, are blanked out.
+    pub fn request_hash(model: &ModelId, context: &Context) -> String {
+        let messages = context
+            .messages
+            .iter()
+            .map(|entry| &entry.message)
+            .collect::<Vec<_>>();
+        let request = serde_json::to_string(&(messages, &context.tools)).unwrap_or_default();
+        let request = SYSTEM_DATE.replace_all(&request, "<system_date></system_date>");
+        let request = SYSTEM_INFO.replace_all(&request, |captures: &regex::Captures| {
+            format!("<{0}></{0}>", &captures[1])
+        });
+
+        let mut hasher = fnv_rs::Fnv64::default();
+        hasher.write(model.as_str().as_bytes());
+        hasher.write(request.as_bytes());
+        format!("{:x}", hasher.finish())
+    }
+}
+
+/// Provider interactions recorded during a session, in the order they
+/// happened. Cassettes are stored as JSON so they can be committed and
+/// replayed without network access.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Models of the recorded provider, so that replayed requests are built
+    /// with the same capabilities
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub models: Vec<Model>,
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Returns the index of the interaction to replay for a request to
+    /// `model` with `context`, skipping those already `used`. An interaction
+    /// with the same request hash is preferred; otherwise the first unused one
+    /// is returned if `mismatch` is lenient.
+    pub fn find(
+        &self,
+        model: &ModelId,
+        context: &Context,
+        used: &[bool],
+        mismatch: ReplayMismatch,
+    ) -> Option<usize> {
+        let hash = Interaction::request_hash(model, context);
+        let mut unused = self
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !used.get(*index).copied().unwrap_or_default());
+
+        match mismatch {
+            ReplayMismatch::Strict => unused
+                .find(|(_, interaction)| interaction.request_hash == hash)
+                .map(|(index, _)| index),
+            ReplayMismatch::Lenient => {
+                let unused = unused.collect::<Vec<_>>();
+                unused
+                    .iter()
+                    .find(|(_, interaction)| interaction.request_hash == hash)
+                    .or(unused.first())
+                    .map(|(index, _)| *index)
+            }
+        }
+    }
+
+    /// Returns the recorded models, followed by models used in interactions
+    /// that were recorded without their details
+    pub fn all_models(&self) -> Vec<Model> {
+        let mut models = self.models.clone();
+        for interaction in &self.interactions {
+            if !models.iter().any(|model| model.id == interaction.model) {
+                models.push(Model {
+                    id: interaction.model.clone(),
+                    name: None,
+                    description: None,
+                    context_length: None,
+                    tools_supported: Some(true),
+                    supports_parallel_tool_calls: None,
+                    supports_reasoning: None,
+                });
+            }
+        }
+        models
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{ContextMessage, ConversationId};
+
+    fn context(prompt: &str) -> Context {
+        Context::default().add_message(ContextMessage::user(prompt, None))
+    }
+
+    fn cassette() -> Cassette {
+        let model = ModelId::new("gpt-4o");
+        Cassette {
+            models: vec![],
+            interactions: vec![
+                Interaction::new(&model, &context("first"), vec![]),
+                Interaction::new(&model, &context("second"), vec![]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_request_hash_ignores_conversation_id() {
+        let model = ModelId::new("gpt-4o");
+        let fixture = context("hello");
+
+        let actual = Interaction::request_hash(
+            &model,
+            &fixture.clone().conversation_id(ConversationId::generate()),
+        );
+
+        let expected = Interaction::request_hash(&model, &fixture);
+        assert_eq!(actual, expected);
+        assert_ne!(
+            actual,
+            Interaction::request_hash(&ModelId::new("o3"), &fixture)
+        );
+    }
+
+    #[test]
+    fn test_request_hash_ignores_environment() {
+        let model = ModelId::new("gpt-4o");
+        let request = |cwd: &str, date: &str| {
+            Context::default()
+                .add_message(ContextMessage::system(format!(
+                    "<current_working_directory>{cwd}</current_working_directory>"
+                )))
+                .add_message(ContextMessage::user(
+                    format!("<task>Fix the build</task>\n<system_date>{date}</system_date>"),
+                    None,
+                ))
+        };
+
+        let actual = Interaction::request_hash(&model, &request("/home/ci/app", "2026-10-19"));
+
+        let expected = Interaction::request_hash(&model, &request("/Users/dev/app", "2026-01-02"));
+        assert_eq!(actual, expected);
+        assert_ne!(
+            actual,
+            Interaction::request_hash(&model, &context("Fix the build"))
+        );
+    }
+
+    #[test]
+    fn test_request_hash_detects_changed_system_prompt() {
+        let model = ModelId::new("gpt-4o");
+        let request = |prompt: &str| {
+            Context::default()
+                .add_message(ContextMessage::system(format!(
+                    "{prompt}\n<system_information>\n<operating_system>linux</operating_system>\n</system_information>"
+                )))
+                .add_message(ContextMessage::user("Fix the build", None))
+        };
+
+        let actual = Interaction::request_hash(&model, &request("You are a careful reviewer"));
+
+        let expected = Interaction::request_hash(&model, &request("You are a fast coder"));
+        assert_ne!(actual, expected);
+    }
+
+    #[test]
+    fn test_find_matches_by_request_hash() {
+        let fixture = cassette();
+        let model = ModelId::new("gpt-4o");
+
+        let actual = [
+            fixture.find(&model, &context("second"), &[], ReplayMismatch::Strict),
+            fixture.find(
+                &model,
+                &context("second"),
+                &[false, true],
+                ReplayMismatch::Strict,
+            ),
+            fixture.find(&model, &context("other"), &[], ReplayMismatch::Strict),
+            fixture.find(&model, &context("other"), &[true], ReplayMismatch::Lenient),
+        ];
+
+        let expected = [Some(1), None, None, Some(1)];
+        assert_eq!(actual, expected);
+    }
+}
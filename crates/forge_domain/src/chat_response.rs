@@ -2,10 +2,12 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use chrono::Local;
+use serde::{Serialize, Serializer};
 
-use crate::{ToolCallFull, ToolName, ToolResult};
+use crate::{Todo, ToolCallFull, ToolName, ToolResult};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "format", content = "value", rename_all = "snake_case")]
 pub enum ChatResponseContent {
     Title(TitleFormat),
     PlainText(String),
@@ -44,15 +46,37 @@ impl ChatResponseContent {
 
 /// Events that are emitted by the agent for external consumption. This includes
 /// events for all internal state changes.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum ChatResponse {
-    TaskMessage { content: ChatResponseContent },
-    TaskReasoning { content: String },
+    TaskMessage {
+        content: ChatResponseContent,
+    },
+    TaskReasoning {
+        content: String,
+    },
     TaskComplete,
     ToolCallStart(ToolCallFull),
     ToolCallEnd(ToolResult),
-    RetryAttempt { cause: Cause, duration: Duration },
-    Interrupt { reason: InterruptionReason },
+    RetryAttempt {
+        cause: Cause,
+        #[serde(rename = "duration_ms", serialize_with = "serialize_duration_ms")]
+        duration: Duration,
+    },
+    Interrupt {
+        reason: InterruptionReason,
+    },
+    /// The task list of the conversation was replaced
+    TodoUpdate {
+        todos: Vec<Todo>,
+    },
+}
+
+fn serialize_duration_ms<S: Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_millis())
 }
 
 impl ChatResponse {
@@ -74,7 +98,8 @@ impl ChatResponse {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum InterruptionReason {
     MaxToolFailurePerTurnLimitReached {
         limit: u64,
@@ -85,7 +110,7 @@ pub enum InterruptionReason {
     },
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 pub struct Cause(String);
 
 impl Cause {
@@ -110,7 +135,8 @@ impl From<&anyhow::Error> for Cause {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Category {
     Action,
     Info,
@@ -120,7 +146,7 @@ pub enum Category {
     Warning,
 }
 
-#[derive(Clone, derive_setters::Setters, Debug, PartialEq)]
+#[derive(Clone, derive_setters::Setters, Debug, PartialEq, Serialize)]
 #[setters(into, strip_option)]
 pub struct TitleFormat {
     pub title: String,
@@ -216,4 +242,58 @@ mod tests {
         assert_eq!(title.category, Category::Action);
         assert_eq!(title.timestamp, timestamp);
     }
+
+    #[test]
+    fn test_chat_response_serializes_with_type_tag() {
+        let fixture = ChatResponse::TaskMessage {
+            content: ChatResponseContent::Markdown("Hello".to_string()),
+        };
+
+        let actual = serde_json::to_value(&fixture).unwrap();
+
+        let expected = serde_json::json!({
+            "type": "task_message",
+            "content": {"format": "markdown", "value": "Hello"}
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_retry_attempt_serializes_duration_in_millis() {
+        let fixture = ChatResponse::RetryAttempt {
+            cause: Cause("rate limited".to_string()),
+            duration: Duration::from_millis(1500),
+        };
+
+        let actual = serde_json::to_value(&fixture).unwrap();
+
+        let expected = serde_json::json!({
+            "type": "retry_attempt",
+            "cause": "rate limited",
+            "duration_ms": 1500
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_interrupt_serializes_reason_kind() {
+        let fixture = ChatResponse::Interrupt {
+            reason: InterruptionReason::MaxRequestPerTurnLimitReached { limit: 10 },
+        };
+
+        let actual = serde_json::to_value(&fixture).unwrap();
+
+        let expected = serde_json::json!({
+            "type": "interrupt",
+            "reason": {"kind": "max_request_per_turn_limit_reached", "limit": 10}
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_task_complete_serializes_as_tag_only() {
+        let actual = serde_json::to_value(ChatResponse::TaskComplete).unwrap();
+        let expected = serde_json::json!({"type": "task_complete"});
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,233 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{Command, Error, Result};
+
+/// An argument accepted by a custom command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CommandArgument {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Type the value is parsed into before it is rendered
+    #[serde(default, rename = "type")]
+    pub kind: CommandArgumentType,
+
+    /// Whether the command fails when no value and no default is given
+    #[serde(default)]
+    pub required: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+impl CommandArgument {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            kind: CommandArgumentType::default(),
+            required: false,
+            default: None,
+        }
+    }
+
+    fn parse(&self, command: &str, value: &str) -> Result<Value> {
+        let invalid = || Error::InvalidCommandArgument {
+            command: command.to_string(),
+            argument: self.name.clone(),
+            value: value.to_string(),
+            expected: self.kind,
+        };
+
+        match self.kind {
+            CommandArgumentType::String => Ok(Value::String(value.to_string())),
+            CommandArgumentType::Number => value
+                .parse::<serde_json::Number>()
+                .map(Value::Number)
+                .map_err(|_| invalid()),
+            CommandArgumentType::Boolean => value
+                .parse::<bool>()
+                .map(Value::Bool)
+                .map_err(|_| invalid()),
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CommandArgumentType {
+    #[default]
+    String,
+    Number,
+    Boolean,
+}
+
+impl Command {
+    /// Binds the values typed after the command to its declared arguments.
+    ///
+    /// Values written as `name=value` are bound to the matching argument.
+    /// Remaining values fill the unbound arguments in declaration order, and
+    /// any surplus is appended to the last argument. Missing arguments fall
+    /// back to their default.
+    pub fn bind_arguments(&self, values: &[String]) -> Result<Map<String, Value>> {
+        let mut raw: Vec<(&str, String)> = Vec::new();
+        let mut positional = Vec::new();
+
+        for value in values {
+            match value.split_once('=') {
+                Some((name, value)) if self.arguments.iter().any(|arg| arg.name == name) => {
+                    raw.push((name, value.to_string()));
+                }
+                _ => positional.push(value.as_str()),
+            }
+        }
+
+        let unbound = self
+            .arguments
+            .iter()
+            .filter(|arg| !raw.iter().any(|(name, _)| *name == arg.name))
+            .collect::<Vec<_>>();
+
+        if let Some((last, rest)) = unbound.split_last() {
+            let mut positional = positional.into_iter();
+            for arg in rest {
+                if let Some(value) = positional.next() {
+                    raw.push((arg.name.as_str(), value.to_string()));
+                }
+            }
+            let remaining = positional.collect::<Vec<_>>();
+            if !remaining.is_empty() {
+                raw.push((last.name.as_str(), remaining.join(" ")));
+            }
+        }
+
+        let mut bound = Map::new();
+        for arg in &self.arguments {
+            let value = raw
+                .iter()
+                .find(|(name, _)| *name == arg.name)
+                .map(|(_, value)| value.as_str())
+                .or(arg.default.as_deref());
+
+            match value {
+                Some(value) => {
+                    bound.insert(arg.name.clone(), arg.parse(&self.name, value)?);
+                }
+                None if arg.required => {
+                    return Err(Error::MissingCommandArgument {
+                        command: self.name.clone(),
+                        argument: arg.name.clone(),
+                    });
+                }
+                None => {}
+            }
+        }
+
+        Ok(bound)
+    }
+
+    /// Returns a usage hint such as `<pr> [focus]`, or `None` when the command
+    /// takes no arguments.
+    pub fn usage(&self) -> Option<String> {
+        if self.arguments.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.arguments
+                .iter()
+                .map(|arg| {
+                    if arg.required {
+                        format!("<{}>", arg.name)
+                    } else {
+                        format!("[{}]", arg.name)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn fixture() -> Command {
+        Command::default().name("review-pr").arguments(vec![
+            CommandArgument {
+                kind: CommandArgumentType::Number,
+                required: true,
+                ..CommandArgument::new("pr")
+            },
+            CommandArgument {
+                default: Some("all".to_string()),
+                ..CommandArgument::new("focus")
+            },
+        ])
+    }
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_bind_arguments_positional_with_surplus() {
+        let actual = fixture()
+            .bind_arguments(&values(&["1234", "error", "handling"]))
+            .unwrap();
+        let expected = json!({"pr": 1234, "focus": "error handling"});
+        assert_eq!(Value::Object(actual), expected);
+    }
+
+    #[test]
+    fn test_bind_arguments_named_and_default() {
+        let actual = fixture().bind_arguments(&values(&["pr=42"])).unwrap();
+        let expected = json!({"pr": 42, "focus": "all"});
+        assert_eq!(Value::Object(actual), expected);
+    }
+
+    #[test]
+    fn test_bind_arguments_missing_required() {
+        let actual = fixture().bind_arguments(&[]).unwrap_err().to_string();
+        let expected = "Missing required argument 'pr' for command 'review-pr'";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_bind_arguments_invalid_type() {
+        let actual = fixture()
+            .bind_arguments(&values(&["abc"]))
+            .unwrap_err()
+            .to_string();
+        let expected =
+            "Invalid value 'abc' for argument 'pr' of command 'review-pr', expected a number";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_usage() {
+        let actual = fixture().usage();
+        let expected = Some("<pr> [focus]".to_string());
+        assert_eq!(actual, expected);
+    }
+}
@@ -62,6 +62,32 @@ pub struct Compact {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[merge(strategy = crate::merge::option)]
     pub on_turn_end: Option<bool>,
+
+    /// How the compacted messages are summarized. Defaults to `structural`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[merge(strategy = crate::merge::option)]
+    pub mode: Option<CompactionMode>,
+}
+
+/// How compacted messages are summarized
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactionMode {
+    /// Lists the messages and tool calls that were compacted
+    #[default]
+    Structural,
+    /// Asks the compaction model for a summary of the goals, decisions, open
+    /// TODOs and touched files. Falls back to `structural` if the model fails.
+    Llm,
+    /// Combines the model summary with the structural one
+    Hybrid,
+}
+
+impl CompactionMode {
+    /// Returns true if the mode asks the compaction model for a summary
+    pub fn uses_model(&self) -> bool {
+        matches!(self, Self::Llm | Self::Hybrid)
+    }
 }
 
 fn deserialize_percentage<'de, D>(deserializer: D) -> Result<f64, D::Error>
@@ -115,6 +141,7 @@ impl Compact {
             eviction_window: 0.2, // Default to 20% compaction
             retention_window: 0,
             on_turn_end: None,
+            mode: None,
         }
     }
 
@@ -73,6 +73,37 @@ impl CompactionStrategy {
         let retention = self.to_fixed(context);
         find_sequence_preserving_last_n(context, retention)
     }
+
+    /// Splits the sequence to compact into the runs of messages between
+    /// pinned ones, so that pinned messages are never evicted
+    pub fn eviction_ranges(&self, context: &Context) -> Vec<(usize, usize)> {
+        let Some((start, end)) = self.eviction_range(context) else {
+            return Vec::new();
+        };
+        let messages = &context.messages;
+
+        let mut ranges = Vec::new();
+        let mut index = start;
+        while index <= end {
+            // Results of a pinned tool call are kept along with it
+            if messages[index].pinned || messages[index].has_tool_result() {
+                index += 1;
+                continue;
+            }
+            let run_start = index;
+            while index < end && !messages[index + 1].pinned {
+                index += 1;
+            }
+            // A call is kept when its results are pinned
+            let run_end = match messages[index].has_tool_call() && index < end {
+                true => index.checked_sub(1).filter(|run_end| *run_end >= run_start),
+                false => Some(index),
+            };
+            ranges.extend(run_end.map(|run_end| (run_start, run_end)));
+            index += 1;
+        }
+        ranges
+    }
 }
 
 /// Finds a sequence in the context for compaction, starting from the first
@@ -177,6 +208,45 @@ mod tests {
         result
     }
 
+    #[test]
+    fn test_eviction_ranges_split_around_pinned_messages() {
+        let mut fixture = context_from_pattern("suauauauau");
+        fixture.messages[3].pinned = true;
+        fixture.messages[6].pinned = true;
+
+        let actual = CompactionStrategy::retain(1).eviction_ranges(&fixture);
+
+        let expected = vec![(2, 2), (4, 5), (7, 8)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_eviction_ranges_keep_results_of_pinned_calls() {
+        let mut fixture = context_from_pattern("suatrauau");
+        fixture.messages[3].pinned = true;
+
+        let actual = CompactionStrategy::retain(1).eviction_ranges(&fixture);
+
+        let expected = vec![(2, 2), (5, 7)];
+        assert_eq!(actual, expected);
+
+        fixture.messages[7].pinned = true;
+        let actual = CompactionStrategy::retain(1).eviction_ranges(&fixture);
+        let expected = vec![(2, 2), (5, 6)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_eviction_ranges_keep_calls_of_pinned_results() {
+        let mut fixture = context_from_pattern("suauatrau");
+        fixture.messages[6].pinned = true;
+
+        let actual = CompactionStrategy::retain(1).eviction_ranges(&fixture);
+
+        let expected = vec![(2, 4), (7, 7)];
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_sequence_finding() {
         // Basic compaction scenarios
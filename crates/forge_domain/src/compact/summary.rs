@@ -5,8 +5,8 @@ use derive_more::From;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    Context, ContextMessage, Role, SearchQuery, TextMessage, ToolCallFull, ToolCallId, ToolCatalog,
-    ToolResult,
+    Context, ContextMessage, Role, SearchQuery, SymbolQuery, TextMessage, ToolCallFull, ToolCallId,
+    ToolCatalog, ToolResult,
 };
 
 /// A simplified summary of a context, focusing on messages and their tool calls
@@ -195,6 +195,9 @@ pub enum SummaryTool {
     Fetch {
         url: String,
     },
+    WebSearch {
+        query: String,
+    },
     Followup {
         question: String,
     },
@@ -239,7 +242,7 @@ impl From<&Context> for ContextSummary {
                         tool_results.insert(call_id, tool_result);
                     }
                 }
-                ContextMessage::Image(_) => {}
+                ContextMessage::Image(_) | ContextMessage::Document(_) => {}
             }
         }
 
@@ -308,14 +311,25 @@ fn extract_tool_info(call: &ToolCallFull) -> Option<SummaryTool> {
             .file_pattern
             .or(input.regex)
             .map(|pattern| SummaryTool::Search { pattern }),
+        ToolCatalog::AstSearch(input) => Some(SummaryTool::Search { pattern: input.query }),
+        ToolCatalog::Symbols(input) => Some(match input.query {
+            SymbolQuery::Outline => SummaryTool::FileRead { path: input.target },
+            _ => SummaryTool::Search { pattern: input.target },
+        }),
+        ToolCatalog::Diagnostics(input) => Some(SummaryTool::FileRead { path: input.path }),
         ToolCatalog::SemSearch(input) => Some(SummaryTool::SemSearch {
             queries: input.queries,
             file_extension: input.file_extension,
         }),
         ToolCatalog::Undo(input) => Some(SummaryTool::Undo { path: input.path }),
         ToolCatalog::Fetch(input) => Some(SummaryTool::Fetch { url: input.url }),
+        ToolCatalog::WebSearch(input) => Some(SummaryTool::WebSearch { query: input.query }),
         ToolCatalog::Followup(input) => Some(SummaryTool::Followup { question: input.question }),
         ToolCatalog::Plan(input) => Some(SummaryTool::Plan { plan_name: input.plan_name }),
+        ToolCatalog::PlanStatus(input) => Some(SummaryTool::FileRead { path: input.path }),
+        ToolCatalog::PlanUpdateStep(input) => Some(SummaryTool::FileUpdate { path: input.path }),
+        // The latest task list is added to every summary from the conversation
+        ToolCatalog::Todo(_) => None,
         ToolCatalog::Skill(input) => Some(SummaryTool::Skill { name: input.name }),
     }
 }
@@ -1147,6 +1161,31 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_context_summary_extracts_web_search_tool_calls() {
+        let fixture = context(vec![assistant_with_tools(
+            "Searching",
+            vec![ToolCatalog::tool_call_web_search("rust async traits").call_id("call_1")],
+        )]);
+
+        let actual = ContextSummary::from(&fixture);
+
+        let expected = ContextSummary::new(vec![SummaryBlock::new(
+            Role::Assistant,
+            vec![
+                Block::content("Searching"),
+                SummaryToolCall {
+                    id: Some(ToolCallId::new("call_1")),
+                    tool: SummaryTool::WebSearch { query: "rust async traits".to_string() },
+                    is_success: false,
+                }
+                .into(),
+            ],
+        )]);
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_context_summary_extracts_followup_tool_calls() {
         let fixture = context(vec![assistant_with_tools(
@@ -18,8 +18,8 @@ use crate::temperature::Temperature;
 use crate::top_k::TopK;
 use crate::top_p::TopP;
 use crate::{
-    Attachment, AttachmentContent, ConversationId, EventValue, Image, ModelId, ReasoningFull,
-    ToolChoice, ToolDefinition, ToolOutput, ToolValue, Usage,
+    Attachment, AttachmentContent, ConversationId, Document, Error, EventValue, Image, ModelId,
+    ReasoningFull, ToolChoice, ToolDefinition, ToolOutput, ToolValue, Usage,
 };
 
 /// Represents a message being sent to the LLM provider
@@ -31,6 +31,7 @@ pub enum ContextMessage {
     Text(TextMessage),
     Tool(ToolResult),
     Image(Image),
+    Document(Document),
 }
 
 /// Creates a filtered version of ToolOutput that excludes base64 images to
@@ -61,6 +62,7 @@ impl ContextMessage {
             ContextMessage::Text(text_message) => Some(&text_message.content),
             ContextMessage::Tool(_) => None,
             ContextMessage::Image(_) => None,
+            ContextMessage::Document(_) => None,
         }
     }
 
@@ -71,6 +73,7 @@ impl ContextMessage {
             ContextMessage::Text(text_message) => text_message.raw_content.as_ref(),
             ContextMessage::Tool(_) => None,
             ContextMessage::Image(_) => None,
+            ContextMessage::Document(_) => None,
         }
     }
 
@@ -95,6 +98,10 @@ impl ContextMessage {
                     _ => 0,
                 })
                 .sum(),
+            // Providers receive either the encoded document or its text
+            ContextMessage::Document(document) => {
+                document.text().chars().count().max(document.data().len())
+            }
             _ => 0,
         };
 
@@ -142,6 +149,10 @@ impl ContextMessage {
                     .render()
             }
             ContextMessage::Image(_) => Element::new("image").attr("path", "[base64 URL]").render(),
+            ContextMessage::Document(document) => Element::new("document")
+                .attr("title", document.title())
+                .attr("mime_type", document.mime_type())
+                .render(),
         }
     }
 
@@ -198,7 +209,7 @@ impl ContextMessage {
         match self {
             ContextMessage::Text(message) => message.role == role,
             ContextMessage::Tool(_) => false,
-            ContextMessage::Image(_) => Role::User == role,
+            ContextMessage::Image(_) | ContextMessage::Document(_) => Role::User == role,
         }
     }
 
@@ -207,6 +218,7 @@ impl ContextMessage {
             ContextMessage::Text(message) => message.droppable,
             ContextMessage::Tool(_) => false,
             ContextMessage::Image(_) => false,
+            ContextMessage::Document(_) => false,
         }
     }
 
@@ -215,6 +227,7 @@ impl ContextMessage {
             ContextMessage::Text(_) => false,
             ContextMessage::Tool(_) => true,
             ContextMessage::Image(_) => false,
+            ContextMessage::Document(_) => false,
         }
     }
 
@@ -223,6 +236,7 @@ impl ContextMessage {
             ContextMessage::Text(message) => message.tool_calls.is_some(),
             ContextMessage::Tool(_) => false,
             ContextMessage::Image(_) => false,
+            ContextMessage::Document(_) => false,
         }
     }
 
@@ -231,6 +245,7 @@ impl ContextMessage {
             ContextMessage::Text(message) => message.reasoning_details.is_some(),
             ContextMessage::Tool(_) => false,
             ContextMessage::Image(_) => false,
+            ContextMessage::Document(_) => false,
         }
     }
 }
@@ -333,11 +348,14 @@ pub struct MessageEntry {
     pub message: ContextMessage,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<Usage>,
+    /// Pinned messages are never evicted during context compaction
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub pinned: bool,
 }
 
 impl From<ContextMessage> for MessageEntry {
     fn from(value: ContextMessage) -> Self {
-        MessageEntry { message: value, usage: Default::default() }
+        MessageEntry { message: value, usage: Default::default(), pinned: false }
     }
 }
 
@@ -445,6 +463,92 @@ impl Context {
 
                     message.into()
                 }
+                AttachmentContent::GitContent { content, start_line, end_line, total_lines } => {
+                    let elm = Element::new("git_content")
+                        .attr("ref", attachment.path)
+                        .attr("start_line", start_line)
+                        .attr("end_line", end_line)
+                        .attr("total_lines", total_lines)
+                        .cdata(content);
+
+                    let mut message = TextMessage::new(Role::User, elm.to_string()).droppable(true);
+
+                    if let Some(model) = model_id.clone() {
+                        message = message.model(model);
+                    }
+
+                    message.into()
+                }
+                AttachmentContent::UrlContent {
+                    content,
+                    content_type,
+                    total_chars,
+                    full_content_path,
+                } => {
+                    let mut elm = Element::new("url_content")
+                        .attr("url", attachment.path)
+                        .attr("content_type", content_type)
+                        .attr("start_char", 0)
+                        .attr("end_char", content.chars().count())
+                        .attr("total_chars", total_chars)
+                        .append(Element::new("body").cdata(content));
+                    if let Some(path) = full_content_path {
+                        elm = elm.append(Element::new("truncated").text(format!(
+                            "Content is truncated, remaining content can be read from path: {path}"
+                        )));
+                    }
+
+                    let mut message = TextMessage::new(Role::User, elm.to_string()).droppable(true);
+
+                    if let Some(model) = model_id.clone() {
+                        message = message.model(model);
+                    }
+
+                    message.into()
+                }
+                AttachmentContent::Document { document, start_page, end_page, total_pages } => {
+                    let elm = Element::new("document_content")
+                        .attr("path", &attachment.path)
+                        .attr("unit", "page")
+                        .attr("start", start_page)
+                        .attr("end", end_page)
+                        .attr("total", total_pages)
+                        .cdata(document.text());
+
+                    ContextMessage::Document(document.with_text(elm.to_string()))
+                }
+                AttachmentContent::DocumentText { content, unit, start, end, total } => {
+                    let elm = Element::new("document_content")
+                        .attr("path", attachment.path)
+                        .attr("unit", unit)
+                        .attr("start", start)
+                        .attr("end", end)
+                        .attr("total", total)
+                        .cdata(content);
+
+                    let mut message = TextMessage::new(Role::User, elm.to_string()).droppable(true);
+
+                    if let Some(model) = model_id.clone() {
+                        message = message.model(model);
+                    }
+
+                    message.into()
+                }
+                AttachmentContent::McpResource { server, uri, mime_type, content } => {
+                    let elm = Element::new("mcp_resource")
+                        .attr("server", server)
+                        .attr("uri", uri)
+                        .attr_if_some("mime_type", mime_type)
+                        .cdata(content);
+
+                    let mut message = TextMessage::new(Role::User, elm.to_string()).droppable(true);
+
+                    if let Some(model) = model_id.clone() {
+                        message = message.model(model);
+                    }
+
+                    message.into()
+                }
                 AttachmentContent::DirectoryListing { entries } => {
                     let elm = Element::new("directory_listing")
                         .attr("path", attachment.path)
@@ -459,6 +563,22 @@ impl Context {
                         message = message.model(model);
                     }
 
+                    message.into()
+                }
+                AttachmentContent::OmittedFiles { count } => {
+                    let elm = Element::new("omitted_files")
+                        .attr("pattern", attachment.path)
+                        .attr("count", count)
+                        .text(format!(
+                            "{count} files matching the pattern were omitted after reaching the attachment limits"
+                        ));
+
+                    let mut message = TextMessage::new(Role::User, elm.to_string()).droppable(true);
+
+                    if let Some(model) = model_id.clone() {
+                        message = message.model(model);
+                    }
+
                     message.into()
                 }
             })
@@ -604,6 +724,112 @@ impl Context {
         result
     }
 
+    fn entry_mut(&mut self, index: usize) -> crate::Result<&mut MessageEntry> {
+        let total = self.messages.len();
+        self.messages
+            .get_mut(index)
+            .ok_or(Error::MessageNotFound { index, total })
+    }
+
+    /// Pins or unpins the message at `index`. Pinned messages are kept as-is
+    /// when the context is compacted.
+    pub fn pin_message(&mut self, index: usize, pinned: bool) -> crate::Result<()> {
+        self.entry_mut(index)?.pinned = pinned;
+        Ok(())
+    }
+
+    /// Replaces the output of the tool result at `index` with a short stub,
+    /// keeping the tool call it answers valid.
+    pub fn stub_tool_output(&mut self, index: usize) -> crate::Result<()> {
+        let entry = self.entry_mut(index)?;
+        let tokens = entry.token_count_approx();
+        let ContextMessage::Tool(result) = &mut entry.message else {
+            return Err(Error::InvalidMessageEdit {
+                index,
+                reason: "only tool results can be stubbed".to_string(),
+            });
+        };
+        result.output = ToolOutput::text(format!(
+            "[Output removed from the context by the user, ~{tokens} tokens]"
+        ))
+        .is_error(result.output.is_error);
+        Ok(())
+    }
+
+    /// Removes the message at `index`. An assistant message is removed along
+    /// with the results of its tool calls that follow it, so that no result is
+    /// left without its call.
+    pub fn drop_message(&mut self, index: usize) -> crate::Result<()> {
+        let entry = self.entry_mut(index)?;
+        let reason = match &entry.message {
+            ContextMessage::Text(message) if message.has_role(Role::System) => {
+                Some("the system prompt cannot be dropped")
+            }
+            ContextMessage::Tool(_) => Some("tool results can only be stubbed"),
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            return Err(Error::InvalidMessageEdit { index, reason: reason.to_string() });
+        }
+
+        // Results directly follow their call, and some providers leave out call ids
+        if self.messages.remove(index).has_tool_call() {
+            while self
+                .messages
+                .get(index)
+                .is_some_and(|entry| entry.has_tool_result())
+            {
+                self.messages.remove(index);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the messages from `start` to `end` (inclusive) can be
+    /// replaced by a summary. The range must not contain the system prompt or
+    /// pinned messages, nor separate a tool call from its results.
+    pub fn check_summary_range(&self, start: usize, end: usize) -> crate::Result<()> {
+        let total = self.messages.len();
+        if end >= total {
+            return Err(Error::MessageNotFound { index: end, total });
+        }
+        if start > end {
+            return Err(Error::InvalidMessageEdit {
+                index: start,
+                reason: format!("the range must end at or after it, not at {end}"),
+            });
+        }
+
+        for (index, entry) in self.messages.iter().enumerate().take(end + 1).skip(start) {
+            let reason = if entry.has_role(Role::System) {
+                "the system prompt cannot be summarized"
+            } else if entry.pinned {
+                "pinned messages cannot be summarized, unpin it first"
+            } else {
+                continue;
+            };
+            return Err(Error::InvalidMessageEdit { index, reason: reason.to_string() });
+        }
+
+        if self.messages[start].has_tool_result() {
+            return Err(Error::InvalidMessageEdit {
+                index: start,
+                reason: "the range must include the tool call this result answers".to_string(),
+            });
+        }
+        if self
+            .messages
+            .get(end + 1)
+            .is_some_and(|entry| entry.has_tool_result())
+        {
+            return Err(Error::InvalidMessageEdit {
+                index: end,
+                reason: "the range must include the results of its tool calls".to_string(),
+            });
+        }
+        Ok(())
+    }
+
     /// Returns the total number of messages in the context
     pub fn total_messages(&self) -> usize {
         self.messages.len()
@@ -1152,6 +1378,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_add_attachments_mcp_resource() {
+        let fixture_attachments = vec![Attachment {
+            path: "mcp:docs/file:///setup.md".to_string(),
+            content: AttachmentContent::McpResource {
+                server: "docs".to_string(),
+                uri: "file:///setup.md".to_string(),
+                mime_type: Some("text/markdown".to_string()),
+                content: "# Setup".to_string(),
+            },
+        }];
+
+        let actual = Context::default().add_attachments(fixture_attachments, None);
+
+        assert_eq!(actual.messages.len(), 1);
+        let message = actual.messages.first().unwrap();
+        assert!(message.is_droppable());
+
+        let text = message.to_text();
+        assert!(text.contains("&lt;mcp_resource"));
+        assert!(text.contains("server=\"docs\""));
+        assert!(text.contains("# Setup"));
+    }
+
+    #[test]
+    fn test_add_attachments_git_content() {
+        let fixture_attachments = vec![Attachment {
+            path: "git:diff:main".to_string(),
+            content: AttachmentContent::GitContent {
+                content: "diff --git a/src/lib.rs b/src/lib.rs".to_string(),
+                start_line: 1,
+                end_line: 1,
+                total_lines: 1,
+            },
+        }];
+
+        let actual = Context::default().add_attachments(fixture_attachments, None);
+
+        assert_eq!(actual.messages.len(), 1);
+        let message = actual.messages.first().unwrap();
+        assert!(message.is_droppable());
+
+        let text = message.to_text();
+        assert!(text.contains("&lt;git_content"));
+        assert!(text.contains("ref=\"git:diff:main\""));
+        assert!(text.contains("diff --git"));
+    }
+
+    #[test]
+    fn test_add_attachments_document() {
+        let fixture_attachments = vec![Attachment {
+            path: "/docs/spec.pdf".to_string(),
+            content: AttachmentContent::Document {
+                document: Document::new_bytes(
+                    b"%PDF".to_vec(),
+                    "application/pdf",
+                    "spec.pdf",
+                    "--- Page 2 ---\nDesign",
+                ),
+                start_page: 2,
+                end_page: 2,
+                total_pages: 3,
+            },
+        }];
+
+        let actual = Context::default().add_attachments(fixture_attachments, None);
+
+        assert_eq!(actual.messages.len(), 1);
+        let ContextMessage::Document(document) = &**actual.messages.first().unwrap() else {
+            panic!("expected a document message");
+        };
+        assert_eq!(document.title(), "spec.pdf");
+        assert!(document.text().contains("<document_content"));
+        assert!(document.text().contains("total=\"3\""));
+        assert!(document.text().contains("Design"));
+    }
+
     #[test]
     fn test_add_attachments_directory_listing() {
         let fixture_attachments = vec![Attachment {
@@ -1293,6 +1596,20 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_context_message_token_count_approx_document() {
+        // Fixture: 12 bytes encode to 16 base64 characters, more than the text
+        let fixture = ContextMessage::Document(Document::new_bytes(
+            b"%PDF-1.7 abc".to_vec(),
+            "application/pdf",
+            "spec.pdf",
+            "Spec",
+        ));
+        let actual = fixture.token_count_approx();
+        let expected = 4; // 16 chars / 4 = 4 tokens
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_context_message_token_count_approx_assistant_text() {
         // Fixture: Assistant text message
@@ -1414,4 +1731,144 @@ mod tests {
         let expected = 5; // 18 chars / 4 = 5 tokens (rounded up)
         assert_eq!(actual, expected);
     }
+
+    fn edit_fixture() -> Context {
+        let call = ToolCallFull {
+            call_id: Some(crate::ToolCallId::new("call1")),
+            name: crate::ToolName::new("shell"),
+            arguments: serde_json::json!({"command": "cat Cargo.lock"}).into(),
+        };
+        Context::default()
+            .add_message(ContextMessage::system("System message"))
+            .add_message(ContextMessage::user("Update the lockfile", None))
+            .add_message(ContextMessage::assistant("", None, Some(vec![call])))
+            .add_tool_results(vec![ToolResult {
+                name: crate::ToolName::new("shell"),
+                call_id: Some(crate::ToolCallId::new("call1")),
+                output: crate::ToolOutput::text("x".repeat(400)),
+            }])
+            .add_message(ContextMessage::assistant("Done", None, None))
+    }
+
+    #[test]
+    fn test_stub_tool_output() {
+        let mut fixture = edit_fixture();
+
+        fixture.stub_tool_output(3).unwrap();
+
+        let actual = fixture.messages[3].message.clone();
+        let expected = ContextMessage::tool_result(ToolResult {
+            name: crate::ToolName::new("shell"),
+            call_id: Some(crate::ToolCallId::new("call1")),
+            output: crate::ToolOutput::text(
+                "[Output removed from the context by the user, ~100 tokens]",
+            ),
+        });
+        assert_eq!(actual, expected);
+        assert!(fixture.stub_tool_output(1).is_err());
+    }
+
+    #[test]
+    fn test_drop_message_removes_tool_results() {
+        let mut fixture = edit_fixture();
+
+        fixture.drop_message(2).unwrap();
+
+        let actual = fixture
+            .messages
+            .iter()
+            .map(|entry| entry.content().map(str::to_string))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            Some("System message".to_string()),
+            Some("Update the lockfile".to_string()),
+            Some("Done".to_string()),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_drop_message_removes_tool_results_without_call_ids() {
+        let call = ToolCallFull {
+            call_id: None,
+            name: crate::ToolName::new("shell"),
+            arguments: serde_json::json!({"command": "ls"}).into(),
+        };
+        let mut fixture = Context::default()
+            .add_message(ContextMessage::user("List the files", None))
+            .add_message(ContextMessage::assistant("", None, Some(vec![call])))
+            .add_tool_results(vec![ToolResult {
+                name: crate::ToolName::new("shell"),
+                call_id: None,
+                output: crate::ToolOutput::text("Cargo.toml"),
+            }])
+            .add_message(ContextMessage::assistant("Done", None, None));
+
+        fixture.drop_message(1).unwrap();
+
+        let actual = fixture
+            .messages
+            .iter()
+            .map(|entry| entry.content().map(str::to_string))
+            .collect::<Vec<_>>();
+        let expected = vec![Some("List the files".to_string()), Some("Done".to_string())];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_drop_message_rejects_system_and_tool_results() {
+        let mut fixture = edit_fixture();
+
+        let actual = [0, 3, 9].map(|index| fixture.drop_message(index).unwrap_err().to_string());
+
+        let expected = [
+            "Cannot edit message 0: the system prompt cannot be dropped".to_string(),
+            "Cannot edit message 3: tool results can only be stubbed".to_string(),
+            "No message at index 9, the context has 5 messages".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_pinned_is_persisted() {
+        let mut fixture = edit_fixture();
+        fixture.pin_message(1, true).unwrap();
+
+        let actual: Context =
+            serde_json::from_str(&serde_json::to_string(&fixture).unwrap()).unwrap();
+
+        assert_eq!(actual, fixture);
+        assert!(actual.messages[1].pinned);
+    }
+
+    #[test]
+    fn test_check_summary_range() {
+        let mut fixture = edit_fixture();
+        fixture.pin_message(4, true).unwrap();
+
+        let actual = [(1, 3), (1, 2), (3, 3), (1, 4), (0, 1), (2, 1)].map(|(start, end)| {
+            fixture
+                .check_summary_range(start, end)
+                .map_err(|e| e.to_string())
+        });
+
+        let expected = [
+            Ok(()),
+            Err(
+                "Cannot edit message 2: the range must include the results of its tool calls"
+                    .to_string(),
+            ),
+            Err(
+                "Cannot edit message 3: the range must include the tool call this result answers"
+                    .to_string(),
+            ),
+            Err(
+                "Cannot edit message 4: pinned messages cannot be summarized, unpin it first"
+                    .to_string(),
+            ),
+            Err("Cannot edit message 0: the system prompt cannot be summarized".to_string()),
+            Err("Cannot edit message 2: the range must end at or after it, not at 1".to_string()),
+        ];
+        assert_eq!(actual, expected);
+    }
 }
@@ -6,7 +6,7 @@ use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{Context, Error, Metrics, Result};
+use crate::{Context, Error, Metrics, Result, Todo};
 
 #[derive(Debug, Default, Display, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(transparent)]
@@ -46,6 +46,9 @@ pub struct Conversation {
     pub context: Option<Context>,
     pub metrics: Metrics,
     pub metadata: MetaData,
+    /// Task list the agent keeps with the `todo` tool
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub todos: Vec<Todo>,
 }
 
 #[derive(Debug, Setters, Serialize, Deserialize, Clone)]
@@ -71,6 +74,7 @@ impl Conversation {
             metadata: MetaData::new(created_at),
             title: None,
             context: None,
+            todos: Vec::new(),
         }
     }
     /// Creates a new conversation with a new conversation ID.
@@ -349,6 +349,12 @@ fn create_conversation_context_section(conversation: &Conversation) -> Element {
                             .append(Element::new("strong").text("Image Attachment"))
                             .append(Element::new("img").attr("src", image.url()))
                     }
+                    ContextMessage::Document(document) => {
+                        // Document message
+                        Element::new("div.message-card.message-user")
+                            .append(Element::new("strong").text("Document Attachment"))
+                            .append(Element::new("pre").text(document.title()))
+                    }
                 }
             }),
         );
@@ -0,0 +1,47 @@
+use base64::Engine;
+use derive_getters::Getters;
+use serde::{Deserialize, Serialize};
+
+/// A binary document such as a PDF, sent as a native document block to
+/// providers that support it.
+#[derive(Default, Clone, Debug, Serialize, Deserialize, Getters, PartialEq, Eq, Hash)]
+pub struct Document {
+    /// Base64 encoded document bytes
+    data: String,
+    mime_type: String,
+    title: String,
+    /// Text rendering of the document, sent to providers without native
+    /// document support
+    text: String,
+}
+
+impl Document {
+    pub fn new_bytes(
+        content: Vec<u8>,
+        mime_type: impl ToString,
+        title: impl ToString,
+        text: impl ToString,
+    ) -> Self {
+        let base64_encoded = base64::engine::general_purpose::STANDARD.encode(&content);
+        Self::new_base64(base64_encoded, mime_type, title, text)
+    }
+
+    pub fn new_base64(
+        base64_encoded: String,
+        mime_type: impl ToString,
+        title: impl ToString,
+        text: impl ToString,
+    ) -> Self {
+        Self {
+            data: base64_encoded,
+            mime_type: mime_type.to_string(),
+            title: title.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    /// Replaces the text rendering of the document.
+    pub fn with_text(self, text: impl ToString) -> Self {
+        Self { text: text.to_string(), ..self }
+    }
+}
@@ -6,7 +6,7 @@ use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::{HttpConfig, ModelId, ProviderId, RetryConfig};
+use crate::{HttpConfig, ModelId, ProviderId, ReplayMismatch, RetryConfig, WebSearchBackend};
 
 const VERSION: &str = match option_env!("APP_VERSION") {
     Some(val) => val,
@@ -90,6 +90,18 @@ pub struct Environment {
     /// If set, this provider will be used as default.
     #[dummy(default)]
     pub override_provider: Option<ProviderId>,
+    /// Backend used by the web search tool. Controlled by FORGE_WEB_SEARCH
+    /// environment variable; web search is disabled when unset.
+    #[dummy(default)]
+    pub web_search: Option<WebSearchBackend>,
+    /// Cassette file that every provider request and response is recorded
+    /// to. Controlled by FORGE_RECORD_CASSETTE environment variable.
+    #[dummy(default)]
+    pub record_cassette: Option<PathBuf>,
+    /// What the replay provider does with requests that were not recorded.
+    /// Controlled by FORGE_REPLAY_MISMATCH environment variable.
+    #[dummy(default)]
+    pub replay_mismatch: ReplayMismatch,
 }
 
 impl Environment {
@@ -130,6 +142,11 @@ impl Environment {
         self.base_path.join("permissions.yaml")
     }
 
+    /// Returns the path of the per-domain credentials used by the fetch tool
+    pub fn fetch_credentials_path(&self) -> PathBuf {
+        self.base_path.join("credentials.yaml")
+    }
+
     pub fn mcp_local_config(&self) -> PathBuf {
         self.cwd.join(".mcp.json")
     }
@@ -298,6 +315,9 @@ fn test_command_path() {
         workspace_server_url: "http://localhost:8080".parse().unwrap(),
         override_model: None,
         override_provider: None,
+        web_search: None,
+        record_cassette: None,
+        replay_mismatch: Default::default(),
     };
 
     let actual = fixture.command_path();
@@ -337,6 +357,9 @@ fn test_command_cwd_path() {
         workspace_server_url: "http://localhost:8080".parse().unwrap(),
         override_model: None,
         override_provider: None,
+        web_search: None,
+        record_cassette: None,
+        replay_mismatch: Default::default(),
     };
 
     let actual = fixture.command_cwd_path();
@@ -376,6 +399,9 @@ fn test_command_cwd_path_independent_from_command_path() {
         workspace_server_url: "http://localhost:8080".parse().unwrap(),
         override_model: None,
         override_provider: None,
+        web_search: None,
+        record_cassette: None,
+        replay_mismatch: Default::default(),
     };
 
     let command_path = fixture.command_path();
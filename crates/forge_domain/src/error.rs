@@ -4,7 +4,7 @@ use derive_more::From;
 use forge_json_repair::JsonRepairError;
 use thiserror::Error;
 
-use crate::{AgentId, ConversationId, ProviderId};
+use crate::{AgentId, CommandArgumentType, ConversationId, ProviderId};
 
 // NOTE: Deriving From for error is a really bad idea. This is because you end
 // up converting errors incorrectly without much context. For eg: You don't want
@@ -102,6 +102,33 @@ pub enum Error {
     #[error("No default model configured for provider: {0}")]
     #[from(skip)]
     NoDefaultModel(ProviderId),
+
+    #[error("Missing required argument '{argument}' for command '{command}'")]
+    MissingCommandArgument { command: String, argument: String },
+
+    #[error(
+        "Invalid value '{value}' for argument '{argument}' of command '{command}', expected a {expected}"
+    )]
+    InvalidCommandArgument {
+        command: String,
+        argument: String,
+        value: String,
+        expected: CommandArgumentType,
+    },
+
+    #[error("No message at index {index}, the context has {total} messages")]
+    MessageNotFound { index: usize, total: usize },
+
+    #[error("Cannot edit message {index}: {reason}")]
+    InvalidMessageEdit { index: usize, reason: String },
+
+    #[error("No step {step} in the plan, it has {total} steps")]
+    #[from(skip)]
+    PlanStepNotFound { step: usize, total: usize },
+
+    #[error("Invalid cron expression '{expression}': {reason}")]
+    #[from(skip)]
+    InvalidCron { expression: String, reason: String },
 }
 
 pub type Result<A> = std::result::Result<A, Error>;
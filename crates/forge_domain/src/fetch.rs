@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// HTTP method used by the fetch tool
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    eserde::Deserialize,
+    JsonSchema,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "UPPERCASE")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum HttpMethod {
+    #[default]
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Head,
+}
+
+/// Headers injected into fetch requests for matching domains. Values may
+/// reference environment variables as `${NAME}` so that secrets don't have
+/// to be written to the file. The headers are added when the request is sent
+/// and never appear in the conversation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchCredentials {
+    #[serde(default)]
+    pub credentials: Vec<DomainCredential>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomainCredential {
+    /// Host the credential applies to, including its subdomains
+    pub domain: String,
+    pub headers: BTreeMap<String, String>,
+}
+
+impl DomainCredential {
+    pub fn matches(&self, host: &str) -> bool {
+        let domain = self.domain.trim_start_matches("*.").to_lowercase();
+        let host = host.to_lowercase();
+        host == domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    }
+}
+
+impl FetchCredentials {
+    /// Returns the headers configured for `host` with lowercase names. When
+    /// several entries match, the most specific domain wins.
+    pub fn headers_for(&self, host: &str) -> BTreeMap<String, String> {
+        let mut matching = self
+            .credentials
+            .iter()
+            .filter(|credential| credential.matches(host))
+            .collect::<Vec<_>>();
+        matching.sort_by_key(|credential| credential.domain.len());
+
+        matching
+            .into_iter()
+            .flat_map(|credential| &credential.headers)
+            .map(|(name, value)| (name.to_lowercase(), value.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn credential(domain: &str, header: &str, value: &str) -> DomainCredential {
+        DomainCredential {
+            domain: domain.to_string(),
+            headers: BTreeMap::from([(header.to_string(), value.to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_headers_for_matches_domain_and_subdomains() {
+        let fixture = FetchCredentials {
+            credentials: vec![
+                credential("example.com", "Authorization", "Bearer outer"),
+                credential("api.example.com", "authorization", "Bearer inner"),
+                credential("other.org", "X-Api-Key", "key"),
+            ],
+        };
+
+        let actual = (
+            fixture.headers_for("api.example.com"),
+            fixture.headers_for("docs.example.com"),
+            fixture.headers_for("notexample.com"),
+        );
+
+        let expected = (
+            BTreeMap::from([("authorization".to_string(), "Bearer inner".to_string())]),
+            BTreeMap::from([("authorization".to_string(), "Bearer outer".to_string())]),
+            BTreeMap::new(),
+        );
+        assert_eq!(actual, expected);
+    }
+}
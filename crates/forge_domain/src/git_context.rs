@@ -0,0 +1,184 @@
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Controls whether and how much repository state is rendered into an
+/// agent's system prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Setters, JsonSchema)]
+#[setters(into)]
+pub struct GitContextConfig {
+    /// Whether the git section is included in the system prompt
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Number of recent commit subjects to include
+    #[serde(default = "default_max_commits")]
+    pub max_commits: usize,
+
+    /// Maximum number of characters the git section may occupy. Entries are
+    /// dropped from the diffstat, the file lists and the commit log, in that
+    /// order, until it fits.
+    #[serde(default = "default_max_chars")]
+    pub max_chars: usize,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_max_commits() -> usize {
+    10
+}
+
+fn default_max_chars() -> usize {
+    4000
+}
+
+impl Default for GitContextConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            max_commits: default_max_commits(),
+            max_chars: default_max_chars(),
+        }
+    }
+}
+
+/// Snapshot of the git repository the agent is working in
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option, into)]
+pub struct GitContext {
+    /// Current branch, or the abbreviated commit when HEAD is detached
+    pub branch: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<GitUpstream>,
+
+    /// Changes in the index, formatted as `<status> <path>`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub staged: Vec<String>,
+
+    /// Changes in the working tree including untracked files, formatted as
+    /// `<status> <path>`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unstaged: Vec<String>,
+
+    /// Most recent commits, formatted as `<short sha> <subject>`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub commits: Vec<String>,
+
+    /// Ref the diffstat is computed against through its merge-base
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+
+    /// Per-file lines of the diffstat against the merge-base
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diff_stat: Vec<String>,
+
+    /// Summary line of the diffstat, e.g. `3 files changed, 10 insertions(+)`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff_summary: Option<String>,
+
+    /// Number of entries dropped to respect the size cap
+    #[serde(skip_serializing_if = "is_zero")]
+    pub omitted: usize,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// Tracking branch of the current branch and how far the two have diverged
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitUpstream {
+    pub name: String,
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+impl GitContext {
+    /// Drops entries until the rendered size of the context fits within
+    /// `max_chars`, recording how many were dropped in `omitted`.
+    pub fn truncate(mut self, max_chars: usize) -> Self {
+        while self.size() > max_chars {
+            let dropped = [
+                &mut self.diff_stat,
+                &mut self.unstaged,
+                &mut self.staged,
+                &mut self.commits,
+            ]
+            .into_iter()
+            .find_map(|entries| entries.pop());
+
+            match dropped {
+                Some(_) => self.omitted += 1,
+                None => break,
+            }
+        }
+        self
+    }
+
+    /// Approximates the number of characters the context occupies once
+    /// rendered, counting one line per entry.
+    fn size(&self) -> usize {
+        let entries = self
+            .staged
+            .iter()
+            .chain(&self.unstaged)
+            .chain(&self.commits)
+            .chain(&self.diff_stat)
+            .chain(&self.diff_summary)
+            .map(|entry| entry.len() + 1)
+            .sum::<usize>();
+        let upstream = self
+            .upstream
+            .as_ref()
+            .map(|upstream| upstream.name.len())
+            .unwrap_or_default();
+
+        self.branch.len() + upstream + self.base.as_ref().map_or(0, String::len) + entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn fixture() -> GitContext {
+        GitContext::default()
+            .branch("main")
+            .staged(vec!["M src/lib.rs".to_string()])
+            .unstaged(vec!["?? notes.md".to_string()])
+            .commits(vec!["abc1234 Initial commit".to_string()])
+            .diff_stat(vec![
+                " src/lib.rs | 2 +-".to_string(),
+                " src/main.rs | 10 ++++++++++".to_string(),
+            ])
+    }
+
+    #[test]
+    fn test_truncate_within_limit_is_unchanged() {
+        let actual = fixture().truncate(1000);
+        let expected = fixture();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_truncate_drops_diff_stat_first() {
+        let actual = fixture().truncate(45);
+        let expected = fixture()
+            .diff_stat(Vec::<String>::new())
+            .unstaged(Vec::<String>::new())
+            .omitted(3usize);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let actual: GitContextConfig = serde_json::from_str("{}").unwrap();
+        let expected = GitContextConfig::default();
+        assert_eq!(actual, expected);
+    }
+}
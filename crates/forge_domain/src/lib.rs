@@ -3,23 +3,30 @@ mod agent_definition;
 mod app_config;
 mod attachment;
 mod auth;
+mod cassette;
 mod chat_request;
 mod chat_response;
+mod command_argument;
 mod compact;
 mod context;
 mod conversation;
 mod conversation_html;
 mod data_gen;
+mod document;
 mod env;
 mod error;
 mod event;
+mod fetch;
 mod file;
 mod file_operation;
+mod git_context;
 mod group_by_key;
 mod http_config;
 mod image;
+mod lsp;
 mod max_tokens;
 mod mcp;
+mod mcp_resource;
 mod mcp_servers;
 mod merge;
 mod message;
@@ -27,26 +34,33 @@ mod message_pattern;
 mod migration;
 mod model;
 mod node;
+mod pipeline;
+mod plan;
 mod point;
 mod policies;
 mod provider;
+mod proxy;
 mod reasoning;
 mod repo;
 mod result_stream_ext;
 mod retry_config;
+mod schedule;
 mod session_metrics;
 mod shell;
 mod skill;
 mod snapshot;
 mod suggestion;
+mod symbol;
 mod system_context;
 mod temperature;
 mod template;
+mod todo;
 mod tools;
 mod top_k;
 mod top_p;
 mod transformer;
 mod update;
+mod web_search;
 mod workflow;
 mod workspace;
 mod xml;
@@ -56,53 +70,67 @@ pub use agent_definition::*;
 pub use attachment::*;
 pub use chat_request::*;
 pub use chat_response::*;
+pub use command_argument::*;
 pub use compact::*;
 pub use context::*;
 pub use conversation::*;
 pub use conversation_html::*;
 pub use data_gen::*;
+pub use document::*;
 pub use env::*;
 pub use error::*;
 pub use event::*;
+pub use fetch::*;
 pub use file::*;
 pub use file_operation::*;
+pub use git_context::*;
 pub use group_by_key::*;
 pub use http_config::*;
 pub use image::*;
+pub use lsp::*;
 pub use max_tokens::*;
 pub use mcp::*;
+pub use mcp_resource::*;
 pub use mcp_servers::*;
 pub use message::*;
 pub use message_pattern::*;
 pub use migration::*;
 pub use model::*;
 pub use node::*;
+pub use pipeline::*;
+pub use plan::*;
 pub use point::*;
 pub use policies::*;
 pub use provider::*;
+pub use proxy::*;
 pub use reasoning::*;
 pub use repo::*;
 pub use result_stream_ext::*;
 pub use retry_config::*;
+pub use schedule::*;
 pub use session_metrics::*;
 pub use shell::*;
 pub use skill::*;
 pub use snapshot::*;
 pub use suggestion::*;
+pub use symbol::*;
 pub use system_context::*;
 pub use temperature::*;
 pub use template::*;
+pub use todo::*;
 pub use tools::*;
 pub use top_k::*;
 pub use top_p::*;
 pub use transformer::*;
 pub use update::*;
+pub use web_search::*;
 pub use workflow::*;
 pub use workspace::*;
 pub use xml::*;
 pub mod line_numbers;
 pub use app_config::*;
 pub use auth::*;
+pub use cassette::*;
 pub use line_numbers::*;
 
 /// Type alias for tokio mpsc Sender that sends ChatResponse results
@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use derive_setters::Setters;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Time to wait for a server to publish diagnostics when the server does not
+/// configure one
+const DEFAULT_LSP_TIMEOUT_SECS: u64 = 10;
+
+/// Language server started for files with one of the configured extensions.
+/// The server is spawned on first use and speaks LSP over stdio.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Setters)]
+#[setters(strip_option, into)]
+pub struct LspServerConfig {
+    /// Command that starts the server, e.g. `rust-analyzer`
+    pub command: String,
+
+    /// Arguments passed to the command
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+
+    /// Environment variables set for the server
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+
+    /// File extensions handled by the server, without the leading dot
+    pub extensions: Vec<String>,
+
+    /// Language identifier sent when opening documents. Defaults to the name
+    /// the server is configured under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_id: Option<String>,
+
+    /// Seconds to wait for the server to start or publish diagnostics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[setters(skip)]
+    pub timeout: Option<u64>,
+}
+
+impl LspServerConfig {
+    pub fn new(command: impl Into<String>, extensions: Vec<String>) -> Self {
+        Self {
+            command: command.into(),
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            extensions,
+            language_id: None,
+            timeout: None,
+        }
+    }
+
+    /// Returns true if the server handles the file at `path`
+    pub fn handles(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| self.extensions.iter().any(|item| item == extension))
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_LSP_TIMEOUT_SECS))
+    }
+}
+
+/// Severity of a diagnostic, ordered from most to least severe
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+impl DiagnosticSeverity {
+    /// Converts the numeric severity used by the language server protocol
+    pub fn from_lsp(severity: u64) -> Option<Self> {
+        match severity {
+            1 => Some(Self::Error),
+            2 => Some(Self::Warning),
+            3 => Some(Self::Information),
+            4 => Some(Self::Hint),
+            _ => None,
+        }
+    }
+}
+
+/// An error, warning or hint reported by a language server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub path: String,
+    /// Line of the start of the diagnostic (1-based)
+    pub line: usize,
+    /// Column of the start of the diagnostic (1-based)
+    pub column: usize,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// Tool that produced the diagnostic, e.g. `rustc` or `eslint`
+    pub source: Option<String>,
+    pub code: Option<String>,
+}
+
+impl Diagnostic {
+    /// Returns true for errors and warnings, the diagnostics worth acting on
+    /// after an edit
+    pub fn is_problem(&self) -> bool {
+        self.severity <= DiagnosticSeverity::Warning
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.path, self.line, self.column, self.severity
+        )?;
+        match (&self.source, &self.code) {
+            (Some(source), Some(code)) => write!(f, "[{source}({code})]")?,
+            (Some(source), None) => write!(f, "[{source}]")?,
+            (None, Some(code)) => write!(f, "[{code}]")?,
+            (None, None) => {}
+        }
+        // Multi-line messages are folded so each diagnostic stays on one line
+        write!(
+            f,
+            ": {}",
+            self.message.lines().collect::<Vec<_>>().join(" ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_display() {
+        let fixture = Diagnostic {
+            path: "/project/src/lib.rs".to_string(),
+            line: 3,
+            column: 9,
+            severity: DiagnosticSeverity::Error,
+            message: "mismatched types\nexpected `u32`, found `&str`".to_string(),
+            source: Some("rustc".to_string()),
+            code: Some("E0308".to_string()),
+        };
+
+        let actual = (fixture.to_string(), fixture.is_problem());
+
+        let expected = (
+            "/project/src/lib.rs:3:9: error[rustc(E0308)]: mismatched types expected `u32`, found `&str`"
+                .to_string(),
+            true,
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_handles() {
+        let fixture = LspServerConfig::new("pyright-langserver", vec!["py".into(), "pyi".into()]);
+
+        let actual = ["app.py", "types.pyi", "main.rs", "Makefile"]
+            .map(|path| fixture.handles(Path::new(path)));
+
+        assert_eq!(actual, [true, true, false, false]);
+    }
+}
@@ -3,11 +3,16 @@
 
 use std::collections::BTreeMap;
 use std::ops::Deref;
+use std::time::Duration;
 
 use derive_more::{Deref, Display, From};
 use derive_setters::Setters;
+use glob::Pattern;
 use merge::Merge;
 use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::ProviderId;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Scope {
@@ -15,6 +20,9 @@ pub enum Scope {
     User,
 }
 
+/// Startup timeout applied to servers that do not configure one
+const DEFAULT_MCP_TIMEOUT_SECS: u64 = 20;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Hash)]
 #[serde(untagged)]
 pub enum McpServerConfig {
@@ -33,13 +41,13 @@ impl McpServerConfig {
             command: command.into(),
             args,
             env: env.unwrap_or_default(),
-            disable: false,
+            ..Default::default()
         })
     }
 
     /// Create a new HTTP-based MCP server (auto-detects transport type)
     pub fn new_http(url: impl Into<String>) -> Self {
-        Self::Http(McpHttpServer { url: url.into(), headers: BTreeMap::new(), disable: false })
+        Self::Http(McpHttpServer { url: url.into(), ..Default::default() })
     }
 
     pub fn is_disabled(&self) -> bool {
@@ -49,6 +57,34 @@ impl McpServerConfig {
         }
     }
 
+    /// Maximum time to wait for the server to start and list its tools
+    pub fn timeout(&self) -> Duration {
+        let timeout = match self {
+            McpServerConfig::Stdio(v) => v.timeout,
+            McpServerConfig::Http(v) => v.timeout,
+        };
+        Duration::from_secs(timeout.unwrap_or(DEFAULT_MCP_TIMEOUT_SECS))
+    }
+
+    /// Returns true when a tool published by this server should be exposed.
+    ///
+    /// A tool must match one of the `enabled_tools` patterns, when any are
+    /// configured, and none of the `disabled_tools` patterns.
+    pub fn is_tool_enabled(&self, tool: &str) -> bool {
+        let (enabled, disabled) = match self {
+            McpServerConfig::Stdio(v) => (&v.enabled_tools, &v.disabled_tools),
+            McpServerConfig::Http(v) => (&v.enabled_tools, &v.disabled_tools),
+        };
+        let matches = |patterns: &Vec<String>| {
+            patterns
+                .iter()
+                .filter_map(|pattern| Pattern::new(pattern).ok())
+                .any(|pattern| pattern.matches(tool))
+        };
+
+        (enabled.is_empty() || matches(enabled)) && !matches(disabled)
+    }
+
     /// Returns the type of MCP server as a string ("STDIO" or "HTTP")
     pub fn server_type(&self) -> &'static str {
         match self {
@@ -73,6 +109,19 @@ pub struct McpStdioServer {
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub env: BTreeMap<String, String>,
 
+    /// Maximum time in seconds to wait for the server to start and list its
+    /// tools. Defaults to 20 seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+
+    /// Glob patterns of tools to expose. All tools are exposed when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enabled_tools: Vec<String>,
+
+    /// Glob patterns of tools to hide, even when they match `enabled_tools`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_tools: Vec<String>,
+
     /// Disable it temporarily without having to
     /// remove it from the config.
     #[serde(default)]
@@ -90,6 +139,24 @@ pub struct McpHttpServer {
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub headers: BTreeMap<String, String>,
 
+    /// Optional overrides for the OAuth settings discovered from the server.
+    /// Tokens are obtained with `forge mcp login <server>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth: Option<Box<McpOAuthConfig>>,
+
+    /// Maximum time in seconds to wait for the server to start and list its
+    /// tools. Defaults to 20 seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+
+    /// Glob patterns of tools to expose. All tools are exposed when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enabled_tools: Vec<String>,
+
+    /// Glob patterns of tools to hide, even when they match `enabled_tools`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_tools: Vec<String>,
+
     /// Disable it temporarily without having to
     /// remove it from the config.
     #[serde(default)]
@@ -98,11 +165,37 @@ pub struct McpHttpServer {
 
 impl McpHttpServer {}
 
+/// OAuth settings for a remote MCP server. The endpoints, scopes and client
+/// are discovered from the server's metadata and registered dynamically, so
+/// every field only overrides what discovery would find.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct McpOAuthConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_url: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_url: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect_uri: Option<String>,
+}
+
 #[derive(
     Clone, Display, Serialize, Deserialize, Debug, PartialEq, Hash, Eq, From, PartialOrd, Ord, Deref,
 )]
 pub struct ServerName(String);
 
+impl ServerName {
+    /// Identifier under which this server's OAuth credential is stored
+    /// alongside provider credentials
+    pub fn credential_id(&self) -> ProviderId {
+        ProviderId::from(format!("mcp_{}", self.0))
+    }
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Hash, Merge)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct McpConfig {
@@ -354,4 +447,97 @@ mod tests {
         let expected = "HTTP";
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_mcp_http_server_oauth_deserialization() {
+        use pretty_assertions::assert_eq;
+
+        let json = r#"{
+            "url": "https://mcp.example.com/mcp",
+            "oauth": {
+                "authUrl": "https://auth.example.com/authorize",
+                "tokenUrl": "https://auth.example.com/token",
+                "clientId": "forge",
+                "scopes": ["read"]
+            }
+        }"#;
+
+        let actual: McpServerConfig = serde_json::from_str(json).unwrap();
+        let expected = McpServerConfig::Http(McpHttpServer {
+            url: "https://mcp.example.com/mcp".to_string(),
+            headers: BTreeMap::new(),
+            oauth: Some(Box::new(McpOAuthConfig {
+                auth_url: Some(Url::parse("https://auth.example.com/authorize").unwrap()),
+                token_url: Some(Url::parse("https://auth.example.com/token").unwrap()),
+                client_id: Some("forge".to_string()),
+                scopes: vec!["read".to_string()],
+                redirect_uri: None,
+            })),
+            ..Default::default()
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_mcp_http_server_oauth_without_overrides() {
+        use pretty_assertions::assert_eq;
+
+        let json = r#"{ "url": "https://mcp.example.com/mcp", "oauth": {} }"#;
+
+        let actual: McpServerConfig = serde_json::from_str(json).unwrap();
+        let expected = McpServerConfig::Http(McpHttpServer {
+            url: "https://mcp.example.com/mcp".to_string(),
+            oauth: Some(Box::default()),
+            ..Default::default()
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_server_name_credential_id() {
+        use pretty_assertions::assert_eq;
+
+        let fixture = ServerName::from("github".to_string());
+        let actual = fixture.credential_id();
+        let expected = ProviderId::from("mcp_github".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_is_tool_enabled_with_patterns() {
+        use pretty_assertions::assert_eq;
+
+        let fixture = McpServerConfig::Stdio(McpStdioServer {
+            command: "node".to_string(),
+            enabled_tools: vec!["read_*".to_string(), "search".to_string()],
+            disabled_tools: vec!["read_secret*".to_string()],
+            ..Default::default()
+        });
+
+        let actual = ["read_file", "read_secrets", "search", "write_file"]
+            .map(|tool| fixture.is_tool_enabled(tool));
+        let expected = [true, false, true, false];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_is_tool_enabled_without_patterns() {
+        let fixture = McpServerConfig::new_http("https://example.com/mcp");
+        assert!(fixture.is_tool_enabled("anything"));
+    }
+
+    #[test]
+    fn test_timeout_defaults_when_not_configured() {
+        use pretty_assertions::assert_eq;
+
+        let fixture = McpServerConfig::new_stdio("node", vec![], None);
+        assert_eq!(fixture.timeout(), Duration::from_secs(20));
+
+        let fixture = McpServerConfig::Http(McpHttpServer {
+            url: "https://example.com/mcp".to_string(),
+            timeout: Some(5),
+            ..Default::default()
+        });
+        assert_eq!(fixture.timeout(), Duration::from_secs(5));
+    }
 }
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A resource published by an MCP server, such as a document or a database
+/// schema.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, derive_setters::Setters)]
+#[serde(rename_all = "camelCase")]
+#[setters(strip_option, into)]
+pub struct McpResource {
+    pub uri: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+impl McpResource {
+    pub fn new(uri: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            name: name.into(),
+            description: None,
+            mime_type: None,
+        }
+    }
+}
+
+/// Contents returned when reading an MCP resource.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum McpResourceContent {
+    Text {
+        uri: String,
+        mime_type: Option<String>,
+        text: String,
+    },
+    /// Binary content encoded as base64
+    Blob {
+        uri: String,
+        mime_type: Option<String>,
+        blob: String,
+    },
+}
+
+/// A prompt template published by an MCP server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, derive_setters::Setters)]
+#[serde(rename_all = "camelCase")]
+#[setters(strip_option, into)]
+pub struct McpPrompt {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<McpPromptArgument>,
+}
+
+/// An argument accepted by an MCP prompt template.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpPromptArgument {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl McpPrompt {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), description: None, arguments: Vec::new() }
+    }
+
+    /// Binds values typed after a prompt command to the prompt's arguments.
+    ///
+    /// Values written as `name=value` are bound to the matching argument.
+    /// Remaining values fill the unbound arguments in declaration order, and
+    /// any surplus is appended to the last argument.
+    pub fn bind_arguments(&self, values: &[String]) -> BTreeMap<String, String> {
+        let mut bound = BTreeMap::new();
+        let mut positional = Vec::new();
+
+        for value in values {
+            match value.split_once('=') {
+                Some((name, value)) if self.arguments.iter().any(|arg| arg.name == name) => {
+                    bound.insert(name.to_string(), value.to_string());
+                }
+                _ => positional.push(value.as_str()),
+            }
+        }
+
+        let unbound = self
+            .arguments
+            .iter()
+            .filter(|arg| !bound.contains_key(&arg.name))
+            .map(|arg| arg.name.clone())
+            .collect::<Vec<_>>();
+
+        if let Some((last, rest)) = unbound.split_last() {
+            let mut positional = positional.into_iter();
+            for name in rest {
+                if let Some(value) = positional.next() {
+                    bound.insert(name.clone(), value.to_string());
+                }
+            }
+            let remaining = positional.collect::<Vec<_>>();
+            if !remaining.is_empty() {
+                bound.insert(last.clone(), remaining.join(" "));
+            }
+        }
+
+        bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn fixture() -> McpPrompt {
+        McpPrompt::new("review").arguments(vec![
+            McpPromptArgument { name: "file".to_string(), description: None, required: true },
+            McpPromptArgument {
+                name: "focus".to_string(),
+                description: None,
+                required: false,
+            },
+        ])
+    }
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_bind_arguments_positional() {
+        let actual = fixture().bind_arguments(&values(&["main.rs", "error", "handling"]));
+        let expected = BTreeMap::from([
+            ("file".to_string(), "main.rs".to_string()),
+            ("focus".to_string(), "error handling".to_string()),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_bind_arguments_named_and_positional() {
+        let actual = fixture().bind_arguments(&values(&["focus=perf", "lib.rs"]));
+        let expected = BTreeMap::from([
+            ("file".to_string(), "lib.rs".to_string()),
+            ("focus".to_string(), "perf".to_string()),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_bind_arguments_unknown_name_is_positional() {
+        let actual = fixture().bind_arguments(&values(&["a=b"]));
+        let expected = BTreeMap::from([("file".to_string(), "a=b".to_string())]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_bind_arguments_without_declared_arguments() {
+        let actual = McpPrompt::new("hello").bind_arguments(&values(&["ignored"]));
+        let expected = BTreeMap::new();
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,8 +1,10 @@
 use std::collections::{HashMap, hash_map};
+use std::time::Duration;
 
+use derive_more::Display;
 use serde::{Deserialize, Serialize};
 
-use crate::{ServerName, ToolDefinition};
+use crate::{McpPrompt, McpResource, ServerName, ToolDefinition};
 
 /// Cache for MCP tool definitions
 ///
@@ -18,6 +20,12 @@ pub struct McpServers {
     /// Failed MCP servers with their error messages
     #[serde(default)]
     failures: HashMap<ServerName, String>,
+    /// Resources published by the loaded servers
+    #[serde(default)]
+    resources: HashMap<ServerName, Vec<McpResource>>,
+    /// Prompt templates published by the loaded servers
+    #[serde(default)]
+    prompts: HashMap<ServerName, Vec<McpPrompt>>,
 }
 
 impl McpServers {
@@ -26,7 +34,12 @@ impl McpServers {
         servers: HashMap<ServerName, Vec<ToolDefinition>>,
         failures: HashMap<ServerName, String>,
     ) -> Self {
-        Self { servers, failures }
+        Self {
+            servers,
+            failures,
+            resources: Default::default(),
+            prompts: Default::default(),
+        }
     }
 
     /// Get the successful servers
@@ -38,6 +51,16 @@ impl McpServers {
     pub fn get_failures(&self) -> &HashMap<ServerName, String> {
         &self.failures
     }
+
+    /// Get the resources published by each server
+    pub fn get_resources(&self) -> &HashMap<ServerName, Vec<McpResource>> {
+        &self.resources
+    }
+
+    /// Get the prompt templates published by each server
+    pub fn get_prompts(&self) -> &HashMap<ServerName, Vec<McpPrompt>> {
+        &self.prompts
+    }
 }
 
 impl IntoIterator for McpServers {
@@ -48,3 +71,30 @@ impl IntoIterator for McpServers {
         self.servers.into_iter()
     }
 }
+
+/// Connection state of a configured MCP server
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum McpConnectionState {
+    Connected,
+    Failed,
+    Disabled,
+}
+
+/// Health report for a configured MCP server
+#[derive(Clone, Debug, PartialEq, derive_setters::Setters)]
+#[setters(strip_option, into)]
+pub struct McpServerStatus {
+    pub name: ServerName,
+    pub state: McpConnectionState,
+    /// Round-trip time of a tool listing request
+    pub latency: Option<Duration>,
+    /// Number of tools exposed after filtering
+    pub tools: usize,
+    pub last_error: Option<String>,
+}
+
+impl McpServerStatus {
+    pub fn new(name: ServerName, state: McpConnectionState) -> Self {
+        Self { name, state, latency: None, tools: 0, last_error: None }
+    }
+}
@@ -37,14 +37,20 @@ impl Usage {
 /// Represents a message that was received from the LLM provider
 /// NOTE: Tool call messages are part of the larger Response object and not part
 /// of the message.
-#[derive(Default, Clone, Debug, Setters, PartialEq)]
+#[derive(Default, Clone, Debug, Setters, PartialEq, Serialize, Deserialize)]
 #[setters(into, strip_option)]
 pub struct ChatCompletionMessage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content: Option<Content>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<Content>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reasoning_details: Option<Vec<Reasoning>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tool_calls: Vec<ToolCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub finish_reason: Option<FinishReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub usage: Option<Usage>,
 }
 
@@ -55,7 +61,8 @@ impl From<FinishReason> for ChatCompletionMessage {
 }
 
 /// Represents partial or full content of a message
-#[derive(Clone, Debug, PartialEq, Eq, From)]
+#[derive(Clone, Debug, PartialEq, Eq, From, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Content {
     Part(ContentPart),
     Full(ContentFull),
@@ -0,0 +1,178 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::AgentId;
+
+/// A step of the pipeline defined by the `stages` of a workflow. Stages run in
+/// order and each one can refer to the output of the stages before it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Stage {
+    Agent(AgentStage),
+    Parallel(ParallelStage),
+    Gate(GateStage),
+}
+
+/// Sends a prompt to an agent in a new conversation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AgentStage {
+    /// Agent that runs the stage
+    pub agent: AgentId,
+
+    /// Instructions for the agent. Handlebars template that can refer to
+    /// `prompt`, `event` and `stages`, e.g. `{{stages.0.output}}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// Content for the agent to work on, sent after the prompt. Handlebars
+    /// template like `prompt`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+
+    /// Shell command that must exit with 0 for the stage to run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+}
+
+/// Runs agents at the same time and waits for all of them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ParallelStage {
+    pub parallel: Vec<AgentStage>,
+
+    /// Shell command that must exit with 0 for the stage to run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+}
+
+/// Runs a verification command and stops the pipeline unless it exits with
+/// the expected code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GateStage {
+    /// Shell command to run
+    pub gate: String,
+
+    /// Exit code that lets the pipeline continue
+    #[serde(default)]
+    pub exit_code: i32,
+}
+
+/// What a stage produced, available to later stages as `stages.<index>`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct StageOutput {
+    /// Last message of the agent, the outputs of all parallel agents, or the
+    /// output of a gate command
+    pub output: String,
+
+    /// Exit code of a gate command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+
+    /// The `when` command of the stage did not exit with 0
+    pub skipped: bool,
+
+    /// Outputs of the agents of a parallel stage, in order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parallel: Vec<StageOutput>,
+}
+
+impl StageOutput {
+    pub fn new(output: impl Into<String>) -> Self {
+        Self { output: output.into(), ..Default::default() }
+    }
+
+    pub fn skipped() -> Self {
+        Self { skipped: true, ..Default::default() }
+    }
+
+    /// Combines the outputs of the agents of a parallel stage
+    pub fn parallel(outputs: Vec<StageOutput>) -> Self {
+        let output = outputs
+            .iter()
+            .filter(|output| !output.skipped)
+            .map(|output| output.output.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        Self { output, parallel: outputs, ..Default::default() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_stages() {
+        let fixture = r#"
+- agent: muse
+  prompt: "Plan {{prompt}}"
+- parallel:
+    - agent: forge
+      input: "{{stages.0.output}}"
+    - agent: sage
+      prompt: Review the plan
+  when: test -f Cargo.toml
+- gate: cargo test
+"#;
+
+        let actual: Vec<Stage> = serde_yml::from_str(fixture).unwrap();
+        let expected = vec![
+            Stage::Agent(AgentStage {
+                agent: AgentId::new("muse"),
+                prompt: Some("Plan {{prompt}}".to_string()),
+                input: None,
+                when: None,
+            }),
+            Stage::Parallel(ParallelStage {
+                parallel: vec![
+                    AgentStage {
+                        agent: AgentId::new("forge"),
+                        prompt: None,
+                        input: Some("{{stages.0.output}}".to_string()),
+                        when: None,
+                    },
+                    AgentStage {
+                        agent: AgentId::new("sage"),
+                        prompt: Some("Review the plan".to_string()),
+                        input: None,
+                        when: None,
+                    },
+                ],
+                when: Some("test -f Cargo.toml".to_string()),
+            }),
+            Stage::Gate(GateStage { gate: "cargo test".to_string(), exit_code: 0 }),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_stage_with_unknown_field_fails() {
+        let fixture = "- agent: forge\n  promt: Fix the tests\n";
+
+        let actual = serde_yml::from_str::<Vec<Stage>>(fixture).is_err();
+        let expected = true;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parallel_output_skips_skipped_agents() {
+        let fixture = vec![
+            StageOutput::new("first"),
+            StageOutput::skipped(),
+            StageOutput::new("second"),
+        ];
+
+        let actual = StageOutput::parallel(fixture.clone());
+        let expected = StageOutput {
+            output: "first\n\nsecond".to_string(),
+            exit_code: None,
+            skipped: false,
+            parallel: fixture,
+        };
+        assert_eq!(actual, expected);
+    }
+}
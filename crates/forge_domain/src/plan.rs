@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::Error;
+
+/// Status of a plan step, written as the character inside its checkbox:
+/// `- [ ]` pending, `- [~]` in progress, `- [x]` done and `- [!]` failed
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    eserde::Deserialize,
+    JsonSchema,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum StepStatus {
+    #[default]
+    Pending,
+    InProgress,
+    Done,
+    Failed,
+}
+
+impl StepStatus {
+    fn from_marker(marker: char) -> Option<Self> {
+        match marker {
+            ' ' => Some(Self::Pending),
+            '~' => Some(Self::InProgress),
+            'x' | 'X' => Some(Self::Done),
+            '!' => Some(Self::Failed),
+            _ => None,
+        }
+    }
+
+    pub fn marker(&self) -> char {
+        match self {
+            Self::Pending => ' ',
+            Self::InProgress => '~',
+            Self::Done => 'x',
+            Self::Failed => '!',
+        }
+    }
+}
+
+/// A checkbox of a plan file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanStep {
+    /// Position of the step in the plan (1-based)
+    pub number: usize,
+    pub status: StepStatus,
+    pub text: String,
+}
+
+/// Number of finished steps out of all steps of a plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+#[display("{done}/{total}")]
+pub struct PlanProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+/// A markdown plan whose steps are written as `- [ ]` checkboxes, as created
+/// by the `plan` tool. Checkboxes inside fenced code blocks are not steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanFile {
+    pub path: PathBuf,
+    pub steps: Vec<PlanStep>,
+}
+
+impl PlanFile {
+    pub fn parse(path: impl Into<PathBuf>, content: &str) -> Self {
+        let steps = checkboxes(content)
+            .enumerate()
+            .map(|(index, (_, status, text))| PlanStep {
+                number: index + 1,
+                status,
+                text: text.to_string(),
+            })
+            .collect();
+        Self { path: path.into(), steps }
+    }
+
+    pub fn progress(&self) -> PlanProgress {
+        let done = self
+            .steps
+            .iter()
+            .filter(|step| step.status == StepStatus::Done)
+            .count();
+        PlanProgress { done, total: self.steps.len() }
+    }
+
+    /// Returns the step to work on: the first one in progress, otherwise the
+    /// first pending one.
+    pub fn next_step(&self) -> Option<&PlanStep> {
+        self.steps
+            .iter()
+            .find(|step| step.status == StepStatus::InProgress)
+            .or_else(|| {
+                self.steps
+                    .iter()
+                    .find(|step| step.status == StepStatus::Pending)
+            })
+    }
+
+    /// Returns true once every step is done
+    pub fn is_complete(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|step| step.status == StepStatus::Done)
+    }
+
+    /// Returns `content` with the checkbox of step `number` set to `status`,
+    /// leaving every other byte of the file unchanged.
+    pub fn update_step(content: &str, number: usize, status: StepStatus) -> crate::Result<String> {
+        let (offset, _, _) = checkboxes(content)
+            .nth(number.wrapping_sub(1))
+            .ok_or_else(|| Error::PlanStepNotFound {
+                step: number,
+                total: checkboxes(content).count(),
+            })?;
+
+        let mut updated = String::with_capacity(content.len());
+        updated.push_str(&content[..offset]);
+        updated.push(status.marker());
+        updated.push_str(&content[offset + 1..]);
+        Ok(updated)
+    }
+}
+
+/// Yields the byte offset of the marker, the status and the text of every
+/// checkbox outside of fenced code blocks
+fn checkboxes(content: &str) -> impl Iterator<Item = (usize, StepStatus, &str)> {
+    let mut offset = 0;
+    let mut in_code_block = false;
+    content.split_inclusive('\n').filter_map(move |line| {
+        let start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            return None;
+        }
+        if in_code_block {
+            return None;
+        }
+
+        let rest = trimmed
+            .strip_prefix("- [")
+            .or_else(|| trimmed.strip_prefix("* ["))
+            .or_else(|| trimmed.strip_prefix("+ ["))?;
+        let marker = rest.chars().next()?;
+        let status = StepStatus::from_marker(marker)?;
+        let text = rest[marker.len_utf8()..].strip_prefix(']')?;
+        if !(text.is_empty() || text.starts_with(char::is_whitespace)) {
+            return None;
+        }
+
+        let marker_offset = start + (line.len() - rest.len());
+        Some((marker_offset, status, text.trim()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const FIXTURE: &str = "# Plan\n\n## Steps\n\n- [x] Add the parser\n- [~] Add the tools\n  - [ ] Nested step\n- [!] Update the prompt\n\n```markdown\n- [ ] Example inside a code block\n```\n\n* [ ] Document it\n- [link](https://example.com)\n";
+
+    #[test]
+    fn test_parse_plan() {
+        let actual = PlanFile::parse("plan.md", FIXTURE);
+        let expected = PlanFile {
+            path: PathBuf::from("plan.md"),
+            steps: vec![
+                PlanStep {
+                    number: 1,
+                    status: StepStatus::Done,
+                    text: "Add the parser".to_string(),
+                },
+                PlanStep {
+                    number: 2,
+                    status: StepStatus::InProgress,
+                    text: "Add the tools".to_string(),
+                },
+                PlanStep {
+                    number: 3,
+                    status: StepStatus::Pending,
+                    text: "Nested step".to_string(),
+                },
+                PlanStep {
+                    number: 4,
+                    status: StepStatus::Failed,
+                    text: "Update the prompt".to_string(),
+                },
+                PlanStep {
+                    number: 5,
+                    status: StepStatus::Pending,
+                    text: "Document it".to_string(),
+                },
+            ],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_plan_progress_and_next_step() {
+        let fixture = PlanFile::parse("plan.md", FIXTURE);
+
+        let actual = (
+            fixture.progress(),
+            fixture.next_step().map(|step| step.number),
+        );
+        let expected = (PlanProgress { done: 1, total: 5 }, Some(2));
+        assert_eq!(actual, expected);
+        assert!(!fixture.is_complete());
+    }
+
+    #[test]
+    fn test_update_step_only_changes_its_marker() {
+        let fixture = "- [ ] First\r\n- [ ] Second\r\n";
+
+        let actual = PlanFile::update_step(fixture, 2, StepStatus::Done).unwrap();
+        let expected = "- [ ] First\r\n- [x] Second\r\n";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_update_step_skips_code_blocks() {
+        let actual = PlanFile::update_step(FIXTURE, 5, StepStatus::InProgress).unwrap();
+        let expected = FIXTURE.replace("* [ ] Document it", "* [~] Document it");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_update_missing_step() {
+        let actual = PlanFile::update_step(FIXTURE, 6, StepStatus::Done)
+            .unwrap_err()
+            .to_string();
+        let expected = "No step 6 in the plan, it has 5 steps";
+        assert_eq!(actual, expected);
+    }
+}
@@ -64,6 +64,7 @@ impl ProviderId {
     pub const ANTHROPIC_COMPATIBLE: ProviderId = ProviderId(Cow::Borrowed("anthropic_compatible"));
     pub const FORGE_SERVICES: ProviderId = ProviderId(Cow::Borrowed("forge_services"));
     pub const IO_INTELLIGENCE: ProviderId = ProviderId(Cow::Borrowed("io_intelligence"));
+    pub const REPLAY: ProviderId = ProviderId(Cow::Borrowed("replay"));
 
     /// Returns all built-in provider IDs
     ///
@@ -88,6 +89,7 @@ impl ProviderId {
             ProviderId::ANTHROPIC_COMPATIBLE,
             ProviderId::FORGE_SERVICES,
             ProviderId::IO_INTELLIGENCE,
+            ProviderId::REPLAY,
         ]
     }
 
@@ -146,6 +148,7 @@ impl std::str::FromStr for ProviderId {
             "anthropic_compatible" => ProviderId::ANTHROPIC_COMPATIBLE,
             "forge_services" => ProviderId::FORGE_SERVICES,
             "io_intelligence" => ProviderId::IO_INTELLIGENCE,
+            "replay" => ProviderId::REPLAY,
             // For custom providers, use Cow::Owned to avoid memory leaks
             custom => ProviderId(Cow::Owned(custom.to_string())),
         };
@@ -163,6 +166,9 @@ impl From<String> for ProviderId {
 pub enum ProviderResponse {
     OpenAI,
     Anthropic,
+    /// Responses are served from a cassette recorded in an earlier session.
+    /// The provider URL is the `file://` URL of the cassette.
+    Replay,
 }
 
 /// Represents the source of models for a provider
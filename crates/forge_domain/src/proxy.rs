@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+
+use crate::ProviderId;
+
+/// Wire format of a request received by `forge proxy`
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+)]
+pub enum ProxyFormat {
+    /// `POST /v1/chat/completions`
+    #[serde(rename = "openai")]
+    #[strum(serialize = "openai")]
+    OpenAI,
+    /// `POST /v1/messages`
+    #[serde(rename = "anthropic")]
+    #[strum(serialize = "anthropic")]
+    Anthropic,
+}
+
+/// A request served by `forge proxy` together with the response sent back
+#[derive(Debug, Clone, PartialEq, Setters)]
+#[setters(into)]
+pub struct ProxyExchange {
+    pub created_at: DateTime<Utc>,
+    pub format: ProxyFormat,
+    /// Model as requested by the client, possibly prefixed with a provider
+    pub model: String,
+    /// Provider the request was routed to, unset if routing failed
+    #[setters(strip_option)]
+    pub provider: Option<ProviderId>,
+    /// Request body as received
+    pub request: String,
+    /// Response body as sent, the concatenated events for streams
+    #[setters(strip_option)]
+    pub response: Option<String>,
+    /// HTTP status code sent to the client
+    pub status: u16,
+    pub duration_ms: u64,
+    #[setters(strip_option)]
+    pub error: Option<String>,
+}
+
+impl ProxyExchange {
+    pub fn new(format: ProxyFormat, model: impl Into<String>, request: impl Into<String>) -> Self {
+        Self {
+            created_at: Utc::now(),
+            format,
+            model: model.into(),
+            provider: None,
+            request: request.into(),
+            response: None,
+            status: 200,
+            duration_ms: 0,
+            error: None,
+        }
+    }
+}
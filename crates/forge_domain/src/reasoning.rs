@@ -20,7 +20,8 @@ pub type ReasoningPart = ReasoningDetail;
 /// Type alias for complete reasoning
 pub type ReasoningFull = ReasoningDetail;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Reasoning {
     Part(Vec<ReasoningPart>),
     Full(Vec<ReasoningFull>),
@@ -5,7 +5,8 @@ use url::Url;
 
 use crate::{
     AnyProvider, AppConfig, AuthCredential, Conversation, ConversationId, MigrationResult,
-    Provider, ProviderId, Skill, Snapshot, UserId, Workspace, WorkspaceAuth, WorkspaceId,
+    Provider, ProviderId, ProxyExchange, Schedule, ScheduleId, ScheduleRun, Skill, Snapshot,
+    UserId, Workspace, WorkspaceAuth, WorkspaceId,
 };
 
 /// Repository for managing file snapshots
@@ -125,6 +126,43 @@ pub trait WorkspaceRepository: Send + Sync {
     async fn delete(&self, workspace_id: &WorkspaceId) -> anyhow::Result<()>;
 }
 
+/// Repository for the log of requests served by `forge proxy`
+#[async_trait::async_trait]
+pub trait ProxyLogRepository: Send + Sync {
+    /// Appends an exchange to the log
+    async fn append_proxy_exchange(&self, exchange: ProxyExchange) -> anyhow::Result<()>;
+
+    /// Returns up to `limit` exchanges, most recent first
+    async fn get_proxy_exchanges(&self, limit: usize) -> anyhow::Result<Vec<ProxyExchange>>;
+}
+
+/// Repository for jobs run by `forge schedule daemon` and their run history
+#[async_trait::async_trait]
+pub trait ScheduleRepository: Send + Sync {
+    /// Stores a new schedule and returns it with its assigned id
+    async fn add_schedule(&self, schedule: Schedule) -> anyhow::Result<Schedule>;
+
+    /// Returns all schedules, oldest first
+    async fn get_schedules(&self) -> anyhow::Result<Vec<Schedule>>;
+
+    /// Removes a schedule and its runs, returning false if it did not exist
+    async fn remove_schedule(&self, id: ScheduleId) -> anyhow::Result<bool>;
+
+    /// Stores a run that just started and records it as the schedule's last
+    /// run, returning the run with its assigned id
+    async fn start_schedule_run(&self, run: ScheduleRun) -> anyhow::Result<ScheduleRun>;
+
+    /// Updates the status, end time and error of a started run
+    async fn finish_schedule_run(&self, run: &ScheduleRun) -> anyhow::Result<()>;
+
+    /// Returns up to `limit` runs of a schedule, most recent first
+    async fn get_schedule_runs(
+        &self,
+        id: ScheduleId,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ScheduleRun>>;
+}
+
 /// Repository for managing codebase indexing and search operations
 #[async_trait::async_trait]
 pub trait ContextEngineRepository: Send + Sync {
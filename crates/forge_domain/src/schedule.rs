@@ -0,0 +1,461 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+
+use crate::{AgentId, ConversationId, Error};
+
+/// A five-field cron expression: minute, hour, day of month, month and day of
+/// week. Fields accept `*`, values, ranges, lists and steps such as `*/15`,
+/// `1-5` or `0,30`. `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly`
+/// are accepted as shorthands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    // Days match when either field matches if both are restricted
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronExpr {
+    /// Returns the first time strictly after `after` that matches the
+    /// expression, or `None` if it never matches.
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let timezone = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut time = start;
+
+        // Covers the rarest expressions, such as the 29th of February on a
+        // Monday, which repeats every 28 years
+        let limit = start + Duration::days(366 * 29);
+        while time < limit {
+            if !contains(self.months, time.month()) {
+                time = first_of_next_month(time.date())?;
+                continue;
+            }
+            if !self.matches_day(time.date()) {
+                time = (time.date() + Duration::days(1)).and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !contains(self.hours, time.hour()) {
+                time = time.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !contains(self.minutes, time.minute()) {
+                time += Duration::minutes(1);
+                continue;
+            }
+            // Skips times that do not exist because of a daylight saving
+            // transition
+            match timezone.from_local_datetime(&time).earliest() {
+                Some(next) => return Some(next),
+                None => time += Duration::minutes(1),
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = contains(self.days, date.day());
+        let weekday = contains(self.weekdays, date.weekday().num_days_from_sunday());
+        if self.days_restricted && self.weekdays_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+}
+
+fn contains(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDateTime> {
+    let (year, month) = match date.month() {
+        12 => (date.year() + 1, 1),
+        month => (date.year(), month + 1),
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)
+}
+
+/// Parses one field into a bit set of the values it matches, returning
+/// whether the field is restricted, i.e. not `*`.
+fn parse_field(field: &str, min: u32, max: u32) -> std::result::Result<(u64, bool), String> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid step '{step}'"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let value = |value: &str| {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|value| (min..=max).contains(value))
+                .ok_or_else(|| format!("'{value}' is not between {min} and {max}"))
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // `5/15` means every 15 starting at 5
+                None if part.contains('/') => (value(range)?, max),
+                None => {
+                    let value = value(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("range '{range}' is reversed"));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok((set, !field.starts_with('*')))
+}
+
+impl FromStr for CronExpr {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> crate::Result<Self> {
+        let error =
+            |reason: String| Error::InvalidCron { expression: expression.to_string(), reason };
+        let fields = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            fields => fields,
+        };
+        let fields = fields.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            return Err(error(format!(
+                "expected 5 fields but found {}",
+                fields.len()
+            )));
+        };
+
+        let (minutes, _) = parse_field(minutes, 0, 59).map_err(error)?;
+        let (hours, _) = parse_field(hours, 0, 23).map_err(error)?;
+        let (days, days_restricted) = parse_field(days, 1, 31).map_err(error)?;
+        let (months, _) = parse_field(months, 1, 12).map_err(error)?;
+        let (mut weekdays, weekdays_restricted) = parse_field(weekdays, 0, 7).map_err(error)?;
+        // Both 0 and 7 are Sunday
+        if contains(weekdays, 7) {
+            weekdays |= 1;
+        }
+
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            minutes,
+            hours,
+            days,
+            months,
+            weekdays,
+            days_restricted,
+            weekdays_restricted,
+        })
+    }
+}
+
+impl fmt::Display for CronExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+/// Identifier of a [`Schedule`], assigned when it is stored
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    derive_more::Display,
+    derive_more::From,
+)]
+#[serde(transparent)]
+pub struct ScheduleId(i64);
+
+impl ScheduleId {
+    pub fn new(id: i64) -> Self {
+        Self(id)
+    }
+
+    pub fn id(&self) -> i64 {
+        self.0
+    }
+}
+
+impl FromStr for ScheduleId {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+/// What a scheduled job asks the agent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchedulePrompt {
+    Text(String),
+    /// A file read every time the job runs, so that it can be edited
+    /// without re-adding the schedule
+    File(PathBuf),
+}
+
+/// A prompt run by `forge schedule daemon` whenever its cron expression is
+/// due
+#[derive(Debug, Clone, PartialEq, Setters)]
+#[setters(into)]
+pub struct Schedule {
+    pub id: ScheduleId,
+    pub cron: CronExpr,
+    #[setters(strip_option)]
+    pub agent: Option<AgentId>,
+    pub prompt: SchedulePrompt,
+    /// Directory the job runs in
+    pub cwd: PathBuf,
+    /// Runs the job in a git worktree of its own instead of `cwd`
+    pub sandbox: bool,
+    pub created_at: DateTime<Utc>,
+    #[setters(strip_option)]
+    pub last_run_at: Option<DateTime<Utc>>,
+}
+
+impl Schedule {
+    pub fn new(cron: CronExpr, prompt: SchedulePrompt, cwd: impl Into<PathBuf>) -> Self {
+        Self {
+            id: ScheduleId::default(),
+            cron,
+            agent: None,
+            prompt,
+            cwd: cwd.into(),
+            sandbox: true,
+            created_at: Utc::now(),
+            last_run_at: None,
+        }
+    }
+
+    /// Returns when the job runs next in the timezone `tz`. Runs missed while
+    /// no daemon was running are due immediately, but only once.
+    pub fn next_run<Tz: TimeZone>(&self, tz: &Tz) -> Option<DateTime<Tz>> {
+        let since = self.last_run_at.unwrap_or(self.created_at);
+        self.cron.next_after(&since.with_timezone(tz))
+    }
+
+    /// Returns true if the job should run at `now`
+    pub fn is_due<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> bool {
+        self.next_run(&now.timezone())
+            .is_some_and(|next| next <= *now)
+    }
+}
+
+/// Outcome of a run of a [`Schedule`], following the exit status of
+/// `forge -p`
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ScheduleRunStatus {
+    Running,
+    Success,
+    Error,
+    LimitReached,
+    Interrupted,
+}
+
+impl ScheduleRunStatus {
+    /// Maps the exit code of `forge -p` to a status. `None` means the process
+    /// was killed by a signal.
+    pub fn from_exit_code(code: Option<i32>) -> Self {
+        match code {
+            Some(0) => Self::Success,
+            Some(2) => Self::LimitReached,
+            Some(130) | None => Self::Interrupted,
+            Some(_) => Self::Error,
+        }
+    }
+}
+
+/// A run of a [`Schedule`]
+#[derive(Debug, Clone, PartialEq, Setters)]
+#[setters(into)]
+pub struct ScheduleRun {
+    /// Assigned when the run is stored
+    pub id: i64,
+    pub schedule_id: ScheduleId,
+    pub conversation_id: ConversationId,
+    /// Name of the worktree the run worked in
+    #[setters(strip_option)]
+    pub sandbox: Option<String>,
+    pub status: ScheduleRunStatus,
+    pub started_at: DateTime<Utc>,
+    #[setters(strip_option)]
+    pub finished_at: Option<DateTime<Utc>>,
+    #[setters(strip_option)]
+    pub error: Option<String>,
+}
+
+impl ScheduleRun {
+    pub fn new(schedule_id: ScheduleId, conversation_id: ConversationId) -> Self {
+        Self {
+            id: 0,
+            schedule_id,
+            conversation_id,
+            sandbox: None,
+            status: ScheduleRunStatus::Running,
+            started_at: Utc::now(),
+            finished_at: None,
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn at(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date).unwrap().to_utc()
+    }
+
+    fn next(expression: &str, after: &str) -> Option<DateTime<Utc>> {
+        CronExpr::from_str(expression)
+            .unwrap()
+            .next_after(&at(after))
+    }
+
+    #[test]
+    fn test_next_after_daily() {
+        let actual = next("0 2 * * *", "2026-10-18T02:00:00Z");
+        let expected = Some(at("2026-10-19T02:00:00Z"));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_next_after_steps_and_lists() {
+        let actual = (
+            next("*/15 9-17 * * *", "2026-10-18T17:50:00Z"),
+            next("0,30 * * * *", "2026-10-18T10:10:00Z"),
+        );
+        let expected = (
+            Some(at("2026-10-19T09:00:00Z")),
+            Some(at("2026-10-18T10:30:00Z")),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_next_after_weekdays() {
+        // 2026-10-18 is a Sunday
+        let actual = (
+            next("0 9 * * 1-5", "2026-10-17T10:00:00Z"),
+            next("@weekly", "2026-10-17T10:00:00Z"),
+            next("0 0 * * 7", "2026-10-17T10:00:00Z"),
+        );
+        let expected = (
+            Some(at("2026-10-19T09:00:00Z")),
+            Some(at("2026-10-18T00:00:00Z")),
+            Some(at("2026-10-18T00:00:00Z")),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_next_after_day_of_month_or_weekday() {
+        // Either the 1st or a Friday, as in standard cron
+        let actual = next("0 0 1 * 5", "2026-10-18T00:00:00Z");
+        let expected = Some(at("2026-10-23T00:00:00Z"));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_next_after_leap_day_and_impossible_date() {
+        let actual = (
+            next("0 0 29 2 *", "2026-10-18T00:00:00Z"),
+            next("0 0 30 2 *", "2026-10-18T00:00:00Z"),
+        );
+        let expected = (Some(at("2028-02-29T00:00:00Z")), None);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_invalid_cron() {
+        let actual = ["0 2 * *", "60 * * * *", "*/0 * * * *", "5-1 * * * *"]
+            .map(|expression| CronExpr::from_str(expression).unwrap_err().to_string());
+        let expected = [
+            "Invalid cron expression '0 2 * *': expected 5 fields but found 4",
+            "Invalid cron expression '60 * * * *': '60' is not between 0 and 59",
+            "Invalid cron expression '*/0 * * * *': invalid step '0'",
+            "Invalid cron expression '5-1 * * * *': range '5-1' is reversed",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_schedule_is_due_once_after_missed_runs() {
+        let fixture = Schedule::new(
+            CronExpr::from_str("0 2 * * *").unwrap(),
+            SchedulePrompt::Text("Audit dependencies".to_string()),
+            "/project",
+        )
+        .created_at(at("2026-10-15T12:00:00Z"))
+        .last_run_at(at("2026-10-16T02:00:00Z"));
+
+        let actual = (
+            fixture.is_due(&at("2026-10-18T12:00:00Z")),
+            fixture.next_run(&Utc),
+            fixture.is_due(&at("2026-10-17T01:59:00Z")),
+        );
+        let expected = (true, Some(at("2026-10-17T02:00:00Z")), false);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_run_status_from_exit_code() {
+        let actual =
+            [Some(0), Some(1), Some(2), Some(130), None].map(ScheduleRunStatus::from_exit_code);
+        let expected = [
+            ScheduleRunStatus::Success,
+            ScheduleRunStatus::Error,
+            ScheduleRunStatus::LimitReached,
+            ScheduleRunStatus::Interrupted,
+            ScheduleRunStatus::Interrupted,
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,119 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Lookup performed by the `symbols` tool: `outline` lists the definitions in
+/// a file, `definition` finds where a symbol is defined along with the impls,
+/// subclasses and implementations that name it, and `references` finds where a
+/// name is used
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    eserde::Deserialize,
+    JsonSchema,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SymbolQuery {
+    #[default]
+    Outline,
+    Definition,
+    References,
+}
+
+/// Kind of a symbol definition
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    Impl,
+    Class,
+    Interface,
+    Type,
+    Module,
+    Constant,
+    Static,
+    Macro,
+}
+
+/// A symbol defined in a source file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolDefinition {
+    /// Name the symbol is looked up by. Impls are named after the type they
+    /// implement.
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Names of the enclosing definitions and the symbol itself, outermost
+    /// first, e.g. `impl Service for Client > call`
+    pub qualified_name: String,
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// First line of the definition
+    pub signature: String,
+    /// Traits, interfaces or base classes the definition implements or
+    /// extends
+    pub implements: Vec<String>,
+}
+
+impl SymbolDefinition {
+    /// Returns true if the definition defines or implements `name`
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.implements.iter().any(|item| item == name)
+    }
+
+    /// Returns true if `line` lies within the definition
+    pub fn contains_line(&self, line: usize) -> bool {
+        self.start_line <= line && line <= self.end_line
+    }
+
+    /// Summarizes the definition as a single line
+    pub fn summary(&self) -> String {
+        format!(
+            "[{} {}, lines {}-{}] {}",
+            self.kind, self.qualified_name, self.start_line, self.end_line, self.signature
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let fixture = SymbolDefinition {
+            name: "Client".to_string(),
+            kind: SymbolKind::Impl,
+            qualified_name: "impl Service for Client".to_string(),
+            path: "/project/src/lib.rs".to_string(),
+            start_line: 12,
+            end_line: 40,
+            signature: "impl Service for Client {".to_string(),
+            implements: vec!["Service".to_string()],
+        };
+
+        let actual = (fixture.summary(), fixture.is_named("Service"));
+
+        let expected = (
+            "[impl impl Service for Client, lines 12-40] impl Service for Client {".to_string(),
+            true,
+        );
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,45 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Status of a task of the conversation's task list
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    eserde::Deserialize,
+    JsonSchema,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TodoStatus {
+    /// Not started yet
+    #[default]
+    Pending,
+    /// Being worked on
+    InProgress,
+    /// Finished
+    Done,
+}
+
+/// A task of the list the agent keeps to track the sub-goals of a
+/// conversation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, eserde::Deserialize, JsonSchema)]
+pub struct Todo {
+    /// Short description of the task
+    pub content: String,
+
+    /// Status of the task
+    #[serde(default)]
+    pub status: TodoStatus,
+}
+
+impl Todo {
+    pub fn new(content: impl ToString, status: TodoStatus) -> Self {
+        Self { content: content.to_string(), status }
+    }
+}
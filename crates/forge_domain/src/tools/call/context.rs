@@ -2,19 +2,30 @@ use std::sync::{Arc, Mutex};
 
 use derive_setters::Setters;
 
-use crate::{ArcSender, ChatResponse, ChatResponseContent, Metrics, TitleFormat};
+use crate::{ArcSender, ChatResponse, ChatResponseContent, Metrics, TitleFormat, Todo};
 
 /// Provides additional context for tool calls.
 #[derive(Debug, Clone, Setters)]
 pub struct ToolCallContext {
     sender: Option<ArcSender>,
     metrics: Arc<Mutex<Metrics>>,
+    #[setters(skip)]
+    todos: Arc<Mutex<Vec<Todo>>>,
 }
 
 impl ToolCallContext {
     /// Creates a new ToolCallContext with default values
     pub fn new(metrics: Metrics) -> Self {
-        Self { sender: None, metrics: Arc::new(Mutex::new(metrics)) }
+        Self {
+            sender: None,
+            metrics: Arc::new(Mutex::new(metrics)),
+            todos: Default::default(),
+        }
+    }
+
+    /// Sets the task list the `todo` tool starts from
+    pub fn todos(self, todos: Vec<Todo>) -> Self {
+        Self { todos: Arc::new(Mutex::new(todos)), ..self }
     }
 
     /// Send a message through the sender if available
@@ -47,6 +58,18 @@ impl ToolCallContext {
             .map_err(|_| anyhow::anyhow!("Failed to acquire metrics lock"))?;
         Ok(f(&mut metrics))
     }
+
+    /// Execute a closure with access to the task list
+    pub fn with_todos<F, R>(&self, f: F) -> anyhow::Result<R>
+    where
+        F: FnOnce(&mut Vec<Todo>) -> R,
+    {
+        let mut todos = self
+            .todos
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire todos lock"))?;
+        Ok(f(&mut todos))
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,5 @@
 #![allow(clippy::enum_variant_names)]
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use convert_case::{Case, Casing};
@@ -13,7 +13,10 @@ use serde_json::Map;
 use strum::IntoEnumIterator;
 use strum_macros::{AsRefStr, Display, EnumDiscriminants, EnumIter};
 
-use crate::{ToolCallArguments, ToolCallFull, ToolDefinition, ToolDescription, ToolName};
+use crate::{
+    HttpMethod, StepStatus, SymbolQuery, Todo, ToolCallArguments, ToolCallFull, ToolDefinition,
+    ToolDescription, ToolName, WebSearchBackend,
+};
 
 /// Enum representing all possible tool input types.
 ///
@@ -42,14 +45,21 @@ pub enum ToolCatalog {
     ReadImage(ReadImage),
     Write(FSWrite),
     Search(FSSearch),
+    AstSearch(AstSearch),
+    Symbols(Symbols),
+    Diagnostics(Diagnostics),
     SemSearch(SemanticSearch),
     Remove(FSRemove),
     Patch(FSPatch),
     Undo(FSUndo),
     Shell(Shell),
     Fetch(NetFetch),
+    WebSearch(WebSearch),
     Followup(Followup),
     Plan(PlanCreate),
+    PlanStatus(PlanStatus),
+    PlanUpdateStep(PlanUpdateStep),
+    Todo(TodoWrite),
     Skill(SkillFetch),
 }
 
@@ -173,6 +183,88 @@ pub struct FSSearch {
     pub file_pattern: Option<String>,
 }
 
+/// Searches code structurally using tree-sitter queries instead of regex.
+/// Use it for questions that line-based search answers unreliably, such as
+/// "all `impl Display for ...` blocks" or "calls to `unwrap()` inside async
+/// functions". Queries use tree-sitter S-expression syntax: named captures
+/// (`@name`) act as metavariables and can be constrained with predicates
+/// like `(#eq? @name "unwrap")` or `(#match? @name "^test_")`. The node
+/// captured as `@match` is reported; without it the outermost captured node
+/// is, so queries need at least one capture. Supports Rust, Python, TypeScript/TSX, JavaScript and Go files,
+/// selected by extension. Each result includes the name of its enclosing
+/// function, type or impl. Requires absolute paths. Example query for
+/// `unwrap()` calls: `(call_expression function: (field_expression field:
+/// (field_identifier) @method) (#eq? @method "unwrap")) @match`.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, ToolDescription, PartialEq)]
+pub struct AstSearch {
+    /// The absolute path of the directory or file to search in. Directories
+    /// are searched recursively.
+    pub path: String,
+
+    /// Tree-sitter query to match, written for the grammar of the searched
+    /// language. Files whose language can't compile the query are skipped.
+    pub query: String,
+
+    /// Optional tree-sitter query restricting results to matches nested
+    /// inside nodes it matches, e.g. `(function_item (function_modifiers
+    /// "async")) @match` to only report matches inside async functions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inside: Option<String>,
+
+    /// Glob pattern to filter files (e.g., '*.rs' for Rust files).
+    /// If not provided, all supported files are searched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_pattern: Option<String>,
+
+    /// Starting index for the search results (1-based).
+    pub start_index: Option<i32>,
+
+    /// Maximum number of lines to return in the search results.
+    pub max_search_lines: Option<i32>,
+}
+
+/// Looks up symbols in a local index of the definitions and references in
+/// Rust, Python, TypeScript/TSX, JavaScript and Go files. Use `outline` to
+/// list the functions, types and impls in a file with their line ranges,
+/// `definition` to find where a name is defined along with the impls,
+/// subclasses and implementations that name it, and `references` to find
+/// where a name is used. Results are a line per symbol, so prefer an outline
+/// followed by a ranged read over reading whole files. The index is built on
+/// first use and only changed files are re-parsed afterwards.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, ToolDescription, PartialEq)]
+pub struct Symbols {
+    /// The lookup to perform: `outline`, `definition` or `references`
+    pub query: SymbolQuery,
+
+    /// The absolute file path for `outline`, or the symbol name for
+    /// `definition` and `references` (e.g. `ProviderRepository`)
+    pub target: String,
+
+    /// The absolute path of the directory searched by `definition` and
+    /// `references`. Defaults to the current working directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Starting index for the results (1-based).
+    pub start_index: Option<i32>,
+
+    /// Maximum number of lines to return in the results.
+    pub max_search_lines: Option<i32>,
+}
+
+/// Reports the errors and warnings the language server configured for a file's
+/// language finds in it, such as type errors, unresolved names and lints. The
+/// file is synced with the server as it is on disk. Use it to check a file
+/// after a series of edits or before relying on code you did not write. Edits
+/// made with `write` and `patch` already report new errors and warnings, so
+/// there is no need to call it after every edit. Language servers are
+/// configured per language in `forge.yaml`.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, ToolDescription, PartialEq)]
+pub struct Diagnostics {
+    /// The absolute path of the file to check
+    pub path: String,
+}
+
 /// A paired query and use_case for semantic search. Each query must have a
 /// corresponding use_case for document reranking.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
@@ -373,7 +465,16 @@ pub struct Shell {
     pub env: Option<Vec<String>>,
 }
 
-/// Input type for the net fetch tool
+/// Retrieves content from URLs as markdown or raw text. Enables access to
+/// current online information including websites, APIs and documentation. Use
+/// for obtaining up-to-date information beyond training data, verifying facts,
+/// or retrieving specific online content. Handles HTTP/HTTPS and converts HTML
+/// to readable markdown by default. Supports other methods, headers and a
+/// request body for calling APIs. Credentials configured for a domain are
+/// added automatically, so never ask for or pass secrets in headers. Respects
+/// robots.txt and may be blocked by anti-scraping measures. For large pages,
+/// returns the first 40,000 characters and stores the complete content in a
+/// temporary file for subsequent access.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, ToolDescription, PartialEq)]
 pub struct NetFetch {
     /// URL to fetch
@@ -383,6 +484,62 @@ pub struct NetFetch {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw: Option<bool>,
+
+    /// HTTP method (default: GET)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<HttpMethod>,
+
+    /// Additional request headers
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, String>>,
+
+    /// Request body, e.g. a JSON payload for POST, PUT or PATCH requests
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Searches the web and returns a list of results, each with a title, URL and
+/// snippet. Use to find current documentation, release notes, error reports or
+/// other online information, then retrieve the most relevant pages with the
+/// fetch tool. Prefer specific queries; results are ranked by the configured
+/// search backend.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, ToolDescription, PartialEq)]
+pub struct WebSearch {
+    /// Search query
+    pub query: String,
+
+    /// Maximum number of results to return (default: 10)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_results: Option<usize>,
+}
+
+impl WebSearch {
+    /// Number of results returned when `max_results` is not given
+    pub const DEFAULT_MAX_RESULTS: usize = 10;
+
+    pub fn max_results(&self) -> usize {
+        self.max_results.unwrap_or(Self::DEFAULT_MAX_RESULTS).max(1)
+    }
+
+    /// Describes the search as a fetch of the backend's policy URL, so that
+    /// web searches are governed by the same policies as fetches.
+    pub fn to_policy_operation(
+        &self,
+        backend: &WebSearchBackend,
+        cwd: PathBuf,
+    ) -> crate::policies::PermissionOperation {
+        crate::policies::PermissionOperation::Fetch {
+            url: backend
+                .policy_url(&self.query, self.max_results())
+                .to_string(),
+            cwd,
+            message: format!("Search the web for: {}", self.query),
+        }
+    }
 }
 
 /// Use this tool when you encounter ambiguities, need clarification, or require
@@ -437,6 +594,47 @@ pub struct PlanCreate {
     pub content: String,
 }
 
+/// Shows the progress of a markdown plan whose steps are written as `- [ ]`
+/// checkboxes: every step with its number and status, and the step to work on
+/// next. Use it when starting or resuming a plan instead of reading the file,
+/// and again before announcing that a plan is complete.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, ToolDescription, PartialEq)]
+pub struct PlanStatus {
+    /// The absolute path of the plan file
+    pub path: String,
+}
+
+/// Sets the status of one step of a markdown plan by rewriting its checkbox,
+/// leaving the rest of the file untouched. Mark a step `in_progress` before
+/// working on it, then `done` once it is finished or `failed` if it cannot be
+/// completed. Steps are numbered from 1 in the order `plan_status` lists them.
+/// Never edit the checkboxes of a plan with `patch` or `write`.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, ToolDescription, PartialEq)]
+pub struct PlanUpdateStep {
+    /// The absolute path of the plan file
+    pub path: String,
+
+    /// Number of the step, as listed by `plan_status`
+    pub step: usize,
+
+    /// New status of the step: `pending` when not started yet, `in_progress`
+    /// while being worked on, `done` once finished or `failed` if it could not
+    /// be completed
+    pub status: StepStatus,
+}
+
+/// Replaces the task list of the conversation with `todos`. Use it on tasks
+/// with three or more steps to record the sub-goals up front, then call it
+/// again with the whole list whenever a task starts or finishes. Keep exactly
+/// one task `in_progress` while working. The list is kept across context
+/// compaction, so rely on it rather than on earlier messages to know what is
+/// left to do.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema, ToolDescription, PartialEq)]
+pub struct TodoWrite {
+    /// The complete task list, replacing the previous one
+    pub todos: Vec<Todo>,
+}
+
 /// Fetches detailed information about a specific skill. Use this tool to load
 /// skill content and instructions when you need to understand how to perform a
 /// specialized task. Skills provide domain-specific knowledge, workflows, and
@@ -456,10 +654,12 @@ fn default_raw() -> Option<bool> {
 /// current online information including websites, APIs and documentation. Use
 /// for obtaining up-to-date information beyond training data, verifying facts,
 /// or retrieving specific online content. Handles HTTP/HTTPS and converts HTML
-/// to readable markdown by default. Cannot access private/restricted resources
-/// requiring authentication. Respects robots.txt and may be blocked by
-/// anti-scraping measures. For large pages, returns the first 40,000 characters
-/// and stores the complete content in a temporary file for subsequent access.
+/// to readable markdown by default. Supports other methods, headers and a
+/// request body for calling APIs. Credentials configured for a domain are
+/// added automatically, so never ask for or pass secrets in headers. Respects
+/// robots.txt and may be blocked by anti-scraping measures. For large pages,
+/// returns the first 40,000 characters and stores the complete content in a
+/// temporary file for subsequent access.
 #[derive(Default, Deserialize, JsonSchema, ToolDescription, PartialEq)]
 pub struct FetchInput {
     /// URL to fetch
@@ -543,7 +743,11 @@ impl ToolDescription for ToolCatalog {
             ToolCatalog::Shell(v) => v.description(),
             ToolCatalog::Followup(v) => v.description(),
             ToolCatalog::Fetch(v) => v.description(),
+            ToolCatalog::WebSearch(v) => v.description(),
             ToolCatalog::Search(v) => v.description(),
+            ToolCatalog::AstSearch(v) => v.description(),
+            ToolCatalog::Symbols(v) => v.description(),
+            ToolCatalog::Diagnostics(v) => v.description(),
             ToolCatalog::SemSearch(v) => v.description(),
             ToolCatalog::Read(v) => v.description(),
             ToolCatalog::ReadImage(v) => v.description(),
@@ -551,6 +755,9 @@ impl ToolDescription for ToolCatalog {
             ToolCatalog::Undo(v) => v.description(),
             ToolCatalog::Write(v) => v.description(),
             ToolCatalog::Plan(v) => v.description(),
+            ToolCatalog::PlanStatus(v) => v.description(),
+            ToolCatalog::PlanUpdateStep(v) => v.description(),
+            ToolCatalog::Todo(v) => v.description(),
             ToolCatalog::Skill(v) => v.description(),
         }
     }
@@ -580,7 +787,11 @@ impl ToolCatalog {
             ToolCatalog::Shell(_) => r#gen.into_root_schema_for::<Shell>(),
             ToolCatalog::Followup(_) => r#gen.into_root_schema_for::<Followup>(),
             ToolCatalog::Fetch(_) => r#gen.into_root_schema_for::<NetFetch>(),
+            ToolCatalog::WebSearch(_) => r#gen.into_root_schema_for::<WebSearch>(),
             ToolCatalog::Search(_) => r#gen.into_root_schema_for::<FSSearch>(),
+            ToolCatalog::AstSearch(_) => r#gen.into_root_schema_for::<AstSearch>(),
+            ToolCatalog::Symbols(_) => r#gen.into_root_schema_for::<Symbols>(),
+            ToolCatalog::Diagnostics(_) => r#gen.into_root_schema_for::<Diagnostics>(),
             ToolCatalog::SemSearch(_) => r#gen.into_root_schema_for::<SemanticSearch>(),
             ToolCatalog::Read(_) => r#gen.into_root_schema_for::<FSRead>(),
             ToolCatalog::ReadImage(_) => r#gen.into_root_schema_for::<ReadImage>(),
@@ -588,6 +799,9 @@ impl ToolCatalog {
             ToolCatalog::Undo(_) => r#gen.into_root_schema_for::<FSUndo>(),
             ToolCatalog::Write(_) => r#gen.into_root_schema_for::<FSWrite>(),
             ToolCatalog::Plan(_) => r#gen.into_root_schema_for::<PlanCreate>(),
+            ToolCatalog::PlanStatus(_) => r#gen.into_root_schema_for::<PlanStatus>(),
+            ToolCatalog::PlanUpdateStep(_) => r#gen.into_root_schema_for::<PlanUpdateStep>(),
+            ToolCatalog::Todo(_) => r#gen.into_root_schema_for::<TodoWrite>(),
             ToolCatalog::Skill(_) => r#gen.into_root_schema_for::<SkillFetch>(),
         }
     }
@@ -662,6 +876,62 @@ impl ToolCatalog {
                     message,
                 })
             }
+            ToolCatalog::AstSearch(input) => {
+                let base_message = format!(
+                    "Structural search in directory/file: {} for query: {}",
+                    display_path_for(&input.path),
+                    input.query
+                );
+                let message = match &input.file_pattern {
+                    Some(pattern) => format!("{base_message} in '{pattern}' files"),
+                    None => base_message,
+                };
+                Some(crate::policies::PermissionOperation::Read {
+                    path: std::path::PathBuf::from(&input.path),
+                    cwd,
+                    message,
+                })
+            }
+            ToolCatalog::Symbols(input) => {
+                let (path, message) = match input.query {
+                    SymbolQuery::Outline => (
+                        PathBuf::from(&input.target),
+                        format!("Outline symbols in: {}", display_path_for(&input.target)),
+                    ),
+                    query => {
+                        let path = input.path.as_ref().map_or(cwd.clone(), PathBuf::from);
+                        let message = format!(
+                            "Find {query} of `{}` in: {}",
+                            input.target,
+                            display_path_for(&path.to_string_lossy())
+                        );
+                        (path, message)
+                    }
+                };
+                Some(crate::policies::PermissionOperation::Read { path, cwd, message })
+            }
+            ToolCatalog::Diagnostics(input) => Some(crate::policies::PermissionOperation::Read {
+                path: std::path::PathBuf::from(&input.path),
+                cwd,
+                message: format!("Check diagnostics of: {}", display_path_for(&input.path)),
+            }),
+            ToolCatalog::PlanStatus(input) => Some(crate::policies::PermissionOperation::Read {
+                path: std::path::PathBuf::from(&input.path),
+                cwd,
+                message: format!("Read plan: {}", display_path_for(&input.path)),
+            }),
+            ToolCatalog::PlanUpdateStep(input) => {
+                Some(crate::policies::PermissionOperation::Write {
+                    path: std::path::PathBuf::from(&input.path),
+                    cwd,
+                    message: format!(
+                        "Mark step {} of plan {} as {}",
+                        input.step,
+                        display_path_for(&input.path),
+                        input.status
+                    ),
+                })
+            }
             ToolCatalog::Remove(input) => Some(crate::policies::PermissionOperation::Write {
                 path: std::path::PathBuf::from(&input.path),
                 cwd,
@@ -680,13 +950,20 @@ impl ToolCatalog {
             ToolCatalog::Fetch(input) => Some(crate::policies::PermissionOperation::Fetch {
                 url: input.url.clone(),
                 cwd,
-                message: format!("Fetch content from URL: {}", input.url),
+                message: match input.method.unwrap_or_default() {
+                    HttpMethod::Get => format!("Fetch content from URL: {}", input.url),
+                    method => format!("Send {method} request to URL: {}", input.url),
+                },
             }),
+            // Web searches depend on the configured backend, see
+            // `WebSearch::to_policy_operation`
+            ToolCatalog::WebSearch(_) => None,
             // Operations that don't require permission checks
             ToolCatalog::SemSearch(_)
             | ToolCatalog::Undo(_)
             | ToolCatalog::Followup(_)
             | ToolCatalog::Plan(_)
+            | ToolCatalog::Todo(_)
             | ToolCatalog::Skill(_) => None,
         }
     }
@@ -752,6 +1029,31 @@ impl ToolCatalog {
         }))
     }
 
+    /// Creates an AstSearch tool call with the specified path and query
+    pub fn tool_call_ast_search(path: &str, query: &str) -> ToolCallFull {
+        ToolCallFull::from(ToolCatalog::AstSearch(AstSearch {
+            path: path.to_string(),
+            query: query.to_string(),
+            ..Default::default()
+        }))
+    }
+
+    /// Creates a Symbols tool call with the specified query and target
+    pub fn tool_call_symbols(query: SymbolQuery, target: &str) -> ToolCallFull {
+        ToolCallFull::from(ToolCatalog::Symbols(Symbols {
+            query,
+            target: target.to_string(),
+            ..Default::default()
+        }))
+    }
+
+    /// Creates a Diagnostics tool call for the specified file
+    pub fn tool_call_diagnostics(path: &str) -> ToolCallFull {
+        ToolCallFull::from(ToolCatalog::Diagnostics(Diagnostics {
+            path: path.to_string(),
+        }))
+    }
+
     /// Creates a Semantic Search tool call with the specified queries
     pub fn tool_call_semantic_search(
         queries: Vec<SearchQuery>,
@@ -776,6 +1078,14 @@ impl ToolCatalog {
         }))
     }
 
+    /// Creates a WebSearch tool call with the specified query
+    pub fn tool_call_web_search(query: &str) -> ToolCallFull {
+        ToolCallFull::from(ToolCatalog::WebSearch(WebSearch {
+            query: query.to_string(),
+            ..Default::default()
+        }))
+    }
+
     /// Creates a Followup tool call with the specified question
     pub fn tool_call_followup(question: &str) -> ToolCallFull {
         ToolCallFull::from(ToolCatalog::Followup(Followup {
@@ -794,6 +1104,27 @@ impl ToolCatalog {
         }))
     }
 
+    /// Creates a PlanStatus tool call for the specified plan file
+    pub fn tool_call_plan_status(path: &str) -> ToolCallFull {
+        ToolCallFull::from(ToolCatalog::PlanStatus(PlanStatus {
+            path: path.to_string(),
+        }))
+    }
+
+    /// Creates a PlanUpdateStep tool call for the specified plan file and step
+    pub fn tool_call_plan_update_step(path: &str, step: usize, status: StepStatus) -> ToolCallFull {
+        ToolCallFull::from(ToolCatalog::PlanUpdateStep(PlanUpdateStep {
+            path: path.to_string(),
+            step,
+            status,
+        }))
+    }
+
+    /// Creates a Todo tool call that replaces the task list with `todos`
+    pub fn tool_call_todo(todos: Vec<Todo>) -> ToolCallFull {
+        ToolCallFull::from(ToolCatalog::Todo(TodoWrite { todos }))
+    }
+
     /// Creates a Skill tool call with the specified skill name
     pub fn tool_call_skill(skill_name: &str) -> ToolCallFull {
         ToolCallFull::from(ToolCatalog::Skill(SkillFetch {
@@ -1021,4 +1352,53 @@ mod tests {
             _ => panic!("Expected Read operation"),
         }
     }
+
+    #[test]
+    fn test_ast_search_message_with_file_pattern() {
+        use std::path::PathBuf;
+
+        use crate::policies::PermissionOperation;
+
+        let fixture = ToolCatalog::AstSearch(crate::AstSearch {
+            path: "/home/user/project".to_string(),
+            query: "(impl_item) @match".to_string(),
+            file_pattern: Some("*.rs".to_string()),
+            ..Default::default()
+        });
+
+        let actual = fixture
+            .to_policy_operation(PathBuf::from("/test/cwd"))
+            .unwrap();
+
+        let expected = PermissionOperation::Read {
+            path: PathBuf::from("/home/user/project"),
+            cwd: PathBuf::from("/test/cwd"),
+            message: "Structural search in directory/file: `/home/user/project` for query: (impl_item) @match in '*.rs' files".to_string(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_symbols_message_defaults_to_cwd() {
+        use std::path::PathBuf;
+
+        use crate::policies::PermissionOperation;
+
+        let fixture = ToolCatalog::Symbols(crate::Symbols {
+            query: crate::SymbolQuery::References,
+            target: "ProviderRepository".to_string(),
+            ..Default::default()
+        });
+
+        let actual = fixture
+            .to_policy_operation(PathBuf::from("/test/cwd"))
+            .unwrap();
+
+        let expected = PermissionOperation::Read {
+            path: PathBuf::from("/test/cwd"),
+            cwd: PathBuf::from("/test/cwd"),
+            message: "Find references of `ProviderRepository` in: `.`".to_string(),
+        };
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Backend that answers `web_search` tool calls. Web search is disabled
+/// unless a backend is configured.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSearchBackend {
+    /// SearxNG instance queried through its JSON API
+    Searxng { url: Url },
+    /// Brave Search API, authenticated with the `BRAVE_API_KEY` variable
+    Brave,
+    /// Tavily Search API, authenticated with the `TAVILY_API_KEY` variable
+    Tavily,
+    /// Search tool built into the provider. The provider runs the searches
+    /// itself, so they never reach Forge.
+    Native,
+}
+
+impl WebSearchBackend {
+    /// Returns the URL a search for `query` is sent to, or `None` for the
+    /// native backend.
+    pub fn search_url(&self, query: &str, max_results: usize) -> Option<Url> {
+        match self {
+            Self::Searxng { url } => {
+                let mut url = url.clone();
+                url.set_path(&format!("{}/search", url.path().trim_end_matches('/')));
+                url.query_pairs_mut()
+                    .append_pair("q", query)
+                    .append_pair("format", "json");
+                Some(url)
+            }
+            Self::Brave => {
+                let mut url = Url::parse("https://api.search.brave.com/res/v1/web/search").ok()?;
+                url.query_pairs_mut()
+                    .append_pair("q", query)
+                    .append_pair("count", &max_results.to_string());
+                Some(url)
+            }
+            Self::Tavily => Url::parse("https://api.tavily.com/search").ok(),
+            Self::Native => None,
+        }
+    }
+
+    /// Returns the URL fetch policies are evaluated against for a search of
+    /// `query`. Native searches have no URL of their own, so they are
+    /// described as `websearch://native?q=<query>`.
+    pub fn policy_url(&self, query: &str, max_results: usize) -> Url {
+        self.search_url(query, max_results).unwrap_or_else(|| {
+            let mut url = Url::parse("websearch://native").expect("valid URL");
+            url.query_pairs_mut().append_pair("q", query);
+            url
+        })
+    }
+}
+
+/// A single hit returned by a web search
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebSearchResult {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_search_url() {
+        let fixture =
+            WebSearchBackend::Searxng { url: Url::parse("http://localhost:8888/searx/").unwrap() };
+        let actual = fixture.search_url("rust async", 5).map(String::from);
+        let expected =
+            Some("http://localhost:8888/searx/search?q=rust+async&format=json".to_string());
+        assert_eq!(actual, expected);
+
+        let actual = WebSearchBackend::Brave
+            .search_url("rust", 3)
+            .map(String::from);
+        let expected =
+            Some("https://api.search.brave.com/res/v1/web/search?q=rust&count=3".to_string());
+        assert_eq!(actual, expected);
+
+        let actual = WebSearchBackend::Native.search_url("rust", 3);
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn test_policy_url() {
+        let actual = WebSearchBackend::Native
+            .policy_url("rust async", 3)
+            .to_string();
+        let expected = "websearch://native?q=rust+async".to_string();
+        assert_eq!(actual, expected);
+
+        let actual = WebSearchBackend::Tavily.policy_url("rust", 3).to_string();
+        let expected = "https://api.tavily.com/search".to_string();
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use derive_setters::Setters;
 use lazy_static::lazy_static;
 use merge::Merge;
@@ -6,7 +8,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::temperature::Temperature;
 use crate::update::Update;
-use crate::{Compact, MaxTokens, TopK, TopP};
+use crate::{
+    AgentId, CommandArgument, Compact, LspServerConfig, MaxTokens, ModelId, Stage, ToolName, TopK,
+    TopP,
+};
 
 /// Configuration for a workflow that contains all settings
 /// required to initialize a workflow.
@@ -117,6 +122,19 @@ pub struct Workflow {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[merge(strategy = crate::merge::option)]
     pub compact: Option<Compact>,
+
+    /// Language servers that report diagnostics for edited files, keyed by
+    /// language name, e.g. `rust` or `typescript`
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[merge(strategy = crate::merge::option)]
+    pub lsp: Option<BTreeMap<String, LspServerConfig>>,
+
+    /// Agents to run one after another when the workflow is run with
+    /// `forge -w`, instead of starting a conversation
+    #[merge(strategy = merge::vec::append)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<Stage>,
 }
 
 lazy_static! {
@@ -141,6 +159,27 @@ pub struct Command {
 
     #[merge(strategy = crate::merge::option)]
     pub prompt: Option<String>,
+
+    /// Arguments bound from the values typed after the command and rendered
+    /// into the prompt by name
+    #[merge(strategy = crate::merge::std::overwrite)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<CommandArgument>,
+
+    /// Agent that runs the command instead of the active one
+    #[merge(strategy = crate::merge::option)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<AgentId>,
+
+    /// Model used while the command runs
+    #[merge(strategy = crate::merge::option)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<ModelId>,
+
+    /// Tools available while the command runs, supports glob patterns
+    #[merge(strategy = crate::merge::option)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolName>>,
 }
 
 impl Workflow {
@@ -160,7 +199,9 @@ impl Workflow {
             max_tool_failure_per_turn: None,
             max_requests_per_turn: None,
             compact: None,
+            lsp: None,
             commands: vec![],
+            stages: vec![],
         }
     }
 }
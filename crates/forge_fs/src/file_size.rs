@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::time::SystemTime;
 
 use anyhow::{Context, Result};
 
@@ -14,4 +15,21 @@ impl crate::ForgeFS {
 
         Ok(metadata.len())
     }
+
+    /// Gets the last modification time of a file
+    pub async fn modified<T: AsRef<Path>>(path: T) -> Result<SystemTime> {
+        let metadata = tokio::fs::metadata(path.as_ref()).await.with_context(|| {
+            format!(
+                "Failed to get metadata for file {}",
+                path.as_ref().display()
+            )
+        })?;
+
+        metadata.modified().with_context(|| {
+            format!(
+                "Failed to get modification time for file {}",
+                path.as_ref().display()
+            )
+        })
+    }
 }
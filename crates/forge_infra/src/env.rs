@@ -3,13 +3,16 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use forge_app::EnvironmentInfra;
-use forge_domain::{Environment, ModelId, ProviderId, RetryConfig, TlsBackend, TlsVersion};
+use forge_domain::{
+    Environment, ModelId, ProviderId, RetryConfig, TlsBackend, TlsVersion, WebSearchBackend,
+};
 use reqwest::Url;
 
 #[derive(Clone)]
 pub struct ForgeEnvironmentInfra {
     restricted: bool,
     cwd: PathBuf,
+    model: Option<(ProviderId, ModelId)>,
 }
 
 impl ForgeEnvironmentInfra {
@@ -21,7 +24,14 @@ impl ForgeEnvironmentInfra {
     /// * `cwd` - Required working directory path
     pub fn new(restricted: bool, cwd: PathBuf) -> Self {
         Self::dot_env(&cwd);
-        Self { restricted, cwd }
+        Self { restricted, cwd, model: None }
+    }
+
+    /// Pins the provider and model used by every agent, taking precedence
+    /// over `FORGE_OVERRIDE_PROVIDER` and `FORGE_OVERRIDE_MODEL`
+    pub fn model(mut self, provider: ProviderId, model: ModelId) -> Self {
+        self.model = Some((provider, model));
+        self
     }
 
     /// Get path to appropriate shell based on platform and mode
@@ -55,9 +65,14 @@ impl ForgeEnvironmentInfra {
         // Parse custom history file path from environment variable
         let custom_history_path = parse_env::<String>("FORGE_HISTORY_FILE").map(PathBuf::from);
 
-        let override_model = parse_env::<String>("FORGE_OVERRIDE_MODEL").map(ModelId::new);
-        let override_provider = parse_env::<String>("FORGE_OVERRIDE_PROVIDER")
-            .and_then(|s| ProviderId::from_str(&s).ok());
+        let (override_provider, override_model) = match self.model.clone() {
+            Some((provider, model)) => (Some(provider), Some(model)),
+            None => (
+                parse_env::<String>("FORGE_OVERRIDE_PROVIDER")
+                    .and_then(|s| ProviderId::from_str(&s).ok()),
+                parse_env::<String>("FORGE_OVERRIDE_MODEL").map(ModelId::new),
+            ),
+        };
 
         Environment {
             os: std::env::consts::OS.to_string(),
@@ -94,6 +109,10 @@ impl ForgeEnvironmentInfra {
                 .unwrap_or_else(|| Url::parse("https://api.forgecode.dev/").unwrap()),
             override_model,
             override_provider,
+            web_search: resolve_web_search(),
+            record_cassette: parse_env::<String>("FORGE_RECORD_CASSETTE").map(PathBuf::from),
+            replay_mismatch: parse_env::<forge_domain::ReplayMismatch>("FORGE_REPLAY_MISMATCH")
+                .unwrap_or_default(),
         }
     }
 
@@ -171,6 +190,7 @@ impl_from_env_str_via_from_str! {
     String,
     forge_domain::TlsBackend,
     forge_domain::TlsVersion,
+    forge_domain::ReplayMismatch,
 }
 
 /// Parse environment variable using custom FromEnvStr trait
@@ -211,6 +231,24 @@ fn resolve_retry_config() -> RetryConfig {
     config
 }
 
+/// Resolves the web search backend from FORGE_WEB_SEARCH, which is one of
+/// `searxng`, `brave`, `tavily` or `native`. SearxNG additionally requires
+/// FORGE_SEARXNG_URL.
+fn resolve_web_search() -> Option<WebSearchBackend> {
+    match parse_env::<String>("FORGE_WEB_SEARCH")?
+        .to_lowercase()
+        .as_str()
+    {
+        "searxng" => parse_env::<String>("FORGE_SEARXNG_URL")
+            .and_then(|url| Url::parse(&url).ok())
+            .map(|url| WebSearchBackend::Searxng { url }),
+        "brave" => Some(WebSearchBackend::Brave),
+        "tavily" => Some(WebSearchBackend::Tavily),
+        "native" => Some(WebSearchBackend::Native),
+        _ => None,
+    }
+}
+
 fn resolve_http_config() -> forge_domain::HttpConfig {
     let mut config = forge_domain::HttpConfig::default();
 
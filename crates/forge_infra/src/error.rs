@@ -2,4 +2,7 @@
 pub enum Error {
     #[error("Unsupported MCP response: {0}")]
     UnsupportedMcpResponse(&'static str),
+
+    #[error("Failed to restart MCP server: {0}")]
+    McpRestartFailed(String),
 }
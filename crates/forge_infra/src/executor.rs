@@ -190,6 +190,32 @@ impl CommandInfra for ForgeCommandExecutorService {
 
         Ok(prepared_command.spawn()?.wait().await?)
     }
+
+    async fn execute_program(
+        &self,
+        program: &str,
+        args: Vec<String>,
+        working_dir: PathBuf,
+    ) -> anyhow::Result<CommandOutput> {
+        tracing::info!(program, args = ?args, "Executing program");
+        let output = Command::new(program)
+            .args(&args)
+            .current_dir(working_dir)
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await?;
+
+        Ok(CommandOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            exit_code: output.status.code(),
+            command: std::iter::once(program.to_string())
+                .chain(args)
+                .collect::<Vec<_>>()
+                .join(" "),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -384,4 +410,23 @@ mod tests {
         assert_eq!(actual.stderr, expected.stderr);
         assert_eq!(actual.success(), expected.success());
     }
+
+    #[tokio::test]
+    async fn test_execute_program_passes_arguments_verbatim() {
+        let fixture = ForgeCommandExecutorService::new(false, test_env());
+
+        let actual = fixture
+            .execute_program(
+                "git",
+                vec!["'$(touch pwned)'; --version".to_string()],
+                PathBuf::from("."),
+            )
+            .await
+            .unwrap();
+
+        // git reports the whole argument as one unknown command, unexpanded
+        assert_ne!(actual.exit_code, Some(0));
+        assert!(actual.stderr.contains("'$(touch pwned)'; --version"));
+        assert!(!PathBuf::from("pwned").exists());
+    }
 }
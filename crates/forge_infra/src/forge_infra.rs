@@ -2,19 +2,20 @@ use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process::ExitStatus;
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use bytes::Bytes;
 use forge_app::{
     CommandInfra, DirectoryReaderInfra, EnvironmentInfra, FileDirectoryInfra, FileInfoInfra,
-    FileReaderInfra, FileRemoverInfra, FileWriterInfra, GrpcInfra, HttpInfra, McpServerInfra,
-    StrategyFactory, UserInfra, WalkerInfra,
+    FileReaderInfra, FileRemoverInfra, FileWriterInfra, GrpcInfra, HttpInfra, LspServerInfra,
+    McpServerInfra, StrategyFactory, UserInfra, WalkerInfra,
 };
 use forge_domain::{
-    AuthMethod, CommandOutput, Environment, FileInfo as FileInfoData, McpServerConfig, ProviderId,
-    URLParam,
+    AuthMethod, CommandOutput, Environment, FileInfo as FileInfoData, LspServerConfig,
+    McpServerConfig, ModelId, ProviderId, URLParam,
 };
 use reqwest::header::HeaderMap;
-use reqwest::{Response, Url};
+use reqwest::{Method, Response, Url};
 use reqwest_eventsource::EventSource;
 
 use crate::auth::{AnyAuthStrategy, ForgeAuthStrategyFactory};
@@ -29,6 +30,8 @@ use crate::fs_write::ForgeFileWriteService;
 use crate::grpc::ForgeGrpcClient;
 use crate::http::ForgeHttpInfra;
 use crate::inquire::ForgeInquire;
+use crate::lsp_client::ForgeLspClient;
+use crate::lsp_server::ForgeLspServer;
 use crate::mcp_client::ForgeMcpClient;
 use crate::mcp_server::ForgeMcpServer;
 use crate::walker::ForgeWalkerService;
@@ -47,6 +50,7 @@ pub struct ForgeInfra {
     command_executor_service: Arc<ForgeCommandExecutorService>,
     inquire_service: Arc<ForgeInquire>,
     mcp_server: ForgeMcpServer,
+    lsp_server: ForgeLspServer,
     walker_service: Arc<ForgeWalkerService>,
     http_service: Arc<ForgeHttpInfra<ForgeFileWriteService>>,
     strategy_factory: Arc<ForgeAuthStrategyFactory>,
@@ -55,7 +59,23 @@ pub struct ForgeInfra {
 
 impl ForgeInfra {
     pub fn new(restricted: bool, cwd: PathBuf) -> Self {
-        let environment_service = Arc::new(ForgeEnvironmentInfra::new(restricted, cwd));
+        Self::from_environment(restricted, ForgeEnvironmentInfra::new(restricted, cwd))
+    }
+
+    /// Creates an infrastructure where every agent uses `model` from
+    /// `provider`, regardless of the user's configuration.
+    pub fn with_model(
+        restricted: bool,
+        cwd: PathBuf,
+        provider: ProviderId,
+        model: ModelId,
+    ) -> Self {
+        let environment = ForgeEnvironmentInfra::new(restricted, cwd).model(provider, model);
+        Self::from_environment(restricted, environment)
+    }
+
+    fn from_environment(restricted: bool, environment: ForgeEnvironmentInfra) -> Self {
+        let environment_service = Arc::new(environment);
         let env = environment_service.get_environment();
 
         let file_write_service = Arc::new(ForgeFileWriteService::new());
@@ -79,6 +99,7 @@ impl ForgeInfra {
             )),
             inquire_service: Arc::new(ForgeInquire::new()),
             mcp_server: ForgeMcpServer,
+            lsp_server: ForgeLspServer,
             walker_service: Arc::new(ForgeWalkerService::new()),
             strategy_factory: Arc::new(ForgeAuthStrategyFactory::new()),
             http_service,
@@ -153,6 +174,10 @@ impl FileInfoInfra for ForgeInfra {
     async fn file_size(&self, path: &Path) -> anyhow::Result<u64> {
         self.file_meta_service.file_size(path).await
     }
+
+    async fn modified(&self, path: &Path) -> anyhow::Result<SystemTime> {
+        self.file_meta_service.modified(path).await
+    }
 }
 #[async_trait::async_trait]
 impl FileRemoverInfra for ForgeInfra {
@@ -192,6 +217,17 @@ impl CommandInfra for ForgeInfra {
             .execute_command_raw(command, working_dir, env_vars)
             .await
     }
+
+    async fn execute_program(
+        &self,
+        program: &str,
+        args: Vec<String>,
+        working_dir: PathBuf,
+    ) -> anyhow::Result<CommandOutput> {
+        self.command_executor_service
+            .execute_program(program, args, working_dir)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -230,6 +266,20 @@ impl McpServerInfra for ForgeInfra {
     }
 }
 
+#[async_trait::async_trait]
+impl LspServerInfra for ForgeInfra {
+    type Client = ForgeLspClient;
+
+    async fn start(
+        &self,
+        language_id: &str,
+        config: &LspServerConfig,
+        root: &Path,
+    ) -> anyhow::Result<Self::Client> {
+        self.lsp_server.start(language_id, config, root).await
+    }
+}
+
 #[async_trait::async_trait]
 impl WalkerInfra for ForgeInfra {
     async fn walk(&self, config: forge_app::Walker) -> anyhow::Result<Vec<forge_app::WalkedFile>> {
@@ -250,6 +300,18 @@ impl HttpInfra for ForgeInfra {
     async fn http_delete(&self, url: &Url) -> anyhow::Result<Response> {
         self.http_service.http_delete(url).await
     }
+
+    async fn http_request(
+        &self,
+        method: Method,
+        url: &Url,
+        headers: HeaderMap,
+        body: Option<Bytes>,
+    ) -> anyhow::Result<Response> {
+        self.http_service
+            .http_request(method, url, headers, body)
+            .await
+    }
     async fn http_eventsource(
         &self,
         url: &Url,
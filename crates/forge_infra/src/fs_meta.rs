@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::time::SystemTime;
 
 use anyhow::Result;
 use forge_app::FileInfoInfra;
@@ -21,4 +22,8 @@ impl FileInfoInfra for ForgeFileMetaService {
     async fn file_size(&self, path: &Path) -> Result<u64> {
         forge_fs::ForgeFS::file_size(path).await
     }
+
+    async fn modified(&self, path: &Path) -> Result<SystemTime> {
+        forge_fs::ForgeFS::modified(path).await
+    }
 }
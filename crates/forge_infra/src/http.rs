@@ -8,7 +8,7 @@ use forge_app::HttpInfra;
 use forge_domain::{Environment, TlsBackend, TlsVersion};
 use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use reqwest::redirect::Policy;
-use reqwest::{Certificate, Client, Response, StatusCode, Url};
+use reqwest::{Certificate, Client, Method, Response, StatusCode, Url};
 use reqwest_eventsource::{EventSource, RequestBuilderExt};
 use tracing::{debug, warn};
 
@@ -19,87 +19,91 @@ const VERSION: &str = match option_env!("APP_VERSION") {
 
 pub struct ForgeHttpInfra<F> {
     client: Client,
+    /// Client that returns redirects instead of following them
+    direct: Client,
     env: Environment,
     file: Arc<F>,
 }
 
-fn to_reqwest_tls(tls: TlsVersion) -> reqwest::tls::Version {
-    use reqwest::tls::Version;
-    match tls {
-        TlsVersion::V1_0 => Version::TLS_1_0,
-        TlsVersion::V1_1 => Version::TLS_1_1,
-        TlsVersion::V1_2 => Version::TLS_1_2,
-        TlsVersion::V1_3 => Version::TLS_1_3,
-    }
-}
-
-impl<F> ForgeHttpInfra<F> {
-    pub fn new(env: Environment, file_writer: Arc<F>) -> Self {
-        let env = env.clone();
-        let env_http = env.clone();
-        let mut client = reqwest::Client::builder()
-            .connect_timeout(std::time::Duration::from_secs(
-                env_http.http.connect_timeout,
-            ))
-            .read_timeout(std::time::Duration::from_secs(env_http.http.read_timeout))
-            .pool_idle_timeout(std::time::Duration::from_secs(
-                env_http.http.pool_idle_timeout,
-            ))
-            .pool_max_idle_per_host(env_http.http.pool_max_idle_per_host)
-            .redirect(Policy::limited(env_http.http.max_redirects))
-            .hickory_dns(env_http.http.hickory)
-            // HTTP/2 configuration from config
-            .http2_adaptive_window(env_http.http.adaptive_window)
-            .http2_keep_alive_interval(env_http.http.keep_alive_interval.map(Duration::from_secs))
-            .http2_keep_alive_timeout(Duration::from_secs(env_http.http.keep_alive_timeout))
-            .http2_keep_alive_while_idle(env_http.http.keep_alive_while_idle);
-
-        // Add root certificates from config
-        if let Some(ref cert_paths) = env_http.http.root_cert_paths {
-            for cert_path in cert_paths {
-                match fs::read(cert_path) {
-                    Ok(buf) => {
-                        if let Ok(cert) = Certificate::from_pem(&buf) {
-                            client = client.add_root_certificate(cert);
-                        } else if let Ok(cert) = Certificate::from_der(&buf) {
-                            client = client.add_root_certificate(cert);
-                        } else {
-                            warn!(
-                                "Failed to parse certificate as PEM or DER format, cert = {}",
-                                cert_path
-                            );
-                        }
-                    }
-                    Err(error) => {
+/// Builds an HTTP client from the configuration with the given redirect
+/// policy
+fn build_client(env: &Environment, redirect: Policy) -> Client {
+    let mut client = reqwest::Client::builder()
+        .connect_timeout(std::time::Duration::from_secs(env.http.connect_timeout))
+        .read_timeout(std::time::Duration::from_secs(env.http.read_timeout))
+        .pool_idle_timeout(std::time::Duration::from_secs(env.http.pool_idle_timeout))
+        .pool_max_idle_per_host(env.http.pool_max_idle_per_host)
+        .redirect(redirect)
+        .hickory_dns(env.http.hickory)
+        // HTTP/2 configuration from config
+        .http2_adaptive_window(env.http.adaptive_window)
+        .http2_keep_alive_interval(env.http.keep_alive_interval.map(Duration::from_secs))
+        .http2_keep_alive_timeout(Duration::from_secs(env.http.keep_alive_timeout))
+        .http2_keep_alive_while_idle(env.http.keep_alive_while_idle);
+
+    // Add root certificates from config
+    if let Some(ref cert_paths) = env.http.root_cert_paths {
+        for cert_path in cert_paths {
+            match fs::read(cert_path) {
+                Ok(buf) => {
+                    if let Ok(cert) = Certificate::from_pem(&buf) {
+                        client = client.add_root_certificate(cert);
+                    } else if let Ok(cert) = Certificate::from_der(&buf) {
+                        client = client.add_root_certificate(cert);
+                    } else {
                         warn!(
-                            "Failed to read certificate file, path = {}, error = {}",
-                            cert_path, error
+                            "Failed to parse certificate as PEM or DER format, cert = {}",
+                            cert_path
                         );
                     }
                 }
+                Err(error) => {
+                    warn!(
+                        "Failed to read certificate file, path = {}, error = {}",
+                        cert_path, error
+                    );
+                }
             }
         }
+    }
 
-        if env_http.http.accept_invalid_certs {
-            client = client.danger_accept_invalid_certs(true);
-        }
+    if env.http.accept_invalid_certs {
+        client = client.danger_accept_invalid_certs(true);
+    }
 
-        if let Some(version) = env_http.http.min_tls_version {
-            client = client.min_tls_version(to_reqwest_tls(version));
-        }
+    if let Some(version) = env.http.min_tls_version.clone() {
+        client = client.min_tls_version(to_reqwest_tls(version));
+    }
 
-        if let Some(version) = env_http.http.max_tls_version {
-            client = client.max_tls_version(to_reqwest_tls(version));
-        }
+    if let Some(version) = env.http.max_tls_version.clone() {
+        client = client.max_tls_version(to_reqwest_tls(version));
+    }
 
-        match env_http.http.tls_backend {
-            TlsBackend::Rustls => {
-                client = client.use_rustls_tls();
-            }
-            TlsBackend::Default => {}
+    match env.http.tls_backend {
+        TlsBackend::Rustls => {
+            client = client.use_rustls_tls();
         }
+        TlsBackend::Default => {}
+    }
+
+    client.build().unwrap()
+}
 
-        Self { env, client: client.build().unwrap(), file: file_writer }
+fn to_reqwest_tls(tls: TlsVersion) -> reqwest::tls::Version {
+    use reqwest::tls::Version;
+    match tls {
+        TlsVersion::V1_0 => Version::TLS_1_0,
+        TlsVersion::V1_1 => Version::TLS_1_1,
+        TlsVersion::V1_2 => Version::TLS_1_2,
+        TlsVersion::V1_3 => Version::TLS_1_3,
+    }
+}
+
+impl<F> ForgeHttpInfra<F> {
+    pub fn new(env: Environment, file_writer: Arc<F>) -> Self {
+        let client = build_client(&env, Policy::limited(env.http.max_redirects));
+        let direct = build_client(&env, Policy::none());
+        Self { env, client, direct, file: file_writer }
     }
 
     async fn get(&self, url: &Url, headers: Option<HeaderMap>) -> anyhow::Result<Response> {
@@ -126,6 +130,31 @@ impl<F> ForgeHttpInfra<F> {
         .await
     }
 
+    async fn request(
+        &self,
+        method: Method,
+        url: &Url,
+        mut headers: HeaderMap,
+        body: Option<Bytes>,
+    ) -> anyhow::Result<Response> {
+        headers
+            .entry(reqwest::header::USER_AGENT)
+            .or_insert(HeaderValue::from_static("Forge"));
+
+        let mut request = self
+            .direct
+            .request(method.clone(), url.clone())
+            .headers(headers);
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+
+        request
+            .send()
+            .await
+            .with_context(|| format_http_context(None, method.as_str(), url))
+    }
+
     /// Generic helper method to execute HTTP requests with consistent error
     /// handling
     async fn execute_request<B>(
@@ -249,6 +278,16 @@ impl<F: forge_app::FileWriterInfra + 'static> HttpInfra for ForgeHttpInfra<F> {
         self.delete(url).await
     }
 
+    async fn http_request(
+        &self,
+        method: Method,
+        url: &Url,
+        headers: HeaderMap,
+        body: Option<Bytes>,
+    ) -> anyhow::Result<Response> {
+        self.request(method, url, headers, body).await
+    }
+
     async fn http_eventsource(
         &self,
         url: &Url,
@@ -14,6 +14,8 @@ mod grpc;
 mod http;
 mod inquire;
 mod kv_storage;
+mod lsp_client;
+mod lsp_server;
 mod mcp_client;
 mod mcp_server;
 mod walker;
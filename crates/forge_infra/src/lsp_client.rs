@@ -0,0 +1,538 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use forge_app::LspClientInfra;
+use forge_domain::{Diagnostic, DiagnosticSeverity};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tokio::process::Child;
+use tokio::sync::{oneshot, watch};
+use url::Url;
+
+type Writer = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Diagnostics of a file as last published by the server
+#[derive(Default)]
+struct Published {
+    /// Number of times diagnostics were published for the file
+    count: u64,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Default)]
+struct State {
+    pending: HashMap<i64, oneshot::Sender<anyhow::Result<Value>>>,
+    published: HashMap<PathBuf, Published>,
+    /// Version of each document opened on the server
+    versions: HashMap<PathBuf, i64>,
+}
+
+struct Connection {
+    writer: tokio::sync::Mutex<Writer>,
+    state: Mutex<State>,
+    next_id: AtomicI64,
+    running: AtomicBool,
+    /// Bumped whenever diagnostics are published or the server exits
+    updates: watch::Sender<()>,
+    language_id: String,
+    // Held so the server is killed once the connection is dropped
+    _child: Option<Child>,
+}
+
+/// Language server client speaking JSON-RPC over the stdio of the server
+#[derive(Clone)]
+pub struct ForgeLspClient {
+    connection: Arc<Connection>,
+}
+
+impl ForgeLspClient {
+    /// Creates a client talking to a server over `reader` and `writer`. The
+    /// client must be initialized before documents are synced.
+    pub fn new(
+        reader: impl AsyncRead + Send + Unpin + 'static,
+        writer: impl AsyncWrite + Send + Unpin + 'static,
+        language_id: impl Into<String>,
+        child: Option<Child>,
+    ) -> Self {
+        let connection = Arc::new(Connection {
+            writer: tokio::sync::Mutex::new(Box::new(writer)),
+            state: Mutex::new(State::default()),
+            next_id: AtomicI64::new(1),
+            running: AtomicBool::new(true),
+            updates: watch::Sender::new(()),
+            language_id: language_id.into(),
+            _child: child,
+        });
+
+        // The reader only holds a weak reference, so dropping the client kills
+        // the server, which closes its output and ends the reader
+        tokio::spawn(read_loop(
+            BufReader::new(reader),
+            Arc::downgrade(&connection),
+        ));
+        Self { connection }
+    }
+
+    /// Performs the initialize handshake for the workspace at `root`
+    pub async fn initialize(&self, root: &Path, timeout: Duration) -> anyhow::Result<()> {
+        let root_uri = file_uri(root)?;
+        let name = root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let params = json!({
+            "processId": std::process::id(),
+            "clientInfo": { "name": "forge" },
+            "rootPath": root,
+            "rootUri": root_uri,
+            "workspaceFolders": [{ "uri": root_uri, "name": name }],
+            "capabilities": {
+                "textDocument": {
+                    "synchronization": { "didSave": true },
+                    "publishDiagnostics": { "versionSupport": false }
+                },
+                "workspace": { "configuration": true, "workspaceFolders": true }
+            }
+        });
+
+        tokio::time::timeout(timeout, self.request("initialize", params))
+            .await
+            .with_context(|| {
+                format!(
+                    "Timed out initializing the {} language server",
+                    self.connection.language_id
+                )
+            })??;
+        self.notify("initialized", json!({})).await
+    }
+
+    async fn request(&self, method: &str, params: Value) -> anyhow::Result<Value> {
+        let id = self.connection.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = oneshot::channel();
+        self.connection.state().pending.insert(id, sender);
+
+        self.connection
+            .send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await?;
+        receiver
+            .await
+            .map_err(|_| anyhow!("Language server exited before responding to {method}"))?
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> anyhow::Result<()> {
+        self.connection
+            .send(json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await
+    }
+
+    fn published_count(&self, path: &Path) -> u64 {
+        self.connection
+            .state()
+            .published
+            .get(path)
+            .map_or(0, |published| published.count)
+    }
+}
+
+impl Connection {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    async fn send(&self, message: Value) -> anyhow::Result<()> {
+        let mut writer = self.writer.lock().await;
+        write_message(&mut *writer, &message)
+            .await
+            .context("Failed to write to the language server")
+    }
+
+    /// Handles a message sent by the server
+    async fn dispatch(&self, message: Value) -> anyhow::Result<()> {
+        let method = message.get("method").and_then(Value::as_str);
+        match (method, message.get("id")) {
+            // Requests from the server are acknowledged without acting on them
+            (Some(method), Some(id)) => {
+                let result = match method {
+                    "workspace/configuration" => {
+                        let items = message
+                            .pointer("/params/items")
+                            .and_then(Value::as_array)
+                            .map_or(0, Vec::len);
+                        Value::Array(vec![Value::Null; items])
+                    }
+                    _ => Value::Null,
+                };
+                self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+                    .await?;
+            }
+            (Some("textDocument/publishDiagnostics"), None) => {
+                let params = message.get("params").cloned().unwrap_or_default();
+                let params = serde_json::from_value::<PublishDiagnosticsParams>(params)
+                    .context("Invalid publishDiagnostics notification")?;
+                let Some(path) = Url::parse(&params.uri)
+                    .ok()
+                    .and_then(|uri| uri.to_file_path().ok())
+                else {
+                    return Ok(());
+                };
+
+                let diagnostics = params
+                    .diagnostics
+                    .into_iter()
+                    .map(|diagnostic| diagnostic.into_domain(&path))
+                    .collect();
+                let mut state = self.state();
+                let published = state.published.entry(path).or_default();
+                published.count += 1;
+                published.diagnostics = diagnostics;
+                drop(state);
+                self.updates.send_replace(());
+            }
+            (None, Some(id)) => {
+                let Some(sender) = id.as_i64().and_then(|id| self.state().pending.remove(&id))
+                else {
+                    return Ok(());
+                };
+                let response = match message.get("error") {
+                    Some(error) => Err(anyhow!(
+                        "Language server error: {}",
+                        error
+                            .get("message")
+                            .and_then(Value::as_str)
+                            .unwrap_or("unknown error")
+                    )),
+                    None => Ok(message.get("result").cloned().unwrap_or_default()),
+                };
+                let _ = sender.send(response);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+async fn read_loop<R: AsyncBufRead + Unpin>(mut reader: R, connection: Weak<Connection>) {
+    loop {
+        let message = read_message(&mut reader).await;
+        let Some(connection) = connection.upgrade() else {
+            return;
+        };
+        match message {
+            Ok(Some(message)) => {
+                if let Err(error) = connection.dispatch(message).await {
+                    tracing::warn!(error = ?error, "Failed to handle language server message");
+                }
+            }
+            Ok(None) | Err(_) => {
+                connection.running.store(false, Ordering::SeqCst);
+                // Dropping the senders fails the requests awaiting a response
+                connection.state().pending.clear();
+                connection.updates.send_replace(());
+                return;
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LspClientInfra for ForgeLspClient {
+    async fn diagnostics(
+        &self,
+        path: &Path,
+        content: &str,
+        timeout: Duration,
+    ) -> anyhow::Result<Vec<Diagnostic>> {
+        let uri = file_uri(path)?;
+        // Published diagnostics are keyed by the path the server reports,
+        // which round-trips through the URI
+        let path = uri.to_file_path().unwrap_or_else(|_| path.to_path_buf());
+        let mut updates = self.connection.updates.subscribe();
+        let seen = self.published_count(&path);
+
+        let version = {
+            let mut state = self.connection.state();
+            let version = state.versions.entry(path.clone()).or_insert(0);
+            *version += 1;
+            *version
+        };
+        if version == 1 {
+            self.notify(
+                "textDocument/didOpen",
+                json!({
+                    "textDocument": {
+                        "uri": uri,
+                        "languageId": self.connection.language_id,
+                        "version": version,
+                        "text": content
+                    }
+                }),
+            )
+            .await?;
+        } else {
+            self.notify(
+                "textDocument/didChange",
+                json!({
+                    "textDocument": { "uri": uri, "version": version },
+                    "contentChanges": [{ "text": content }]
+                }),
+            )
+            .await?;
+        }
+        // Some servers only check files once they are saved
+        self.notify(
+            "textDocument/didSave",
+            json!({ "textDocument": { "uri": uri }, "text": content }),
+        )
+        .await?;
+
+        let published = async {
+            while self.published_count(&path) <= seen && self.is_running() {
+                if updates.changed().await.is_err() {
+                    break;
+                }
+            }
+        };
+        let _ = tokio::time::timeout(timeout, published).await;
+
+        Ok(self
+            .connection
+            .state()
+            .published
+            .get(&path)
+            .map(|published| published.diagnostics.clone())
+            .unwrap_or_default())
+    }
+
+    fn is_running(&self) -> bool {
+        self.connection.running.load(Ordering::SeqCst)
+    }
+}
+
+fn file_uri(path: &Path) -> anyhow::Result<Url> {
+    Url::from_file_path(path).map_err(|_| anyhow!("Path must be absolute: {}", path.display()))
+}
+
+#[derive(Deserialize)]
+struct PublishDiagnosticsParams {
+    uri: String,
+    diagnostics: Vec<LspDiagnostic>,
+}
+
+#[derive(Deserialize)]
+struct LspDiagnostic {
+    range: LspRange,
+    severity: Option<u64>,
+    code: Option<Value>,
+    source: Option<String>,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct LspRange {
+    start: LspPosition,
+}
+
+#[derive(Deserialize)]
+struct LspPosition {
+    line: usize,
+    character: usize,
+}
+
+impl LspDiagnostic {
+    fn into_domain(self, path: &Path) -> Diagnostic {
+        Diagnostic {
+            path: path.display().to_string(),
+            line: self.range.start.line + 1,
+            column: self.range.start.character + 1,
+            // Clients decide the severity of diagnostics that omit it
+            severity: self
+                .severity
+                .and_then(DiagnosticSeverity::from_lsp)
+                .unwrap_or(DiagnosticSeverity::Error),
+            message: self.message,
+            source: self.source,
+            code: self.code.and_then(|code| match code {
+                Value::String(code) => Some(code),
+                Value::Number(code) => Some(code.to_string()),
+                _ => None,
+            }),
+        }
+    }
+}
+
+/// Reads a message framed with a `Content-Length` header. Returns `None`
+/// once the stream ends.
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> anyhow::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = Some(
+                value
+                    .trim()
+                    .parse::<usize>()
+                    .with_context(|| format!("Invalid Content-Length header: {line}"))?,
+            );
+        }
+    }
+
+    let mut body = vec![0; length.unwrap_or_default()];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+async fn write_message<W: AsyncWrite + Unpin + ?Sized>(
+    writer: &mut W,
+    message: &Value,
+) -> anyhow::Result<()> {
+    let body = serde_json::to_vec(message)?;
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(&body).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tokio::io::DuplexStream;
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Fake language server that reports an error on every line containing
+    /// `todo!()`. It asks the client for its configuration after each sync and
+    /// publishes the diagnostics once the client responds.
+    async fn fake_server(stream: DuplexStream) {
+        let (reader, mut writer) = tokio::io::split(stream);
+        let mut reader = BufReader::new(reader);
+        let mut synced = None;
+        while let Ok(Some(message)) = read_message(&mut reader).await {
+            let document = &message["params"]["textDocument"];
+            match message["method"].as_str() {
+                Some("initialize") => {
+                    let response = json!({ "jsonrpc": "2.0", "id": message["id"], "result": { "capabilities": {} } });
+                    write_message(&mut writer, &response).await.unwrap();
+                }
+                Some("textDocument/didOpen") => {
+                    synced = Some((document["uri"].clone(), document["text"].clone()));
+                }
+                Some("textDocument/didChange") => {
+                    let text = message["params"]["contentChanges"][0]["text"].clone();
+                    synced = Some((document["uri"].clone(), text));
+                }
+                Some("textDocument/didSave") => {
+                    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "workspace/configuration", "params": { "items": [{}] } });
+                    write_message(&mut writer, &request).await.unwrap();
+                }
+                Some(_) => {}
+                None => {
+                    assert_eq!(message["result"], json!([null]));
+                    let (uri, text) = synced.take().unwrap();
+                    let diagnostics = text
+                        .as_str()
+                        .unwrap_or_default()
+                        .lines()
+                        .enumerate()
+                        .filter_map(|(line, content)| {
+                            let character = content.find("todo!()")?;
+                            Some(json!({
+                                "range": {
+                                    "start": { "line": line, "character": character },
+                                    "end": { "line": line, "character": character + 7 }
+                                },
+                                "severity": 1,
+                                "code": 101,
+                                "source": "fake",
+                                "message": "not yet implemented"
+                            }))
+                        })
+                        .collect::<Vec<_>>();
+                    let notification = json!({
+                        "jsonrpc": "2.0",
+                        "method": "textDocument/publishDiagnostics",
+                        "params": { "uri": uri, "diagnostics": diagnostics }
+                    });
+                    write_message(&mut writer, &notification).await.unwrap();
+                }
+            }
+        }
+    }
+
+    async fn fixture_client() -> ForgeLspClient {
+        let (client, server) = tokio::io::duplex(4096);
+        tokio::spawn(fake_server(server));
+        let (reader, writer) = tokio::io::split(client);
+        let client = ForgeLspClient::new(reader, writer, "rust", None);
+        client
+            .initialize(Path::new("/project"), TIMEOUT)
+            .await
+            .unwrap();
+        client
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_published_after_open_and_change() {
+        let client = fixture_client().await;
+        let path = Path::new("/project/src/lib.rs");
+
+        let opened = client
+            .diagnostics(path, "fn main() {\n    todo!()\n}\n", TIMEOUT)
+            .await
+            .unwrap();
+        let changed = client
+            .diagnostics(path, "fn main() {}\n", TIMEOUT)
+            .await
+            .unwrap();
+
+        let expected = vec![Diagnostic {
+            path: "/project/src/lib.rs".to_string(),
+            line: 2,
+            column: 5,
+            severity: DiagnosticSeverity::Error,
+            message: "not yet implemented".to_string(),
+            source: Some("fake".to_string()),
+            code: Some("101".to_string()),
+        }];
+        assert_eq!(opened, expected);
+        assert_eq!(changed, vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_server_exit_stops_client() {
+        let (client, server) = tokio::io::duplex(4096);
+        // Exits without answering the initialize request
+        tokio::spawn(async move {
+            let _ = read_message(&mut BufReader::new(server)).await;
+        });
+        let (reader, writer) = tokio::io::split(client);
+        let client = ForgeLspClient::new(reader, writer, "rust", None);
+
+        let actual = client.initialize(Path::new("/project"), TIMEOUT).await;
+
+        assert!(actual.is_err());
+        assert!(!client.is_running());
+    }
+}
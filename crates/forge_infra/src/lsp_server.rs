@@ -0,0 +1,49 @@
+use std::path::Path;
+use std::process::Stdio;
+
+use anyhow::Context;
+use forge_app::LspServerInfra;
+use forge_domain::LspServerConfig;
+use tokio::process::Command;
+
+use crate::lsp_client::ForgeLspClient;
+
+#[derive(Clone)]
+pub struct ForgeLspServer;
+
+#[async_trait::async_trait]
+impl LspServerInfra for ForgeLspServer {
+    type Client = ForgeLspClient;
+
+    async fn start(
+        &self,
+        language_id: &str,
+        config: &LspServerConfig,
+        root: &Path,
+    ) -> anyhow::Result<Self::Client> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .envs(&config.env)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            // Server logs are not surfaced to the user
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start language server '{}'", config.command))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .context("Language server stdin is unavailable")?;
+        let stdout = child
+            .stdout
+            .take()
+            .context("Language server stdout is unavailable")?;
+
+        let client = ForgeLspClient::new(stdout, stdin, language_id, Some(child));
+        client.initialize(root, config.timeout()).await?;
+        Ok(client)
+    }
+}
@@ -2,13 +2,20 @@ use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::future::Future;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock, RwLock};
 
 use backon::{ExponentialBuilder, Retryable};
 use forge_app::McpClientInfra;
-use forge_domain::{Image, McpHttpServer, McpServerConfig, ToolDefinition, ToolName, ToolOutput};
+use forge_domain::{
+    Image, McpHttpServer, McpPrompt, McpPromptArgument, McpResource, McpResourceContent,
+    McpServerConfig, ToolDefinition, ToolName, ToolOutput,
+};
 use http::{HeaderName, HeaderValue, header};
-use rmcp::model::{CallToolRequestParam, ClientInfo, Implementation, InitializeRequestParam};
+use rmcp::model::{
+    CallToolRequestParam, ClientInfo, GetPromptRequestParam, Implementation,
+    InitializeRequestParam, PromptMessageContent, ReadResourceRequestParam, ResourceContents,
+};
 use rmcp::service::RunningService;
 use rmcp::transport::sse_client::SseClientConfig;
 use rmcp::transport::streamable_http_client::StreamableHttpClientTransportConfig;
@@ -33,6 +40,9 @@ pub struct ForgeMcpClient {
     config: McpServerConfig,
     env_vars: BTreeMap<String, String>,
     resolved_config: Arc<OnceLock<anyhow::Result<McpServerConfig>>>,
+    /// Set once the server has started successfully, after which connection
+    /// failures are treated as crashes and retried with backoff
+    started: Arc<AtomicBool>,
 }
 
 impl ForgeMcpClient {
@@ -42,6 +52,7 @@ impl ForgeMcpClient {
             config,
             env_vars: env_vars.clone(),
             resolved_config: Arc::new(OnceLock::new()),
+            started: Default::default(),
         }
     }
 
@@ -76,12 +87,19 @@ impl ForgeMcpClient {
     /// if already connected.
     async fn connect(&self) -> anyhow::Result<Arc<RmcpClient>> {
         if let Some(client) = self.get_client() {
-            Ok(client.clone())
-        } else {
-            let client = self.create_connection().await?;
-            self.set_client(client.clone());
-            Ok(client.clone())
+            return Ok(client.clone());
         }
+
+        let client = match self.create_connection().await {
+            Ok(client) => client,
+            Err(error) if self.started.load(Ordering::Relaxed) => {
+                return Err(Error::McpRestartFailed(format!("{error:#}")).into());
+            }
+            Err(error) => return Err(error),
+        };
+        self.started.store(true, Ordering::Relaxed);
+        self.set_client(client.clone());
+        Ok(client)
     }
 
     fn get_client(&self) -> Option<Arc<RmcpClient>> {
@@ -210,6 +228,97 @@ impl ForgeMcpClient {
             .is_error(result.is_error.unwrap_or_default()))
     }
 
+    async fn list_resources(&self) -> anyhow::Result<Vec<McpResource>> {
+        let client = self.connect().await?;
+        let supported = client
+            .peer_info()
+            .is_some_and(|info| info.capabilities.resources.is_some());
+        if !supported {
+            return Ok(vec![]);
+        }
+
+        let resources = client.list_all_resources().await?;
+        Ok(resources
+            .into_iter()
+            .map(|resource| {
+                let resource = resource.raw;
+                McpResource {
+                    uri: resource.uri,
+                    name: resource.name,
+                    description: resource.description,
+                    mime_type: resource.mime_type,
+                }
+            })
+            .collect())
+    }
+
+    async fn read_resource(&self, uri: &str) -> anyhow::Result<Vec<McpResourceContent>> {
+        let client = self.connect().await?;
+        let result = client
+            .read_resource(ReadResourceRequestParam { uri: uri.to_string() })
+            .await?;
+
+        Ok(result
+            .contents
+            .into_iter()
+            .map(to_resource_content)
+            .collect())
+    }
+
+    async fn list_prompts(&self) -> anyhow::Result<Vec<McpPrompt>> {
+        let client = self.connect().await?;
+        let supported = client
+            .peer_info()
+            .is_some_and(|info| info.capabilities.prompts.is_some());
+        if !supported {
+            return Ok(vec![]);
+        }
+
+        let prompts = client.list_all_prompts().await?;
+        Ok(prompts
+            .into_iter()
+            .map(|prompt| McpPrompt {
+                name: prompt.name,
+                description: prompt.description,
+                arguments: prompt
+                    .arguments
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|arg| McpPromptArgument {
+                        name: arg.name,
+                        description: arg.description,
+                        required: arg.required.unwrap_or_default(),
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: &BTreeMap<String, String>,
+    ) -> anyhow::Result<String> {
+        let client = self.connect().await?;
+        let arguments = arguments
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+            .collect();
+        let result = client
+            .get_prompt(GetPromptRequestParam {
+                name: name.to_string(),
+                arguments: Some(arguments),
+            })
+            .await?;
+
+        Ok(result
+            .messages
+            .into_iter()
+            .filter_map(|message| prompt_text(message.content))
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
     async fn attempt_with_retry<T, F>(&self, call: impl Fn() -> F) -> anyhow::Result<T>
     where
         F: Future<Output = anyhow::Result<T>>,
@@ -234,7 +343,16 @@ impl ForgeMcpClient {
                 guard.take();
             }
 
-            is_transport
+            // A crashed server is restarted on the next attempt; keep backing
+            // off while it fails to come back up
+            let is_restart = err
+                .downcast_ref::<Error>()
+                .is_some_and(|e| matches!(e, Error::McpRestartFailed(_)));
+
+            is_transport || is_restart
+        })
+        .notify(|err, delay| {
+            tracing::warn!(error = %err, delay = ?delay, "MCP server unavailable, retrying");
         })
         .await
     }
@@ -250,6 +368,51 @@ impl McpClientInfra for ForgeMcpClient {
         self.attempt_with_retry(|| self.call(tool_name, &input))
             .await
     }
+
+    async fn list_resources(&self) -> anyhow::Result<Vec<McpResource>> {
+        self.attempt_with_retry(|| self.list_resources()).await
+    }
+
+    async fn read_resource(&self, uri: &str) -> anyhow::Result<Vec<McpResourceContent>> {
+        self.attempt_with_retry(|| self.read_resource(uri)).await
+    }
+
+    async fn list_prompts(&self) -> anyhow::Result<Vec<McpPrompt>> {
+        self.attempt_with_retry(|| self.list_prompts()).await
+    }
+
+    async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: BTreeMap<String, String>,
+    ) -> anyhow::Result<String> {
+        self.attempt_with_retry(|| self.get_prompt(name, &arguments))
+            .await
+    }
+}
+
+fn to_resource_content(content: ResourceContents) -> McpResourceContent {
+    match content {
+        ResourceContents::TextResourceContents { uri, mime_type, text, .. } => {
+            McpResourceContent::Text { uri, mime_type, text }
+        }
+        ResourceContents::BlobResourceContents { uri, mime_type, blob, .. } => {
+            McpResourceContent::Blob { uri, mime_type, blob }
+        }
+    }
+}
+
+/// Extracts the text of a prompt message. Images and linked resources carry
+/// no text and are skipped.
+fn prompt_text(content: PromptMessageContent) -> Option<String> {
+    match content {
+        PromptMessageContent::Text { text } => Some(text),
+        PromptMessageContent::Resource { resource } => match resource.raw.resource {
+            ResourceContents::TextResourceContents { text, .. } => Some(text),
+            ResourceContents::BlobResourceContents { .. } => None,
+        },
+        PromptMessageContent::Image { .. } | PromptMessageContent::ResourceLink { .. } => None,
+    }
 }
 
 /// Resolves mustache templates in McpHttpServer headers using Handlebars
@@ -277,9 +440,43 @@ fn resolve_http_templates(
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
+    use rmcp::model::{AnnotateAble, RawImageContent};
 
     use super::*;
 
+    #[test]
+    fn test_to_resource_content_text() {
+        let fixture = ResourceContents::text("# Setup", "file:///setup.md");
+        let actual = to_resource_content(fixture);
+        let expected = McpResourceContent::Text {
+            uri: "file:///setup.md".to_string(),
+            mime_type: Some("text".to_string()),
+            text: "# Setup".to_string(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_prompt_text_skips_images() {
+        let fixture = vec![
+            PromptMessageContent::text("Review this"),
+            PromptMessageContent::Image {
+                image: RawImageContent {
+                    data: "aGk=".to_string(),
+                    mime_type: "image/png".to_string(),
+                    meta: None,
+                }
+                .no_annotation(),
+            },
+        ];
+        let actual = fixture
+            .into_iter()
+            .filter_map(prompt_text)
+            .collect::<Vec<_>>();
+        let expected = vec!["Review this".to_string()];
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_resolve_http_templates_with_env() {
         let env_vars = BTreeMap::from([
@@ -297,7 +494,7 @@ mod tests {
                 ("X-API-Key".to_string(), "{{env.API_KEY}}".to_string()),
                 ("Content-Type".to_string(), "application/json".to_string()),
             ]),
-            disable: false,
+            ..Default::default()
         };
 
         let resolved = resolve_http_templates(http, &env_vars).unwrap();
@@ -326,7 +523,7 @@ mod tests {
                 "Authorization".to_string(),
                 "Bearer {{env.MISSING_VAR}}".to_string(),
             )]),
-            disable: false,
+            ..Default::default()
         };
 
         let resolved = resolve_http_templates(http, &env_vars).unwrap();
@@ -346,6 +543,7 @@ mod tests {
             url: "https://test.example.com".to_string(),
             headers: BTreeMap::from([("Auth".to_string(), "{{env.TOKEN}}".to_string())]),
             disable: true,
+            ..Default::default()
         };
 
         let resolved = resolve_http_templates(http, &env_vars).unwrap();
@@ -8,7 +8,7 @@
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand, ValueEnum};
-use forge_domain::{AgentId, ConversationId, ProviderId};
+use forge_domain::{AgentId, ConversationId, ProviderId, ScheduleId};
 
 #[derive(Parser)]
 #[command(version = env!("CARGO_PKG_VERSION"))]
@@ -47,7 +47,10 @@ pub struct Cli {
     pub directory: Option<PathBuf>,
 
     /// Name for an isolated git worktree to create for experimentation.
-    #[arg(long)]
+    ///
+    /// Without a name, a fresh worktree is named after the prompt. When a
+    /// prompt finishes in a sandbox, a summary of its changes is printed.
+    #[arg(long, num_args = 0..=1, default_missing_value = "")]
     pub sandbox: Option<String>,
 
     /// Enable verbose logging output.
@@ -73,6 +76,14 @@ pub struct Cli {
     /// Event to dispatch to the workflow in JSON format.
     #[arg(long, short = 'e')]
     pub event: Option<String>,
+
+    /// Output format for non-interactive runs.
+    ///
+    /// `json` and `jsonl` emit every chat event as structured data on stdout
+    /// instead of rendered markdown. Only applies when a prompt, piped input
+    /// or event is provided.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
 }
 
 impl Cli {
@@ -83,6 +94,44 @@ impl Cli {
     pub fn is_interactive(&self) -> bool {
         self.prompt.is_none() && self.piped_input.is_none() && self.subcommands.is_none()
     }
+
+    /// Returns the structured output format to emit chat events in, if any.
+    ///
+    /// Returns `None` for text output, interactive sessions and subcommands,
+    /// which keep their human-readable rendering.
+    pub fn event_format(&self) -> Option<OutputFormat> {
+        let has_input = self.prompt.is_some() || self.piped_input.is_some() || self.event.is_some();
+        match self.output_format {
+            OutputFormat::Text => None,
+            format if has_input && self.subcommands.is_none() => Some(format),
+            _ => None,
+        }
+    }
+
+    /// Returns true when stdin and stdout carry a protocol stream rather than
+    /// user input and output.
+    pub fn is_stdio_server(&self) -> bool {
+        matches!(
+            &self.subcommands,
+            Some(TopLevelCommand::Mcp(McpCommandGroup {
+                command: McpCommand::Serve,
+                ..
+            }))
+        )
+    }
+}
+
+/// Output format for chat events in non-interactive runs.
+#[derive(Copy, Clone, Debug, Default, ValueEnum, PartialEq, Eq)]
+#[clap(rename_all = "lower")]
+pub enum OutputFormat {
+    /// Human-readable rendered markdown.
+    #[default]
+    Text,
+    /// A single JSON document with all events, written on completion.
+    Json,
+    /// One JSON object per event, streamed as they occur.
+    Jsonl,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -144,6 +193,236 @@ pub enum TopLevelCommand {
 
     /// Process JSONL data through LLM with schema-constrained tools.
     Data(DataCommandGroup),
+
+    /// Run an evaluation suite and write a report of the results.
+    Eval(EvalCommandGroup),
+
+    /// Serve OpenAI and Anthropic compatible endpoints backed by the
+    /// configured providers.
+    Proxy(ProxyCommandGroup),
+
+    /// List, inspect and resume markdown plans.
+    Plan(PlanCommandGroup),
+
+    /// Run prompts on a cron schedule.
+    Schedule(ScheduleCommandGroup),
+
+    /// List, review, merge and clean up `--sandbox` worktrees.
+    Sandbox(SandboxCommandGroup),
+}
+
+/// Command group for running evaluation suites.
+#[derive(Parser, Debug, Clone)]
+pub struct EvalCommandGroup {
+    /// Path to the suite YAML file.
+    pub suite: PathBuf,
+
+    /// Directory to write `report.json` and `report.md` to.
+    ///
+    /// Defaults to `results/<timestamp>` next to the suite file.
+    #[arg(long, short = 'o')]
+    pub output: Option<PathBuf>,
+
+    /// Number of tasks to run at once, overriding the suite.
+    #[arg(long, short = 'j')]
+    pub concurrency: Option<usize>,
+}
+
+/// Command group for the local provider proxy.
+#[derive(Parser, Debug, Clone)]
+pub struct ProxyCommandGroup {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: std::net::IpAddr,
+
+    /// Port to listen on.
+    #[arg(long, default_value_t = 8787)]
+    pub port: u16,
+
+    /// Token that clients must send as `Authorization: Bearer` or
+    /// `x-api-key`. Defaults to FORGE_PROXY_TOKEN, or a token generated at
+    /// startup. Required to listen on a non-loopback address.
+    #[arg(long)]
+    pub token: Option<String>,
+}
+
+/// Command group for plan management.
+#[derive(Parser, Debug, Clone)]
+pub struct PlanCommandGroup {
+    #[command(subcommand)]
+    pub command: PlanCommand,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum PlanCommand {
+    /// List the plans in the `plans` directory with their progress.
+    #[command(alias = "ls")]
+    List {
+        /// Output in machine-readable format.
+        #[arg(long)]
+        porcelain: bool,
+    },
+
+    /// Show the steps of a plan and their status.
+    Show {
+        /// Path to the plan file.
+        path: PathBuf,
+    },
+
+    /// Continue working through a plan from its next unfinished step.
+    Resume {
+        /// Path to the plan file.
+        path: PathBuf,
+    },
+}
+
+/// Command group for scheduled agent runs.
+#[derive(Parser, Debug, Clone)]
+pub struct ScheduleCommandGroup {
+    #[command(subcommand)]
+    pub command: ScheduleCommand,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleCommand {
+    /// Schedule a prompt to run in the current directory.
+    Add {
+        /// Five-field cron expression in local time, such as "0 2 * * *".
+        #[arg(long)]
+        cron: String,
+
+        /// Agent to run the prompt with.
+        #[arg(long)]
+        agent: Option<AgentId>,
+
+        /// Prompt to run.
+        #[arg(
+            long,
+            conflicts_with = "prompt_file",
+            required_unless_present = "prompt_file"
+        )]
+        prompt: Option<String>,
+
+        /// File to read the prompt from every time the job runs.
+        #[arg(long)]
+        prompt_file: Option<PathBuf>,
+
+        /// Run in the current directory instead of a fresh git worktree.
+        #[arg(long)]
+        no_sandbox: bool,
+    },
+
+    /// List scheduled jobs with their next run.
+    #[command(alias = "ls")]
+    List {
+        /// Output in machine-readable format.
+        #[arg(long)]
+        porcelain: bool,
+    },
+
+    /// Remove a scheduled job and its run history.
+    #[command(alias = "rm")]
+    Remove {
+        /// ID of the job.
+        id: ScheduleId,
+    },
+
+    /// Run a scheduled job immediately in the foreground.
+    RunNow {
+        /// ID of the job.
+        id: ScheduleId,
+    },
+
+    /// Show the recent runs of a scheduled job.
+    Runs {
+        /// ID of the job.
+        id: ScheduleId,
+
+        /// Maximum number of runs to show.
+        #[arg(long, short = 'n', default_value_t = 20)]
+        limit: usize,
+
+        /// Output in machine-readable format.
+        #[arg(long)]
+        porcelain: bool,
+    },
+
+    /// Run due jobs until stopped with Ctrl+C.
+    Daemon,
+}
+
+/// Command group for sandbox worktrees.
+#[derive(Parser, Debug, Clone)]
+pub struct SandboxCommandGroup {
+    #[command(subcommand)]
+    pub command: SandboxCommand,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum SandboxCommand {
+    /// List sandboxes with their commits ahead of and behind the base branch.
+    #[command(alias = "ls")]
+    List {
+        /// Output in machine-readable format.
+        #[arg(long)]
+        porcelain: bool,
+    },
+
+    /// Show the changes of a sandbox since it branched off.
+    Diff {
+        /// Name or path of the sandbox.
+        name: String,
+
+        /// Show a summary of changed files instead of the full diff.
+        #[arg(long)]
+        stat: bool,
+    },
+
+    /// Merge the branch of a sandbox into the current branch.
+    Merge {
+        /// Name or path of the sandbox.
+        name: String,
+
+        /// Stage the changes as a single commit instead of merging.
+        #[arg(long)]
+        squash: bool,
+
+        /// Remove the sandbox after merging.
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Remove a sandbox and its branch.
+    #[command(alias = "rm")]
+    Remove {
+        /// Name or path of the sandbox.
+        name: String,
+
+        /// Discard unmerged commits and uncommitted changes.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Remove every sandbox that matches a cleanup policy. Without a policy,
+    /// merged sandboxes are removed.
+    Prune {
+        /// Remove sandboxes with no unmerged commits or uncommitted changes.
+        #[arg(long)]
+        merged: bool,
+
+        /// Remove sandboxes whose worktree is unchanged for this long, e.g. `7d`. Work
+        /// that is not merged is kept unless `--force` is given.
+        #[arg(long, value_parser = humantime::parse_duration)]
+        older_than: Option<std::time::Duration>,
+
+        /// Discard unmerged work of sandboxes matched by `--older-than`.
+        #[arg(long, requires = "older_than")]
+        force: bool,
+
+        /// Print the sandboxes that would be removed.
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Command group for custom command management.
@@ -364,6 +643,18 @@ pub enum McpCommand {
 
     /// Reload servers and rebuild caches.
     Reload,
+
+    /// Show connection state, latency and tool counts for each server.
+    Status,
+
+    /// Serve Forge's built-in tools to other MCP clients over stdio.
+    Serve,
+
+    /// Authorize access to a remote server using OAuth.
+    Login(McpLoginArgs),
+
+    /// Remove stored OAuth credentials for a server.
+    Logout(McpLogoutArgs),
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -393,6 +684,18 @@ pub struct McpShowArgs {
     pub name: String,
 }
 
+#[derive(Parser, Debug, Clone)]
+pub struct McpLoginArgs {
+    /// Name of the server to authorize.
+    pub name: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct McpLogoutArgs {
+    /// Name of the server to log out from.
+    pub name: String,
+}
+
 /// Configuration scope for settings.
 #[derive(Copy, Clone, Debug, ValueEnum, Default)]
 pub enum Scope {
@@ -538,6 +841,15 @@ pub enum ConversationCommand {
         porcelain: bool,
     },
 
+    /// Inspect and edit individual messages of the conversation context.
+    Context {
+        /// Conversation ID.
+        id: ConversationId,
+
+        #[command(subcommand)]
+        command: Option<ContextCommand>,
+    },
+
     /// Clone conversation with a new ID.
     Clone {
         /// Conversation ID to clone.
@@ -555,6 +867,57 @@ pub enum ConversationCommand {
     },
 }
 
+/// Edits applied to individual messages of a conversation context. Messages
+/// are addressed by the index shown by `list`.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum ContextCommand {
+    /// List messages with their estimated token counts.
+    List {
+        /// Output in machine-readable format.
+        #[arg(long)]
+        porcelain: bool,
+    },
+
+    /// Pin a message so that compaction never evicts it.
+    Pin {
+        /// Index of the message.
+        index: usize,
+    },
+
+    /// Unpin a message.
+    Unpin {
+        /// Index of the message.
+        index: usize,
+    },
+
+    /// Remove a message, along with the results of its tool calls.
+    Drop {
+        /// Index of the message.
+        index: usize,
+    },
+
+    /// Replace the output of a tool result with a short stub.
+    Stub {
+        /// Index of the tool result.
+        index: usize,
+    },
+
+    /// Replace a range of messages with a summary.
+    Summarize {
+        /// Index of the first message to summarize.
+        start: usize,
+
+        /// Index of the last message to summarize.
+        end: usize,
+    },
+}
+
+impl Default for ContextCommand {
+    fn default() -> Self {
+        Self::List { porcelain: false }
+    }
+}
+
 /// Command group for provider authentication management.
 #[derive(Parser, Debug, Clone)]
 pub struct ProviderCommandGroup {
@@ -910,6 +1273,139 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_conversation_context_summarize() {
+        let fixture = Cli::parse_from([
+            "forge",
+            "conversation",
+            "context",
+            "550e8400-e29b-41d4-a716-446655440003",
+            "summarize",
+            "2",
+            "7",
+        ]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Conversation(conversation)) => match conversation.command {
+                ConversationCommand::Context { command, .. } => command,
+                _ => None,
+            },
+            _ => None,
+        };
+        let expected = Some(ContextCommand::Summarize { start: 2, end: 7 });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_conversation_context_defaults_to_list() {
+        let fixture = Cli::parse_from([
+            "forge",
+            "conversation",
+            "context",
+            "550e8400-e29b-41d4-a716-446655440003",
+        ]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Conversation(conversation)) => match conversation.command {
+                ConversationCommand::Context { command, .. } => command.unwrap_or_default(),
+                _ => ContextCommand::Drop { index: 0 },
+            },
+            _ => ContextCommand::Drop { index: 0 },
+        };
+        let expected = ContextCommand::List { porcelain: false };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_plan_list_with_porcelain() {
+        let fixture = Cli::parse_from(["forge", "plan", "ls", "--porcelain"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Plan(plan)) => Some(plan.command),
+            _ => None,
+        };
+        let expected = Some(PlanCommand::List { porcelain: true });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_plan_resume() {
+        let fixture = Cli::parse_from(["forge", "plan", "resume", "plans/2025-01-01-auth-v1.md"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Plan(plan)) => Some(plan.command),
+            _ => None,
+        };
+        let expected =
+            Some(PlanCommand::Resume { path: PathBuf::from("plans/2025-01-01-auth-v1.md") });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_schedule_add_with_prompt_file() {
+        let fixture = Cli::parse_from([
+            "forge",
+            "schedule",
+            "add",
+            "--cron",
+            "0 2 * * *",
+            "--agent",
+            "forge",
+            "--prompt-file",
+            "prompts/audit.md",
+        ]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Schedule(schedule)) => Some(schedule.command),
+            _ => None,
+        };
+        let expected = Some(ScheduleCommand::Add {
+            cron: "0 2 * * *".to_string(),
+            agent: Some(AgentId::new("forge")),
+            prompt: None,
+            prompt_file: Some(PathBuf::from("prompts/audit.md")),
+            no_sandbox: false,
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_schedule_add_requires_a_prompt() {
+        let actual = Cli::try_parse_from(["forge", "schedule", "add", "--cron", "@daily"]).is_err();
+        let expected = true;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_sandbox_without_name() {
+        let fixture = Cli::parse_from(["forge", "--sandbox", "-p", "Fix the build"]);
+        let actual = (fixture.sandbox, fixture.prompt);
+        let expected = (Some(String::new()), Some("Fix the build".to_string()));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_sandbox_prune_older_than() {
+        let fixture = Cli::parse_from(["forge", "sandbox", "prune", "--older-than", "7d"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Sandbox(sandbox)) => Some(sandbox.command),
+            _ => None,
+        };
+        let expected = Some(SandboxCommand::Prune {
+            merged: false,
+            older_than: Some(std::time::Duration::from_secs(7 * 24 * 60 * 60)),
+            force: false,
+            dry_run: false,
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_schedule_run_now() {
+        let fixture = Cli::parse_from(["forge", "schedule", "run-now", "3"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Schedule(schedule)) => Some(schedule.command),
+            _ => None,
+        };
+        let expected = Some(ScheduleCommand::RunNow { id: ScheduleId::new(3) });
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_conversation_last_with_id() {
         let fixture = Cli::parse_from([
@@ -1095,6 +1591,36 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_mcp_serve_is_stdio_server() {
+        let fixture = Cli::parse_from(["forge", "mcp", "serve"]);
+        let actual = fixture.is_stdio_server();
+        let expected = true;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_mcp_list_is_not_stdio_server() {
+        let fixture = Cli::parse_from(["forge", "mcp", "list"]);
+        let actual = fixture.is_stdio_server();
+        let expected = false;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_mcp_login_with_server_name() {
+        let fixture = Cli::parse_from(["forge", "mcp", "login", "github"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Mcp(mcp)) => match mcp.command {
+                McpCommand::Login(args) => args.name,
+                _ => String::new(),
+            },
+            _ => String::new(),
+        };
+        let expected = "github";
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_conversation_list_with_porcelain() {
         let fixture = Cli::parse_from(["forge", "conversation", "list", "--porcelain"]);
@@ -1428,4 +1954,42 @@ mod tests {
         let fixture = Cli::parse_from(["forge", "-p", "--something"]);
         assert_eq!(fixture.prompt, Some("--something".to_string()));
     }
+
+    #[test]
+    fn test_output_format_defaults_to_text() {
+        let fixture = Cli::parse_from(["forge", "-p", "hello"]);
+        assert_eq!(fixture.output_format, OutputFormat::Text);
+        assert_eq!(fixture.event_format(), None);
+    }
+
+    #[test]
+    fn test_output_format_jsonl_with_prompt() {
+        let fixture = Cli::parse_from(["forge", "-p", "hello", "--output-format", "jsonl"]);
+        let actual = fixture.event_format();
+        let expected = Some(OutputFormat::Jsonl);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_output_format_ignored_without_prompt() {
+        let fixture = Cli::parse_from(["forge", "--output-format", "json"]);
+        let actual = fixture.event_format();
+        let expected = None;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_eval_command_with_options() {
+        let fixture = Cli::parse_from(["forge", "eval", "suite.yml", "-o", "out", "-j", "4"]);
+        let actual = match fixture.subcommands {
+            Some(TopLevelCommand::Eval(group)) => (group.suite, group.output, group.concurrency),
+            _ => panic!("Expected Eval command"),
+        };
+        let expected = (
+            PathBuf::from("suite.yml"),
+            Some(PathBuf::from("out")),
+            Some(4),
+        );
+        assert_eq!(actual, expected);
+    }
 }
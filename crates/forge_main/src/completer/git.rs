@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use reedline::{Span, Suggestion};
+
+/// Number of recent commits offered after `@[git:`
+const MAX_COMMITS: usize = 20;
+
+/// Suggests git attachments after `@[git:`: the uncommitted diff, the diff
+/// against each branch and the most recent commits.
+pub struct GitCompleter {
+    cwd: PathBuf,
+}
+
+impl GitCompleter {
+    pub fn new(cwd: PathBuf) -> Self {
+        Self { cwd }
+    }
+
+    /// Completes `query`, the text typed after `@[git:`, replacing `span`.
+    pub fn complete(&self, query: &str, span: Span) -> Vec<Suggestion> {
+        let branches = self
+            .git(&[
+                "for-each-ref",
+                "--format=%(refname:short)",
+                "refs/heads",
+                "refs/remotes",
+            ])
+            .map(|output| output.lines().map(str::to_string).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let log_limit = format!("-n{MAX_COMMITS}");
+        let commits = self
+            .git(&["log", "--no-color", &log_limit, "--format=%h%x09%s"])
+            .map(|output| {
+                output
+                    .lines()
+                    .filter_map(|line| line.split_once('\t'))
+                    .map(|(sha, subject)| (sha.to_string(), subject.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        candidates(&branches, &commits)
+            .into_iter()
+            .filter(|(reference, _)| reference.contains(query))
+            .map(|(reference, description)| Suggestion {
+                value: format!("[git:{reference}]"),
+                description: Some(description),
+                style: None,
+                extra: None,
+                span,
+                append_whitespace: true,
+            })
+            .collect()
+    }
+
+    /// Runs git in the working directory, returning stdout on success.
+    fn git(&self, args: &[&str]) -> Option<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&self.cwd)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Builds the git references offered for completion, each paired with a
+/// description.
+fn candidates(branches: &[String], commits: &[(String, String)]) -> Vec<(String, String)> {
+    let diff = ("diff".to_string(), "Uncommitted changes".to_string());
+    let branches = branches.iter().map(|branch| {
+        (
+            format!("diff:{branch}"),
+            format!("Changes against {branch}"),
+        )
+    });
+    let commits = commits
+        .iter()
+        .map(|(sha, subject)| (format!("commit:{sha}"), subject.clone()));
+
+    std::iter::once(diff)
+        .chain(branches)
+        .chain(commits)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_candidates() {
+        let branches = vec!["main".to_string(), "origin/main".to_string()];
+        let commits = vec![("abc1234".to_string(), "Add git attachments".to_string())];
+
+        let actual = candidates(&branches, &commits);
+
+        let expected = vec![
+            ("diff".to_string(), "Uncommitted changes".to_string()),
+            ("diff:main".to_string(), "Changes against main".to_string()),
+            (
+                "diff:origin/main".to_string(),
+                "Changes against origin/main".to_string(),
+            ),
+            (
+                "commit:abc1234".to_string(),
+                "Add git attachments".to_string(),
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_complete_outside_repository_offers_uncommitted_diff() {
+        let fixture = tempfile::TempDir::new().unwrap();
+        let completer = GitCompleter::new(fixture.path().to_path_buf());
+
+        let actual = completer
+            .complete("di", Span::new(0, 8))
+            .into_iter()
+            .map(|suggestion| suggestion.value)
+            .collect::<Vec<_>>();
+
+        let expected = vec!["[git:diff]".to_string()];
+        assert_eq!(actual, expected);
+    }
+}
@@ -7,21 +7,24 @@ use nucleo::{Config, Matcher, Utf32Str};
 use reedline::{Completer, Suggestion};
 
 use crate::completer::CommandCompleter;
+use crate::completer::git::GitCompleter;
 use crate::completer::search_term::SearchTerm;
 use crate::model::ForgeCommandManager;
 
 pub struct InputCompleter {
     walker: Walker,
     command: CommandCompleter,
+    git: GitCompleter,
     fuzzy_matcher: Matcher,
 }
 
 impl InputCompleter {
     pub fn new(cwd: PathBuf, command_manager: Arc<ForgeCommandManager>) -> Self {
-        let walker = Walker::max_all().cwd(cwd).skip_binary(true);
+        let walker = Walker::max_all().cwd(cwd.clone()).skip_binary(true);
         Self {
             walker,
             command: CommandCompleter::new(command_manager),
+            git: GitCompleter::new(cwd),
             fuzzy_matcher: Matcher::new(Config::DEFAULT.match_paths()),
         }
     }
@@ -39,6 +42,10 @@ impl Completer for InputCompleter {
         }
 
         if let Some(query) = SearchTerm::new(line, pos).process() {
+            if let Some(reference) = query.term.strip_prefix("[git:") {
+                return self.git.complete(reference, query.span);
+            }
+
             let files = self.walker.get_blocking().unwrap_or_default();
             let pattern = Pattern::parse(
                 escape_for_pattern_parse(query.term).as_str(),
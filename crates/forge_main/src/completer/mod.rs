@@ -1,4 +1,5 @@
 mod command;
+mod git;
 mod input_completer;
 mod search_term;
 
@@ -0,0 +1,338 @@
+//! Evaluation harness behind `forge eval`.
+//!
+//! A suite pairs prompts with fixture directories and checks. Every task runs
+//! with every model in a fresh copy of its fixture, and the results are
+//! written as `report.json` and `report.md` so runs can be compared.
+
+mod report;
+mod suite;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::Context as _;
+use async_recursion::async_recursion;
+use forge_api::API;
+use forge_domain::{
+    ChatRequest, ChatResponse, ChatResponseContent, Conversation, ConversationId, Event,
+    TitleFormat, ToolCallFull,
+};
+use futures::StreamExt;
+pub use report::*;
+pub use suite::*;
+
+use crate::cli::EvalCommandGroup;
+use crate::{ExitStatus, TitleDisplayExt};
+
+/// Runs a [`Suite`], creating one API per task so that each task has its own
+/// working directory and model.
+pub struct Eval<F> {
+    suite: Suite,
+    new_api: F,
+}
+
+/// What the agent did while working on a task.
+#[derive(Default)]
+struct Trace {
+    tool_calls: Vec<ToolCallFull>,
+    output: String,
+}
+
+impl<A: API + 'static, F: Fn(PathBuf, Option<EvalModel>) -> A + Send + Sync> Eval<F> {
+    pub fn new(suite: Suite, new_api: F) -> Self {
+        Self { suite, new_api }
+    }
+
+    pub async fn run(&self) -> Report {
+        let started_at = chrono::Local::now();
+        let models = match self.suite.models.is_empty() {
+            true => vec![None],
+            false => self.suite.models.iter().cloned().map(Some).collect(),
+        };
+        let runs = models
+            .iter()
+            .flat_map(|model| self.suite.tasks.iter().map(move |task| (task, model)))
+            .collect::<Vec<_>>();
+
+        let results = futures::stream::iter(runs)
+            .map(|(task, model)| self.run_task(task, model.clone()))
+            .buffered(self.suite.concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        Report::new(self.suite.name.clone(), started_at, results)
+    }
+
+    async fn run_task(&self, task: &EvalTask, model: Option<EvalModel>) -> TaskResult {
+        let label = model
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| "default".to_string());
+        let result = self
+            .try_run_task(task, model, &label)
+            .await
+            .unwrap_or_else(|error| {
+                let mut result = TaskResult::new(&task.name, &label, TaskStatus::Error);
+                result.error = Some(format!("{error:#}"));
+                result
+            });
+
+        let title = match result.status {
+            TaskStatus::Passed => TitleFormat::action(&task.name),
+            _ => TitleFormat::error(&task.name),
+        };
+        println!(
+            "{}",
+            title
+                .sub_title(format!(
+                    "{label} {} in {}ms",
+                    result.status, result.duration_ms
+                ))
+                .display()
+        );
+        result
+    }
+
+    async fn try_run_task(
+        &self,
+        task: &EvalTask,
+        model: Option<EvalModel>,
+        label: &str,
+    ) -> anyhow::Result<TaskResult> {
+        let dir = tempfile::Builder::new().prefix("forge-eval-").tempdir()?;
+        let cwd = dir.path().to_path_buf();
+        if let Some(fixture) = &task.fixture {
+            copy_dir(fixture, &cwd)
+                .await
+                .with_context(|| format!("Failed to copy fixture {}", fixture.display()))?;
+        }
+
+        let api = (self.new_api)(cwd.clone(), model);
+        if let Some(agent) = self.suite.agent.clone() {
+            api.set_active_agent(agent).await?;
+        }
+        let conversation = Conversation::generate();
+        let conversation_id = conversation.id;
+        api.upsert_conversation(conversation).await?;
+
+        // The conversation is deleted afterwards so that eval runs don't show up
+        // among the user's own conversations
+        let result = self
+            .run_conversation(&api, task, label, conversation_id, &cwd)
+            .await;
+        let deleted = api.delete_conversation(&conversation_id).await;
+        let result = result?;
+        deleted?;
+        Ok(result)
+    }
+
+    /// Sends the prompt of `task` in the conversation and verifies the outcome
+    async fn run_conversation(
+        &self,
+        api: &A,
+        task: &EvalTask,
+        label: &str,
+        conversation_id: ConversationId,
+        cwd: &Path,
+    ) -> anyhow::Result<TaskResult> {
+        let start = Instant::now();
+        let mut trace = Trace::default();
+        let timeout = Duration::from_secs(self.suite.timeout);
+        let outcome = tokio::time::timeout(
+            timeout,
+            chat(
+                api,
+                ChatRequest::new(Event::new(task.prompt.clone()), conversation_id),
+                &mut trace,
+            ),
+        )
+        .await;
+
+        let status = match outcome {
+            Err(_) => Err((
+                TaskStatus::Timeout,
+                format!("Timed out after {}s", timeout.as_secs()),
+            )),
+            Ok(Err(error)) => Err((TaskStatus::Error, format!("{error:#}"))),
+            Ok(Ok(())) => Ok(()),
+        };
+        let mut result = TaskResult::new(&task.name, label, TaskStatus::Passed);
+        result.duration_ms = start.elapsed().as_millis() as u64;
+        result.tool_calls = trace.tool_calls.len();
+        if let Some(usage) = api
+            .conversation(&conversation_id)
+            .await?
+            .and_then(|conversation| conversation.accumulated_usage())
+        {
+            result = result.usage(&usage);
+        }
+
+        if let Err((status, error)) = status {
+            result.status = status;
+            result.error = Some(error);
+            return Ok(result);
+        }
+
+        for check in &task.checks {
+            result.checks.push(verify(api, check, cwd, &trace).await);
+        }
+        if result.checks.iter().any(|check| !check.passed) {
+            result.status = TaskStatus::Failed;
+        }
+        Ok(result)
+    }
+}
+
+/// Sends `request` and records the tool calls and text of the agent until it
+/// finishes.
+async fn chat<A: API>(api: &A, request: ChatRequest, trace: &mut Trace) -> anyhow::Result<()> {
+    let mut stream = api.chat(request).await?;
+    while let Some(response) = stream.next().await {
+        match response? {
+            ChatResponse::ToolCallStart(call) => trace.tool_calls.push(call),
+            ChatResponse::TaskMessage {
+                content: ChatResponseContent::Markdown(text) | ChatResponseContent::PlainText(text),
+            } => trace.output.push_str(&text),
+            ChatResponse::Interrupt { reason } => anyhow::bail!("Interrupted: {reason:?}"),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+async fn verify<A: API>(api: &A, check: &Check, cwd: &Path, trace: &Trace) -> CheckResult {
+    let (passed, message) = match check {
+        Check::Command(check) => {
+            let timeout = Duration::from_secs(check.timeout);
+            match tokio::time::timeout(
+                timeout,
+                api.execute_shell_command(&check.command, cwd.to_path_buf()),
+            )
+            .await
+            {
+                Err(_) => (false, format!("Timed out after {}s", timeout.as_secs())),
+                Ok(Err(error)) => (false, format!("{error:#}")),
+                Ok(Ok(output)) => {
+                    let passed = output.exit_code == Some(check.exit_code);
+                    let message = match output.exit_code {
+                        Some(code) => format!("Exited with {code}"),
+                        None => "Terminated by a signal".to_string(),
+                    };
+                    (passed, message)
+                }
+            }
+        }
+        Check::Tool(check) => match check.verify(&trace.tool_calls) {
+            Ok(count) => (true, format!("Called {count} times")),
+            Err(count) => (false, format!("Called {count} times")),
+        },
+        Check::Output(check) => match check.verify(&trace.output) {
+            true => (true, "Matched".to_string()),
+            false => (false, "Did not match".to_string()),
+        },
+    };
+    CheckResult { name: check.name(), passed, message }
+}
+
+#[async_recursion]
+async fn copy_dir(from: &Path, to: &Path) -> anyhow::Result<()> {
+    tokio::fs::create_dir_all(to).await?;
+    let mut entries = tokio::fs::read_dir(from).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let target = to.join(entry.file_name());
+        if entry.file_type().await?.is_dir() {
+            copy_dir(&entry.path(), &target).await?;
+        } else {
+            tokio::fs::copy(entry.path(), target).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs the suite of an `forge eval` invocation and writes its report.
+pub async fn run_eval<A: API + 'static>(
+    group: EvalCommandGroup,
+    new_api: impl Fn(PathBuf, Option<EvalModel>) -> A + Send + Sync,
+) -> anyhow::Result<ExitStatus> {
+    let mut suite = Suite::load(&group.suite).await?;
+    if let Some(concurrency) = group.concurrency {
+        suite.concurrency = concurrency.max(1);
+    }
+    let output = group.output.unwrap_or_else(|| {
+        let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+        group
+            .suite
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("results")
+            .join(timestamp.to_string())
+    });
+
+    // Providers configured through environment variables are migrated once
+    // up front, as a session would on startup, instead of by every task.
+    let base = group.suite.parent().unwrap_or(Path::new(".")).to_path_buf();
+    new_api(base, None).migrate_env_credentials().await?;
+
+    let report = Eval::new(suite, new_api).run().await;
+    write_report(&report, &output).await?;
+
+    for item in &report.summary {
+        println!(
+            "{}",
+            TitleFormat::info(&item.model)
+                .sub_title(format!(
+                    "{}/{} passed, {} tokens",
+                    item.passed, item.tasks, item.tokens
+                ))
+                .display()
+        );
+    }
+    println!(
+        "{}",
+        TitleFormat::info("Report")
+            .sub_title(output.join("report.md").display().to_string())
+            .display()
+    );
+
+    Ok(match report.passed() {
+        true => ExitStatus::Success,
+        false => ExitStatus::Error,
+    })
+}
+
+/// Writes `report.json` and `report.md` into `dir`.
+async fn write_report(report: &Report, dir: &Path) -> anyhow::Result<()> {
+    forge_fs::ForgeFS::create_dir_all(dir).await?;
+    forge_fs::ForgeFS::write(
+        dir.join("report.json"),
+        serde_json::to_string_pretty(report)?,
+    )
+    .await?;
+    forge_fs::ForgeFS::write(dir.join("report.md"), report.to_markdown()).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_copy_dir_copies_nested_files() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(source.path().join("src")).unwrap();
+        std::fs::write(source.path().join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(source.path().join("src/lib.rs"), "fn main() {}").unwrap();
+
+        copy_dir(source.path(), target.path()).await.unwrap();
+
+        let actual = (
+            std::fs::read_to_string(target.path().join("Cargo.toml")).unwrap(),
+            std::fs::read_to_string(target.path().join("src/lib.rs")).unwrap(),
+        );
+        let expected = ("[package]".to_string(), "fn main() {}".to_string());
+        assert_eq!(actual, expected);
+    }
+}
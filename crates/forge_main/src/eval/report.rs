@@ -0,0 +1,262 @@
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Local};
+use forge_domain::Usage;
+use serde::Serialize;
+
+/// Outcome of a single task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, strum_macros::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskStatus {
+    /// The agent finished and every check passed
+    Passed,
+    /// The agent finished but at least one check failed
+    Failed,
+    /// The agent did not finish within the suite's timeout
+    Timeout,
+    /// The agent stopped with an error or hit a limit
+    Error,
+}
+
+/// Result of a single check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+/// Result of running one task with one model.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskResult {
+    pub task: String,
+    pub model: String,
+    pub status: TaskStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub checks: Vec<CheckResult>,
+    pub duration_ms: u64,
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    pub cached_tokens: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    pub tool_calls: usize,
+}
+
+impl TaskResult {
+    pub fn new(task: impl Into<String>, model: impl Into<String>, status: TaskStatus) -> Self {
+        Self {
+            task: task.into(),
+            model: model.into(),
+            status,
+            error: None,
+            checks: vec![],
+            duration_ms: 0,
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            cached_tokens: 0,
+            cost: None,
+            tool_calls: 0,
+        }
+    }
+
+    pub fn usage(mut self, usage: &Usage) -> Self {
+        self.prompt_tokens = *usage.prompt_tokens;
+        self.completion_tokens = *usage.completion_tokens;
+        self.cached_tokens = *usage.cached_tokens;
+        self.cost = usage.cost;
+        self
+    }
+
+    pub fn tokens(&self) -> usize {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// Aggregated results of one model across the suite.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelSummary {
+    pub model: String,
+    pub tasks: usize,
+    pub passed: usize,
+    pub pass_rate: f64,
+    pub tokens: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    pub average_duration_ms: u64,
+}
+
+/// Results of a suite run, written as `report.json` and `report.md`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub suite: String,
+    pub started_at: DateTime<Local>,
+    pub summary: Vec<ModelSummary>,
+    pub results: Vec<TaskResult>,
+}
+
+impl Report {
+    pub fn new(
+        suite: impl Into<String>,
+        started_at: DateTime<Local>,
+        results: Vec<TaskResult>,
+    ) -> Self {
+        let mut summary: Vec<ModelSummary> = vec![];
+        for result in &results {
+            let index = match summary.iter().position(|item| item.model == result.model) {
+                Some(index) => index,
+                None => {
+                    summary.push(ModelSummary {
+                        model: result.model.clone(),
+                        tasks: 0,
+                        passed: 0,
+                        pass_rate: 0.0,
+                        tokens: 0,
+                        cost: None,
+                        average_duration_ms: 0,
+                    });
+                    summary.len() - 1
+                }
+            };
+            let item = &mut summary[index];
+            item.tasks += 1;
+            item.passed += usize::from(result.status == TaskStatus::Passed);
+            item.tokens += result.tokens();
+            item.cost = match (item.cost, result.cost) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            };
+            item.average_duration_ms += result.duration_ms;
+        }
+        for item in &mut summary {
+            item.pass_rate = item.passed as f64 / item.tasks as f64;
+            item.average_duration_ms /= item.tasks as u64;
+        }
+
+        Self { suite: suite.into(), started_at, summary, results }
+    }
+
+    /// Returns true when every task passed
+    pub fn passed(&self) -> bool {
+        self.results
+            .iter()
+            .all(|result| result.status == TaskStatus::Passed)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Eval: {}\n", self.suite);
+        let _ = writeln!(
+            out,
+            "Started at {}\n",
+            self.started_at.format("%Y-%m-%d %H:%M:%S")
+        );
+
+        let _ = writeln!(out, "## Summary\n");
+        let _ = writeln!(
+            out,
+            "| Model | Passed | Pass rate | Tokens | Cost | Avg latency |"
+        );
+        let _ = writeln!(out, "|---|---|---|---|---|---|");
+        for item in &self.summary {
+            let _ = writeln!(
+                out,
+                "| {} | {}/{} | {:.0}% | {} | {} | {:.1}s |",
+                item.model,
+                item.passed,
+                item.tasks,
+                item.pass_rate * 100.0,
+                item.tokens,
+                format_cost(item.cost),
+                item.average_duration_ms as f64 / 1000.0
+            );
+        }
+
+        let _ = writeln!(out, "\n## Tasks\n");
+        let _ = writeln!(
+            out,
+            "| Task | Model | Status | Tokens | Cost | Latency | Failures |"
+        );
+        let _ = writeln!(out, "|---|---|---|---|---|---|---|");
+        for result in &self.results {
+            let failures = result
+                .checks
+                .iter()
+                .filter(|check| !check.passed)
+                .map(|check| format!("{}: {}", check.name, check.message))
+                .chain(result.error.clone())
+                .collect::<Vec<_>>()
+                .join("<br>")
+                .replace('|', "\\|");
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {:.1}s | {} |",
+                result.task,
+                result.model,
+                result.status,
+                result.tokens(),
+                format_cost(result.cost),
+                result.duration_ms as f64 / 1000.0,
+                failures
+            );
+        }
+        out
+    }
+}
+
+fn format_cost(cost: Option<f64>) -> String {
+    cost.map(|cost| format!("${cost:.4}"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn fixture_result(task: &str, model: &str, status: TaskStatus, cost: f64) -> TaskResult {
+        let mut result = TaskResult::new(task, model, status);
+        result.prompt_tokens = 1000;
+        result.completion_tokens = 200;
+        result.cost = Some(cost);
+        result.duration_ms = 2000;
+        result
+    }
+
+    #[test]
+    fn test_report_summarizes_each_model() {
+        let fixture = vec![
+            fixture_result("rename", "a/x", TaskStatus::Passed, 0.25),
+            fixture_result("rename", "b/y", TaskStatus::Failed, 0.5),
+            fixture_result("extract", "a/x", TaskStatus::Timeout, 0.125),
+        ];
+        let started_at = Local.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let actual = Report::new("refactor", started_at, fixture).summary;
+        let expected = vec![
+            ModelSummary {
+                model: "a/x".to_string(),
+                tasks: 2,
+                passed: 1,
+                pass_rate: 0.5,
+                tokens: 2400,
+                cost: Some(0.375),
+                average_duration_ms: 2000,
+            },
+            ModelSummary {
+                model: "b/y".to_string(),
+                tasks: 1,
+                passed: 0,
+                pass_rate: 0.0,
+                tokens: 1200,
+                cost: Some(0.5),
+                average_duration_ms: 2000,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+}
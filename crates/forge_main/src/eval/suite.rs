@@ -0,0 +1,325 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use forge_domain::{AgentId, ModelId, ProviderId, ToolCallFull, ToolName};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A set of tasks run against one or more models, loaded from YAML.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suite {
+    /// Name shown in reports, defaults to the file stem of the suite
+    #[serde(default)]
+    pub name: String,
+
+    /// Agent that runs every task, defaults to the configured active agent
+    #[serde(default)]
+    pub agent: Option<AgentId>,
+
+    /// Models to compare, the configured default model when empty
+    #[serde(default)]
+    pub models: Vec<EvalModel>,
+
+    /// Number of tasks to run at once
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+
+    /// Maximum duration of a single task in seconds
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+
+    pub tasks: Vec<EvalTask>,
+}
+
+fn default_concurrency() -> usize {
+    1
+}
+
+fn default_timeout() -> u64 {
+    600
+}
+
+fn default_check_timeout() -> u64 {
+    300
+}
+
+impl Suite {
+    /// Reads a suite from a YAML file. Fixture directories are resolved
+    /// relative to the file.
+    pub async fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = forge_fs::ForgeFS::read_utf8(path).await?;
+        let mut suite: Suite = serde_yml::from_str(&content)
+            .with_context(|| format!("Invalid eval suite: {}", path.display()))?;
+
+        if suite.name.is_empty() {
+            suite.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+        let base = path.parent().unwrap_or(Path::new("."));
+        for task in &mut suite.tasks {
+            if let Some(fixture) = task.fixture.take() {
+                task.fixture = Some(base.join(fixture));
+            }
+        }
+        suite.validate()?;
+        Ok(suite)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.tasks.is_empty(), "Eval suite has no tasks");
+        anyhow::ensure!(self.concurrency > 0, "Eval concurrency must be at least 1");
+        for task in &self.tasks {
+            for check in &task.checks {
+                check
+                    .validate()
+                    .with_context(|| format!("Invalid check in task {}", task.name))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A model to run the suite with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvalModel {
+    pub provider: ProviderId,
+    pub model: ModelId,
+}
+
+impl fmt::Display for EvalModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.provider, self.model)
+    }
+}
+
+/// A prompt given to the agent in a fresh copy of a fixture directory.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvalTask {
+    pub name: String,
+    pub prompt: String,
+
+    /// Directory copied into the task's working directory before it runs
+    #[serde(default)]
+    pub fixture: Option<PathBuf>,
+
+    /// Checks that must all pass once the agent has finished
+    #[serde(default)]
+    pub checks: Vec<Check>,
+}
+
+/// A verification run after the agent has finished a task.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Check {
+    Command(CommandCheck),
+    Tool(ToolCheck),
+    Output(OutputCheck),
+}
+
+/// Runs a shell command in the task directory and compares its exit code.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommandCheck {
+    pub command: String,
+    #[serde(default)]
+    pub exit_code: i32,
+
+    /// Maximum duration of the command in seconds
+    #[serde(default = "default_check_timeout")]
+    pub timeout: u64,
+}
+
+/// Counts the agent's calls to a tool, optionally only those whose arguments
+/// match a pattern. Without bounds the tool must be called at least once.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolCheck {
+    pub tool: ToolName,
+    #[serde(default)]
+    pub arguments: Option<String>,
+    #[serde(default)]
+    pub min: Option<usize>,
+    #[serde(default)]
+    pub max: Option<usize>,
+}
+
+/// Matches the text the agent wrote against a pattern.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputCheck {
+    pub output: String,
+}
+
+impl Check {
+    fn validate(&self) -> anyhow::Result<()> {
+        match self {
+            Check::Command(_) => {}
+            Check::Tool(check) => {
+                if let Some(pattern) = &check.arguments {
+                    Regex::new(pattern)?;
+                }
+            }
+            Check::Output(check) => {
+                Regex::new(&check.output)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Short description used in reports
+    pub fn name(&self) -> String {
+        match self {
+            Check::Command(check) => format!("`{}` exits with {}", check.command, check.exit_code),
+            Check::Tool(check) => {
+                let calls = match &check.arguments {
+                    Some(pattern) => format!("`{}` calls matching /{pattern}/", check.tool),
+                    None => format!("`{}` calls", check.tool),
+                };
+                match (check.min, check.max) {
+                    (Some(min), Some(max)) => format!("{min} to {max} {calls}"),
+                    (None, Some(max)) => format!("at most {max} {calls}"),
+                    (min, None) => format!("at least {} {calls}", min.unwrap_or(1)),
+                }
+            }
+            Check::Output(check) => format!("output matches /{}/", check.output),
+        }
+    }
+}
+
+impl ToolCheck {
+    /// Checks the number of matching calls in `trace` against the bounds,
+    /// returning the count on failure.
+    pub fn verify(&self, trace: &[ToolCallFull]) -> Result<usize, usize> {
+        let pattern = self
+            .arguments
+            .as_deref()
+            .and_then(|pattern| Regex::new(pattern).ok());
+        let count = trace
+            .iter()
+            .filter(|call| call.name == self.tool)
+            .filter(|call| {
+                pattern
+                    .as_ref()
+                    .is_none_or(|pattern| pattern.is_match(&call.arguments.clone().into_string()))
+            })
+            .count();
+
+        let min = self.min.unwrap_or(if self.max.is_some() { 0 } else { 1 });
+        if count >= min && self.max.is_none_or(|max| count <= max) {
+            Ok(count)
+        } else {
+            Err(count)
+        }
+    }
+}
+
+impl OutputCheck {
+    pub fn verify(&self, output: &str) -> bool {
+        Regex::new(&self.output).is_ok_and(|pattern| pattern.is_match(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::ToolCallArguments;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_suite() {
+        let fixture = r#"
+models:
+  - provider: open_router
+    model: openai/gpt-5.2
+concurrency: 4
+tasks:
+  - name: rename
+    prompt: Rename foo to bar
+    fixture: fixtures/rename
+    checks:
+      - command: cargo test
+      - tool: patch
+        min: 2
+      - tool: shell
+        arguments: git apply
+        max: 0
+      - output: Done
+"#;
+        let actual: Suite = serde_yml::from_str(fixture).unwrap();
+        let expected = Suite {
+            name: String::new(),
+            agent: None,
+            models: vec![EvalModel {
+                provider: ProviderId::OPEN_ROUTER,
+                model: ModelId::new("openai/gpt-5.2"),
+            }],
+            concurrency: 4,
+            timeout: 600,
+            tasks: vec![EvalTask {
+                name: "rename".to_string(),
+                prompt: "Rename foo to bar".to_string(),
+                fixture: Some(PathBuf::from("fixtures/rename")),
+                checks: vec![
+                    Check::Command(CommandCheck {
+                        command: "cargo test".to_string(),
+                        exit_code: 0,
+                        timeout: 300,
+                    }),
+                    Check::Tool(ToolCheck {
+                        tool: ToolName::new("patch"),
+                        arguments: None,
+                        min: Some(2),
+                        max: None,
+                    }),
+                    Check::Tool(ToolCheck {
+                        tool: ToolName::new("shell"),
+                        arguments: Some("git apply".to_string()),
+                        min: None,
+                        max: Some(0),
+                    }),
+                    Check::Output(OutputCheck { output: "Done".to_string() }),
+                ],
+            }],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_tool_check_counts_matching_calls() {
+        let call = |name: &str, command: &str| {
+            ToolCallFull::new(name).arguments(ToolCallArguments::from_json(
+                &json!({"command": command}).to_string(),
+            ))
+        };
+        let fixture = vec![
+            call("shell", "git apply fix.diff"),
+            call("shell", "cargo test"),
+            call("patch", ""),
+        ];
+
+        let no_git_apply = ToolCheck {
+            tool: ToolName::new("shell"),
+            arguments: Some("git apply".to_string()),
+            min: None,
+            max: Some(0),
+        };
+        let any_patch = ToolCheck {
+            tool: ToolName::new("patch"),
+            arguments: None,
+            min: None,
+            max: None,
+        };
+
+        let actual = (no_git_apply.verify(&fixture), any_patch.verify(&fixture));
+        let expected = (Err(1), Ok(1));
+        assert_eq!(actual, expected);
+    }
+}
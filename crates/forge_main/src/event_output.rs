@@ -0,0 +1,222 @@
+use chrono::{DateTime, Utc};
+use forge_api::{ChatResponse, ConversationId};
+use forge_domain::Usage;
+use serde::Serialize;
+
+use crate::cli::OutputFormat;
+
+/// Final status of a non-interactive run, mapped to the process exit code.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitStatus {
+    /// The task ran to completion.
+    #[default]
+    Success,
+    /// The run failed with an error.
+    Error,
+    /// The agent stopped after hitting a request or tool-failure limit.
+    LimitReached,
+    /// The user interrupted the run with Ctrl+C.
+    Interrupted,
+}
+
+impl ExitStatus {
+    /// Process exit code for this status.
+    pub fn code(&self) -> u8 {
+        match self {
+            ExitStatus::Success => 0,
+            ExitStatus::Error => 1,
+            ExitStatus::LimitReached => 2,
+            ExitStatus::Interrupted => 130,
+        }
+    }
+}
+
+impl From<ExitStatus> for std::process::ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        std::process::ExitCode::from(status.code())
+    }
+}
+
+/// A single chat event as written to the structured output.
+#[derive(Serialize)]
+struct EventRecord<'a> {
+    timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    event: &'a ChatResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage: Option<Usage>,
+}
+
+/// Summary record written once the run is over.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RunResult {
+    pub status: ExitStatus,
+    pub exit_code: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<ConversationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RunResult {
+    pub fn new(status: ExitStatus) -> Self {
+        Self { status, exit_code: status.code(), ..Default::default() }
+    }
+}
+
+/// Serializes chat events for machine consumption.
+///
+/// In `jsonl` mode every event is returned as a line to print immediately,
+/// followed by a final `result` line. In `json` mode events are buffered and
+/// emitted as a single document once the run finishes.
+pub struct EventWriter {
+    format: OutputFormat,
+    events: Vec<serde_json::Value>,
+}
+
+impl EventWriter {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format, events: Vec::new() }
+    }
+
+    /// Records an event and returns the line to print right away, if the
+    /// format streams events.
+    pub fn event(
+        &mut self,
+        event: &ChatResponse,
+        usage: Option<Usage>,
+    ) -> anyhow::Result<Option<String>> {
+        let record = EventRecord { timestamp: Utc::now(), event, usage };
+        match self.format {
+            OutputFormat::Jsonl => Ok(Some(serde_json::to_string(&record)?)),
+            _ => {
+                self.events.push(serde_json::to_value(&record)?);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Consumes the writer and returns the trailing output for the run.
+    pub fn finish(self, result: RunResult) -> anyhow::Result<String> {
+        let mut result = serde_json::to_value(&result)?;
+        if let Some(object) = result.as_object_mut() {
+            object.insert("timestamp".to_string(), serde_json::to_value(Utc::now())?);
+        }
+
+        match self.format {
+            OutputFormat::Jsonl => {
+                let mut record = serde_json::Map::new();
+                record.insert("type".to_string(), "result".into());
+                if let serde_json::Value::Object(fields) = result {
+                    record.extend(fields);
+                }
+                Ok(serde_json::to_string(&record)?)
+            }
+            _ => Ok(serde_json::to_string_pretty(&serde_json::json!({
+                "events": self.events,
+                "result": result,
+            }))?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::{ChatResponseContent, TokenCount};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn without_timestamp(mut value: serde_json::Value) -> serde_json::Value {
+        if let Some(object) = value.as_object_mut() {
+            object.remove("timestamp");
+        }
+        value
+    }
+
+    #[test]
+    fn test_exit_status_codes_are_distinct() {
+        let actual = [
+            ExitStatus::Success.code(),
+            ExitStatus::Error.code(),
+            ExitStatus::LimitReached.code(),
+            ExitStatus::Interrupted.code(),
+        ];
+        let expected = [0, 1, 2, 130];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_jsonl_streams_each_event() {
+        let mut fixture = EventWriter::new(OutputFormat::Jsonl);
+        let event =
+            ChatResponse::TaskMessage { content: ChatResponseContent::Markdown("Hi".to_string()) };
+
+        let line = fixture.event(&event, None).unwrap().unwrap();
+
+        let actual = without_timestamp(serde_json::from_str(&line).unwrap());
+        let expected = serde_json::json!({
+            "type": "task_message",
+            "content": {"format": "markdown", "value": "Hi"}
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_jsonl_event_includes_usage() {
+        let mut fixture = EventWriter::new(OutputFormat::Jsonl);
+        let usage = Usage { total_tokens: TokenCount::Actual(42), ..Default::default() };
+
+        let line = fixture
+            .event(&ChatResponse::TaskComplete, Some(usage))
+            .unwrap()
+            .unwrap();
+
+        let actual: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(actual["type"], "task_complete");
+        assert_eq!(
+            actual["usage"]["total_tokens"],
+            serde_json::json!({"actual": 42})
+        );
+    }
+
+    #[test]
+    fn test_jsonl_finish_writes_result_line() {
+        let fixture = EventWriter::new(OutputFormat::Jsonl);
+        let line = fixture
+            .finish(RunResult::new(ExitStatus::LimitReached))
+            .unwrap();
+
+        let actual = without_timestamp(serde_json::from_str(&line).unwrap());
+        let expected = serde_json::json!({
+            "type": "result",
+            "status": "limit_reached",
+            "exit_code": 2
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_json_buffers_events_until_finish() {
+        let mut fixture = EventWriter::new(OutputFormat::Json);
+
+        let streamed = fixture.event(&ChatResponse::TaskComplete, None).unwrap();
+        assert_eq!(streamed, None);
+
+        let mut result = RunResult::new(ExitStatus::Error);
+        result.error = Some("boom".to_string());
+        let output = fixture.finish(result).unwrap();
+
+        let mut actual: serde_json::Value = serde_json::from_str(&output).unwrap();
+        actual["events"][0] = without_timestamp(actual["events"][0].take());
+        actual["result"] = without_timestamp(actual["result"].take());
+        let expected = serde_json::json!({
+            "events": [{"type": "task_complete"}],
+            "result": {"status": "error", "exit_code": 1, "error": "boom"}
+        });
+        assert_eq!(actual, expected);
+    }
+}
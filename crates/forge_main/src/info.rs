@@ -994,6 +994,7 @@ mod tests {
             context: None,
             metrics,
             metadata: forge_domain::MetaData::new(Utc::now()),
+            todos: Vec::new(),
         };
 
         let actual = super::Info::from(&fixture);
@@ -1021,6 +1022,7 @@ mod tests {
             context: None,
             metrics,
             metadata: forge_domain::MetaData::new(Utc::now()),
+            todos: Vec::new(),
         };
 
         let actual = super::Info::from(&fixture);
@@ -1061,6 +1063,7 @@ mod tests {
             context: Some(context),
             metrics,
             metadata: forge_domain::MetaData::new(Utc::now()),
+            todos: Vec::new(),
         };
 
         let actual = super::Info::from(&fixture);
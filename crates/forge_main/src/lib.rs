@@ -5,12 +5,18 @@ mod conversation_selector;
 mod display_constants;
 mod editor;
 mod env;
+mod eval;
+mod event_output;
 mod info;
 mod input;
+mod mcp_server;
 mod model;
+mod pipeline;
 mod porcelain;
 mod prompt;
+mod proxy;
 mod sandbox;
+mod schedule;
 mod state;
 mod sync_display;
 mod title_display;
@@ -23,9 +29,11 @@ mod zsh_plugin;
 
 mod update;
 
-pub use cli::{Cli, TopLevelCommand};
+pub use cli::{Cli, EvalCommandGroup, OutputFormat, TopLevelCommand};
+pub use eval::{EvalModel, run_eval};
+pub use event_output::ExitStatus;
 use lazy_static::lazy_static;
-pub use sandbox::Sandbox;
+pub use sandbox::{Sandbox, SandboxStatus, Sandboxes};
 pub use title_display::*;
 pub use ui::UI;
 
@@ -1,15 +1,17 @@
 use std::io::Read;
 use std::panic;
 use std::path::PathBuf;
+use std::process::ExitCode;
 
 use anyhow::Result;
+use chrono::Local;
 use clap::Parser;
 use forge_api::ForgeAPI;
 use forge_domain::TitleFormat;
-use forge_main::{Cli, Sandbox, TitleDisplayExt, UI, tracker};
+use forge_main::{Cli, Sandbox, TitleDisplayExt, TopLevelCommand, UI, run_eval, tracker};
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
     // Set up panic hook for better error display
     panic::set_hook(Box::new(|panic_info| {
         let message = if let Some(s) = panic_info.payload().downcast_ref::<&str>() {
@@ -28,8 +30,8 @@ async fn main() -> Result<()> {
     // Initialize and run the UI
     let mut cli = Cli::parse();
 
-    // Check if there's piped input
-    if !atty::is(atty::Stream::Stdin) {
+    // Check if there's piped input, unless stdin carries the MCP protocol
+    if !cli.is_stdio_server() && !atty::is(atty::Stream::Stdin) {
         let mut stdin_content = String::new();
         std::io::stdin().read_to_string(&mut stdin_content)?;
         let trimmed_content = stdin_content.trim();
@@ -38,6 +40,29 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Every eval task runs in its own directory with its own model
+    let restricted = cli.restricted;
+    if let Some(TopLevelCommand::Eval(group)) = cli.subcommands.clone() {
+        let status = run_eval(group, move |cwd, model| match model {
+            Some(model) => ForgeAPI::init_with_model(restricted, cwd, model.provider, model.model),
+            None => ForgeAPI::init(restricted, cwd),
+        })
+        .await?;
+        return Ok(status.into());
+    }
+
+    // `--sandbox` without a name runs in a fresh sandbox named after the prompt,
+    // which is removed again if the run changes nothing
+    let fresh = cli.sandbox.as_deref() == Some("");
+    if fresh {
+        let prompt = cli.prompt.as_deref().or(cli.piped_input.as_deref());
+        cli.sandbox = Some(Sandbox::generate_name(prompt, Local::now()));
+    }
+    let sandbox = cli
+        .sandbox
+        .clone()
+        .filter(|_| cli.prompt.is_some() || cli.piped_input.is_some());
+
     // Handle worktree creation if specified
     let cwd: PathBuf = match (&cli.sandbox, &cli.directory) {
         (Some(sandbox), Some(cli)) => {
@@ -54,16 +79,21 @@ async fn main() -> Result<()> {
     };
 
     // Initialize the ForgeAPI with the restricted mode if specified
-    let restricted = cli.restricted;
     let mut ui = UI::init(cli, move || ForgeAPI::init(restricted, cwd.clone()))?;
-    ui.run().await;
+    let status = ui.run().await;
+
+    // Summarize what a one-shot run changed in its sandbox
+    if let Some(sandbox) = sandbox
+        && let Err(error) = Sandbox::new(&sandbox).finish(fresh)
+    {
+        println!("{}", TitleFormat::error(format!("{error:#}")).display());
+    }
 
-    Ok(())
+    Ok(status.into())
 }
 
 #[cfg(test)]
 mod tests {
-    use forge_main::TopLevelCommand;
     use pretty_assertions::assert_eq;
 
     use super::*;
@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use forge_api::API;
+use forge_domain::{ToolCallArguments, ToolCallFull, ToolDefinition, ToolOutput, ToolValue};
+use forge_tracker::VERSION;
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, Content, Implementation, JsonObject, ListToolsResult,
+    PaginatedRequestParam, ServerCapabilities, ServerInfo, Tool,
+};
+use rmcp::service::RequestContext;
+use rmcp::{ErrorData as McpError, RoleServer, ServerHandler, ServiceExt};
+
+/// Exposes Forge's built-in tools to external MCP clients over stdio.
+///
+/// Stdout carries the protocol, so nothing else may be written to it while
+/// the server is running.
+pub struct ForgeMcpServer<A> {
+    api: Arc<A>,
+}
+
+impl<A: API + 'static> ForgeMcpServer<A> {
+    pub fn new(api: Arc<A>) -> Self {
+        Self { api }
+    }
+
+    /// Serves requests on stdin/stdout until the client disconnects.
+    pub async fn serve_stdio(self) -> anyhow::Result<()> {
+        let service = self.serve(rmcp::transport::stdio()).await?;
+        service.waiting().await?;
+        Ok(())
+    }
+}
+
+impl<A: API + 'static> ServerHandler for ForgeMcpServer<A> {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: Implementation {
+                name: "forge".to_string(),
+                version: VERSION.to_string(),
+                icons: None,
+                title: Some("Forge".to_string()),
+                website_url: None,
+            },
+            instructions: Some(
+                "Built-in Forge tools for reading, searching, editing files and running shell commands in the current workspace."
+                    .to_string(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        let tools = self
+            .api
+            .get_server_tools()
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(ListToolsResult::with_all_items(
+            tools.into_iter().map(to_mcp_tool).collect(),
+        ))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = self
+            .api
+            .call_server_tool(to_tool_call(request))
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(to_mcp_result(result.output))
+    }
+}
+
+fn to_mcp_tool(definition: ToolDefinition) -> Tool {
+    let schema = match serde_json::to_value(&definition.input_schema) {
+        Ok(serde_json::Value::Object(schema)) => schema,
+        _ => JsonObject::new(),
+    };
+
+    Tool::new(
+        definition.name.to_string(),
+        definition.description,
+        Arc::new(schema),
+    )
+}
+
+fn to_tool_call(request: CallToolRequestParam) -> ToolCallFull {
+    let arguments = request.arguments.unwrap_or_default();
+    ToolCallFull::new(request.name.to_string()).arguments(ToolCallArguments::from(
+        serde_json::Value::Object(arguments),
+    ))
+}
+
+fn to_mcp_result(output: ToolOutput) -> CallToolResult {
+    let content = output
+        .values
+        .into_iter()
+        .filter_map(|value| match value {
+            ToolValue::Text(text) => Some(Content::text(text)),
+            ToolValue::AI { value, .. } => Some(Content::text(value)),
+            ToolValue::Image(image) => Some(Content::image(image.data(), image.mime_type())),
+            ToolValue::Empty => None,
+        })
+        .collect();
+
+    if output.is_error {
+        CallToolResult::error(content)
+    } else {
+        CallToolResult::success(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::{Image, ToolCatalog, ToolName};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_to_mcp_tool_keeps_name_and_schema() {
+        let fixture = ToolCatalog::Read(Default::default()).definition();
+
+        let actual = to_mcp_tool(fixture.clone());
+
+        assert_eq!(actual.name, "read");
+        assert_eq!(
+            actual.description.as_deref(),
+            Some(fixture.description.as_str())
+        );
+        assert_eq!(
+            serde_json::Value::Object(actual.input_schema.as_ref().clone()),
+            serde_json::to_value(&fixture.input_schema).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_to_tool_call_parses_arguments() {
+        let mut arguments = JsonObject::new();
+        arguments.insert("path".to_string(), "/tmp/a.txt".into());
+        let fixture = CallToolRequestParam { name: "read".into(), arguments: Some(arguments) };
+
+        let actual = to_tool_call(fixture);
+
+        let expected = ToolCallFull::new(ToolName::new("read")).arguments(ToolCallArguments::from(
+            serde_json::json!({"path": "/tmp/a.txt"}),
+        ));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_to_mcp_result_maps_values() {
+        let fixture = ToolOutput::text("hello")
+            .combine(ToolOutput::image(Image::new_base64(
+                "aGk=".to_string(),
+                "image/png",
+            )))
+            .is_error(true);
+
+        let actual = to_mcp_result(fixture);
+
+        let expected = CallToolResult::error(vec![
+            Content::text("hello"),
+            Content::image("aGk=", "image/png"),
+        ]);
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,12 +1,14 @@
 use std::fmt::Display;
 use std::sync::{Arc, Mutex};
 
+use clap::Parser;
 use colored::Colorize;
 use forge_api::{Agent, AnyProvider, Model, ProviderId, Template};
-use forge_domain::UserCommand;
+use forge_domain::{McpPrompt, ServerName, UserCommand};
 use strum::{EnumProperty, IntoEnumIterator};
 use strum_macros::{EnumIter, EnumProperty};
 
+use crate::cli::ContextCommand;
 use crate::display_constants::markers;
 use crate::info::Info;
 
@@ -121,6 +123,13 @@ impl From<&[Model]> for Info {
     }
 }
 
+/// Arguments of the `/context` command
+#[derive(Parser)]
+struct ContextArgs {
+    #[command(subcommand)]
+    command: Option<ContextCommand>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ForgeCommand {
     pub name: String,
@@ -179,6 +188,7 @@ impl ForgeCommandManager {
                 | "conversations"
                 | "list"
                 | "commit"
+                | "context"
         )
     }
 
@@ -188,6 +198,7 @@ impl ForgeCommandManager {
             .filter(|command| !matches!(command, SlashCommand::Custom(_)))
             .filter(|command| !matches!(command, SlashCommand::Shell(_)))
             .filter(|command| !matches!(command, SlashCommand::AgentSwitch(_)))
+            .filter(|command| !matches!(command, SlashCommand::McpPrompt { .. }))
             .map(|command| ForgeCommand {
                 name: command.name().to_string(),
                 description: command.usage().to_string(),
@@ -206,7 +217,10 @@ impl ForgeCommandManager {
         // Add new workflow commands
         let new_commands = commands.into_iter().map(|cmd| {
             let name = cmd.name.clone();
-            let description = format!("⚙ {}", cmd.description);
+            let description = match cmd.usage() {
+                Some(usage) => format!("⚙ {} {usage}", cmd.description),
+                None => format!("⚙ {}", cmd.description),
+            };
             let value = cmd.prompt.clone();
 
             ForgeCommand { name, description, value }
@@ -259,6 +273,51 @@ impl ForgeCommandManager {
         result
     }
 
+    /// Registers prompt templates published by MCP servers as
+    /// `mcp-<server>:<prompt>` commands. Sanitized names never contain `:`,
+    /// and prompts whose sanitized names still collide get a numeric suffix.
+    pub fn register_mcp_prompts<'a>(
+        &self,
+        prompts: impl IntoIterator<Item = (&'a ServerName, &'a Vec<McpPrompt>)>,
+    ) {
+        let mut guard = self.commands.lock().unwrap();
+
+        // Remove existing MCP prompt commands (those with 🔌 prefix in description)
+        guard.retain(|cmd| !cmd.description.starts_with("🔌 "));
+
+        for (server, server_prompts) in prompts {
+            for prompt in server_prompts {
+                let base = format!(
+                    "mcp-{}:{}",
+                    Self::sanitize_agent_id(server.as_str()),
+                    Self::sanitize_agent_id(&prompt.name)
+                );
+                let mut name = base.clone();
+                let mut suffix = 1;
+                while guard.iter().any(|cmd| cmd.name == name) {
+                    suffix += 1;
+                    name = format!("{base}-{suffix}");
+                }
+                let description = format!(
+                    "🔌 {}",
+                    prompt
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| format!("Prompt from {server} MCP server"))
+                );
+
+                guard.push(ForgeCommand {
+                    name,
+                    description,
+                    value: Some(format!("{server}/{}", prompt.name)),
+                });
+            }
+        }
+
+        // Sort commands for consistent completion behavior
+        guard.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
     /// Finds a command by name.
     fn find(&self, command: &str) -> Option<ForgeCommand> {
         self.commands
@@ -363,6 +422,10 @@ impl ForgeCommandManager {
                 Ok(SlashCommand::Commit { max_diff_size })
             }
             "/index" => Ok(SlashCommand::Index),
+            "/context" => {
+                let args = ContextArgs::try_parse_from(std::iter::once(command).chain(parameters))?;
+                Ok(SlashCommand::Context(args.command.unwrap_or_default()))
+            }
             text => {
                 let parts = text.split_ascii_whitespace().collect::<Vec<&str>>();
 
@@ -379,6 +442,26 @@ impl ForgeCommandManager {
                         return Err(anyhow::anyhow!("{command} is not a valid agent command"));
                     }
 
+                    // Check if it's an MCP prompt command (/mcp-*), otherwise it may still be
+                    // a custom command with the same prefix
+                    if command.starts_with("/mcp-")
+                        && let Some((server, prompt)) = self
+                            .find(command.strip_prefix('/').unwrap())
+                            .filter(|found| found.description.starts_with("🔌 "))
+                            .and_then(|found| found.value)
+                            .and_then(|value| {
+                                value
+                                    .split_once('/')
+                                    .map(|(s, p)| (s.to_string(), p.to_string()))
+                            })
+                    {
+                        return Ok(SlashCommand::McpPrompt {
+                            server,
+                            prompt,
+                            arguments: parameters.into_iter().map(|s| s.to_owned()).collect(),
+                        });
+                    }
+
                     // Handle custom workflow commands
                     let command_name = command.strip_prefix('/').unwrap_or(command);
                     if let Some(command) = self.find(command_name) {
@@ -505,6 +588,14 @@ pub enum SlashCommand {
     #[strum(props(usage = "Switch directly to a specific agent"))]
     AgentSwitch(String),
 
+    /// Sends a prompt template published by an MCP server
+    #[strum(props(usage = "Send a prompt published by an MCP server"))]
+    McpPrompt {
+        server: String,
+        prompt: String,
+        arguments: Vec<String>,
+    },
+
     /// Generate and optionally commit changes with AI-generated message
     ///
     /// Examples:
@@ -518,6 +609,17 @@ pub enum SlashCommand {
     /// Index the current workspace for semantic code search
     #[strum(props(usage = "Index the current workspace for semantic search"))]
     Index,
+
+    /// Inspect and edit individual messages of the conversation context
+    ///
+    /// Examples:
+    /// - `/context` - List messages with their token estimates
+    /// - `/context pin 3` - Keep message 3 when compacting
+    /// - `/context summarize 2 9` - Replace messages 2 to 9 with a summary
+    #[strum(props(
+        usage = "List, pin, drop, stub or summarize context messages. Format: /context <list|pin|unpin|drop|stub|summarize>"
+    ))]
+    Context(ContextCommand),
 }
 
 impl SlashCommand {
@@ -549,7 +651,9 @@ impl SlashCommand {
             SlashCommand::Conversations => "conversation",
             SlashCommand::Delete => "delete",
             SlashCommand::AgentSwitch(agent_id) => agent_id,
+            SlashCommand::McpPrompt { prompt, .. } => prompt,
             SlashCommand::Index => "index",
+            SlashCommand::Context(_) => "context",
         }
     }
 
@@ -886,6 +990,172 @@ mod tests {
         assert!(agent_commands.iter().any(|cmd| cmd.name == "agent-another"));
     }
 
+    #[test]
+    fn test_register_mcp_prompts() {
+        let fixture = ForgeCommandManager::default();
+        let server = ServerName::from("Git Hub".to_string());
+        let prompts = vec![
+            McpPrompt::new("review_pr").description("Review a pull request"),
+            McpPrompt::new("triage"),
+        ];
+
+        fixture.register_mcp_prompts([(&server, &prompts)]);
+
+        let actual = fixture
+            .list()
+            .into_iter()
+            .filter(|cmd| cmd.name.starts_with("mcp-"))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            ForgeCommand {
+                name: "mcp-git-hub:review-pr".to_string(),
+                description: "🔌 Review a pull request".to_string(),
+                value: Some("Git Hub/review_pr".to_string()),
+            },
+            ForgeCommand {
+                name: "mcp-git-hub:triage".to_string(),
+                description: "🔌 Prompt from Git Hub MCP server".to_string(),
+                value: Some("Git Hub/triage".to_string()),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_register_mcp_prompts_disambiguates_colliding_names() {
+        let fixture = ForgeCommandManager::default();
+        let github = ServerName::from("github".to_string());
+        let github_prompts = vec![McpPrompt::new("review_pr"), McpPrompt::new("review-pr")];
+        let git = ServerName::from("git".to_string());
+        let git_prompts = vec![McpPrompt::new("hub-review-pr")];
+        let hub = ServerName::from("git-hub".to_string());
+        let hub_prompts = vec![McpPrompt::new("review-pr")];
+
+        fixture.register_mcp_prompts([
+            (&github, &github_prompts),
+            (&git, &git_prompts),
+            (&hub, &hub_prompts),
+        ]);
+
+        let actual = fixture
+            .list()
+            .into_iter()
+            .filter(|cmd| cmd.name.starts_with("mcp-"))
+            .map(|cmd| (cmd.name, cmd.value.unwrap_or_default()))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (
+                "mcp-git-hub:review-pr".to_string(),
+                "git-hub/review-pr".to_string(),
+            ),
+            (
+                "mcp-git:hub-review-pr".to_string(),
+                "git/hub-review-pr".to_string(),
+            ),
+            (
+                "mcp-github:review-pr".to_string(),
+                "github/review_pr".to_string(),
+            ),
+            (
+                "mcp-github:review-pr-2".to_string(),
+                "github/review-pr".to_string(),
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_register_mcp_prompts_keeps_custom_mcp_commands() {
+        let fixture = ForgeCommandManager::default();
+        fixture.register_all(vec![
+            forge_domain::Command::default()
+                .name("mcp-check")
+                .description("Check the MCP setup")
+                .prompt("Check the servers in .mcp.json"),
+        ]);
+        let server = ServerName::from("github".to_string());
+        let prompts = vec![McpPrompt::new("review_pr")];
+        fixture.register_mcp_prompts([(&server, &prompts)]);
+
+        fixture.register_mcp_prompts(std::iter::empty());
+
+        let actual = fixture
+            .list()
+            .into_iter()
+            .filter(|cmd| cmd.name.starts_with("mcp-"))
+            .map(|cmd| cmd.name)
+            .collect::<Vec<_>>();
+        let expected = vec!["mcp-check".to_string()];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_custom_command_with_mcp_prefix() {
+        let fixture = ForgeCommandManager::default();
+        fixture.register_all(vec![
+            forge_domain::Command::default()
+                .name("mcp-check")
+                .description("Check the MCP setup")
+                .prompt("Check the servers in src/mcp.json"),
+        ]);
+
+        let actual = fixture.parse("/mcp-check").unwrap();
+
+        let expected = SlashCommand::Custom(UserCommand::new(
+            "mcp-check".to_string(),
+            Template::new("Check the servers in src/mcp.json"),
+            vec![],
+        ));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_register_all_shows_argument_usage() {
+        let fixture = ForgeCommandManager::default();
+        let commands = vec![
+            forge_domain::Command::default()
+                .name("review-pr")
+                .description("Review a pull request")
+                .prompt("Review PR {{pr}}")
+                .arguments(vec![forge_domain::CommandArgument {
+                    required: true,
+                    ..forge_domain::CommandArgument::new("pr")
+                }]),
+        ];
+
+        fixture.register_all(commands);
+
+        let actual = fixture.find("review-pr").map(|cmd| cmd.description);
+        let expected = Some("⚙ Review a pull request <pr>".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_mcp_prompt_command() {
+        let fixture = ForgeCommandManager::default();
+        let server = ServerName::from("github".to_string());
+        let prompts = vec![McpPrompt::new("review_pr")];
+        fixture.register_mcp_prompts([(&server, &prompts)]);
+
+        let actual = fixture
+            .parse("/mcp-github:review-pr 42 focus=tests")
+            .unwrap();
+
+        let expected = SlashCommand::McpPrompt {
+            server: "github".to_string(),
+            prompt: "review_pr".to_string(),
+            arguments: vec!["42".to_string(), "focus=tests".to_string()],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_unknown_mcp_prompt_command() {
+        let fixture = ForgeCommandManager::default();
+        let actual = fixture.parse("/mcp-missing").is_err();
+        assert_eq!(actual, true);
+    }
+
     #[test]
     fn test_parse_agent_switch_command() {
         use forge_api::Agent;
@@ -1205,6 +1475,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_context_command() {
+        let fixture = ForgeCommandManager::default();
+
+        let actual = ["/context", "/context pin 3", "/context summarize 2 9"]
+            .map(|input| fixture.parse(input).unwrap());
+
+        let expected = [
+            SlashCommand::Context(ContextCommand::List { porcelain: false }),
+            SlashCommand::Context(ContextCommand::Pin { index: 3 }),
+            SlashCommand::Context(ContextCommand::Summarize { start: 2, end: 9 }),
+        ];
+        assert_eq!(actual, expected);
+        assert!(fixture.parse("/context pin").is_err());
+    }
+
     #[test]
     fn test_parse_tool_command() {
         // Setup
@@ -0,0 +1,505 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use forge_api::API;
+use forge_app::TemplateEngine;
+use forge_domain::{
+    AgentId, AgentStage, ChatRequest, ChatResponse, ChatResponseContent, CommandOutput,
+    Conversation, Event, GateStage, InterruptionReason, Stage, StageOutput, TitleFormat,
+};
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use serde_json::{Value, json};
+
+use crate::{ExitStatus, TitleDisplayExt};
+
+/// A stage that ended the pipeline early.
+#[derive(Debug, thiserror::Error)]
+enum Stop {
+    #[error("Gate `{command}` exited with {} instead of {expected}", exit_description(*.actual))]
+    Gate {
+        command: String,
+        expected: i32,
+        actual: Option<i32>,
+    },
+
+    #[error("Agent {agent} stopped early: {reason:?}")]
+    Interrupted {
+        agent: String,
+        reason: InterruptionReason,
+    },
+}
+
+/// Describes how a command exited: its exit code, or a signal when it has none
+fn exit_description(code: Option<i32>) -> String {
+    code.map_or_else(|| "a signal".to_string(), |code| code.to_string())
+}
+
+/// The parts of the API that a pipeline uses
+pub trait PipelineApi: Send + Sync {
+    async fn execute_shell_command(
+        &self,
+        command: &str,
+        working_dir: PathBuf,
+    ) -> anyhow::Result<CommandOutput>;
+    async fn set_active_agent(&self, agent_id: AgentId) -> anyhow::Result<()>;
+    async fn upsert_conversation(&self, conversation: Conversation) -> anyhow::Result<()>;
+    async fn chat(
+        &self,
+        chat: ChatRequest,
+    ) -> anyhow::Result<BoxStream<'static, anyhow::Result<ChatResponse>>>;
+}
+
+impl<A: API> PipelineApi for A {
+    async fn execute_shell_command(
+        &self,
+        command: &str,
+        working_dir: PathBuf,
+    ) -> anyhow::Result<CommandOutput> {
+        API::execute_shell_command(self, command, working_dir).await
+    }
+
+    async fn set_active_agent(&self, agent_id: AgentId) -> anyhow::Result<()> {
+        API::set_active_agent(self, agent_id).await
+    }
+
+    async fn upsert_conversation(&self, conversation: Conversation) -> anyhow::Result<()> {
+        API::upsert_conversation(self, conversation).await
+    }
+
+    async fn chat(
+        &self,
+        chat: ChatRequest,
+    ) -> anyhow::Result<BoxStream<'static, anyhow::Result<ChatResponse>>> {
+        Ok(API::chat(self, chat).await?.boxed())
+    }
+}
+
+/// Runs the `stages` of a workflow. Every agent stage gets an API of its own so
+/// that parallel agents do not share the active agent.
+pub struct Pipeline<A, F> {
+    api: Arc<A>,
+    new_api: Arc<F>,
+    cwd: PathBuf,
+}
+
+impl<A: PipelineApi + 'static, F: Fn() -> A + Send + Sync> Pipeline<A, F> {
+    pub fn new(api: Arc<A>, new_api: Arc<F>, cwd: PathBuf) -> Self {
+        Self { api, new_api, cwd }
+    }
+
+    /// Runs `stages` in order. `prompt` and `event` are available to the
+    /// templates of every stage.
+    pub async fn run(
+        &self,
+        stages: &[Stage],
+        prompt: Option<String>,
+        event: Option<Value>,
+    ) -> anyhow::Result<ExitStatus> {
+        let mut outputs = Vec::new();
+        for (index, stage) in stages.iter().enumerate() {
+            let context = json!({ "prompt": prompt, "event": event, "stages": outputs });
+            let result = self
+                .run_stage(stage, &context)
+                .await
+                .with_context(|| format!("Stage {index} failed"));
+            match result {
+                Ok(output) => outputs.push(output),
+                Err(error) => {
+                    let status = match error.downcast_ref::<Stop>() {
+                        Some(Stop::Interrupted { .. }) => ExitStatus::LimitReached,
+                        _ => ExitStatus::Error,
+                    };
+                    println!("{}", TitleFormat::error(format!("{error:#}")).display());
+                    return Ok(status);
+                }
+            }
+        }
+        Ok(ExitStatus::Success)
+    }
+
+    async fn run_stage(&self, stage: &Stage, context: &Value) -> anyhow::Result<StageOutput> {
+        match stage {
+            Stage::Agent(stage) => self.run_agent(stage, context).await,
+            Stage::Parallel(stage) => {
+                if !self.when(stage.when.as_deref()).await? {
+                    return Ok(StageOutput::skipped());
+                }
+                let outputs = futures::future::try_join_all(
+                    stage
+                        .parallel
+                        .iter()
+                        .map(|stage| self.run_agent(stage, context)),
+                )
+                .await?;
+                Ok(StageOutput::parallel(outputs))
+            }
+            Stage::Gate(stage) => self.run_gate(stage).await,
+        }
+    }
+
+    /// Returns true if the stage has no `when` command or it exits with 0
+    async fn when(&self, command: Option<&str>) -> anyhow::Result<bool> {
+        let Some(command) = command else {
+            return Ok(true);
+        };
+        let output = self
+            .api
+            .execute_shell_command(command, self.cwd.clone())
+            .await?;
+        let run = output.exit_code == Some(0);
+        if !run {
+            println!(
+                "{}",
+                TitleFormat::info("Skipped")
+                    .sub_title(format!(
+                        "`{command}` exited with {}",
+                        exit_description(output.exit_code)
+                    ))
+                    .display()
+            );
+        }
+        Ok(run)
+    }
+
+    async fn run_agent(&self, stage: &AgentStage, context: &Value) -> anyhow::Result<StageOutput> {
+        if !self.when(stage.when.as_deref()).await? {
+            return Ok(StageOutput::skipped());
+        }
+
+        let message = render_message(stage, context)?;
+        let api = (self.new_api)();
+        api.set_active_agent(stage.agent.clone()).await?;
+        let conversation = Conversation::generate();
+        let conversation_id = conversation.id;
+        api.upsert_conversation(conversation).await?;
+        println!(
+            "{}",
+            TitleFormat::action(stage.agent.to_string())
+                .sub_title(conversation_id.into_string())
+                .display()
+        );
+
+        let stream = api
+            .chat(ChatRequest::new(Event::new(message), conversation_id))
+            .await?;
+        let output = final_text(stream, &stage.agent).await?;
+
+        println!(
+            "{}",
+            TitleFormat::info(format!("{} finished", stage.agent)).display()
+        );
+        println!("{output}");
+        Ok(StageOutput::new(output))
+    }
+
+    async fn run_gate(&self, stage: &GateStage) -> anyhow::Result<StageOutput> {
+        let output = self
+            .api
+            .execute_shell_command(&stage.gate, self.cwd.clone())
+            .await?;
+        if output.exit_code != Some(stage.exit_code) {
+            return Err(Stop::Gate {
+                command: stage.gate.clone(),
+                expected: stage.exit_code,
+                actual: output.exit_code,
+            }
+            .into());
+        }
+
+        println!(
+            "{}",
+            TitleFormat::info("Gate passed")
+                .sub_title(&stage.gate)
+                .display()
+        );
+        let mut result = StageOutput::new(format!("{}{}", output.stdout, output.stderr));
+        result.exit_code = output.exit_code;
+        Ok(result)
+    }
+}
+
+/// Drains the responses of an agent and returns the text of its final turn.
+/// The text arrives in chunks, and anything written before the last tool call
+/// belongs to an earlier turn.
+async fn final_text(
+    mut stream: impl Stream<Item = anyhow::Result<ChatResponse>> + Unpin,
+    agent: &AgentId,
+) -> anyhow::Result<String> {
+    let mut output = String::new();
+    while let Some(response) = stream.next().await {
+        match response? {
+            ChatResponse::TaskMessage { content: ChatResponseContent::Title(title) } => {
+                println!("{}", title.display())
+            }
+            ChatResponse::TaskMessage { content: ChatResponseContent::Markdown(text) } => {
+                output.push_str(&text)
+            }
+            ChatResponse::ToolCallStart(_) => output.clear(),
+            ChatResponse::Interrupt { reason } => {
+                return Err(Stop::Interrupted { agent: agent.to_string(), reason }.into());
+            }
+            _ => {}
+        }
+    }
+    Ok(output.trim().to_string())
+}
+
+/// Renders the prompt and input of an agent stage into the message sent to it
+fn render_message(stage: &AgentStage, context: &Value) -> anyhow::Result<String> {
+    let engine = TemplateEngine::default();
+    let message = [&stage.prompt, &stage.input]
+        .into_iter()
+        .flatten()
+        .map(|template| engine.render_template(template.as_str(), context))
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .join("\n\n");
+    anyhow::ensure!(
+        !message.trim().is_empty(),
+        "Agent {} has neither a prompt nor an input",
+        stage.agent
+    );
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use forge_domain::{ParallelStage, ToolCallFull};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn stage(prompt: Option<&str>, input: Option<&str>) -> AgentStage {
+        AgentStage {
+            agent: AgentId::new("forge"),
+            prompt: prompt.map(ToString::to_string),
+            input: input.map(ToString::to_string),
+            when: None,
+        }
+    }
+
+    #[test]
+    fn test_render_message_with_previous_stages() {
+        let fixture = json!({
+            "prompt": "Add login",
+            "event": { "name": "review", "value": "PR 42" },
+            "stages": [
+                StageOutput::new("The plan"),
+                StageOutput::parallel(vec![StageOutput::new("a"), StageOutput::new("b")]),
+            ],
+        });
+
+        let actual = render_message(
+            &stage(
+                Some("Implement {{prompt}} for {{event.value}}"),
+                Some("{{stages.0.output}} / {{stages.1.parallel.1.output}}"),
+            ),
+            &fixture,
+        )
+        .unwrap();
+        let expected = "Implement Add login for PR 42\n\nThe plan / b";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_message_with_missing_stage_fails() {
+        let fixture = json!({ "prompt": null, "event": null, "stages": [] });
+
+        let actual = render_message(&stage(None, Some("{{stages.3.output}}")), &fixture).is_err();
+        let expected = true;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_message_without_prompt_or_input_fails() {
+        let fixture = json!({ "prompt": null, "event": null, "stages": [] });
+
+        let actual = render_message(&stage(None, None), &fixture)
+            .unwrap_err()
+            .to_string();
+        let expected = "Agent forge has neither a prompt nor an input";
+        assert_eq!(actual, expected);
+    }
+
+    fn markdown(text: &str) -> anyhow::Result<ChatResponse> {
+        Ok(ChatResponse::TaskMessage { content: ChatResponseContent::Markdown(text.to_string()) })
+    }
+
+    #[tokio::test]
+    async fn test_final_text_joins_chunks_of_the_last_turn() {
+        let fixture = futures::stream::iter(vec![
+            markdown("Let me read"),
+            markdown(" the code."),
+            Ok(ChatResponse::ToolCallStart(ToolCallFull::new("read"))),
+            markdown("The plan"),
+            markdown(" has two"),
+            markdown(" steps."),
+            Ok(ChatResponse::TaskComplete),
+        ]);
+
+        let actual = final_text(fixture, &AgentId::new("muse")).await.unwrap();
+        let expected = "The plan has two steps.";
+        assert_eq!(actual, expected);
+    }
+
+    /// Records the shell commands it runs and the messages agents receive.
+    /// `exit <code>` commands exit with that code and any other command is
+    /// killed by a signal. Agents answer `<agent> done`, and `slow` answers
+    /// last.
+    struct MockApi {
+        log: Arc<Mutex<Vec<String>>>,
+        agent: Mutex<Option<AgentId>>,
+    }
+
+    impl PipelineApi for MockApi {
+        async fn execute_shell_command(
+            &self,
+            command: &str,
+            _working_dir: PathBuf,
+        ) -> anyhow::Result<CommandOutput> {
+            self.log.lock().unwrap().push(format!("$ {command}"));
+            Ok(CommandOutput {
+                command: command.to_string(),
+                stdout: String::new(),
+                stderr: String::new(),
+                exit_code: command
+                    .strip_prefix("exit ")
+                    .and_then(|code| code.parse().ok()),
+            })
+        }
+
+        async fn set_active_agent(&self, agent_id: AgentId) -> anyhow::Result<()> {
+            *self.agent.lock().unwrap() = Some(agent_id);
+            Ok(())
+        }
+
+        async fn upsert_conversation(&self, _conversation: Conversation) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn chat(
+            &self,
+            chat: ChatRequest,
+        ) -> anyhow::Result<BoxStream<'static, anyhow::Result<ChatResponse>>> {
+            let agent = self.agent.lock().unwrap().clone().unwrap();
+            let message = chat
+                .event
+                .value
+                .as_ref()
+                .and_then(|value| value.as_user_prompt())
+                .map(|prompt| prompt.to_string())
+                .unwrap_or_default();
+            self.log.lock().unwrap().push(format!("{agent}: {message}"));
+            let delay = if agent.as_str() == "slow" { 50 } else { 0 };
+            Ok(futures::stream::once(async move {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                markdown(&format!("{agent} done"))
+            })
+            .boxed())
+        }
+    }
+
+    async fn run(stages: Vec<Stage>) -> (ExitStatus, Vec<String>) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let new_api = {
+            let log = log.clone();
+            move || MockApi { log: log.clone(), agent: Mutex::new(None) }
+        };
+        let pipeline = Pipeline::new(Arc::new(new_api()), Arc::new(new_api), PathBuf::new());
+        let status = pipeline.run(&stages, None, None).await.unwrap();
+        let log = log.lock().unwrap().clone();
+        (status, log)
+    }
+
+    fn agent(name: &str, input: &str, when: Option<&str>) -> AgentStage {
+        AgentStage {
+            agent: AgentId::new(name),
+            prompt: None,
+            input: Some(input.to_string()),
+            when: when.map(ToString::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_at_failed_gate() {
+        let fixture = vec![
+            Stage::Gate(GateStage { gate: "exit 2".to_string(), exit_code: 0 }),
+            Stage::Agent(agent("forge", "Fix it", None)),
+        ];
+
+        let actual = run(fixture).await;
+
+        let expected = (ExitStatus::Error, vec!["$ exit 2".to_string()]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_gate_killed_by_signal_message() {
+        let fixture = Stop::Gate { command: "make test".to_string(), expected: 0, actual: None };
+
+        let actual = fixture.to_string();
+
+        let expected = "Gate `make test` exited with a signal instead of 0";
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_run_skips_stages_whose_when_fails() {
+        let fixture = vec![
+            Stage::Agent(agent("muse", "Plan", Some("exit 1"))),
+            Stage::Parallel(ParallelStage {
+                parallel: vec![agent("sage", "Review", None)],
+                when: Some("exit 0".to_string()),
+            }),
+            Stage::Agent(agent(
+                "forge",
+                "{{stages.0.skipped}} {{stages.1.skipped}}",
+                None,
+            )),
+        ];
+
+        let actual = run(fixture).await;
+
+        let expected = (
+            ExitStatus::Success,
+            vec![
+                "$ exit 1".to_string(),
+                "$ exit 0".to_string(),
+                "sage: Review".to_string(),
+                "forge: true false".to_string(),
+            ],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_run_keeps_parallel_outputs_in_stage_order() {
+        let fixture = vec![
+            Stage::Parallel(ParallelStage {
+                parallel: vec![agent("slow", "One", None), agent("fast", "Two", None)],
+                when: None,
+            }),
+            Stage::Agent(agent(
+                "forge",
+                "{{stages.0.parallel.0.output}}, {{stages.0.parallel.1.output}}",
+                None,
+            )),
+        ];
+
+        let actual = run(fixture).await;
+
+        let expected = (
+            ExitStatus::Success,
+            vec![
+                "slow: One".to_string(),
+                "fast: Two".to_string(),
+                "forge: slow done, fast done".to_string(),
+            ],
+        );
+        assert_eq!(actual, expected);
+    }
+}
@@ -5,7 +5,7 @@ use std::process::Command;
 
 use convert_case::{Case, Casing};
 use derive_setters::Setters;
-use forge_api::{AgentId, ModelId, Usage};
+use forge_api::{AgentId, ModelId, PlanProgress, Usage};
 use forge_tracker::VERSION;
 use nu_ansi_term::{Color, Style};
 use reedline::{Prompt, PromptHistorySearchStatus};
@@ -24,6 +24,8 @@ pub struct ForgePrompt {
     pub usage: Option<Usage>,
     pub agent_id: AgentId,
     pub model: Option<ModelId>,
+    /// Progress of the plan linked to the conversation
+    pub plan: Option<PlanProgress>,
 }
 
 impl Prompt for ForgePrompt {
@@ -89,6 +91,10 @@ impl Prompt for ForgePrompt {
             write!(result, "/{usage}").unwrap();
         }
 
+        if let Some(plan) = self.plan.as_ref() {
+            write!(result, "/plan {plan}").unwrap();
+        }
+
         write!(result, "]").unwrap();
 
         // Apply styling once at the end
@@ -180,6 +186,7 @@ mod tests {
                 usage: None,
                 agent_id: AgentId::default(),
                 model: None,
+                plan: None,
             }
         }
     }
@@ -310,4 +317,13 @@ mod tests {
         assert!(actual.contains(&VERSION.to_string()));
         assert!(actual.contains("30"));
     }
+
+    #[test]
+    fn test_render_prompt_right_with_plan() {
+        let mut prompt = ForgePrompt::default();
+        let _ = prompt.plan(PlanProgress { done: 3, total: 7 });
+
+        let actual = prompt.render_prompt_right();
+        assert!(actual.contains("/plan 3/7]"));
+    }
 }
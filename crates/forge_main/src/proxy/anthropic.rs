@@ -0,0 +1,617 @@
+//! Anthropic messages API, `POST /v1/messages`.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use axum::http::StatusCode;
+use forge_domain::{
+    ChatCompletionMessage, ChatCompletionMessageFull, Context, ContextMessage, FinishReason, Image,
+    ProxyFormat, Temperature, ToolCall, ToolCallArguments, ToolCallFull, ToolCallId, ToolChoice,
+    ToolName, ToolOutput, ToolResult, ToolValue, TopK, TopP, Usage,
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{StreamEncoder, WireFormat, sse, tool_definition};
+
+pub struct Anthropic;
+
+#[derive(Debug, Deserialize)]
+pub struct MessagesRequest {
+    pub model: String,
+    pub max_tokens: usize,
+    pub messages: Vec<Message>,
+    #[serde(default)]
+    pub system: Option<Text>,
+    #[serde(default)]
+    pub stream: bool,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    #[serde(default)]
+    pub top_k: Option<u32>,
+    #[serde(default)]
+    pub tools: Vec<Tool>,
+    #[serde(default)]
+    pub tool_choice: Option<RequestToolChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub role: MessageRole,
+    pub content: Content,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Content {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+/// A string or a list of text blocks, as accepted for the system prompt
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Text {
+    Text(String),
+    Blocks(Vec<TextBlock>),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TextBlock {
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    Image {
+        source: ImageSource,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        #[serde(default)]
+        input: Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        #[serde(default)]
+        content: Option<ToolResultContent>,
+        #[serde(default)]
+        is_error: bool,
+    },
+    /// Thinking and other blocks that are not sent to providers
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ToolResultContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageSource {
+    Base64 {
+        media_type: String,
+        data: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Tool {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub input_schema: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RequestToolChoice {
+    Auto,
+    Any,
+    Tool { name: String },
+    None,
+}
+
+impl Text {
+    fn into_string(self) -> String {
+        match self {
+            Text::Text(text) => text,
+            Text::Blocks(blocks) => blocks
+                .into_iter()
+                .map(|block| block.text)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+impl ImageSource {
+    fn into_image(self) -> anyhow::Result<Image> {
+        match self {
+            ImageSource::Base64 { media_type, data } => Ok(Image::new_base64(data, media_type)),
+            ImageSource::Other => anyhow::bail!("Only base64 image sources are supported"),
+        }
+    }
+}
+
+impl Content {
+    fn into_blocks(self) -> Vec<ContentBlock> {
+        match self {
+            Content::Text(text) => vec![ContentBlock::Text { text }],
+            Content::Blocks(blocks) => blocks,
+        }
+    }
+}
+
+impl TryFrom<MessagesRequest> for Context {
+    type Error = anyhow::Error;
+
+    fn try_from(request: MessagesRequest) -> anyhow::Result<Self> {
+        // Tool results only carry the id of the call, so names are looked up
+        // from the assistant messages that made the calls
+        let mut tool_names = HashMap::new();
+        let mut context = Context::default().max_tokens(request.max_tokens);
+
+        if let Some(system) = request.system {
+            context = context.add_message(ContextMessage::system(system.into_string()));
+        }
+
+        for message in request.messages {
+            let blocks = message.content.into_blocks();
+            match message.role {
+                MessageRole::User => {
+                    for block in blocks {
+                        let message = match block {
+                            ContentBlock::Text { text } => ContextMessage::user(text, None),
+                            ContentBlock::Image { source } => {
+                                ContextMessage::Image(source.into_image()?)
+                            }
+                            ContentBlock::ToolResult { tool_use_id, content, is_error } => {
+                                let name = tool_names.get(&tool_use_id).with_context(|| {
+                                    format!("Unknown tool_use_id: {tool_use_id}")
+                                })?;
+                                let mut result = ToolResult::new(ToolName::new(name))
+                                    .call_id(ToolCallId::new(tool_use_id));
+                                result.output = tool_output(content, is_error)?;
+                                ContextMessage::tool_result(result)
+                            }
+                            ContentBlock::ToolUse { .. } | ContentBlock::Other => continue,
+                        };
+                        context = context.add_message(message);
+                    }
+                }
+                MessageRole::Assistant => {
+                    let mut text = Vec::new();
+                    let mut tool_calls = Vec::new();
+                    for block in blocks {
+                        match block {
+                            ContentBlock::Text { text: part } => text.push(part),
+                            ContentBlock::ToolUse { id, name, input } => {
+                                tool_names.insert(id.clone(), name.clone());
+                                tool_calls.push(
+                                    ToolCallFull::new(ToolName::new(name))
+                                        .call_id(ToolCallId::new(id))
+                                        .arguments(ToolCallArguments::from_json(
+                                            &input.to_string(),
+                                        )),
+                                );
+                            }
+                            _ => {}
+                        }
+                    }
+                    context = context.add_message(ContextMessage::assistant(
+                        text.join("\n"),
+                        None,
+                        Some(tool_calls),
+                    ));
+                }
+            }
+        }
+
+        for tool in request.tools {
+            context = context.add_tool(tool_definition(
+                tool.name,
+                tool.description,
+                tool.input_schema,
+            )?);
+        }
+        if let Some(choice) = request.tool_choice {
+            context = context.tool_choice(match choice {
+                RequestToolChoice::Auto => ToolChoice::Auto,
+                RequestToolChoice::Any => ToolChoice::Required,
+                RequestToolChoice::Tool { name } => ToolChoice::Call(ToolName::new(name)),
+                RequestToolChoice::None => ToolChoice::None,
+            });
+        }
+        if let Some(temperature) = request.temperature {
+            context =
+                context.temperature(Temperature::new(temperature).map_err(anyhow::Error::msg)?);
+        }
+        if let Some(top_p) = request.top_p {
+            context = context.top_p(TopP::new(top_p).map_err(anyhow::Error::msg)?);
+        }
+        if let Some(top_k) = request.top_k {
+            context = context.top_k(TopK::new(top_k).map_err(anyhow::Error::msg)?);
+        }
+        Ok(context)
+    }
+}
+
+fn tool_output(content: Option<ToolResultContent>, is_error: bool) -> anyhow::Result<ToolOutput> {
+    let values = match content {
+        None => vec![],
+        Some(ToolResultContent::Text(text)) => vec![ToolValue::text(text)],
+        Some(ToolResultContent::Blocks(blocks)) => blocks
+            .into_iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text } => Some(Ok(ToolValue::text(text))),
+                ContentBlock::Image { source } => Some(source.into_image().map(ToolValue::image)),
+                _ => None,
+            })
+            .collect::<anyhow::Result<_>>()?,
+    };
+    Ok(ToolOutput { is_error, values })
+}
+
+fn stop_reason(reason: Option<&FinishReason>, has_tool_calls: bool) -> &'static str {
+    match reason {
+        Some(FinishReason::Length) => "max_tokens",
+        Some(FinishReason::ToolCalls) => "tool_use",
+        _ if has_tool_calls => "tool_use",
+        _ => "end_turn",
+    }
+}
+
+fn usage(usage: &Usage) -> Value {
+    json!({
+        "input_tokens": *usage.prompt_tokens,
+        "output_tokens": *usage.completion_tokens,
+        "cache_read_input_tokens": *usage.cached_tokens,
+    })
+}
+
+fn message_id() -> String {
+    format!("msg_{}", uuid::Uuid::new_v4().simple())
+}
+
+impl WireFormat for Anthropic {
+    const FORMAT: ProxyFormat = ProxyFormat::Anthropic;
+    type Request = MessagesRequest;
+    type Encoder = Encoder;
+
+    fn model(request: &Self::Request) -> &str {
+        &request.model
+    }
+
+    fn stream(request: &Self::Request) -> bool {
+        request.stream
+    }
+
+    fn context(request: Self::Request) -> anyhow::Result<Context> {
+        Context::try_from(request)
+    }
+
+    fn response(model: &str, message: ChatCompletionMessageFull) -> Value {
+        let mut content = Vec::new();
+        if !message.content.is_empty() {
+            content.push(json!({"type": "text", "text": message.content}));
+        }
+        for call in &message.tool_calls {
+            content.push(json!({
+                "type": "tool_use",
+                "id": call.call_id.as_ref().map(ToolCallId::as_str),
+                "name": call.name.as_str(),
+                "input": call.arguments.parse().unwrap_or_else(|_| json!({})),
+            }));
+        }
+
+        json!({
+            "id": message_id(),
+            "type": "message",
+            "role": "assistant",
+            "model": model,
+            "content": content,
+            "stop_reason": stop_reason(
+                message.finish_reason.as_ref(),
+                !message.tool_calls.is_empty(),
+            ),
+            "stop_sequence": null,
+            "usage": usage(&message.usage),
+        })
+    }
+
+    fn encoder(model: &str) -> Self::Encoder {
+        Encoder {
+            id: message_id(),
+            model: model.to_string(),
+            started: false,
+            blocks: 0,
+            open: None,
+            has_tool_calls: false,
+            finish_reason: None,
+            usage: None,
+        }
+    }
+
+    fn error(status: StatusCode, message: &str) -> Value {
+        let kind = match status {
+            StatusCode::BAD_REQUEST => "invalid_request_error",
+            _ => "api_error",
+        };
+        json!({"type": "error", "error": {"type": kind, "message": message}})
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Block {
+    Text,
+    ToolUse,
+}
+
+/// Encodes a provider stream as messages API events.
+pub struct Encoder {
+    id: String,
+    model: String,
+    started: bool,
+    /// Number of content blocks started so far, the index of the next one
+    blocks: usize,
+    open: Option<Block>,
+    has_tool_calls: bool,
+    finish_reason: Option<FinishReason>,
+    usage: Option<Usage>,
+}
+
+impl Encoder {
+    fn event(name: &str, mut data: Value) -> String {
+        data["type"] = json!(name);
+        sse(Some(name), &data)
+    }
+
+    fn start(&mut self, frames: &mut Vec<String>) {
+        if !self.started {
+            self.started = true;
+            frames.push(Self::event(
+                "message_start",
+                json!({"message": {
+                    "id": self.id,
+                    "type": "message",
+                    "role": "assistant",
+                    "model": self.model,
+                    "content": [],
+                    "stop_reason": null,
+                    "stop_sequence": null,
+                    "usage": {"input_tokens": 0, "output_tokens": 0},
+                }}),
+            ));
+        }
+    }
+
+    fn close(&mut self, frames: &mut Vec<String>) {
+        if self.open.take().is_some() {
+            frames.push(Self::event(
+                "content_block_stop",
+                json!({"index": self.blocks - 1}),
+            ));
+        }
+    }
+
+    fn open(&mut self, block: Block, content_block: Value, frames: &mut Vec<String>) {
+        self.close(frames);
+        frames.push(Self::event(
+            "content_block_start",
+            json!({"index": self.blocks, "content_block": content_block}),
+        ));
+        self.blocks += 1;
+        self.open = Some(block);
+    }
+
+    fn delta(&self, delta: Value, frames: &mut Vec<String>) {
+        frames.push(Self::event(
+            "content_block_delta",
+            json!({"index": self.blocks - 1, "delta": delta}),
+        ));
+    }
+}
+
+impl StreamEncoder for Encoder {
+    fn encode(&mut self, message: ChatCompletionMessage) -> Vec<String> {
+        let mut frames = Vec::new();
+        self.start(&mut frames);
+
+        if let Some(content) = message.content.filter(|content| !content.is_empty()) {
+            if self.open != Some(Block::Text) {
+                self.open(
+                    Block::Text,
+                    json!({"type": "text", "text": ""}),
+                    &mut frames,
+                );
+            }
+            self.delta(
+                json!({"type": "text_delta", "text": content.as_str()}),
+                &mut frames,
+            );
+        }
+
+        for call in message.tool_calls {
+            let (id, name, arguments) = match call {
+                ToolCall::Full(call) => {
+                    (call.call_id, Some(call.name), call.arguments.into_string())
+                }
+                ToolCall::Part(part) => (part.call_id, part.name, part.arguments_part),
+            };
+            // A part with an id or a name starts a new call, other parts
+            // continue the arguments of the current one
+            if id.is_some() || name.is_some() || self.open != Some(Block::ToolUse) {
+                self.has_tool_calls = true;
+                let id = id
+                    .map(|id| id.as_str().to_string())
+                    .unwrap_or_else(|| format!("toolu_{}", self.blocks));
+                let name = name.map(ToolName::into_string).unwrap_or_default();
+                self.open(
+                    Block::ToolUse,
+                    json!({"type": "tool_use", "id": id, "name": name, "input": {}}),
+                    &mut frames,
+                );
+            }
+            if !arguments.is_empty() {
+                self.delta(
+                    json!({"type": "input_json_delta", "partial_json": arguments}),
+                    &mut frames,
+                );
+            }
+        }
+
+        if let Some(reason) = message.finish_reason {
+            self.finish_reason = Some(reason);
+        }
+        if let Some(usage) = message.usage {
+            self.usage = Some(usage);
+        }
+        frames
+    }
+
+    fn finish(&mut self) -> Vec<String> {
+        let mut frames = Vec::new();
+        self.start(&mut frames);
+        self.close(&mut frames);
+        let stop_reason = stop_reason(self.finish_reason.as_ref(), self.has_tool_calls);
+        let usage = self
+            .usage
+            .as_ref()
+            .map(usage)
+            .unwrap_or_else(|| json!({"output_tokens": 0}));
+        frames.push(Self::event(
+            "message_delta",
+            json!({
+                "delta": {"stop_reason": stop_reason, "stop_sequence": null},
+                "usage": usage,
+            }),
+        ));
+        frames.push(Self::event("message_stop", json!({})));
+        frames
+    }
+
+    fn error(&mut self, message: &str) -> Vec<String> {
+        vec![sse(
+            Some("error"),
+            &Anthropic::error(StatusCode::BAD_GATEWAY, message),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::{Content as MessageContent, ToolCallPart};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_request_into_context() {
+        let fixture: MessagesRequest = serde_json::from_value(json!({
+            "model": "claude-sonnet-4-5",
+            "max_tokens": 1024,
+            "system": [{"type": "text", "text": "Be brief"}],
+            "messages": [
+                {"role": "user", "content": "Weather in Paris?"},
+                {"role": "assistant", "content": [
+                    {"type": "thinking", "thinking": "Use the tool", "signature": "abc"},
+                    {"type": "tool_use", "id": "toolu_1", "name": "weather", "input": {"city": "Paris"}}
+                ]},
+                {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": "Sunny"}
+                ]}
+            ],
+            "tools": [{"name": "weather", "input_schema": {"type": "object"}}],
+            "tool_choice": {"type": "any"}
+        }))
+        .unwrap();
+
+        let actual = Context::try_from(fixture).unwrap();
+
+        let expected = Context::default()
+            .max_tokens(1024usize)
+            .add_message(ContextMessage::system("Be brief"))
+            .add_message(ContextMessage::user("Weather in Paris?", None))
+            .add_message(ContextMessage::assistant(
+                "",
+                None,
+                Some(vec![
+                    ToolCallFull::new("weather")
+                        .call_id(ToolCallId::new("toolu_1"))
+                        .arguments(ToolCallArguments::from_json(r#"{"city":"Paris"}"#)),
+                ]),
+            ))
+            .add_message(ContextMessage::tool_result(
+                ToolResult::new("weather")
+                    .call_id(ToolCallId::new("toolu_1"))
+                    .success("Sunny"),
+            ))
+            .add_tool(
+                tool_definition("weather".to_string(), None, Some(json!({"type": "object"})))
+                    .unwrap(),
+            )
+            .tool_choice(ToolChoice::Required);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_encoder_streams_text_then_tool_use() {
+        let mut fixture = Anthropic::encoder("claude-sonnet-4-5");
+        fixture.id = "msg_1".to_string();
+
+        let mut frames = fixture.encode(ChatCompletionMessage::assistant(MessageContent::part(
+            "Checking",
+        )));
+        frames.extend(
+            fixture.encode(
+                ChatCompletionMessage::default().add_tool_call(
+                    ToolCallPart::default()
+                        .call_id(ToolCallId::new("toolu_1"))
+                        .name(ToolName::new("weather"))
+                        .arguments_part("{}"),
+                ),
+            ),
+        );
+        frames.extend(fixture.finish());
+
+        let actual = frames
+            .iter()
+            .map(|frame| frame.lines().next().unwrap_or_default().to_string())
+            .collect::<Vec<_>>();
+        let expected = vec![
+            "event: message_start",
+            "event: content_block_start",
+            "event: content_block_delta",
+            "event: content_block_stop",
+            "event: content_block_start",
+            "event: content_block_delta",
+            "event: content_block_stop",
+            "event: message_delta",
+            "event: message_stop",
+        ];
+        assert_eq!(actual, expected);
+        assert!(frames[7].contains(r#""stop_reason":"tool_use""#));
+    }
+}
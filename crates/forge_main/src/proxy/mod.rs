@@ -0,0 +1,504 @@
+//! Local HTTP server behind `forge proxy`.
+//!
+//! Accepts OpenAI chat completions and Anthropic messages requests and sends
+//! them through the configured providers, so that other tools can share
+//! Forge's providers and credentials. Every request is logged together with
+//! its response to the `proxy_exchanges` table of the local database.
+
+mod anthropic;
+mod openai;
+
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use forge_api::API;
+use forge_domain::{
+    ChatCompletionMessage, ChatCompletionMessageFull, Context, Image, ProxyExchange, ProxyFormat,
+    ResultStreamExt, TitleFormat, ToolDefinition,
+};
+use futures::StreamExt;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio_stream::wrappers::ReceiverStream;
+
+use self::anthropic::Anthropic;
+use self::openai::OpenAI;
+use crate::TitleDisplayExt;
+
+/// A client API spoken by the proxy.
+trait WireFormat: Send + 'static {
+    const FORMAT: ProxyFormat;
+    type Request: DeserializeOwned + Send;
+    type Encoder: StreamEncoder;
+
+    fn model(request: &Self::Request) -> &str;
+    fn stream(request: &Self::Request) -> bool;
+    fn context(request: Self::Request) -> anyhow::Result<Context>;
+
+    /// Body of a response when streaming is disabled
+    fn response(model: &str, message: ChatCompletionMessageFull) -> Value;
+
+    /// Creates the encoder for a streaming response
+    fn encoder(model: &str) -> Self::Encoder;
+
+    /// Body of an error response
+    fn error(status: StatusCode, message: &str) -> Value;
+}
+
+/// Converts the chunks of a provider stream into server-sent events.
+trait StreamEncoder: Send + 'static {
+    fn encode(&mut self, message: ChatCompletionMessage) -> Vec<String>;
+
+    /// Events sent once the provider stream has ended
+    fn finish(&mut self) -> Vec<String>;
+
+    /// Events sent when the provider stream fails part way through
+    fn error(&mut self, message: &str) -> Vec<String>;
+}
+
+/// Formats a server-sent event.
+fn sse(event: Option<&str>, data: &impl Serialize) -> String {
+    let data = serde_json::to_string(data).unwrap_or_default();
+    match event {
+        Some(event) => format!("event: {event}\ndata: {data}\n\n"),
+        None => format!("data: {data}\n\n"),
+    }
+}
+
+fn tool_definition(
+    name: String,
+    description: Option<String>,
+    parameters: Option<Value>,
+) -> anyhow::Result<ToolDefinition> {
+    let definition = serde_json::json!({
+        "name": name,
+        "description": description.unwrap_or_default(),
+        "input_schema": parameters.unwrap_or_else(|| serde_json::json!({"type": "object"})),
+    });
+    serde_json::from_value(definition).with_context(|| format!("Invalid parameters of tool {name}"))
+}
+
+/// Parses a `data:<mime type>;base64,<data>` URL. Remote images are not
+/// fetched.
+fn image_from_data_url(url: &str) -> anyhow::Result<Image> {
+    let (mime_type, data) = url
+        .strip_prefix("data:")
+        .and_then(|url| url.split_once(";base64,"))
+        .context("Only base64 data URLs are supported for images")?;
+    Ok(Image::new_base64(data.to_string(), mime_type))
+}
+
+/// Serves OpenAI and Anthropic compatible endpoints backed by the configured
+/// providers.
+pub struct ProxyServer<A> {
+    api: Arc<A>,
+    token: Option<String>,
+}
+
+impl<A: API + 'static> ProxyServer<A> {
+    /// Creates a server that requires `token` from clients. Without one, a
+    /// token is generated when the server starts.
+    pub fn new(api: Arc<A>, token: Option<String>) -> Self {
+        Self { api, token }
+    }
+
+    /// Serves requests on `addr` until the process is stopped.
+    pub async fn serve(self, addr: SocketAddr) -> anyhow::Result<()> {
+        let loopback = addr.ip().is_loopback();
+        let (token, generated) = match self.token {
+            Some(token) => (token, false),
+            None if loopback => (format!("forge-{}", uuid::Uuid::new_v4().simple()), true),
+            None => anyhow::bail!(
+                "Listening on {addr} exposes your provider credentials to the network. Set a token with --token or FORGE_PROXY_TOKEN."
+            ),
+        };
+
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to bind proxy to {addr}"))?;
+        let state = Arc::new(Shared {
+            api: self.api,
+            access: Access { token: token.clone(), loopback },
+        });
+        let router = Router::new()
+            .route("/v1/chat/completions", post(handle::<A, OpenAI>))
+            .route("/v1/messages", post(handle::<A, Anthropic>))
+            .with_state(state);
+
+        println!(
+            "{}",
+            TitleFormat::info("Proxy listening")
+                .sub_title(format!("http://{}", listener.local_addr()?))
+                .display()
+        );
+        if generated {
+            println!(
+                "{}",
+                TitleFormat::info("Proxy token")
+                    .sub_title(format!(
+                        "{token} (send as `Authorization: Bearer` or `x-api-key`)"
+                    ))
+                    .display()
+            );
+        }
+        axum::serve(listener, router).await?;
+        Ok(())
+    }
+}
+
+/// State shared by the request handlers.
+struct Shared<A> {
+    api: Arc<A>,
+    access: Access,
+}
+
+/// Decides which requests may use the proxy.
+///
+/// Requiring a token and a JSON content type stops web pages from sending
+/// requests through the proxy, and checking the `Host` of a loopback server
+/// stops them from reading responses through DNS rebinding.
+#[derive(Debug)]
+struct Access {
+    token: String,
+    loopback: bool,
+}
+
+impl Access {
+    fn check(&self, headers: &HeaderMap) -> Result<(), (StatusCode, &'static str)> {
+        if self.loopback
+            && !headers
+                .get(header::HOST)
+                .and_then(|host| host.to_str().ok())
+                .is_some_and(is_loopback_host)
+        {
+            return Err((StatusCode::FORBIDDEN, "Host is not allowed"));
+        }
+
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let api_key = headers
+            .get("x-api-key")
+            .and_then(|value| value.to_str().ok());
+        if ![bearer, api_key]
+            .into_iter()
+            .flatten()
+            .any(|token| matches_token(token.trim(), &self.token))
+        {
+            return Err((StatusCode::UNAUTHORIZED, "Missing or invalid proxy token"));
+        }
+
+        let json = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+        if !json {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "Content-Type must be application/json",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if a `Host` header names the local machine
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Compares tokens in constant time
+fn matches_token(actual: &str, expected: &str) -> bool {
+    actual.len() == expected.len()
+        && actual
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// A failed request, answered with an error body in the client's format.
+struct Failure {
+    status: StatusCode,
+    error: anyhow::Error,
+}
+
+impl Failure {
+    fn new(status: StatusCode) -> impl FnOnce(anyhow::Error) -> Self {
+        move |error| Self { status, error }
+    }
+}
+
+async fn handle<A: API + 'static, W: WireFormat>(
+    State(state): State<Arc<Shared<A>>>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    if let Err((status, message)) = state.access.check(&headers) {
+        return (status, axum::Json(W::error(status, message))).into_response();
+    }
+
+    let api = &state.api;
+    let started = Instant::now();
+    let mut exchange = ProxyExchange::new(W::FORMAT, String::new(), body.clone());
+
+    match serve::<A, W>(api, &body, &mut exchange, started).await {
+        Ok(response) => response,
+        Err(Failure { status, error }) => {
+            let message = format!("{error:#}");
+            let body = W::error(status, &message);
+            exchange = exchange
+                .status(status.as_u16())
+                .response(body.to_string())
+                .error(message)
+                .duration_ms(started.elapsed().as_millis() as u64);
+            log(api.as_ref(), exchange).await;
+            (status, axum::Json(body)).into_response()
+        }
+    }
+}
+
+async fn serve<A: API + 'static, W: WireFormat>(
+    api: &Arc<A>,
+    body: &str,
+    exchange: &mut ProxyExchange,
+    started: Instant,
+) -> Result<Response, Failure> {
+    let request: W::Request = serde_json::from_str(body)
+        .context("Invalid request body")
+        .map_err(Failure::new(StatusCode::BAD_REQUEST))?;
+    let model = W::model(&request).to_string();
+    let stream = W::stream(&request);
+    exchange.model = model.clone();
+
+    let context = W::context(request).map_err(Failure::new(StatusCode::BAD_REQUEST))?;
+    let (provider, model_id) = api
+        .resolve_proxy_model(&model)
+        .await
+        .map_err(Failure::new(StatusCode::BAD_REQUEST))?;
+    exchange.provider = Some(provider.clone());
+    let messages = api
+        .proxy_chat(provider, &model_id, context)
+        .await
+        .map_err(Failure::new(StatusCode::BAD_GATEWAY))?;
+
+    if !stream {
+        let message = messages
+            .into_full(false, None)
+            .await
+            .map_err(Failure::new(StatusCode::BAD_GATEWAY))?;
+        let body = W::response(&model, message);
+        let exchange = exchange
+            .clone()
+            .response(body.to_string())
+            .duration_ms(started.elapsed().as_millis() as u64);
+        log(api.as_ref(), exchange).await;
+        return Ok(axum::Json(body).into_response());
+    }
+
+    // Events are forwarded as they arrive and logged once the stream ends,
+    // including when the client disconnects early
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<String, Infallible>>(64);
+    let api = api.clone();
+    let mut exchange = exchange.clone();
+    tokio::spawn(async move {
+        let mut encoder = W::encoder(&model);
+        let mut transcript = String::new();
+        let mut messages = messages;
+        let mut error = None;
+
+        while let Some(message) = messages.next().await {
+            let frames = match message {
+                Ok(message) => encoder.encode(message),
+                Err(e) => {
+                    let message = format!("{e:#}");
+                    let frames = encoder.error(&message);
+                    error = Some(message);
+                    frames
+                }
+            };
+            if !send(&tx, frames, &mut transcript).await {
+                error.get_or_insert_with(|| "Client disconnected".to_string());
+            }
+            if error.is_some() {
+                break;
+            }
+        }
+        if error.is_none() && !send(&tx, encoder.finish(), &mut transcript).await {
+            error = Some("Client disconnected".to_string());
+        }
+
+        exchange = exchange
+            .response(transcript)
+            .duration_ms(started.elapsed().as_millis() as u64);
+        if let Some(error) = error {
+            exchange = exchange.error(error);
+        }
+        log(api.as_ref(), exchange).await;
+    });
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(Body::from_stream(ReceiverStream::new(rx)))
+        .context("Failed to build streaming response")
+        .map_err(Failure::new(StatusCode::INTERNAL_SERVER_ERROR))
+}
+
+/// Sends `frames` to the client, returning false once it has disconnected.
+async fn send(
+    tx: &tokio::sync::mpsc::Sender<Result<String, Infallible>>,
+    frames: Vec<String>,
+    transcript: &mut String,
+) -> bool {
+    for frame in frames {
+        transcript.push_str(&frame);
+        if tx.send(Ok(frame)).await.is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Records an exchange in the request log and prints a line for it.
+async fn log<A: API>(api: &A, exchange: ProxyExchange) {
+    let title = match exchange.error {
+        Some(_) => TitleFormat::error(exchange.format.to_string()),
+        None => TitleFormat::info(exchange.format.to_string()),
+    };
+    println!(
+        "{}",
+        title
+            .sub_title(format!(
+                "{} {} in {}ms",
+                exchange.model, exchange.status, exchange.duration_ms
+            ))
+            .display()
+    );
+    if let Err(error) = api.log_proxy_exchange(exchange).await {
+        tracing::warn!(error = ?error, "Failed to log proxy exchange");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn access() -> Access {
+        Access { token: "secret".to_string(), loopback: true }
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| {
+                (
+                    header::HeaderName::from_static(name),
+                    HeaderValue::from_static(value),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_access_allows_authorized_json_requests() {
+        let fixture = [
+            headers(&[
+                ("host", "127.0.0.1:8787"),
+                ("authorization", "Bearer secret"),
+                ("content-type", "application/json"),
+            ]),
+            headers(&[
+                ("host", "localhost:8787"),
+                ("x-api-key", "secret"),
+                ("content-type", "application/json; charset=utf-8"),
+            ]),
+            headers(&[
+                ("host", "[::1]:8787"),
+                ("x-api-key", "secret"),
+                ("content-type", "application/json"),
+            ]),
+        ];
+
+        let actual = fixture
+            .iter()
+            .map(|headers| access().check(headers))
+            .collect::<Vec<_>>();
+        let expected = vec![Ok(()), Ok(()), Ok(())];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_access_rejects_cross_site_requests() {
+        let fixture = [
+            // A form posted by a web page carries no token
+            headers(&[("host", "127.0.0.1:8787"), ("content-type", "text/plain")]),
+            headers(&[
+                ("host", "127.0.0.1:8787"),
+                ("authorization", "Bearer wrong"),
+                ("content-type", "application/json"),
+            ]),
+            headers(&[
+                ("host", "127.0.0.1:8787"),
+                ("x-api-key", "secret"),
+                ("content-type", "text/plain"),
+            ]),
+            // DNS rebinding keeps the attacker's host name
+            headers(&[
+                ("host", "evil.example:8787"),
+                ("x-api-key", "secret"),
+                ("content-type", "application/json"),
+            ]),
+            headers(&[
+                ("x-api-key", "secret"),
+                ("content-type", "application/json"),
+            ]),
+        ];
+
+        let actual = fixture
+            .iter()
+            .map(|headers| access().check(headers).unwrap_err().0)
+            .collect::<Vec<_>>();
+        let expected = vec![
+            StatusCode::UNAUTHORIZED,
+            StatusCode::UNAUTHORIZED,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            StatusCode::FORBIDDEN,
+            StatusCode::FORBIDDEN,
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_access_skips_host_check_off_loopback() {
+        let fixture = headers(&[
+            ("host", "forge.internal:8787"),
+            ("x-api-key", "secret"),
+            ("content-type", "application/json"),
+        ]);
+
+        let actual = Access { loopback: false, ..access() }.check(&fixture);
+        let expected = Ok(());
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,528 @@
+//! OpenAI chat completions API, `POST /v1/chat/completions`.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use axum::http::StatusCode;
+use forge_domain::{
+    ChatCompletionMessage, ChatCompletionMessageFull, Context, ContextMessage, FinishReason,
+    ProxyFormat, Temperature, ToolCall, ToolCallArguments, ToolCallFull, ToolCallId, ToolChoice,
+    ToolName, ToolResult, TopP, Usage,
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{StreamEncoder, WireFormat, image_from_data_url, sse, tool_definition};
+
+pub struct OpenAI;
+
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<Message>,
+    #[serde(default)]
+    pub stream: bool,
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    #[serde(default)]
+    pub max_completion_tokens: Option<usize>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    #[serde(default)]
+    pub tools: Vec<Tool>,
+    #[serde(default)]
+    pub tool_choice: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "role", rename_all = "snake_case")]
+pub enum Message {
+    #[serde(alias = "developer")]
+    System {
+        content: MessageContent,
+    },
+    User {
+        content: MessageContent,
+    },
+    Assistant {
+        #[serde(default)]
+        content: Option<MessageContent>,
+        #[serde(default)]
+        tool_calls: Vec<MessageToolCall>,
+    },
+    Tool {
+        tool_call_id: String,
+        content: MessageContent,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImageUrl {
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MessageToolCall {
+    pub id: String,
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FunctionCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Tool {
+    pub function: FunctionDefinition,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FunctionDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameters: Option<Value>,
+}
+
+impl MessageContent {
+    /// Splits the content into its text and the images attached to it
+    fn into_parts(self) -> anyhow::Result<(String, Vec<ContextMessage>)> {
+        match self {
+            MessageContent::Text(text) => Ok((text, vec![])),
+            MessageContent::Parts(parts) => {
+                let mut text = Vec::new();
+                let mut images = Vec::new();
+                for part in parts {
+                    match part {
+                        ContentPart::Text { text: part } => text.push(part),
+                        ContentPart::ImageUrl { image_url } => {
+                            images.push(ContextMessage::Image(image_from_data_url(&image_url.url)?))
+                        }
+                    }
+                }
+                Ok((text.join("\n"), images))
+            }
+        }
+    }
+}
+
+impl TryFrom<ChatCompletionRequest> for Context {
+    type Error = anyhow::Error;
+
+    fn try_from(request: ChatCompletionRequest) -> anyhow::Result<Self> {
+        // Tool messages only carry the call id, so names are looked up from
+        // the assistant messages that made the calls
+        let mut tool_names = HashMap::new();
+        let mut context = Context::default();
+
+        for message in request.messages {
+            match message {
+                Message::System { content } => {
+                    let (text, _) = content.into_parts()?;
+                    context = context.add_message(ContextMessage::system(text));
+                }
+                Message::User { content } => {
+                    let (text, images) = content.into_parts()?;
+                    if !text.is_empty() {
+                        context = context.add_message(ContextMessage::user(text, None));
+                    }
+                    for image in images {
+                        context = context.add_message(image);
+                    }
+                }
+                Message::Assistant { content, tool_calls } => {
+                    let text = match content {
+                        Some(content) => content.into_parts()?.0,
+                        None => String::new(),
+                    };
+                    let tool_calls = tool_calls
+                        .into_iter()
+                        .map(|call| {
+                            tool_names.insert(call.id.clone(), call.function.name.clone());
+                            ToolCallFull::new(call.function.name)
+                                .call_id(ToolCallId::new(call.id))
+                                .arguments(ToolCallArguments::from_json(&call.function.arguments))
+                        })
+                        .collect();
+                    context = context.add_message(ContextMessage::assistant(
+                        text,
+                        None,
+                        Some(tool_calls),
+                    ));
+                }
+                Message::Tool { tool_call_id, content } => {
+                    let (text, _) = content.into_parts()?;
+                    let name = tool_names
+                        .get(&tool_call_id)
+                        .with_context(|| format!("Unknown tool call id: {tool_call_id}"))?;
+                    context = context.add_message(ContextMessage::tool_result(
+                        ToolResult::new(ToolName::new(name))
+                            .call_id(ToolCallId::new(tool_call_id))
+                            .success(text),
+                    ));
+                }
+            }
+        }
+
+        for tool in request.tools {
+            context = context.add_tool(tool_definition(
+                tool.function.name,
+                tool.function.description,
+                tool.function.parameters,
+            )?);
+        }
+        if let Some(choice) = request.tool_choice {
+            context = context.tool_choice(tool_choice(choice)?);
+        }
+        if let Some(max_tokens) = request.max_completion_tokens.or(request.max_tokens) {
+            context = context.max_tokens(max_tokens);
+        }
+        if let Some(temperature) = request.temperature {
+            context =
+                context.temperature(Temperature::new(temperature).map_err(anyhow::Error::msg)?);
+        }
+        if let Some(top_p) = request.top_p {
+            context = context.top_p(TopP::new(top_p).map_err(anyhow::Error::msg)?);
+        }
+        Ok(context)
+    }
+}
+
+fn tool_choice(value: Value) -> anyhow::Result<ToolChoice> {
+    match &value {
+        Value::String(choice) => match choice.as_str() {
+            "none" => Ok(ToolChoice::None),
+            "auto" => Ok(ToolChoice::Auto),
+            "required" => Ok(ToolChoice::Required),
+            _ => anyhow::bail!("Unsupported tool_choice: {choice}"),
+        },
+        _ => value
+            .pointer("/function/name")
+            .and_then(Value::as_str)
+            .map(|name| ToolChoice::Call(ToolName::new(name)))
+            .with_context(|| format!("Unsupported tool_choice: {value}")),
+    }
+}
+
+fn finish_reason(reason: Option<&FinishReason>, has_tool_calls: bool) -> &'static str {
+    match reason {
+        Some(FinishReason::Length) => "length",
+        Some(FinishReason::ContentFilter) => "content_filter",
+        Some(FinishReason::ToolCalls) => "tool_calls",
+        Some(FinishReason::Stop) => "stop",
+        None if has_tool_calls => "tool_calls",
+        None => "stop",
+    }
+}
+
+fn usage(usage: &Usage) -> Value {
+    json!({
+        "prompt_tokens": *usage.prompt_tokens,
+        "completion_tokens": *usage.completion_tokens,
+        "total_tokens": *usage.total_tokens,
+    })
+}
+
+fn created() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+impl WireFormat for OpenAI {
+    const FORMAT: ProxyFormat = ProxyFormat::OpenAI;
+    type Request = ChatCompletionRequest;
+    type Encoder = Encoder;
+
+    fn model(request: &Self::Request) -> &str {
+        &request.model
+    }
+
+    fn stream(request: &Self::Request) -> bool {
+        request.stream
+    }
+
+    fn context(request: Self::Request) -> anyhow::Result<Context> {
+        Context::try_from(request)
+    }
+
+    fn response(model: &str, message: ChatCompletionMessageFull) -> Value {
+        let tool_calls = message
+            .tool_calls
+            .iter()
+            .map(|call| {
+                json!({
+                    "id": call.call_id.as_ref().map(ToolCallId::as_str),
+                    "type": "function",
+                    "function": {
+                        "name": call.name.as_str(),
+                        "arguments": call.arguments.clone().into_string(),
+                    },
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut response_message = json!({"role": "assistant", "content": message.content});
+        if !tool_calls.is_empty() {
+            response_message["tool_calls"] = Value::Array(tool_calls);
+        }
+
+        json!({
+            "id": format!("chatcmpl-{}", uuid::Uuid::new_v4().simple()),
+            "object": "chat.completion",
+            "created": created(),
+            "model": model,
+            "choices": [{
+                "index": 0,
+                "message": response_message,
+                "finish_reason": finish_reason(
+                    message.finish_reason.as_ref(),
+                    !message.tool_calls.is_empty(),
+                ),
+            }],
+            "usage": usage(&message.usage),
+        })
+    }
+
+    fn encoder(model: &str) -> Self::Encoder {
+        Encoder {
+            id: format!("chatcmpl-{}", uuid::Uuid::new_v4().simple()),
+            model: model.to_string(),
+            created: created(),
+            started: false,
+            tool_calls: 0,
+            finish_reason: None,
+            usage: None,
+        }
+    }
+
+    fn error(status: StatusCode, message: &str) -> Value {
+        let kind = match status {
+            StatusCode::BAD_REQUEST => "invalid_request_error",
+            _ => "api_error",
+        };
+        json!({"error": {"message": message, "type": kind, "code": status.as_u16()}})
+    }
+}
+
+/// Encodes a provider stream as `chat.completion.chunk` events.
+pub struct Encoder {
+    id: String,
+    model: String,
+    created: i64,
+    started: bool,
+    /// Number of tool calls started so far, the index of the next one
+    tool_calls: usize,
+    finish_reason: Option<FinishReason>,
+    usage: Option<Usage>,
+}
+
+impl Encoder {
+    fn chunk(&self, choices: Value) -> Value {
+        json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": self.created,
+            "model": self.model,
+            "choices": choices,
+        })
+    }
+}
+
+impl StreamEncoder for Encoder {
+    fn encode(&mut self, message: ChatCompletionMessage) -> Vec<String> {
+        let mut delta = serde_json::Map::new();
+        if !self.started {
+            self.started = true;
+            delta.insert("role".to_string(), json!("assistant"));
+        }
+        if let Some(content) = message.content.filter(|content| !content.is_empty()) {
+            delta.insert("content".to_string(), json!(content.as_str()));
+        }
+
+        let mut tool_calls = Vec::new();
+        for call in message.tool_calls {
+            let (id, name, arguments) = match call {
+                ToolCall::Full(call) => {
+                    (call.call_id, Some(call.name), call.arguments.into_string())
+                }
+                ToolCall::Part(part) => (part.call_id, part.name, part.arguments_part),
+            };
+            // A part with an id or a name starts a new call, other parts
+            // continue the arguments of the current one
+            let mut value = json!({"function": {"arguments": arguments}});
+            if id.is_some() || name.is_some() || self.tool_calls == 0 {
+                self.tool_calls += 1;
+                value["id"] = json!(
+                    id.map(|id| id.as_str().to_string())
+                        .unwrap_or_else(|| format!("call_{}", self.tool_calls))
+                );
+                value["type"] = json!("function");
+                value["function"]["name"] = json!(name.map(ToolName::into_string));
+            }
+            value["index"] = json!(self.tool_calls - 1);
+            tool_calls.push(value);
+        }
+        if !tool_calls.is_empty() {
+            delta.insert("tool_calls".to_string(), Value::Array(tool_calls));
+        }
+
+        if let Some(reason) = message.finish_reason {
+            self.finish_reason = Some(reason);
+        }
+        if let Some(usage) = message.usage {
+            self.usage = Some(usage);
+        }
+
+        if delta.is_empty() {
+            return vec![];
+        }
+        let chunk = self.chunk(json!([{"index": 0, "delta": delta, "finish_reason": null}]));
+        vec![sse(None, &chunk)]
+    }
+
+    fn finish(&mut self) -> Vec<String> {
+        let reason = finish_reason(self.finish_reason.as_ref(), self.tool_calls > 0);
+        let chunk = self.chunk(json!([{"index": 0, "delta": {}, "finish_reason": reason}]));
+        let mut frames = vec![sse(None, &chunk)];
+        // Usage is sent in a final chunk without choices, as OpenAI does with
+        // `stream_options.include_usage`
+        if let Some(value) = self.usage.as_ref() {
+            let mut chunk = self.chunk(json!([]));
+            chunk["usage"] = usage(value);
+            frames.push(sse(None, &chunk));
+        }
+        frames.push("data: [DONE]\n\n".to_string());
+        frames
+    }
+
+    fn error(&mut self, message: &str) -> Vec<String> {
+        vec![sse(None, &OpenAI::error(StatusCode::BAD_GATEWAY, message))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::ToolCallPart;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_request_into_context() {
+        let fixture: ChatCompletionRequest = serde_json::from_value(json!({
+            "model": "anthropic/claude-sonnet-4-5",
+            "max_tokens": 512,
+            "messages": [
+                {"role": "developer", "content": "Be brief"},
+                {"role": "user", "content": [{"type": "text", "text": "Weather in Paris?"}]},
+                {"role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call_1",
+                    "type": "function",
+                    "function": {"name": "weather", "arguments": "{\"city\":\"Paris\"}"}
+                }]},
+                {"role": "tool", "tool_call_id": "call_1", "content": "Sunny"}
+            ],
+            "tools": [{"type": "function", "function": {"name": "weather"}}],
+            "tool_choice": "auto"
+        }))
+        .unwrap();
+
+        let actual = Context::try_from(fixture).unwrap();
+
+        let expected = Context::default()
+            .add_message(ContextMessage::system("Be brief"))
+            .add_message(ContextMessage::user("Weather in Paris?", None))
+            .add_message(ContextMessage::assistant(
+                "",
+                None,
+                Some(vec![
+                    ToolCallFull::new("weather")
+                        .call_id(ToolCallId::new("call_1"))
+                        .arguments(ToolCallArguments::from_json(r#"{"city":"Paris"}"#)),
+                ]),
+            ))
+            .add_message(ContextMessage::tool_result(
+                ToolResult::new("weather")
+                    .call_id(ToolCallId::new("call_1"))
+                    .success("Sunny"),
+            ))
+            .add_tool(tool_definition("weather".to_string(), None, None).unwrap())
+            .tool_choice(ToolChoice::Auto)
+            .max_tokens(512usize);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_request_with_unknown_tool_call_id() {
+        let fixture: ChatCompletionRequest = serde_json::from_value(json!({
+            "model": "gpt-5",
+            "messages": [{"role": "tool", "tool_call_id": "call_9", "content": "Sunny"}]
+        }))
+        .unwrap();
+
+        let actual = Context::try_from(fixture).unwrap_err().to_string();
+
+        let expected = "Unknown tool call id: call_9";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_encoder_streams_content_and_tool_calls() {
+        let mut fixture = OpenAI::encoder("gpt-5");
+        fixture.id = "chatcmpl-1".to_string();
+        fixture.created = 0;
+
+        let mut frames = fixture.encode(ChatCompletionMessage::assistant(
+            forge_domain::Content::part("Hi"),
+        ));
+        frames.extend(
+            fixture.encode(
+                ChatCompletionMessage::default().add_tool_call(
+                    ToolCallPart::default()
+                        .call_id(ToolCallId::new("call_1"))
+                        .name(ToolName::new("weather"))
+                        .arguments_part("{\"city\":"),
+                ),
+            ),
+        );
+        frames.extend(
+            fixture.encode(
+                ChatCompletionMessage::default()
+                    .add_tool_call(ToolCallPart::default().arguments_part("\"Paris\"}")),
+            ),
+        );
+        frames.extend(fixture.finish());
+
+        let actual = frames
+            .iter()
+            .filter_map(|frame| frame.strip_prefix("data: "))
+            .map(|data| data.trim_end().to_string())
+            .collect::<Vec<_>>();
+        let expected = vec![
+            r#"{"choices":[{"delta":{"content":"Hi","role":"assistant"},"finish_reason":null,"index":0}],"created":0,"id":"chatcmpl-1","model":"gpt-5","object":"chat.completion.chunk"}"#,
+            r#"{"choices":[{"delta":{"tool_calls":[{"function":{"arguments":"{\"city\":","name":"weather"},"id":"call_1","index":0,"type":"function"}]},"finish_reason":null,"index":0}],"created":0,"id":"chatcmpl-1","model":"gpt-5","object":"chat.completion.chunk"}"#,
+            r#"{"choices":[{"delta":{"tool_calls":[{"function":{"arguments":"\"Paris\"}"},"index":0}]},"finish_reason":null,"index":0}],"created":0,"id":"chatcmpl-1","model":"gpt-5","object":"chat.completion.chunk"}"#,
+            r#"{"choices":[{"delta":{},"finish_reason":"tool_calls","index":0}],"created":0,"id":"chatcmpl-1","model":"gpt-5","object":"chat.completion.chunk"}"#,
+            "[DONE]",
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,11 +1,18 @@
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Local, Utc};
+use convert_case::{Case, Casing};
 use forge_domain::TitleFormat;
 
 use crate::title_display::TitleDisplayExt;
 
+/// File in the git directory of a worktree that marks it as created by
+/// `--sandbox`. It holds the name of the branch created along with it, if any.
+const MARKER: &str = "forge-sandbox";
+
 pub struct Sandbox<'a> {
     dir: &'a str,
 }
@@ -15,12 +22,39 @@ impl<'a> Sandbox<'a> {
         Self { dir }
     }
 
+    /// Generates the name of a fresh sandbox from the first words of the
+    /// prompt it runs and the time, e.g. `forge-fix-login-bug-20251123-140205`
+    pub fn generate_name(prompt: Option<&str>, now: DateTime<Local>) -> String {
+        let words = prompt
+            .unwrap_or_default()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+            .collect::<String>()
+            .split_whitespace()
+            .take(4)
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_case(Case::Kebab);
+        let time = now.format("%Y%m%d-%H%M%S");
+        match words.is_empty() {
+            true => format!("forge-{time}"),
+            false => format!("forge-{words}-{time}"),
+        }
+    }
+
     /// Handles worktree creation and returns the path to the worktree directory
     pub fn create(&self) -> Result<PathBuf> {
+        let cwd = std::env::current_dir().context("Failed to get current directory")?;
+        self.create_in(&cwd)
+    }
+
+    /// Creates the worktree next to the repository containing `cwd`
+    pub fn create_in(&self, cwd: &Path) -> Result<PathBuf> {
         let worktree_name = self.dir;
         // First check if we're in a git repository
         let git_check = Command::new("git")
             .args(["rev-parse", "--is-inside-work-tree"])
+            .current_dir(cwd)
             .output()
             .context("Failed to check if current directory is a git repository")?;
 
@@ -33,6 +67,7 @@ impl<'a> Sandbox<'a> {
         // Get the git root directory
         let git_root_output = Command::new("git")
             .args(["rev-parse", "--show-toplevel"])
+            .current_dir(cwd)
             .output()
             .context("Failed to get git root directory")?;
 
@@ -124,6 +159,12 @@ impl<'a> Sandbox<'a> {
             bail!("Failed to create git worktree: {stderr}");
         }
 
+        // Only worktrees and branches made here are ever removed by `forge sandbox`
+        let git_dir = git(&worktree_path, &["rev-parse", "--absolute-git-dir"])?;
+        let branch = if branch_exists { "" } else { worktree_name };
+        fs::write(Path::new(&git_dir).join(MARKER), branch)
+            .context("Failed to mark the worktree as a sandbox")?;
+
         println!(
             "{}",
             TitleFormat::info("Worktree [Created]")
@@ -136,4 +177,562 @@ impl<'a> Sandbox<'a> {
             .canonicalize()
             .context("Failed to canonicalize worktree path")
     }
+
+    /// Prints what a run changed in the sandbox. With `cleanup`, a sandbox
+    /// that ends up without changes is removed.
+    pub fn finish(&self, cleanup: bool) -> Result<()> {
+        let cwd = std::env::current_dir().context("Failed to get current directory")?;
+        self.finish_in(&cwd, cleanup)
+    }
+
+    /// Finishes the sandbox of the repository containing `cwd`
+    pub fn finish_in(&self, cwd: &Path, cleanup: bool) -> Result<()> {
+        let sandboxes = Sandboxes::open(cwd)?;
+        let sandbox = sandboxes.find_worktree(self.dir)?;
+        if sandbox.is_merged() {
+            if cleanup && sandbox.managed {
+                sandboxes.remove(&sandbox, false)?;
+                println!(
+                    "{}",
+                    TitleFormat::info("Worktree [Removed]")
+                        .sub_title("no changes were made")
+                        .display()
+                );
+            }
+            return Ok(());
+        }
+
+        println!("{}", sandboxes.diff(&sandbox, true)?);
+        println!(
+            "{}",
+            TitleFormat::info(format!(
+                "Sandbox {} has {} new commits and {} changed files",
+                sandbox.name, sandbox.ahead, sandbox.dirty
+            ))
+            .sub_title(format!(
+                "forge sandbox diff {0} | forge sandbox merge {0}",
+                sandbox.name
+            ))
+            .display()
+        );
+        Ok(())
+    }
+}
+
+/// A linked worktree created next to the repository with `--sandbox`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxStatus {
+    /// Directory name, which is also the name passed to `--sandbox`
+    pub name: String,
+    pub path: PathBuf,
+    /// Checked out branch, `None` if the HEAD is detached
+    pub branch: Option<String>,
+    /// Commits on the sandbox that are not on the base branch
+    pub ahead: usize,
+    /// Commits on the base branch that are not on the sandbox
+    pub behind: usize,
+    /// Files with uncommitted changes, including untracked ones
+    pub dirty: usize,
+    /// Last change to the worktree directory, its HEAD or its index
+    pub updated_at: DateTime<Utc>,
+    /// Whether the worktree was created with `--sandbox`. Other worktrees are
+    /// never removed.
+    pub managed: bool,
+    /// Whether the branch was created along with the sandbox, in which case
+    /// removing the sandbox deletes it
+    pub owns_branch: bool,
+}
+
+impl SandboxStatus {
+    /// Returns true if the sandbox holds no work that is missing from the base
+    /// branch
+    pub fn is_merged(&self) -> bool {
+        self.ahead == 0 && self.dirty == 0
+    }
+}
+
+/// An entry of `git worktree list --porcelain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Worktree {
+    path: PathBuf,
+    head: String,
+    branch: Option<String>,
+}
+
+fn parse_worktrees(output: &str) -> Vec<Worktree> {
+    output
+        .split("\n\n")
+        .filter_map(|entry| {
+            let mut path = None;
+            let mut head = String::new();
+            let mut branch = None;
+            for line in entry.lines() {
+                if let Some(value) = line.strip_prefix("worktree ") {
+                    path = Some(PathBuf::from(value));
+                } else if let Some(value) = line.strip_prefix("HEAD ") {
+                    head = value.to_string();
+                } else if let Some(value) = line.strip_prefix("branch ") {
+                    branch = Some(value.trim_start_matches("refs/heads/").to_string());
+                }
+            }
+            Some(Worktree { path: path?, head, branch })
+        })
+        .collect()
+}
+
+/// Runs git in `dir` and returns its trimmed standard output
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+/// The sandboxes of a repository, compared with the branch checked out in its
+/// main worktree.
+pub struct Sandboxes {
+    root: PathBuf,
+    base: String,
+}
+
+impl Sandboxes {
+    /// Opens the repository containing `cwd`, which may be the main worktree
+    /// or one of its sandboxes
+    pub fn open(cwd: &Path) -> Result<Self> {
+        let worktrees = parse_worktrees(&git(cwd, &["worktree", "list", "--porcelain"])?);
+        let main = worktrees
+            .into_iter()
+            .next()
+            .context("Current directory is not inside a git repository")?;
+        let base = main.branch.unwrap_or(main.head);
+        Ok(Self { root: main.path, base })
+    }
+
+    /// Branch or commit that sandboxes are compared with and merged into
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Lists the sandboxes created with `--sandbox`, oldest activity first
+    pub fn list(&self) -> Result<Vec<SandboxStatus>> {
+        Ok(self
+            .worktrees()?
+            .into_iter()
+            .filter(|sandbox| sandbox.managed)
+            .collect())
+    }
+
+    /// Lists every worktree next to the repository, including ones that were
+    /// not created with `--sandbox`
+    fn worktrees(&self) -> Result<Vec<SandboxStatus>> {
+        let parent = self.root.parent().map(Path::to_path_buf);
+        let mut sandboxes =
+            parse_worktrees(&git(&self.root, &["worktree", "list", "--porcelain"])?)
+                .into_iter()
+                .skip(1)
+                // Worktrees created elsewhere were not made with `--sandbox`
+                .filter(|worktree| worktree.path.parent().map(Path::to_path_buf) == parent)
+                .filter(|worktree| worktree.path.exists())
+                .map(|worktree| self.status(worktree))
+                .collect::<Result<Vec<_>>>()?;
+        sandboxes.sort_by_key(|sandbox| sandbox.updated_at);
+        Ok(sandboxes)
+    }
+
+    /// Finds a sandbox by name or path
+    pub fn find(&self, name: &str) -> Result<SandboxStatus> {
+        Self::lookup(self.list()?, name)
+    }
+
+    /// Finds a worktree next to the repository by name or path, whether or not
+    /// it was created with `--sandbox`
+    pub fn find_worktree(&self, name: &str) -> Result<SandboxStatus> {
+        Self::lookup(self.worktrees()?, name)
+    }
+
+    fn lookup(sandboxes: Vec<SandboxStatus>, name: &str) -> Result<SandboxStatus> {
+        let path = Path::new(name).canonicalize().ok();
+        sandboxes
+            .into_iter()
+            .find(|sandbox| sandbox.name == name || Some(&sandbox.path) == path.as_ref())
+            .with_context(|| format!("No sandbox named '{name}'"))
+    }
+
+    fn status(&self, worktree: Worktree) -> Result<SandboxStatus> {
+        let counts = git(
+            &self.root,
+            &[
+                "rev-list",
+                "--left-right",
+                "--count",
+                &format!("{}...{}", self.base, worktree.head),
+            ],
+        )?;
+        let (behind, ahead) = counts
+            .split_once('\t')
+            .and_then(|(behind, ahead)| Some((behind.parse().ok()?, ahead.parse().ok()?)))
+            .with_context(|| format!("Unexpected output of git rev-list: {counts}"))?;
+        let dirty = git(&worktree.path, &["status", "--porcelain"])?
+            .lines()
+            .count();
+        let git_dir = PathBuf::from(git(&worktree.path, &["rev-parse", "--absolute-git-dir"])?);
+        let updated_at = [
+            worktree.path.clone(),
+            git_dir.clone(),
+            git_dir.join("HEAD"),
+            git_dir.join("index"),
+        ]
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .max()
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_default();
+        let marker = fs::read_to_string(git_dir.join(MARKER)).ok();
+        let owns_branch = marker
+            .as_deref()
+            .zip(worktree.branch.as_deref())
+            .is_some_and(|(created, branch)| created.trim() == branch);
+        Ok(SandboxStatus {
+            name: worktree
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: worktree.path,
+            branch: worktree.branch,
+            ahead,
+            behind,
+            dirty,
+            updated_at,
+            managed: marker.is_some(),
+            owns_branch,
+        })
+    }
+
+    /// Returns the changes of a sandbox since it branched off the base,
+    /// including uncommitted ones
+    pub fn diff(&self, sandbox: &SandboxStatus, stat: bool) -> Result<String> {
+        let merge_base = git(&sandbox.path, &["merge-base", &self.base, "HEAD"])?;
+        let mut args = vec!["diff", merge_base.as_str()];
+        if stat {
+            args.push("--stat");
+        }
+        git(&sandbox.path, &args)
+    }
+
+    /// Merges the branch of a sandbox into the base branch
+    pub fn merge(&self, sandbox: &SandboxStatus, squash: bool) -> Result<()> {
+        let Some(branch) = &sandbox.branch else {
+            bail!("Sandbox '{}' has no branch to merge", sandbox.name);
+        };
+        if sandbox.dirty > 0 {
+            bail!(
+                "Sandbox '{}' has uncommitted changes. Commit them in {} first.",
+                sandbox.name,
+                sandbox.path.display()
+            );
+        }
+        match squash {
+            true => git(&self.root, &["merge", "--squash", branch])?,
+            false => git(&self.root, &["merge", "--no-ff", "--no-edit", branch])?,
+        };
+        Ok(())
+    }
+
+    /// Removes the worktree and branch of a sandbox. Work that is not on the
+    /// base branch is only discarded with `force`.
+    pub fn remove(&self, sandbox: &SandboxStatus, force: bool) -> Result<()> {
+        if !force && !sandbox.is_merged() {
+            bail!(
+                "Sandbox '{}' has {} unmerged commits and {} changed files. Use --force to discard them.",
+                sandbox.name,
+                sandbox.ahead,
+                sandbox.dirty
+            );
+        }
+        self.remove_worktree(sandbox, force)?;
+        if let Some(branch) = sandbox.branch.as_ref().filter(|_| sandbox.owns_branch) {
+            git(
+                &self.root,
+                &["branch", if force { "-D" } else { "-d" }, branch],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Removes the worktree of a sandbox but keeps its branch
+    pub fn remove_worktree(&self, sandbox: &SandboxStatus, force: bool) -> Result<()> {
+        if !sandbox.managed {
+            bail!(
+                "Worktree '{}' was not created with --sandbox",
+                sandbox.path.display()
+            );
+        }
+        if !force && sandbox.dirty > 0 {
+            bail!(
+                "Sandbox '{}' has {} changed files. Use --force to discard them.",
+                sandbox.name,
+                sandbox.dirty
+            );
+        }
+        let path = sandbox.path.display().to_string();
+        let mut args = vec!["worktree", "remove", path.as_str()];
+        if force {
+            args.push("--force");
+        }
+        git(&self.root, &args)?;
+        Ok(())
+    }
+
+    /// Selects the sandboxes a cleanup policy removes: merged ones with
+    /// `merged`, and ones unchanged since `cutoff` that hold no unmerged work
+    /// unless `force` is given. The sandbox `cwd` is in is never selected.
+    pub fn prunable(
+        &self,
+        merged: bool,
+        cutoff: Option<DateTime<Utc>>,
+        force: bool,
+        cwd: &Path,
+    ) -> Result<Vec<SandboxStatus>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|sandbox| {
+                let stale = cutoff.is_some_and(|cutoff| sandbox.updated_at < cutoff)
+                    && (force || sandbox.is_merged());
+                (merged && sandbox.is_merged() || stale) && !cwd.starts_with(&sandbox.path)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_worktrees() {
+        let fixture = "worktree /work/app\nHEAD 1111\nbranch refs/heads/main\n\nworktree /work/forge-fix\nHEAD 2222\nbranch refs/heads/forge-fix\n\nworktree /work/detached\nHEAD 3333\ndetached\n";
+
+        let actual = parse_worktrees(fixture);
+        let expected = vec![
+            Worktree {
+                path: PathBuf::from("/work/app"),
+                head: "1111".to_string(),
+                branch: Some("main".to_string()),
+            },
+            Worktree {
+                path: PathBuf::from("/work/forge-fix"),
+                head: "2222".to_string(),
+                branch: Some("forge-fix".to_string()),
+            },
+            Worktree {
+                path: PathBuf::from("/work/detached"),
+                head: "3333".to_string(),
+                branch: None,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_generate_name() {
+        let now = Local.with_ymd_and_hms(2025, 11, 23, 14, 2, 5).unwrap();
+
+        let actual = [
+            Sandbox::generate_name(Some("Fix the login bug in auth.rs, then test"), now),
+            Sandbox::generate_name(None, now),
+        ];
+        let expected = [
+            "forge-fix-the-login-bug-20251123-140205".to_string(),
+            "forge-20251123-140205".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    fn run(dir: &Path, args: &[&str]) -> String {
+        git(dir, args).unwrap()
+    }
+
+    fn commit(dir: &Path, file: &str) {
+        fs::write(dir.join(file), file).unwrap();
+        run(dir, &["add", file]);
+        run(dir, &["commit", "-m", file]);
+    }
+
+    /// A repository with one commit on `main`, in its own parent directory
+    fn repository() -> (tempfile::TempDir, PathBuf) {
+        let parent = tempfile::tempdir().unwrap();
+        let root = parent.path().canonicalize().unwrap().join("repo");
+        fs::create_dir(&root).unwrap();
+        run(&root, &["init", "-b", "main"]);
+        run(&root, &["config", "user.name", "forge"]);
+        run(&root, &["config", "user.email", "forge@example.com"]);
+        commit(&root, "README.md");
+        (parent, root)
+    }
+
+    #[test]
+    fn test_list_counts_commits_ahead_and_behind() {
+        let (_parent, root) = repository();
+        let path = Sandbox::new("forge-work").create_in(&root).unwrap();
+        commit(&path, "feature.rs");
+        commit(&path, "tests.rs");
+        commit(&root, "CHANGELOG.md");
+        fs::write(path.join("notes.md"), "draft").unwrap();
+
+        let actual = Sandboxes::open(&root)
+            .unwrap()
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|sandbox| (sandbox.name, sandbox.ahead, sandbox.behind, sandbox.dirty))
+            .collect::<Vec<_>>();
+
+        let expected = vec![("forge-work".to_string(), 2, 1, 1)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_list_ignores_worktrees_not_created_as_sandboxes() {
+        let (parent, root) = repository();
+        let user = parent.path().join("hotfix");
+        run(
+            &root,
+            &["worktree", "add", "-b", "hotfix", user.to_str().unwrap()],
+        );
+        Sandbox::new("forge-work").create_in(&root).unwrap();
+        let sandboxes = Sandboxes::open(&root).unwrap();
+
+        let actual = (
+            sandboxes
+                .list()
+                .unwrap()
+                .into_iter()
+                .map(|sandbox| sandbox.name)
+                .collect::<Vec<_>>(),
+            sandboxes.find("hotfix").is_err(),
+            sandboxes
+                .remove_worktree(&sandboxes.find_worktree("hotfix").unwrap(), true)
+                .is_err(),
+        );
+
+        let expected = (vec!["forge-work".to_string()], true, true);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_remove_refuses_unmerged_work_and_keeps_existing_branches() {
+        let (_parent, root) = repository();
+        run(&root, &["branch", "feature"]);
+        let reused = Sandbox::new("feature").create_in(&root).unwrap();
+        let path = Sandbox::new("forge-work").create_in(&root).unwrap();
+        commit(&path, "feature.rs");
+        let sandboxes = Sandboxes::open(&root).unwrap();
+
+        let refused = sandboxes
+            .remove(&sandboxes.find("forge-work").unwrap(), false)
+            .is_err();
+        sandboxes
+            .remove(&sandboxes.find("feature").unwrap(), false)
+            .unwrap();
+        let actual = (
+            refused,
+            path.exists(),
+            reused.exists(),
+            run(&root, &["branch", "--list", "feature"]).is_empty(),
+        );
+
+        let expected = (true, true, false, false);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_prune_removes_only_merged_sandboxes() {
+        let (_parent, root) = repository();
+        let merged = Sandbox::new("forge-merged").create_in(&root).unwrap();
+        commit(&merged, "done.rs");
+        let pending = Sandbox::new("forge-pending").create_in(&root).unwrap();
+        commit(&pending, "wip.rs");
+        let sandboxes = Sandboxes::open(&root).unwrap();
+        sandboxes
+            .merge(&sandboxes.find("forge-merged").unwrap(), false)
+            .unwrap();
+
+        let now = Utc::now() + chrono::Duration::days(1);
+        for sandbox in sandboxes.prunable(true, Some(now), false, &root).unwrap() {
+            sandboxes.remove(&sandbox, false).unwrap();
+        }
+        let actual = (
+            sandboxes
+                .list()
+                .unwrap()
+                .into_iter()
+                .map(|sandbox| sandbox.name)
+                .collect::<Vec<_>>(),
+            run(&root, &["branch", "--format=%(refname:short)"]),
+        );
+
+        let expected = (
+            vec!["forge-pending".to_string()],
+            "forge-pending\nmain".to_string(),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_remove_worktree_keeps_branch_of_squashed_sandbox() {
+        let (_parent, root) = repository();
+        let path = Sandbox::new("forge-work").create_in(&root).unwrap();
+        commit(&path, "feature.rs");
+        let sandboxes = Sandboxes::open(&root).unwrap();
+        let sandbox = sandboxes.find("forge-work").unwrap();
+        sandboxes.merge(&sandbox, true).unwrap();
+
+        sandboxes.remove_worktree(&sandbox, false).unwrap();
+        let actual = (
+            path.exists(),
+            run(&root, &["log", "-1", "--format=%s", "forge-work"]),
+        );
+
+        let expected = (false, "feature.rs".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_finish_in_removes_only_unchanged_sandboxes() {
+        let (_parent, root) = repository();
+        let unchanged = Sandbox::new("forge-unchanged").create_in(&root).unwrap();
+        let changed = Sandbox::new("forge-changed").create_in(&root).unwrap();
+        commit(&changed, "feature.rs");
+
+        Sandbox::new("forge-unchanged")
+            .finish_in(&root, true)
+            .unwrap();
+        Sandbox::new("forge-changed")
+            .finish_in(&root, true)
+            .unwrap();
+        let actual = (
+            unchanged.exists(),
+            changed.exists(),
+            run(&root, &["branch", "--format=%(refname:short)"]),
+        );
+
+        let expected = (false, true, "forge-changed\nmain".to_string());
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{Local, Utc};
+use forge_api::API;
+use forge_domain::{
+    ConversationId, Schedule, ScheduleId, SchedulePrompt, ScheduleRun, ScheduleRunStatus,
+    TitleFormat,
+};
+use tokio::process::Command;
+use tokio::task::{self, JoinError, JoinSet};
+
+use crate::sandbox::Sandbox;
+use crate::title_display::TitleDisplayExt;
+
+/// How often the daemon looks for due jobs
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Runs scheduled jobs as `forge -p` child processes and records their
+/// outcome.
+pub struct ScheduleRunner<A> {
+    api: Arc<A>,
+}
+
+impl<A: API + 'static> ScheduleRunner<A> {
+    pub fn new(api: Arc<A>) -> Self {
+        Self { api }
+    }
+
+    /// Runs a job once and returns the finished run. The child writes to
+    /// `log` when given, otherwise to the terminal. A sandbox the run leaves
+    /// without changes is removed afterwards.
+    pub async fn run(&self, schedule: &Schedule, log: Option<PathBuf>) -> Result<ScheduleRun> {
+        let mut run = ScheduleRun::new(schedule.id, ConversationId::generate());
+        if schedule.sandbox {
+            let sandbox = sandbox_name(schedule.id, &run);
+            run = run.sandbox(sandbox);
+        }
+        let mut run = self.api.start_schedule_run(run).await?;
+
+        match self.execute(schedule, &run, log).await {
+            Ok(code) => {
+                run.status = ScheduleRunStatus::from_exit_code(code);
+                if run.status == ScheduleRunStatus::Error {
+                    run.error = code.map(|code| format!("Exited with status {code}"));
+                }
+            }
+            Err(error) => {
+                run.status = ScheduleRunStatus::Error;
+                run.error = Some(format!("{error:#}"));
+            }
+        }
+        run.finished_at = Some(Utc::now());
+        self.api.finish_schedule_run(&run).await?;
+
+        if let Some(sandbox) = &run.sandbox
+            && let Err(error) = Sandbox::new(sandbox).finish_in(&schedule.cwd, true)
+        {
+            println!("{}", TitleFormat::error(format!("{error:#}")).display());
+        }
+        Ok(run)
+    }
+
+    async fn execute(
+        &self,
+        schedule: &Schedule,
+        run: &ScheduleRun,
+        log: Option<PathBuf>,
+    ) -> Result<Option<i32>> {
+        let prompt = match &schedule.prompt {
+            SchedulePrompt::Text(text) => text.clone(),
+            SchedulePrompt::File(path) => tokio::fs::read_to_string(path)
+                .await
+                .with_context(|| format!("Failed to read prompt file {}", path.display()))?,
+        };
+
+        // The sandbox is created and finished here rather than by the child, so
+        // that it can be cleaned up like a fresh `--sandbox`
+        let cwd = match &run.sandbox {
+            Some(sandbox) => Sandbox::new(sandbox).create_in(&schedule.cwd)?,
+            None => schedule.cwd.clone(),
+        };
+
+        let mut command = Command::new(std::env::current_exe()?);
+        command
+            .arg("--prompt")
+            .arg(prompt)
+            .arg("--conversation-id")
+            .arg(run.conversation_id.into_string())
+            .current_dir(cwd)
+            .stdin(Stdio::null());
+        if let Some(agent) = &schedule.agent {
+            command.arg("--agent").arg(agent.as_str());
+        }
+        if let Some(log) = log {
+            if let Some(parent) = log.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let file = std::fs::File::create(&log)
+                .with_context(|| format!("Failed to create log file {}", log.display()))?;
+            command.stdout(file.try_clone()?).stderr(file);
+        }
+
+        let status = command
+            .status()
+            .await
+            .context("Failed to start forge for the scheduled job")?;
+        Ok(status.code())
+    }
+
+    /// Runs due jobs until interrupted. Jobs missed while the daemon was not
+    /// running are run once when it starts.
+    pub async fn daemon(self) -> Result<()> {
+        let runner = Arc::new(self);
+        let log_dir = runner.api.environment().base_path.join("schedules");
+        // Jobs that are still running, by the task running them
+        let mut running = HashMap::new();
+        let mut tasks = JoinSet::new();
+
+        println!(
+            "{}",
+            TitleFormat::info("Schedule daemon started")
+                .sub_title(format!("logs in {}", log_dir.display()))
+                .display()
+        );
+
+        loop {
+            let now = Local::now();
+            for schedule in runner.api.get_schedules().await? {
+                // A job that is still running is not started again
+                if !schedule.is_due(&now) || running.values().any(|id| *id == schedule.id) {
+                    continue;
+                }
+
+                let runner = runner.clone();
+                let log = log_dir.join(format!(
+                    "{}-{}.log",
+                    schedule.id,
+                    now.format("%Y%m%d-%H%M%S")
+                ));
+                println!(
+                    "{}",
+                    TitleFormat::action(format!("Running job {}", schedule.id))
+                        .sub_title(log.display().to_string())
+                        .display()
+                );
+                let id = schedule.id;
+                let task = tasks.spawn(async move { runner.run(&schedule, Some(log)).await });
+                running.insert(task.id(), id);
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                Some(finished) = tasks.join_next_with_id() => print_finished(&mut running, finished),
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+
+        // Running children receive the interrupt too; wait for their outcome to
+        // be recorded
+        while let Some(finished) = tasks.join_next_with_id().await {
+            print_finished(&mut running, finished);
+        }
+        Ok(())
+    }
+}
+
+/// Prints the outcome of a job and forgets its task. A job whose task
+/// panicked is reported as failed, so that it doesn't stop the daemon.
+fn print_finished(
+    running: &mut HashMap<task::Id, ScheduleId>,
+    finished: Result<(task::Id, Result<ScheduleRun>), JoinError>,
+) {
+    let (task, result) = match finished {
+        Ok((task, result)) => (task, result),
+        Err(error) => (error.id(), Err(error.into())),
+    };
+    let Some(id) = running.remove(&task) else {
+        return;
+    };
+    let title = match result {
+        Ok(run) if run.status == ScheduleRunStatus::Success => {
+            TitleFormat::info(format!("Job {id} finished"))
+                .sub_title(run.conversation_id.into_string())
+        }
+        Ok(run) => TitleFormat::error(format!("Job {id} finished with {}", run.status))
+            .sub_title(run.conversation_id.into_string()),
+        Err(error) => TitleFormat::error(format!("Job {id} failed: {error:#}")),
+    };
+    println!("{}", title.display());
+}
+
+/// Names the worktree of a run after its job and start time, so that
+/// concurrent and repeated runs never share one
+fn sandbox_name(id: ScheduleId, run: &ScheduleRun) -> String {
+    format!(
+        "forge-schedule-{id}-{}",
+        run.started_at.with_timezone(&Local).format("%Y%m%d-%H%M%S")
+    )
+}
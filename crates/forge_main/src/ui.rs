@@ -17,11 +17,12 @@ use forge_app::utils::{format_display_path, truncate_key};
 use forge_app::{CommitResult, ToolResolver};
 use forge_display::MarkdownWriter;
 use forge_domain::{
-    AuthMethod, ChatResponseContent, ContextMessage, Role, TitleFormat, UserCommand,
+    AuthMethod, ChatResponseContent, ContextMessage, CronExpr, PlanFile, Role, Schedule,
+    ScheduleId, SchedulePrompt, ScheduleRun, Stage, TitleFormat, Todo, TodoStatus, UserCommand,
 };
 use forge_fs::ForgeFS;
 use forge_select::ForgeSelect;
-use forge_spinner::SpinnerManager;
+use forge_spinner::{ChecklistItem, ChecklistStatus, SpinnerManager, render_checklist};
 use forge_tracker::ToolCallPayload;
 use merge::Merge;
 use tokio_stream::StreamExt;
@@ -29,17 +30,23 @@ use tracing::debug;
 use url::Url;
 
 use crate::cli::{
-    Cli, CommitCommandGroup, ConversationCommand, ExtensionCommand, ListCommand, McpCommand,
-    TopLevelCommand,
+    Cli, CommitCommandGroup, ContextCommand, ConversationCommand, ExtensionCommand, ListCommand,
+    McpCommand, PlanCommand, SandboxCommand, ScheduleCommand, TopLevelCommand,
 };
 use crate::conversation_selector::ConversationSelector;
 use crate::display_constants::{CommandType, headers, markers, status};
 use crate::env::should_show_completion_prompt;
+use crate::event_output::{EventWriter, ExitStatus, RunResult};
 use crate::info::Info;
 use crate::input::Console;
+use crate::mcp_server::ForgeMcpServer;
 use crate::model::{CliModel, CliProvider, ForgeCommandManager, SlashCommand};
+use crate::pipeline::Pipeline;
 use crate::porcelain::Porcelain;
 use crate::prompt::ForgePrompt;
+use crate::proxy::ProxyServer;
+use crate::sandbox::Sandboxes;
+use crate::schedule::ScheduleRunner;
 use crate::state::UIState;
 use crate::sync_display::SyncProgressDisplay;
 use crate::title_display::TitleDisplayExt;
@@ -51,6 +58,22 @@ use crate::{TRACKER, banner, tracker};
 // File-specific constants
 const MISSING_AGENT_TITLE: &str = "<missing agent.title>";
 
+/// Converts the task list of a conversation into the checklist drawn above
+/// the spinner
+fn checklist(todos: &[Todo]) -> Vec<ChecklistItem> {
+    todos
+        .iter()
+        .map(|todo| {
+            let status = match todo.status {
+                TodoStatus::Pending => ChecklistStatus::Pending,
+                TodoStatus::InProgress => ChecklistStatus::Active,
+                TodoStatus::Done => ChecklistStatus::Done,
+            };
+            ChecklistItem::new(&todo.content, status)
+        })
+        .collect()
+}
+
 /// Formats an MCP server config for display, redacting sensitive information.
 /// Returns the command/URL string only.
 fn format_mcp_server(server: &forge_domain::McpServerConfig) -> String {
@@ -69,6 +92,26 @@ fn format_mcp_server(server: &forge_domain::McpServerConfig) -> String {
     }
 }
 
+/// Builds the health report shown by `forge mcp status`.
+fn format_mcp_status(statuses: &[forge_domain::McpServerStatus]) -> Info {
+    let mut info = Info::new();
+    for status in statuses {
+        info = info
+            .add_title(status.name.to_uppercase())
+            .add_key_value("State", status.state.to_string().to_lowercase());
+        if let Some(latency) = status.latency {
+            info = info.add_key_value("Latency", format!("{}ms", latency.as_millis()));
+        }
+        if status.state == forge_domain::McpConnectionState::Connected {
+            info = info.add_key_value("Tools", status.tools.to_string());
+        }
+        if let Some(error) = &status.last_error {
+            info = info.add_key_value("Last Error", error);
+        }
+    }
+    info
+}
+
 /// Formats HTTP headers for display, redacting values.
 /// Returns None if there are no headers.
 fn format_mcp_headers(server: &forge_domain::McpServerConfig) -> Option<String> {
@@ -90,6 +133,65 @@ fn format_mcp_headers(server: &forge_domain::McpServerConfig) -> Option<String>
     }
 }
 
+/// Takes the authorization code from what the user pasted, which is either
+/// the code itself or the URL the browser was redirected to.
+fn authorization_code(input: &str, request: &CodeRequest) -> anyhow::Result<String> {
+    let input = input.trim();
+    let Ok(url) = Url::parse(input) else {
+        return Ok(input.to_string());
+    };
+
+    let params: HashMap<_, _> = url.query_pairs().collect();
+    if let Some(error) = params.get("error") {
+        anyhow::bail!("Authorization failed: {error}");
+    }
+    if params.get("state").map(|state| state.as_ref()) != Some(request.state.as_str()) {
+        anyhow::bail!("Authorization response does not match the request");
+    }
+    params
+        .get("code")
+        .map(|code| code.to_string())
+        .context("Redirect URL has no authorization code")
+}
+
+/// Lists the resources and prompts published by an MCP server.
+fn format_mcp_catalog(servers: &forge_domain::McpServers, name: &forge_api::ServerName) -> Info {
+    let mut info = Info::new();
+
+    if let Some(resources) = servers.get_resources().get(name) {
+        info = info.add_title("RESOURCES");
+        for resource in resources {
+            let value = format!("@[mcp:{name}/{}]", resource.uri);
+            info = info.add_key_value(&resource.name, value);
+        }
+    }
+
+    if let Some(prompts) = servers.get_prompts().get(name) {
+        info = info.add_title("PROMPTS");
+        for prompt in prompts {
+            let arguments = prompt
+                .arguments
+                .iter()
+                .map(|arg| {
+                    if arg.required {
+                        format!("<{}>", arg.name)
+                    } else {
+                        format!("[{}]", arg.name)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            info = if arguments.is_empty() {
+                info.add_value(&prompt.name)
+            } else {
+                info.add_key_value(&prompt.name, arguments)
+            };
+        }
+    }
+
+    info
+}
+
 pub struct UI<A, F: Fn() -> A> {
     markdown: MarkdownWriter,
     state: UIState,
@@ -100,6 +202,8 @@ pub struct UI<A, F: Fn() -> A> {
     cli: Cli,
     spinner: SpinnerManager,
     ctrl_c_rx: tokio::sync::broadcast::Receiver<()>,
+    events: Option<EventWriter>,
+    exit_status: ExitStatus,
     #[allow(dead_code)] // The guard is kept alive by being held in the struct
     _guard: forge_tracker::Guard,
 }
@@ -107,13 +211,19 @@ pub struct UI<A, F: Fn() -> A> {
 impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
     /// Writes a line to the console output
     /// Takes anything that implements ToString trait
+    ///
+    /// Human-readable output goes to stderr when stdout carries structured
+    /// events.
     fn writeln<T: ToString>(&mut self, content: T) -> anyhow::Result<()> {
+        if self.events.is_some() {
+            return self.spinner.ewrite_ln(content);
+        }
         self.spinner.write_ln(content)
     }
 
     /// Writes a TitleFormat to the console output with proper formatting
     fn writeln_title(&mut self, title: TitleFormat) -> anyhow::Result<()> {
-        self.spinner.write_ln(title.display())
+        self.writeln(title.display())
     }
 
     fn writeln_to_stderr(&mut self, title: String) -> anyhow::Result<()> {
@@ -215,8 +325,15 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         let api = Arc::new(f());
         let env = api.environment();
         let command = Arc::new(ForgeCommandManager::default());
+        let events = cli.event_format().map(EventWriter::new);
         let mut spinner = SpinnerManager::new();
-        let ctrl_c_rx = spinner.init()?;
+        // The spinner owns the terminal, so it stays uninitialized when stdout
+        // carries structured events or a protocol stream.
+        let ctrl_c_rx = if events.is_some() || cli.is_stdio_server() {
+            tokio::sync::broadcast::channel(1).1
+        } else {
+            spinner.init()?
+        };
         Ok(Self {
             state: Default::default(),
             api,
@@ -226,22 +343,31 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
             command,
             spinner,
             ctrl_c_rx,
+            events,
+            exit_status: ExitStatus::default(),
             markdown: MarkdownWriter::new(),
             _guard: forge_tracker::init_tracing(env.log_path(), TRACKER.clone())?,
         })
     }
 
     async fn prompt(&self) -> Result<SlashCommand> {
-        // Get usage from current conversation if available
-        let usage = if let Some(conversation_id) = &self.state.conversation_id {
-            self.api
-                .conversation(conversation_id)
+        // Get usage and plan progress from current conversation if available
+        let conversation = match &self.state.conversation_id {
+            Some(conversation_id) => self.api.conversation(conversation_id).await.ok().flatten(),
+            None => None,
+        };
+        let usage = conversation
+            .as_ref()
+            .and_then(|conv| conv.accumulated_usage());
+        let plan = match conversation.and_then(|conv| conv.metrics.plan) {
+            Some(path) => self
+                .api
+                .get_plan(&path)
                 .await
                 .ok()
-                .flatten()
-                .and_then(|conv| conv.accumulated_usage())
-        } else {
-            None
+                .filter(|plan| !plan.steps.is_empty())
+                .map(|plan| plan.progress()),
+            None => None,
         };
 
         // Prompt the user for input
@@ -249,13 +375,15 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         let model = self
             .get_agent_model(self.api.get_active_agent().await)
             .await;
-        let forge_prompt = ForgePrompt { cwd: self.state.cwd.clone(), usage, model, agent_id };
+        let forge_prompt =
+            ForgePrompt { cwd: self.state.cwd.clone(), usage, model, agent_id, plan };
         self.console.prompt(forge_prompt).await
     }
 
-    pub async fn run(&mut self) {
-        match self.run_inner().await {
-            Ok(_) => {}
+    /// Runs the session and returns the status the process should exit with.
+    pub async fn run(&mut self) -> ExitStatus {
+        let (status, error) = match self.run_inner().await {
+            Ok(_) => (self.exit_status, None),
             Err(error) => {
                 tracing::error!(error = ?error);
 
@@ -267,10 +395,71 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
                     source = err.source();
                 }
 
-                let _ =
-                    self.writeln_to_stderr(TitleFormat::error(error_message).display().to_string());
+                let _ = self
+                    .writeln_to_stderr(TitleFormat::error(&error_message).display().to_string());
+                (ExitStatus::Error, Some(error_message))
+            }
+        };
+
+        if let Err(error) = self.finish_events(status, error).await {
+            tracing::error!(error = ?error, "Failed to write structured output");
+        }
+
+        status
+    }
+
+    /// Writes the trailing structured output, if enabled, with the final
+    /// status and accumulated usage of the run.
+    async fn finish_events(&mut self, status: ExitStatus, error: Option<String>) -> Result<()> {
+        let Some(events) = self.events.take() else {
+            return Ok(());
+        };
+
+        let mut result = RunResult::new(status);
+        result.error = error;
+        result.conversation_id = self.state.conversation_id;
+        result.usage = self.accumulated_usage().await;
+
+        println!("{}", events.finish(result)?);
+        Ok(())
+    }
+
+    /// Accumulated usage of the current conversation, if any.
+    async fn accumulated_usage(&self) -> Option<forge_domain::Usage> {
+        let conversation_id = self.state.conversation_id.as_ref()?;
+        self.api
+            .conversation(conversation_id)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|conv| conv.accumulated_usage())
+    }
+
+    /// Sends a single message in non-interactive mode, treating Ctrl+C as an
+    /// interruption of the run.
+    async fn on_message_once(&mut self, content: String) -> Result<()> {
+        self.run_once(async move |ui| ui.on_message(Some(content)).await)
+            .await
+    }
+
+    /// Runs a non-interactive request, treating Ctrl+C as an interruption of
+    /// the run.
+    async fn run_once(&mut self, run: impl AsyncFnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let mut ctrl_c_rx = self.ctrl_c_rx.resubscribe();
+        self.spinner.start(None)?;
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                tracing::info!("User interrupted operation with Ctrl+C");
+                self.exit_status = ExitStatus::Interrupted;
             }
+            Ok(()) = ctrl_c_rx.recv() => {
+                tracing::info!("User interrupted operation with Ctrl+C (spinner)");
+                self.exit_status = ExitStatus::Interrupted;
+            }
+            result = run(self) => result?,
         }
+        self.spinner.stop(None)?;
+        Ok(())
     }
 
     async fn run_inner(&mut self) -> Result<()> {
@@ -280,10 +469,16 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
 
         // Display the banner in dimmed colors since we're in interactive mode
         self.display_banner()?;
-        self.init_state(true).await?;
+        let workflow = self.init_state(true).await?;
 
         self.trace_user();
         self.hydrate_caches();
+
+        // A workflow with stages runs as a pipeline instead of a conversation
+        if self.cli.workflow.is_some() && !workflow.stages.is_empty() {
+            return self.on_pipeline(workflow.stages).await;
+        }
+
         self.init_conversation().await?;
 
         // Check for dispatch flag first
@@ -294,17 +489,13 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         // Handle direct prompt if provided (raw text messages)
         let prompt = self.cli.prompt.clone();
         if let Some(prompt) = prompt {
-            self.spinner.start(None)?;
-            self.on_message(Some(prompt)).await?;
-            return Ok(());
+            return self.on_message_once(prompt).await;
         }
 
         // Handle piped input if provided (treat it like --prompt)
         let piped_input = self.cli.piped_input.clone();
         if let Some(piped) = piped_input {
-            self.spinner.start(None)?;
-            self.on_message(Some(piped)).await?;
-            return Ok(());
+            return self.on_message_once(piped).await;
         }
 
         // Create a local receiver for Ctrl+C events to avoid borrowing self in the loop
@@ -362,7 +553,12 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         let api = self.api.clone();
         tokio::spawn(async move { api.get_models().await });
         let api = self.api.clone();
-        tokio::spawn(async move { api.get_tools().await });
+        let command = self.command.clone();
+        tokio::spawn(async move {
+            if let Ok(tools) = api.get_tools().await {
+                command.register_mcp_prompts(tools.mcp.get_prompts());
+            }
+        });
         let api = self.api.clone();
         tokio::spawn(async move { api.get_agents().await });
         let api = self.api.clone();
@@ -500,12 +696,34 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
                     if let Some(error) = tools.mcp.get_failures().get(&name) {
                         self.writeln_title(TitleFormat::error(error))?;
                     }
+
+                    let info = format_mcp_catalog(&tools.mcp, &name);
+                    if !info.sections().is_empty() {
+                        self.writeln(info)?;
+                    }
+                }
+                McpCommand::Status => {
+                    self.on_mcp_status(mcp_command.porcelain).await?;
                 }
                 McpCommand::Reload => {
                     self.spinner.start(Some("Reloading MCPs"))?;
                     self.api.reload_mcp().await?;
                     self.writeln_title(TitleFormat::info("MCP reloaded"))?;
                 }
+                McpCommand::Serve => {
+                    ForgeMcpServer::new(self.api.clone()).serve_stdio().await?;
+                }
+                McpCommand::Login(args) => {
+                    self.on_mcp_login(forge_api::ServerName::from(args.name))
+                        .await?;
+                }
+                McpCommand::Logout(args) => {
+                    let name = forge_api::ServerName::from(args.name);
+                    self.api.logout_mcp(&name).await?;
+                    self.writeln_title(TitleFormat::info(format!(
+                        "Logged out from MCP server '{name}'"
+                    )))?;
+                }
             },
             TopLevelCommand::Info { porcelain, conversation_id } => {
                 // Make sure to init model
@@ -627,6 +845,370 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
                     self.writeln(data?)?;
                 }
             }
+            TopLevelCommand::Eval(_) => {
+                // Each task needs its own working directory, so suites are run
+                // by `run_eval` before the session starts.
+                anyhow::bail!("Eval suites cannot be run from a session");
+            }
+            TopLevelCommand::Proxy(group) => {
+                let token = group
+                    .token
+                    .or_else(|| std::env::var("FORGE_PROXY_TOKEN").ok())
+                    .filter(|token| !token.is_empty());
+                ProxyServer::new(self.api.clone(), token)
+                    .serve(std::net::SocketAddr::new(group.host, group.port))
+                    .await?;
+            }
+            TopLevelCommand::Plan(group) => {
+                self.handle_plan_command(group.command).await?;
+            }
+            TopLevelCommand::Schedule(group) => {
+                self.handle_schedule_command(group.command).await?;
+            }
+            TopLevelCommand::Sandbox(group) => {
+                self.handle_sandbox_command(group.command)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_plan_command(&mut self, command: PlanCommand) -> anyhow::Result<()> {
+        match command {
+            PlanCommand::List { porcelain } => self.on_show_plans(porcelain).await,
+            PlanCommand::Show { path } => {
+                let plan = self.api.get_plan(&self.state.cwd.join(path)).await?;
+                self.on_show_plan(plan)
+            }
+            PlanCommand::Resume { path } => self.on_resume_plan(path).await,
+        }
+    }
+
+    async fn on_show_plans(&mut self, porcelain: bool) -> anyhow::Result<()> {
+        let plans = self.api.get_plans().await?;
+        if plans.is_empty() {
+            return Ok(());
+        }
+
+        let mut info = Info::new();
+        for plan in plans {
+            let next = plan
+                .next_step()
+                .map(|step| step.text.clone())
+                .unwrap_or_else(|| markers::EMPTY.to_string());
+            info = info
+                .add_title(format_display_path(&plan.path, &self.state.cwd))
+                .add_key_value("Progress", plan.progress().to_string())
+                .add_key_value("Next", next);
+        }
+
+        if porcelain {
+            let porcelain = Porcelain::from(&info).truncate(2, 60).uppercase_headers();
+            self.writeln(porcelain)?;
+        } else {
+            self.writeln(info)?;
+        }
+
+        Ok(())
+    }
+
+    fn on_show_plan(&mut self, plan: PlanFile) -> anyhow::Result<()> {
+        let mut info = Info::new().add_title(format!(
+            "{} [{}]",
+            format_display_path(&plan.path, &self.state.cwd),
+            plan.progress()
+        ));
+        for step in &plan.steps {
+            info = info.add_key_value(
+                format!("{}. {}", step.number, step.status),
+                step.text.as_str(),
+            );
+        }
+        self.writeln(info)
+    }
+
+    /// Continues the conversation that last worked on the plan, or starts a
+    /// new one, and asks the agent to carry on from the next unfinished step.
+    async fn on_resume_plan(&mut self, path: PathBuf) -> anyhow::Result<()> {
+        let path = self.state.cwd.join(path);
+        let plan = self.api.get_plan(&path).await?;
+        if plan.is_complete() {
+            self.writeln_title(TitleFormat::info(format!(
+                "Plan is already complete: {}",
+                format_display_path(&plan.path, &self.state.cwd)
+            )))?;
+            return Ok(());
+        }
+
+        self.init_state(true).await?;
+
+        let max_conversations = self.api.environment().max_conversations;
+        let conversation = self
+            .api
+            .get_conversations(Some(max_conversations))
+            .await?
+            .into_iter()
+            .find(|conversation| conversation.metrics.plan.as_deref() == Some(path.as_path()));
+        if let Some(conversation) = conversation {
+            self.cli.conversation_id = Some(conversation.id);
+        }
+
+        // Plans are written by the planning agent but carried out by forge
+        let agent = self.cli.agent.clone().unwrap_or(AgentId::FORGE);
+        self.api.set_active_agent(agent).await?;
+        self.init_conversation().await?;
+
+        self.on_message_once(format!(
+            "Resume the plan at `{}`: call `plan_status` to find the next unfinished step, then work through the remaining steps, marking each with `plan_update_step` as you go.",
+            path.display()
+        ))
+        .await
+    }
+
+    async fn handle_schedule_command(&mut self, command: ScheduleCommand) -> anyhow::Result<()> {
+        match command {
+            ScheduleCommand::Add { cron, agent, prompt, prompt_file, no_sandbox } => {
+                let cwd = self.api.environment().cwd;
+                let prompt = match (prompt, prompt_file) {
+                    (_, Some(path)) => SchedulePrompt::File(cwd.join(path)),
+                    (Some(text), None) => SchedulePrompt::Text(text),
+                    (None, None) => anyhow::bail!("Provide either --prompt or --prompt-file"),
+                };
+                let mut schedule =
+                    Schedule::new(CronExpr::from_str(&cron)?, prompt, cwd).sandbox(!no_sandbox);
+                schedule.agent = agent;
+                let schedule = self.api.add_schedule(schedule).await?;
+                let next = schedule
+                    .next_run(&chrono::Local)
+                    .map(|next| next.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| markers::EMPTY.to_string());
+                self.writeln_title(
+                    TitleFormat::info(format!("Scheduled job {}", schedule.id))
+                        .sub_title(format!("next run {next}")),
+                )?;
+            }
+            ScheduleCommand::List { porcelain } => self.on_show_schedules(porcelain).await?,
+            ScheduleCommand::Remove { id } => {
+                if !self.api.remove_schedule(id).await? {
+                    anyhow::bail!("No scheduled job with id {id}");
+                }
+                self.writeln_title(TitleFormat::info(format!("Removed scheduled job {id}")))?;
+            }
+            ScheduleCommand::RunNow { id } => {
+                let schedule = self.get_schedule(id).await?;
+                let run = ScheduleRunner::new(self.api.clone())
+                    .run(&schedule, None)
+                    .await?;
+                self.on_show_schedule_runs(vec![run], false)?;
+            }
+            ScheduleCommand::Runs { id, limit, porcelain } => {
+                self.get_schedule(id).await?;
+                let runs = self.api.get_schedule_runs(id, limit).await?;
+                self.on_show_schedule_runs(runs, porcelain)?;
+            }
+            ScheduleCommand::Daemon => ScheduleRunner::new(self.api.clone()).daemon().await?,
+        }
+        Ok(())
+    }
+
+    async fn get_schedule(&self, id: ScheduleId) -> anyhow::Result<Schedule> {
+        self.api
+            .get_schedules()
+            .await?
+            .into_iter()
+            .find(|schedule| schedule.id == id)
+            .with_context(|| format!("No scheduled job with id {id}"))
+    }
+
+    async fn on_show_schedules(&mut self, porcelain: bool) -> anyhow::Result<()> {
+        let schedules = self.api.get_schedules().await?;
+        if schedules.is_empty() {
+            return Ok(());
+        }
+
+        let format_time = |time: Option<chrono::DateTime<chrono::Local>>| {
+            time.map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| markers::EMPTY.to_string())
+        };
+        let cwd = self.api.environment().cwd;
+        let mut info = Info::new();
+        for schedule in schedules {
+            let prompt = match &schedule.prompt {
+                SchedulePrompt::Text(text) => text.clone(),
+                SchedulePrompt::File(path) => format_display_path(path, &cwd),
+            };
+            info = info
+                .add_title(schedule.id)
+                .add_key_value("Cron", schedule.cron.to_string())
+                .add_key_value("Next", format_time(schedule.next_run(&chrono::Local)))
+                .add_key_value("Last", format_time(schedule.last_run_at.map(Into::into)))
+                .add_key_value(
+                    "Agent",
+                    schedule
+                        .agent
+                        .map(|agent| agent.to_string())
+                        .unwrap_or_else(|| markers::EMPTY.to_string()),
+                )
+                .add_key_value("Prompt", prompt)
+                .add_key_value("Directory", schedule.cwd.display().to_string())
+                .add_key_value("Sandbox", schedule.sandbox.to_string());
+        }
+
+        if porcelain {
+            let porcelain = Porcelain::from(&info).truncate(6, 60).uppercase_headers();
+            self.writeln(porcelain)?;
+        } else {
+            self.writeln(info)?;
+        }
+        Ok(())
+    }
+
+    fn on_show_schedule_runs(
+        &mut self,
+        runs: Vec<ScheduleRun>,
+        porcelain: bool,
+    ) -> anyhow::Result<()> {
+        if runs.is_empty() {
+            return Ok(());
+        }
+
+        let mut info = Info::new();
+        for run in runs {
+            let duration = run
+                .finished_at
+                .map(|finished_at| {
+                    let duration = (finished_at - run.started_at).num_seconds().max(0) as u64;
+                    humantime::format_duration(Duration::from_secs(duration)).to_string()
+                })
+                .unwrap_or_else(|| markers::EMPTY.to_string());
+            info = info
+                .add_title(run.conversation_id)
+                .add_key_value("Status", run.status.to_string())
+                .add_key_value(
+                    "Started",
+                    run.started_at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string(),
+                )
+                .add_key_value("Duration", duration)
+                .add_key_value(
+                    "Sandbox",
+                    run.sandbox.unwrap_or_else(|| markers::EMPTY.to_string()),
+                )
+                .add_key_value(
+                    "Error",
+                    run.error.unwrap_or_else(|| markers::EMPTY.to_string()),
+                );
+        }
+
+        if porcelain {
+            let porcelain = Porcelain::from(&info).uppercase_headers();
+            self.writeln(porcelain)?;
+        } else {
+            self.writeln(info)?;
+        }
+        Ok(())
+    }
+
+    fn handle_sandbox_command(&mut self, command: SandboxCommand) -> anyhow::Result<()> {
+        let cwd = self.api.environment().cwd;
+        let sandboxes = Sandboxes::open(&cwd)?;
+        match command {
+            SandboxCommand::List { porcelain } => {
+                self.on_show_sandboxes(&sandboxes, porcelain)?;
+            }
+            SandboxCommand::Diff { name, stat } => {
+                let sandbox = sandboxes.find(&name)?;
+                self.writeln(sandboxes.diff(&sandbox, stat)?)?;
+            }
+            SandboxCommand::Merge { name, squash, remove } => {
+                let sandbox = sandboxes.find(&name)?;
+                sandboxes.merge(&sandbox, squash)?;
+                self.writeln_title(
+                    TitleFormat::info(format!("Merged sandbox {}", sandbox.name))
+                        .sub_title(format!("into {}", sandboxes.base())),
+                )?;
+                if squash {
+                    self.writeln_title(TitleFormat::info(
+                        "The changes are staged, commit them to finish the squash",
+                    ))?;
+                }
+                if remove {
+                    let sandbox = sandboxes.find(&name)?;
+                    match (squash, &sandbox.branch) {
+                        // Until the staged changes are committed, the branch is their only copy
+                        (true, Some(branch)) => {
+                            sandboxes.remove_worktree(&sandbox, false)?;
+                            self.writeln_title(
+                                TitleFormat::info(format!("Removed sandbox {}", sandbox.name))
+                                    .sub_title(format!("kept branch {branch}")),
+                            )?;
+                        }
+                        _ => {
+                            sandboxes.remove(&sandbox, false)?;
+                            self.writeln_title(TitleFormat::info(format!(
+                                "Removed sandbox {}",
+                                sandbox.name
+                            )))?;
+                        }
+                    }
+                }
+            }
+            SandboxCommand::Remove { name, force } => {
+                let sandbox = sandboxes.find(&name)?;
+                sandboxes.remove(&sandbox, force)?;
+                self.writeln_title(TitleFormat::info(format!(
+                    "Removed sandbox {}",
+                    sandbox.name
+                )))?;
+            }
+            SandboxCommand::Prune { merged, older_than, force, dry_run } => {
+                let merged = merged || older_than.is_none();
+                let cutoff = older_than.map(|duration| chrono::Utc::now() - duration);
+                for sandbox in sandboxes.prunable(merged, cutoff, force, &cwd)? {
+                    let action = match dry_run {
+                        true => "Would remove",
+                        false => {
+                            sandboxes.remove(&sandbox, force)?;
+                            "Removed"
+                        }
+                    };
+                    self.writeln_title(
+                        TitleFormat::info(format!("{action} sandbox {}", sandbox.name))
+                            .sub_title(sandbox.path.display().to_string()),
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn on_show_sandboxes(&mut self, sandboxes: &Sandboxes, porcelain: bool) -> anyhow::Result<()> {
+        let list = sandboxes.list()?;
+        if list.is_empty() {
+            return Ok(());
+        }
+
+        let mut info = Info::new();
+        for sandbox in list {
+            info = info
+                .add_title(&sandbox.name)
+                .add_key_value(
+                    "Branch",
+                    sandbox.branch.unwrap_or_else(|| markers::EMPTY.to_string()),
+                )
+                .add_key_value("Ahead", sandbox.ahead.to_string())
+                .add_key_value("Behind", sandbox.behind.to_string())
+                .add_key_value("Changed", sandbox.dirty.to_string())
+                .add_key_value("Updated", humanize_time(sandbox.updated_at))
+                .add_key_value("Path", sandbox.path.display().to_string());
+        }
+
+        if porcelain {
+            let porcelain = Porcelain::from(&info).uppercase_headers();
+            self.writeln(porcelain)?;
+        } else {
+            self.writeln(info)?;
         }
         Ok(())
     }
@@ -705,6 +1287,12 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
 
                 self.on_show_conv_stats(conversation, porcelain).await?;
             }
+            ConversationCommand::Context { id, command } => {
+                self.validate_conversation_exists(&id).await?;
+
+                self.on_context_command(id, command.unwrap_or_default())
+                    .await?;
+            }
             ConversationCommand::Clone { id, porcelain } => {
                 let conversation = self.validate_conversation_exists(&id).await?;
 
@@ -1164,7 +1752,8 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         for command in custom_commands {
             info = info
                 .add_title(command.name.clone())
-                .add_key_value("description", command.description.clone());
+                .add_key_value("description", command.description.clone())
+                .add_key_value("usage", command.usage());
         }
 
         if porcelain {
@@ -1270,6 +1859,21 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
     }
 
     /// Displays all MCP servers with their available tools
+    async fn on_mcp_status(&mut self, porcelain: bool) -> anyhow::Result<()> {
+        self.spinner.start(Some("Checking MCP servers"))?;
+        let statuses = self.api.get_mcp_status().await?;
+        self.spinner.stop(None)?;
+
+        let info = format_mcp_status(&statuses);
+        if porcelain {
+            self.writeln(Porcelain::from(&info).uppercase_headers().truncate(4, 60))?;
+        } else {
+            self.writeln(info)?;
+        }
+
+        Ok(())
+    }
+
     async fn on_show_mcp_servers(&mut self, porcelain: bool) -> anyhow::Result<()> {
         self.spinner.start(Some("Loading MCP servers"))?;
         let mcp_servers = self.api.read_mcp_config(None).await?;
@@ -1677,6 +2281,10 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
                 self.spinner.start(None)?;
                 self.on_message(None).await?;
             }
+            SlashCommand::Context(command) => {
+                let conversation_id = self.init_conversation().await?;
+                self.on_context_command(conversation_id, command).await?;
+            }
             SlashCommand::Index => {
                 let working_dir = self.state.cwd.clone();
                 // Use default batch size of 10 for slash command
@@ -1695,10 +2303,34 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
                     ));
                 }
             }
+            SlashCommand::McpPrompt { server, prompt, arguments } => {
+                self.spinner.start(Some("Loading prompt"))?;
+                self.on_mcp_prompt(forge_api::ServerName::from(server), prompt, arguments)
+                    .await?;
+            }
         }
 
         Ok(false)
     }
+
+    /// Renders a prompt published by an MCP server and sends it as a message.
+    async fn on_mcp_prompt(
+        &mut self,
+        server: forge_api::ServerName,
+        prompt: String,
+        arguments: Vec<String>,
+    ) -> Result<()> {
+        let servers = self.api.get_tools().await?.mcp;
+        let definition = servers
+            .get_prompts()
+            .get(&server)
+            .and_then(|prompts| prompts.iter().find(|p| p.name == prompt))
+            .ok_or_else(|| anyhow::anyhow!("Prompt '{prompt}' not found on MCP server {server}"))?;
+
+        let arguments = definition.bind_arguments(&arguments);
+        let content = self.api.get_mcp_prompt(&server, &prompt, arguments).await?;
+        self.on_message(Some(content)).await
+    }
     async fn on_compaction(&mut self) -> Result<(), anyhow::Error> {
         let conversation_id = self.init_conversation().await?;
         let compaction_result = self.api.compact_conversation(&conversation_id).await?;
@@ -1711,6 +2343,142 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         Ok(())
     }
 
+    async fn on_context_command(
+        &mut self,
+        conversation_id: ConversationId,
+        command: ContextCommand,
+    ) -> anyhow::Result<()> {
+        match command {
+            ContextCommand::List { porcelain } => {
+                self.on_show_context(conversation_id, porcelain).await
+            }
+            ContextCommand::Pin { index } => {
+                self.on_context_edit(conversation_id, |context| {
+                    context.pin_message(index, true)?;
+                    Ok(format!("Pinned message {index}"))
+                })
+                .await
+            }
+            ContextCommand::Unpin { index } => {
+                self.on_context_edit(conversation_id, |context| {
+                    context.pin_message(index, false)?;
+                    Ok(format!("Unpinned message {index}"))
+                })
+                .await
+            }
+            ContextCommand::Drop { index } => {
+                self.on_context_edit(conversation_id, |context| {
+                    context.drop_message(index)?;
+                    Ok(format!("Dropped message {index}"))
+                })
+                .await
+            }
+            ContextCommand::Stub { index } => {
+                self.on_context_edit(conversation_id, |context| {
+                    context.stub_tool_output(index)?;
+                    Ok(format!(
+                        "Replaced the output of message {index} with a stub"
+                    ))
+                })
+                .await
+            }
+            ContextCommand::Summarize { start, end } => {
+                self.spinner.start(Some("Summarizing"))?;
+                let result = self
+                    .api
+                    .compact_conversation_range(&conversation_id, start, end)
+                    .await?;
+                self.writeln_title(TitleFormat::action(format!(
+                    "Summarized messages {start} to {end}, context size reduced by {:.1}% (tokens)",
+                    result.token_reduction_percentage()
+                )))
+            }
+        }
+    }
+
+    /// Applies `edit` to the conversation context and persists it
+    async fn on_context_edit(
+        &mut self,
+        conversation_id: ConversationId,
+        edit: impl FnOnce(&mut forge_domain::Context) -> forge_domain::Result<String>,
+    ) -> anyhow::Result<()> {
+        let mut conversation = self.validate_conversation_exists(&conversation_id).await?;
+        let context = conversation
+            .context
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Conversation '{conversation_id}' has no messages"))?;
+
+        let message = edit(context)?;
+
+        self.api.upsert_conversation(conversation).await?;
+        self.writeln_title(TitleFormat::action(message))
+    }
+
+    /// Lists the messages in the conversation context with their estimated
+    /// token counts
+    async fn on_show_context(
+        &mut self,
+        conversation_id: ConversationId,
+        porcelain: bool,
+    ) -> anyhow::Result<()> {
+        let conversation = self.validate_conversation_exists(&conversation_id).await?;
+        let Some(context) = conversation.context else {
+            return Ok(());
+        };
+
+        let mut info = Info::new();
+        for (index, entry) in context.messages.iter().enumerate() {
+            let (role, content) = match &entry.message {
+                ContextMessage::Text(message) => {
+                    let calls = message
+                        .tool_calls
+                        .iter()
+                        .flatten()
+                        .map(|call| call.name.to_string())
+                        .collect::<Vec<_>>();
+                    let content = if calls.is_empty() {
+                        message.content.clone()
+                    } else {
+                        format!("{} [calls: {}]", message.content, calls.join(", "))
+                    };
+                    (message.role.to_string().to_lowercase(), content)
+                }
+                ContextMessage::Tool(result) => (
+                    "tool".to_string(),
+                    format!(
+                        "{}: {}",
+                        result.name,
+                        result.output.as_str().unwrap_or_default()
+                    ),
+                ),
+                ContextMessage::Image(_) => ("image".to_string(), String::new()),
+                ContextMessage::Document(document) => {
+                    ("document".to_string(), document.title().to_string())
+                }
+            };
+            let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+            let content = match content.char_indices().nth(80) {
+                Some((end, _)) => format!("{}…", &content[..end]),
+                None => content,
+            };
+
+            info = info
+                .add_title(index)
+                .add_key_value("Role", role)
+                .add_key_value("Tokens", entry.token_count_approx().to_string())
+                .add_key_value("Pinned", if entry.pinned { "yes" } else { "no" })
+                .add_key_value("Content", content);
+        }
+
+        if porcelain {
+            self.writeln(Porcelain::from(&info).uppercase_headers())?;
+        } else {
+            self.writeln(info)?;
+        }
+
+        Ok(())
+    }
+
     async fn handle_delete_conversation(&mut self) -> anyhow::Result<()> {
         let conversation_id = self.init_conversation().await?;
         self.on_conversation_delete(conversation_id).await?;
@@ -1924,13 +2692,42 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         provider_id: ProviderId,
         request: &CodeRequest,
     ) -> anyhow::Result<()> {
+        let code = self
+            .prompt_authorization_code(&format!("{provider_id} account"), request)
+            .await?;
+
+        self.spinner
+            .start(Some("Exchanging authorization code..."))?;
+
+        let response = AuthContextResponse::code(request.clone(), &code);
+
+        self.api
+            .complete_provider_auth(
+                provider_id,
+                response,
+                Duration::from_secs(0), // No timeout needed since we have the data
+            )
+            .await?;
+
+        self.spinner.stop(None)?;
+
+        Ok(())
+    }
+
+    /// Opens the authorization URL and asks the user to paste the resulting
+    /// authorization code
+    async fn prompt_authorization_code(
+        &mut self,
+        account: &str,
+        request: &CodeRequest,
+    ) -> anyhow::Result<String> {
         use colored::Colorize;
 
         self.spinner.stop(None)?;
 
         self.writeln(format!(
             "{}",
-            format!("Authenticate using your {provider_id} account").dimmed()
+            format!("Authenticate using your {account}").dimmed()
         ))?;
 
         // Display authorization URL
@@ -1956,21 +2753,29 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
             anyhow::bail!("Authorization code cannot be empty");
         }
 
-        self.spinner
-            .start(Some("Exchanging authorization code..."))?;
+        Ok(code)
+    }
 
-        let response = AuthContextResponse::code(request.clone(), &code);
+    /// Runs the OAuth authorization code flow for a remote MCP server
+    async fn on_mcp_login(&mut self, name: forge_api::ServerName) -> anyhow::Result<()> {
+        self.spinner.start(Some("Preparing authorization..."))?;
+        let request = self.api.init_mcp_auth(&name).await?;
 
-        self.api
-            .complete_provider_auth(
-                provider_id,
-                response,
-                Duration::from_secs(0), // No timeout needed since we have the data
-            )
+        let input = self
+            .prompt_authorization_code(&format!("'{name}' MCP server account"), &request)
             .await?;
+        let code = authorization_code(&input, &request)?;
 
+        self.spinner
+            .start(Some("Exchanging authorization code..."))?;
+        let response = AuthContextResponse::code(request, &code);
+        self.api.complete_mcp_auth(&name, response).await?;
         self.spinner.stop(None)?;
 
+        self.writeln_title(TitleFormat::info(format!(
+            "Logged in to MCP server '{name}'"
+        )))?;
+
         Ok(())
     }
 
@@ -2211,6 +3016,30 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         Ok(())
     }
 
+    async fn on_pipeline(&mut self, stages: Vec<Stage>) -> Result<()> {
+        let event = self
+            .cli
+            .event
+            .as_deref()
+            .map(serde_json::from_str::<serde_json::Value>)
+            .transpose()
+            .context("Failed to parse --event")?;
+        let prompt = self.cli.prompt.clone().or(self.cli.piped_input.clone());
+        let pipeline = Pipeline::new(
+            self.api.clone(),
+            self.new_api.clone(),
+            self.api.environment().cwd,
+        );
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                tracing::info!("User interrupted pipeline with Ctrl+C");
+                self.exit_status = ExitStatus::Interrupted;
+            }
+            status = pipeline.run(&stages, prompt, event) => self.exit_status = status?,
+        }
+        Ok(())
+    }
+
     // Handle dispatching events from the CLI
     async fn handle_dispatch(&mut self, json: String) -> Result<()> {
         // Initialize the conversation
@@ -2222,7 +3051,7 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         // Create the chat request with the event
         let chat = ChatRequest::new(event.into(), conversation_id);
 
-        self.on_chat(chat).await
+        self.run_once(async move |ui| ui.on_chat(chat).await).await
     }
 
     /// Initializes and returns a conversation ID for the current session.
@@ -2271,6 +3100,18 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         // Print if the state is being reinitialized
         if self.state.conversation_id.is_none() {
             self.print_conversation_status(is_new, id)?;
+
+            // Show the task list of the conversation being continued
+            let todos = match is_new {
+                true => Vec::new(),
+                false => self
+                    .api
+                    .conversation(&id)
+                    .await?
+                    .map(|conversation| conversation.todos)
+                    .unwrap_or_default(),
+            };
+            self.spinner.set_checklist(checklist(&todos))?;
         }
 
         // Always set the conversation id in state
@@ -2472,6 +3313,16 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
             return Ok(());
         }
 
+        match &message {
+            ChatResponse::Interrupt { .. } => self.exit_status = ExitStatus::LimitReached,
+            ChatResponse::TaskComplete => self.exit_status = ExitStatus::Success,
+            _ => {}
+        }
+
+        if self.events.is_some() {
+            return self.write_event(message).await;
+        }
+
         match message {
             ChatResponse::TaskMessage { content } => match content {
                 ChatResponseContent::Title(title) => self.writeln(title.display())?,
@@ -2530,6 +3381,9 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
                     self.markdown.add_chunk_dimmed(&content, &mut self.spinner);
                 }
             }
+            ChatResponse::TodoUpdate { todos } => {
+                self.spinner.set_checklist(checklist(&todos))?;
+            }
             ChatResponse::TaskComplete => {
                 if let Some(conversation_id) = self.state.conversation_id
                     && let Ok(conversation) =
@@ -2542,6 +3396,21 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
         Ok(())
     }
 
+    /// Writes a chat event to the structured output instead of rendering it.
+    async fn write_event(&mut self, message: ChatResponse) -> Result<()> {
+        let usage = match message {
+            ChatResponse::TaskComplete => self.accumulated_usage().await,
+            _ => None,
+        };
+
+        if let Some(events) = self.events.as_mut()
+            && let Some(line) = events.event(&message, usage)?
+        {
+            println!("{line}");
+        }
+        Ok(())
+    }
+
     async fn should_continue(&mut self) -> anyhow::Result<()> {
         let should_continue = ForgeSelect::confirm("Do you want to continue anyway?")
             .with_default(true)
@@ -2866,6 +3735,14 @@ impl<A: API + 'static, F: Fn() -> A + Send + Sync> UI<A, F> {
             self.markdown.add_chunk(message, &mut self.spinner);
         }
 
+        if !conversation.todos.is_empty() {
+            self.writeln_title(TitleFormat::info("Tasks"))?;
+            // Printed once, so long items may wrap
+            for line in render_checklist(&checklist(&conversation.todos), usize::MAX) {
+                self.writeln(line)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -3140,4 +4017,59 @@ mod tests {
     // ForgeSelect::confirm is not easily mockable in the current
     // architecture. The functionality is tested through integration tests
     // instead.
+
+    use forge_domain::{CodeRequest, OAuthConfig};
+    use pretty_assertions::assert_eq;
+    use url::Url;
+
+    use super::authorization_code;
+
+    fn code_request() -> CodeRequest {
+        let url = Url::parse("https://auth.example.com/authorize").unwrap();
+        CodeRequest {
+            authorization_url: url.clone(),
+            state: "expected".to_string().into(),
+            pkce_verifier: None,
+            oauth_config: OAuthConfig {
+                auth_url: url.clone(),
+                token_url: url,
+                client_id: "forge".to_string().into(),
+                scopes: vec![],
+                redirect_uri: None,
+                use_pkce: true,
+                token_refresh_url: None,
+                custom_headers: None,
+                extra_auth_params: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_authorization_code_from_redirect_url() {
+        let fixture = "http://127.0.0.1/callback?code=abc%2F1&state=expected";
+        let actual = authorization_code(fixture, &code_request()).unwrap();
+        let expected = "abc/1";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_authorization_code_rejects_mismatched_state() {
+        let fixture = "http://127.0.0.1/callback?code=abc&state=forged";
+        let actual = authorization_code(fixture, &code_request()).is_err();
+        assert!(actual);
+    }
+
+    #[test]
+    fn test_authorization_code_rejects_missing_state() {
+        let fixture = "http://127.0.0.1/callback?code=abc";
+        let actual = authorization_code(fixture, &code_request()).is_err();
+        assert!(actual);
+    }
+
+    #[test]
+    fn test_authorization_code_accepts_plain_code() {
+        let actual = authorization_code(" abc ", &code_request()).unwrap();
+        let expected = "abc";
+        assert_eq!(actual, expected);
+    }
 }
@@ -25,6 +25,32 @@ impl From<ModelIdRecord> for forge_domain::ModelId {
     }
 }
 
+/// Repository-specific representation of Document
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(super) struct DocumentRecord {
+    data: String,
+    mime_type: String,
+    title: String,
+    text: String,
+}
+
+impl From<&forge_domain::Document> for DocumentRecord {
+    fn from(document: &forge_domain::Document) -> Self {
+        Self {
+            data: document.data().to_string(),
+            mime_type: document.mime_type().to_string(),
+            title: document.title().to_string(),
+            text: document.text().to_string(),
+        }
+    }
+}
+
+impl From<DocumentRecord> for forge_domain::Document {
+    fn from(record: DocumentRecord) -> Self {
+        forge_domain::Document::new_base64(record.data, record.mime_type, record.title, record.text)
+    }
+}
+
 /// Repository-specific representation of Image
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub(super) struct ImageRecord {
@@ -463,6 +489,7 @@ pub(super) enum ContextMessageValueRecord {
     Text(TextMessageRecord),
     Tool(ToolResultRecord),
     Image(ImageRecord),
+    Document(DocumentRecord),
 }
 
 impl From<&forge_domain::ContextMessage> for ContextMessageValueRecord {
@@ -473,6 +500,9 @@ impl From<&forge_domain::ContextMessage> for ContextMessageValueRecord {
                 Self::Tool(ToolResultRecord::from(result))
             }
             forge_domain::ContextMessage::Image(img) => Self::Image(ImageRecord::from(img)),
+            forge_domain::ContextMessage::Document(document) => {
+                Self::Document(DocumentRecord::from(document))
+            }
         }
     }
 }
@@ -485,6 +515,7 @@ impl TryFrom<ContextMessageValueRecord> for forge_domain::ContextMessage {
             ContextMessageValueRecord::Text(msg) => Self::Text(msg.try_into()?),
             ContextMessageValueRecord::Tool(result) => Self::Tool(result.try_into()?),
             ContextMessageValueRecord::Image(img) => Self::Image(img.into()),
+            ContextMessageValueRecord::Document(document) => Self::Document(document.into()),
         })
     }
 }
@@ -495,6 +526,8 @@ pub(super) struct ContextMessageRecord {
     message: ContextMessageValueRecord,
     #[serde(skip_serializing_if = "Option::is_none")]
     usage: Option<UsageRecord>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pinned: bool,
 }
 
 // TODO: Move this deserialization logic into Conversation repo
@@ -510,17 +543,19 @@ impl<'de> Deserialize<'de> for ContextMessageRecord {
             Wrapper {
                 message: ContextMessageValueRecord,
                 usage: Option<UsageRecord>,
+                #[serde(default)]
+                pinned: bool,
             },
             // Fall back to old format (direct ContextMessage)
             Direct(ContextMessageValueRecord),
         }
 
         match ContextMessageParser::deserialize(deserializer)? {
-            ContextMessageParser::Wrapper { message, usage } => {
-                Ok(ContextMessageRecord { message, usage })
+            ContextMessageParser::Wrapper { message, usage, pinned } => {
+                Ok(ContextMessageRecord { message, usage, pinned })
             }
             ContextMessageParser::Direct(message) => {
-                Ok(ContextMessageRecord { message, usage: None })
+                Ok(ContextMessageRecord { message, usage: None, pinned: false })
             }
         }
     }
@@ -531,6 +566,7 @@ impl From<&forge_domain::MessageEntry> for ContextMessageRecord {
         Self {
             message: ContextMessageValueRecord::from(&msg.message),
             usage: msg.usage.as_ref().map(UsageRecord::from),
+            pinned: msg.pinned,
         }
     }
 }
@@ -542,6 +578,7 @@ impl TryFrom<ContextMessageRecord> for forge_domain::MessageEntry {
         Ok(forge_domain::MessageEntry {
             message: record.message.try_into()?,
             usage: record.usage.map(Into::into),
+            pinned: record.pinned,
         })
     }
 }
@@ -818,6 +855,8 @@ pub(super) enum FileOperationOrArray {
 pub(super) struct MetricsRecord {
     started_at: Option<chrono::DateTime<chrono::Utc>>,
     files_changed: std::collections::HashMap<String, FileOperationOrArray>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    plan: Option<std::path::PathBuf>,
 }
 
 impl From<&forge_domain::Metrics> for MetricsRecord {
@@ -834,6 +873,7 @@ impl From<&forge_domain::Metrics> for MetricsRecord {
                     )
                 })
                 .collect(),
+            plan: metrics.plan.clone(),
         }
     }
 }
@@ -859,10 +899,30 @@ impl From<MetricsRecord> for forge_domain::Metrics {
                     Some((path, operation))
                 })
                 .collect(),
+            plan: record.plan,
         }
     }
 }
 
+/// Repository-specific representation of Todo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct TodoRecord {
+    content: String,
+    status: forge_domain::TodoStatus,
+}
+
+impl From<&forge_domain::Todo> for TodoRecord {
+    fn from(todo: &forge_domain::Todo) -> Self {
+        Self { content: todo.content.clone(), status: todo.status }
+    }
+}
+
+impl From<TodoRecord> for forge_domain::Todo {
+    fn from(record: TodoRecord) -> Self {
+        Self::new(record.content, record.status)
+    }
+}
+
 /// Database model for conversations table
 #[derive(Debug, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
 #[diesel(table_name = crate::database::schema::conversations)]
@@ -875,6 +935,7 @@ pub(super) struct ConversationRecord {
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: Option<chrono::NaiveDateTime>,
     pub metrics: Option<String>,
+    pub todos: Option<String>,
 }
 
 impl ConversationRecord {
@@ -892,6 +953,10 @@ impl ConversationRecord {
         let updated_at = context.as_ref().map(|_| chrono::Utc::now().naive_utc());
         let metrics_record = MetricsRecord::from(&conversation.metrics);
         let metrics = serde_json::to_string(&metrics_record).ok();
+        let todos = Some(&conversation.todos)
+            .filter(|todos| !todos.is_empty())
+            .map(|todos| todos.iter().map(TodoRecord::from).collect::<Vec<_>>())
+            .and_then(|records| serde_json::to_string(&records).ok());
 
         Self {
             conversation_id: conversation.id.into_string(),
@@ -901,6 +966,7 @@ impl ConversationRecord {
             updated_at,
             workspace_id: workspace_id.id() as i64,
             metrics,
+            todos,
         }
     }
 }
@@ -943,10 +1009,17 @@ impl TryFrom<ConversationRecord> for forge_domain::Conversation {
                 forge_domain::Metrics::default().started_at(record.created_at.and_utc())
             });
 
+        let todos = record
+            .todos
+            .and_then(|t| serde_json::from_str::<Vec<TodoRecord>>(&t).ok())
+            .map(|records| records.into_iter().map(Into::into).collect::<Vec<_>>())
+            .unwrap_or_default();
+
         Ok(forge_domain::Conversation::new(id)
             .context(context)
             .title(record.title)
             .metrics(metrics)
+            .todos(todos)
             .metadata(
                 forge_domain::MetaData::new(record.created_at.and_utc())
                     .updated_at(record.updated_at.map(|updated_at| updated_at.and_utc())),
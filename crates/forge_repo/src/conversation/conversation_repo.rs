@@ -34,6 +34,7 @@ impl ConversationRepository for ConversationRepositoryImpl {
                 conversations::context.eq(&record.context),
                 conversations::updated_at.eq(record.updated_at),
                 conversations::metrics.eq(&record.metrics),
+                conversations::todos.eq(&record.todos),
             ))
             .execute(&mut connection)?;
         Ok(())
@@ -349,6 +350,7 @@ mod tests {
             updated_at: None,
             workspace_id: 0,
             metrics: None,
+            todos: None,
         };
 
         let actual = Conversation::try_from(fixture)?;
@@ -359,6 +361,26 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_upsert_and_retrieve_conversation_with_todos() -> anyhow::Result<()> {
+        let repo = repository()?;
+        let todos = vec![
+            forge_domain::Todo::new("Add the parser", forge_domain::TodoStatus::Done),
+            forge_domain::Todo::new("Wire the tool", forge_domain::TodoStatus::InProgress),
+        ];
+        let fixture = Conversation::generate().todos(todos.clone());
+
+        repo.upsert_conversation(fixture.clone()).await?;
+        let actual = repo
+            .get_conversation(&fixture.id)
+            .await?
+            .expect("Conversation should exist")
+            .todos;
+
+        assert_eq!(actual, todos);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_upsert_and_retrieve_conversation_with_metrics() -> anyhow::Result<()> {
         let repo = repository()?;
@@ -709,6 +731,7 @@ mod tests {
                     cached_tokens: forge_domain::TokenCount::Actual(0),
                     cost: Some(0.001),
                 }),
+                pinned: true,
             },
         ];
 
@@ -766,6 +789,16 @@ mod tests {
             _ => panic!("Expected tool result message"),
         }
 
+        // Verify pins are preserved
+        assert_eq!(
+            actual
+                .messages
+                .iter()
+                .map(|msg| msg.pinned)
+                .collect::<Vec<_>>(),
+            vec![false, false, false, true]
+        );
+
         // Verify usage is preserved
         match &actual.messages[3].usage {
             Some(usage) => {
@@ -790,6 +823,7 @@ mod tests {
             updated_at: None,
             workspace_id: 0,
             metrics: None,
+            todos: None,
         };
 
         let result = Conversation::try_from(fixture);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_proxy_exchanges_created;
+DROP TABLE IF EXISTS proxy_exchanges;
@@ -0,0 +1,15 @@
+-- Create proxy_exchanges table to log requests served by `forge proxy`
+CREATE TABLE IF NOT EXISTS proxy_exchanges (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    format TEXT NOT NULL,
+    model TEXT NOT NULL,
+    provider TEXT,
+    request TEXT NOT NULL,
+    response TEXT,
+    status INTEGER NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    error TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_proxy_exchanges_created ON proxy_exchanges(created_at DESC);
@@ -0,0 +1,2 @@
+-- Remove todos column from conversations table
+ALTER TABLE conversations DROP COLUMN todos;
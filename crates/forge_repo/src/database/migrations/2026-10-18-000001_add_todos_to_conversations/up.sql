@@ -0,0 +1,2 @@
+-- Add todos column to conversations table
+ALTER TABLE conversations ADD COLUMN todos TEXT;
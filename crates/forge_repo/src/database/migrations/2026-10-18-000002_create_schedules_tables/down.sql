@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_schedule_runs_schedule;
+DROP TABLE IF EXISTS schedule_runs;
+DROP TABLE IF EXISTS schedules;
@@ -0,0 +1,25 @@
+-- Create tables for jobs run by `forge schedule daemon` and their run history
+CREATE TABLE IF NOT EXISTS schedules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    cron TEXT NOT NULL,
+    agent TEXT,
+    prompt TEXT,
+    prompt_file TEXT,
+    cwd TEXT NOT NULL,
+    sandbox BOOLEAN NOT NULL DEFAULT 1,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_run_at TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS schedule_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    schedule_id INTEGER NOT NULL REFERENCES schedules(id),
+    conversation_id TEXT NOT NULL,
+    sandbox TEXT,
+    status TEXT NOT NULL,
+    started_at TIMESTAMP NOT NULL,
+    finished_at TIMESTAMP,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_schedule_runs_schedule ON schedule_runs(schedule_id, id DESC);
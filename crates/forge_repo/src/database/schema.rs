@@ -9,6 +9,49 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Nullable<Timestamp>,
         metrics -> Nullable<Text>,
+        todos -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    proxy_exchanges (id) {
+        id -> Integer,
+        format -> Text,
+        model -> Text,
+        provider -> Nullable<Text>,
+        request -> Text,
+        response -> Nullable<Text>,
+        status -> Integer,
+        duration_ms -> BigInt,
+        error -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    schedule_runs (id) {
+        id -> Integer,
+        schedule_id -> Integer,
+        conversation_id -> Text,
+        sandbox -> Nullable<Text>,
+        status -> Text,
+        started_at -> Timestamp,
+        finished_at -> Nullable<Timestamp>,
+        error -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    schedules (id) {
+        id -> Integer,
+        cron -> Text,
+        agent -> Nullable<Text>,
+        prompt -> Nullable<Text>,
+        prompt_file -> Nullable<Text>,
+        cwd -> Text,
+        sandbox -> Bool,
+        created_at -> Timestamp,
+        last_run_at -> Nullable<Timestamp>,
     }
 }
 
@@ -22,4 +65,12 @@ diesel::table! {
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(conversations, workspace,);
+diesel::joinable!(schedule_runs -> schedules (schedule_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    conversations,
+    proxy_exchanges,
+    schedule_runs,
+    schedules,
+    workspace,
+);
@@ -1,23 +1,25 @@
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use bytes::Bytes;
 use forge_app::{
     AgentRepository, CommandInfra, DirectoryReaderInfra, EnvironmentInfra, FileDirectoryInfra,
     FileInfoInfra, FileReaderInfra, FileRemoverInfra, FileWriterInfra, GrpcInfra, HttpInfra,
-    KVStore, McpServerInfra, StrategyFactory, UserInfra, WalkedFile, Walker, WalkerInfra,
+    KVStore, LspServerInfra, McpServerInfra, StrategyFactory, UserInfra, WalkedFile, Walker,
+    WalkerInfra,
 };
 use forge_domain::{
     AnyProvider, AppConfig, AppConfigRepository, AuthCredential, CommandOutput, Conversation,
-    ConversationId, ConversationRepository, Environment, FileInfo, McpServerConfig,
-    MigrationResult, Provider, ProviderId, ProviderRepository, Skill, SkillRepository, Snapshot,
-    SnapshotRepository,
+    ConversationId, ConversationRepository, Environment, FileInfo, LspServerConfig,
+    McpServerConfig, MigrationResult, Provider, ProviderId, ProviderRepository, Skill,
+    SkillRepository, Snapshot, SnapshotRepository,
 };
 // Re-export CacacheStorage from forge_infra
 pub use forge_infra::CacacheStorage;
 use reqwest::header::HeaderMap;
-use reqwest::Response;
+use reqwest::{Method, Response};
 use reqwest_eventsource::EventSource;
 use url::Url;
 
@@ -41,6 +43,8 @@ pub struct ForgeRepo<F> {
     mcp_cache_repository: Arc<CacacheStorage>,
     provider_repository: Arc<ForgeProviderRepository<F>>,
     indexing_repository: Arc<crate::ForgeWorkspaceRepository>,
+    proxy_log_repository: Arc<crate::ForgeProxyLogRepository>,
+    schedule_repository: Arc<crate::ForgeScheduleRepository>,
     codebase_repo: Arc<crate::ForgeContextEngineRepository<F>>,
     agent_repository: Arc<ForgeAgentRepository<F>>,
     skill_repository: Arc<ForgeSkillRepository<F>>,
@@ -68,6 +72,8 @@ impl<F: EnvironmentInfra + FileReaderInfra + FileWriterInfra + GrpcInfra> ForgeR
         let provider_repository = Arc::new(ForgeProviderRepository::new(infra.clone()));
 
         let indexing_repository = Arc::new(crate::ForgeWorkspaceRepository::new(db_pool.clone()));
+        let proxy_log_repository = Arc::new(crate::ForgeProxyLogRepository::new(db_pool.clone()));
+        let schedule_repository = Arc::new(crate::ForgeScheduleRepository::new(db_pool.clone()));
 
         let codebase_repo = Arc::new(crate::ForgeContextEngineRepository::new(infra.clone()));
         let agent_repository = Arc::new(ForgeAgentRepository::new(infra.clone()));
@@ -81,6 +87,8 @@ impl<F: EnvironmentInfra + FileReaderInfra + FileWriterInfra + GrpcInfra> ForgeR
             mcp_cache_repository,
             provider_repository,
             indexing_repository,
+            proxy_log_repository,
+            schedule_repository,
             codebase_repo,
             agent_repository,
             skill_repository,
@@ -218,6 +226,16 @@ impl<F: HttpInfra> HttpInfra for ForgeRepo<F> {
         self.infra.http_delete(url).await
     }
 
+    async fn http_request(
+        &self,
+        method: Method,
+        url: &Url,
+        headers: HeaderMap,
+        body: Option<Bytes>,
+    ) -> anyhow::Result<Response> {
+        self.infra.http_request(method, url, headers, body).await
+    }
+
     async fn http_eventsource(
         &self,
         url: &Url,
@@ -304,6 +322,10 @@ where
     async fn file_size(&self, path: &Path) -> anyhow::Result<u64> {
         self.infra.file_size(path).await
     }
+
+    async fn modified(&self, path: &Path) -> anyhow::Result<SystemTime> {
+        self.infra.modified(path).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -397,6 +419,23 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<F> LspServerInfra for ForgeRepo<F>
+where
+    F: LspServerInfra + Send + Sync,
+{
+    type Client = F::Client;
+
+    async fn start(
+        &self,
+        language_id: &str,
+        config: &LspServerConfig,
+        root: &Path,
+    ) -> anyhow::Result<F::Client> {
+        self.infra.start(language_id, config, root).await
+    }
+}
+
 #[async_trait::async_trait]
 impl<F> CommandInfra for ForgeRepo<F>
 where
@@ -424,6 +463,15 @@ where
             .execute_command_raw(command, working_dir, env_vars)
             .await
     }
+
+    async fn execute_program(
+        &self,
+        program: &str,
+        args: Vec<String>,
+        working_dir: PathBuf,
+    ) -> anyhow::Result<CommandOutput> {
+        self.infra.execute_program(program, args, working_dir).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -487,6 +535,62 @@ impl<F: Send + Sync> forge_domain::WorkspaceRepository for ForgeRepo<F> {
     }
 }
 
+#[async_trait::async_trait]
+impl<F: Send + Sync> forge_domain::ProxyLogRepository for ForgeRepo<F> {
+    async fn append_proxy_exchange(
+        &self,
+        exchange: forge_domain::ProxyExchange,
+    ) -> anyhow::Result<()> {
+        self.proxy_log_repository
+            .append_proxy_exchange(exchange)
+            .await
+    }
+
+    async fn get_proxy_exchanges(
+        &self,
+        limit: usize,
+    ) -> anyhow::Result<Vec<forge_domain::ProxyExchange>> {
+        self.proxy_log_repository.get_proxy_exchanges(limit).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<F: Send + Sync> forge_domain::ScheduleRepository for ForgeRepo<F> {
+    async fn add_schedule(
+        &self,
+        schedule: forge_domain::Schedule,
+    ) -> anyhow::Result<forge_domain::Schedule> {
+        self.schedule_repository.add_schedule(schedule).await
+    }
+
+    async fn get_schedules(&self) -> anyhow::Result<Vec<forge_domain::Schedule>> {
+        self.schedule_repository.get_schedules().await
+    }
+
+    async fn remove_schedule(&self, id: forge_domain::ScheduleId) -> anyhow::Result<bool> {
+        self.schedule_repository.remove_schedule(id).await
+    }
+
+    async fn start_schedule_run(
+        &self,
+        run: forge_domain::ScheduleRun,
+    ) -> anyhow::Result<forge_domain::ScheduleRun> {
+        self.schedule_repository.start_schedule_run(run).await
+    }
+
+    async fn finish_schedule_run(&self, run: &forge_domain::ScheduleRun) -> anyhow::Result<()> {
+        self.schedule_repository.finish_schedule_run(run).await
+    }
+
+    async fn get_schedule_runs(
+        &self,
+        id: forge_domain::ScheduleId,
+        limit: usize,
+    ) -> anyhow::Result<Vec<forge_domain::ScheduleRun>> {
+        self.schedule_repository.get_schedule_runs(id, limit).await
+    }
+}
+
 #[async_trait::async_trait]
 impl<F: GrpcInfra + Send + Sync> forge_domain::ContextEngineRepository for ForgeRepo<F> {
     async fn authenticate(&self) -> anyhow::Result<forge_domain::WorkspaceAuth> {
@@ -6,6 +6,8 @@ mod database;
 mod forge_repo;
 mod fs_snap;
 mod provider;
+mod proxy_log;
+mod schedule;
 mod skill;
 mod validation;
 mod workspace;
@@ -18,6 +20,8 @@ pub use database::*;
 pub use forge_repo::*;
 pub use fs_snap::*;
 pub use provider::*;
+pub use proxy_log::*;
+pub use schedule::*;
 pub use skill::*;
 pub use validation::*;
 pub use workspace::*;
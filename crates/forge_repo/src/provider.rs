@@ -269,11 +269,13 @@ impl<F: EnvironmentInfra + FileReaderInfra + FileWriterInfra> ForgeProviderRepos
     /// The credential file (.credentials.json) is the single source of
     /// truth.
     async fn create_provider(&self, config: &ProviderConfig) -> anyhow::Result<Provider<Url>> {
-        // Get credential from file
-        let credential = self
-            .get_credential(&config.id)
-            .await?
-            .ok_or_else(|| Error::provider_not_available(config.id.clone()))?;
+        // Get credential from file. Providers without authentication are configured
+        // by their environment variables alone.
+        let credential = match self.get_credential(&config.id).await? {
+            Some(credential) => credential,
+            None if config.auth_methods.is_empty() => self.create_credential_from_env(config)?,
+            None => return Err(Error::provider_not_available(config.id.clone()).into()),
+        };
 
         // Build template data from URL parameters in credential
         let mut template_data = std::collections::HashMap::new();
@@ -958,6 +960,21 @@ mod env_tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_replay_provider_needs_only_its_cassette() {
+        let fixture = HashMap::from([(
+            "FORGE_REPLAY_CASSETTE".to_string(),
+            "/tmp/session.json".to_string(),
+        )]);
+        let registry = ForgeProviderRepository::new(Arc::new(MockInfra::new(fixture)));
+
+        let actual = registry.get_provider(ProviderId::REPLAY).await.unwrap();
+
+        let expected = "file:///tmp/session.json";
+        assert_eq!(actual.url.as_str(), expected);
+        assert!(actual.auth_methods.is_empty());
+    }
+
     #[tokio::test]
     async fn test_default_provider_urls() {
         let mut env_vars = HashMap::new();
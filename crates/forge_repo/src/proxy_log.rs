@@ -0,0 +1,166 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use forge_domain::{ProviderId, ProxyExchange, ProxyFormat, ProxyLogRepository};
+
+use crate::database::DatabasePool;
+use crate::database::schema::proxy_exchanges;
+
+/// Repository implementation for the proxy request log in local database
+pub struct ForgeProxyLogRepository {
+    pool: Arc<DatabasePool>,
+}
+
+impl ForgeProxyLogRepository {
+    pub fn new(pool: Arc<DatabasePool>) -> Self {
+        Self { pool }
+    }
+}
+
+/// Database model for proxy_exchanges table
+#[derive(Debug, Insertable)]
+#[diesel(table_name = proxy_exchanges)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+struct NewProxyExchangeRecord {
+    format: String,
+    model: String,
+    provider: Option<String>,
+    request: String,
+    response: Option<String>,
+    status: i32,
+    duration_ms: i64,
+    error: Option<String>,
+    created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = proxy_exchanges)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+struct ProxyExchangeRecord {
+    format: String,
+    model: String,
+    provider: Option<String>,
+    request: String,
+    response: Option<String>,
+    status: i32,
+    duration_ms: i64,
+    error: Option<String>,
+    created_at: NaiveDateTime,
+}
+
+impl From<ProxyExchange> for NewProxyExchangeRecord {
+    fn from(exchange: ProxyExchange) -> Self {
+        Self {
+            format: exchange.format.to_string(),
+            model: exchange.model,
+            provider: exchange
+                .provider
+                .map(|provider| provider.as_ref().to_string()),
+            request: exchange.request,
+            response: exchange.response,
+            status: exchange.status as i32,
+            duration_ms: exchange.duration_ms as i64,
+            error: exchange.error,
+            created_at: exchange.created_at.naive_utc(),
+        }
+    }
+}
+
+impl TryFrom<ProxyExchangeRecord> for ProxyExchange {
+    type Error = anyhow::Error;
+
+    fn try_from(record: ProxyExchangeRecord) -> anyhow::Result<Self> {
+        Ok(Self {
+            created_at: record.created_at.and_utc(),
+            format: ProxyFormat::from_str(&record.format)?,
+            model: record.model,
+            provider: record
+                .provider
+                .map(|provider| ProviderId::from_str(&provider))
+                .transpose()?,
+            request: record.request,
+            response: record.response,
+            status: u16::try_from(record.status)?,
+            duration_ms: u64::try_from(record.duration_ms)?,
+            error: record.error,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ProxyLogRepository for ForgeProxyLogRepository {
+    async fn append_proxy_exchange(&self, exchange: ProxyExchange) -> anyhow::Result<()> {
+        let mut connection = self.pool.get_connection()?;
+        diesel::insert_into(proxy_exchanges::table)
+            .values(NewProxyExchangeRecord::from(exchange))
+            .execute(&mut connection)?;
+        Ok(())
+    }
+
+    async fn get_proxy_exchanges(&self, limit: usize) -> anyhow::Result<Vec<ProxyExchange>> {
+        let mut connection = self.pool.get_connection()?;
+        let records = proxy_exchanges::table
+            .order(proxy_exchanges::id.desc())
+            .limit(limit as i64)
+            .select(ProxyExchangeRecord::as_select())
+            .load(&mut connection)?;
+        records.into_iter().map(ProxyExchange::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn repo_impl() -> ForgeProxyLogRepository {
+        let pool = Arc::new(DatabasePool::in_memory().unwrap());
+        ForgeProxyLogRepository::new(pool)
+    }
+
+    #[tokio::test]
+    async fn test_append_and_get_proxy_exchanges() {
+        let fixture = repo_impl();
+        let timestamp = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let first = ProxyExchange::new(ProxyFormat::OpenAI, "anthropic/claude", "{}")
+            .created_at(timestamp)
+            .provider(ProviderId::ANTHROPIC)
+            .response("data: [DONE]")
+            .duration_ms(120u64);
+        let second = ProxyExchange::new(ProxyFormat::Anthropic, "unknown/model", "{}")
+            .created_at(timestamp)
+            .status(400u16)
+            .error("Unknown provider");
+
+        fixture.append_proxy_exchange(first.clone()).await.unwrap();
+        fixture.append_proxy_exchange(second.clone()).await.unwrap();
+
+        let actual = fixture.get_proxy_exchanges(10).await.unwrap();
+        let expected = vec![second, first];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_get_proxy_exchanges_respects_limit() {
+        let fixture = repo_impl();
+        for model in ["a", "b", "c"] {
+            fixture
+                .append_proxy_exchange(ProxyExchange::new(ProxyFormat::OpenAI, model, "{}"))
+                .await
+                .unwrap();
+        }
+
+        let actual = fixture
+            .get_proxy_exchanges(2)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|exchange| exchange.model)
+            .collect::<Vec<_>>();
+        let expected = vec!["c".to_string(), "b".to_string()];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,315 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use forge_domain::{
+    AgentId, ConversationId, CronExpr, Schedule, ScheduleId, SchedulePrompt, ScheduleRepository,
+    ScheduleRun, ScheduleRunStatus,
+};
+
+use crate::database::DatabasePool;
+use crate::database::schema::{schedule_runs, schedules};
+
+diesel::define_sql_function! {
+    /// Id of the row inserted last on the connection
+    fn last_insert_rowid() -> diesel::sql_types::Integer;
+}
+
+/// Repository implementation for scheduled jobs in local database
+pub struct ForgeScheduleRepository {
+    pool: Arc<DatabasePool>,
+}
+
+impl ForgeScheduleRepository {
+    pub fn new(pool: Arc<DatabasePool>) -> Self {
+        Self { pool }
+    }
+}
+
+/// Database model for schedules table
+#[derive(Debug, Insertable)]
+#[diesel(table_name = schedules)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+struct NewScheduleRecord {
+    cron: String,
+    agent: Option<String>,
+    prompt: Option<String>,
+    prompt_file: Option<String>,
+    cwd: String,
+    sandbox: bool,
+    created_at: NaiveDateTime,
+    last_run_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = schedules)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+struct ScheduleRecord {
+    id: i32,
+    cron: String,
+    agent: Option<String>,
+    prompt: Option<String>,
+    prompt_file: Option<String>,
+    cwd: String,
+    sandbox: bool,
+    created_at: NaiveDateTime,
+    last_run_at: Option<NaiveDateTime>,
+}
+
+impl From<&Schedule> for NewScheduleRecord {
+    fn from(schedule: &Schedule) -> Self {
+        let (prompt, prompt_file) = match &schedule.prompt {
+            SchedulePrompt::Text(text) => (Some(text.clone()), None),
+            SchedulePrompt::File(path) => (None, Some(path.display().to_string())),
+        };
+        Self {
+            cron: schedule.cron.to_string(),
+            agent: schedule.agent.as_ref().map(|agent| agent.to_string()),
+            prompt,
+            prompt_file,
+            cwd: schedule.cwd.display().to_string(),
+            sandbox: schedule.sandbox,
+            created_at: schedule.created_at.naive_utc(),
+            last_run_at: schedule.last_run_at.map(|time| time.naive_utc()),
+        }
+    }
+}
+
+impl TryFrom<ScheduleRecord> for Schedule {
+    type Error = anyhow::Error;
+
+    fn try_from(record: ScheduleRecord) -> anyhow::Result<Self> {
+        let prompt = match (record.prompt, record.prompt_file) {
+            (_, Some(path)) => SchedulePrompt::File(PathBuf::from(path)),
+            (Some(text), None) => SchedulePrompt::Text(text),
+            (None, None) => anyhow::bail!("Schedule {} has no prompt", record.id),
+        };
+        Ok(Self {
+            id: ScheduleId::new(record.id as i64),
+            cron: CronExpr::from_str(&record.cron)?,
+            agent: record.agent.map(AgentId::new),
+            prompt,
+            cwd: PathBuf::from(record.cwd),
+            sandbox: record.sandbox,
+            created_at: record.created_at.and_utc(),
+            last_run_at: record.last_run_at.map(|time| time.and_utc()),
+        })
+    }
+}
+
+/// Database model for schedule_runs table
+#[derive(Debug, Insertable)]
+#[diesel(table_name = schedule_runs)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+struct NewScheduleRunRecord {
+    schedule_id: i32,
+    conversation_id: String,
+    sandbox: Option<String>,
+    status: String,
+    started_at: NaiveDateTime,
+    finished_at: Option<NaiveDateTime>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = schedule_runs)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+struct ScheduleRunRecord {
+    id: i32,
+    schedule_id: i32,
+    conversation_id: String,
+    sandbox: Option<String>,
+    status: String,
+    started_at: NaiveDateTime,
+    finished_at: Option<NaiveDateTime>,
+    error: Option<String>,
+}
+
+impl From<&ScheduleRun> for NewScheduleRunRecord {
+    fn from(run: &ScheduleRun) -> Self {
+        Self {
+            schedule_id: run.schedule_id.id() as i32,
+            conversation_id: run.conversation_id.into_string(),
+            sandbox: run.sandbox.clone(),
+            status: run.status.to_string(),
+            started_at: run.started_at.naive_utc(),
+            finished_at: run.finished_at.map(|time| time.naive_utc()),
+            error: run.error.clone(),
+        }
+    }
+}
+
+impl TryFrom<ScheduleRunRecord> for ScheduleRun {
+    type Error = anyhow::Error;
+
+    fn try_from(record: ScheduleRunRecord) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: record.id as i64,
+            schedule_id: ScheduleId::new(record.schedule_id as i64),
+            conversation_id: ConversationId::parse(record.conversation_id)?,
+            sandbox: record.sandbox,
+            status: ScheduleRunStatus::from_str(&record.status)?,
+            started_at: record.started_at.and_utc(),
+            finished_at: record.finished_at.map(|time| time.and_utc()),
+            error: record.error,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ScheduleRepository for ForgeScheduleRepository {
+    async fn add_schedule(&self, schedule: Schedule) -> anyhow::Result<Schedule> {
+        let mut connection = self.pool.get_connection()?;
+        let id = connection.transaction(|connection| {
+            diesel::insert_into(schedules::table)
+                .values(NewScheduleRecord::from(&schedule))
+                .execute(connection)?;
+            diesel::select(last_insert_rowid()).get_result::<i32>(connection)
+        })?;
+        Ok(schedule.id(ScheduleId::new(id as i64)))
+    }
+
+    async fn get_schedules(&self) -> anyhow::Result<Vec<Schedule>> {
+        let mut connection = self.pool.get_connection()?;
+        let records = schedules::table
+            .order(schedules::id.asc())
+            .select(ScheduleRecord::as_select())
+            .load(&mut connection)?;
+        records.into_iter().map(Schedule::try_from).collect()
+    }
+
+    async fn remove_schedule(&self, id: ScheduleId) -> anyhow::Result<bool> {
+        let mut connection = self.pool.get_connection()?;
+        let id = id.id() as i32;
+        let removed = connection.transaction(|connection| {
+            diesel::delete(schedule_runs::table.filter(schedule_runs::schedule_id.eq(id)))
+                .execute(connection)?;
+            diesel::delete(schedules::table.find(id)).execute(connection)
+        })?;
+        Ok(removed > 0)
+    }
+
+    async fn start_schedule_run(&self, run: ScheduleRun) -> anyhow::Result<ScheduleRun> {
+        let mut connection = self.pool.get_connection()?;
+        let id = connection.transaction(|connection| {
+            diesel::update(schedules::table.find(run.schedule_id.id() as i32))
+                .set(schedules::last_run_at.eq(run.started_at.naive_utc()))
+                .execute(connection)?;
+            diesel::insert_into(schedule_runs::table)
+                .values(NewScheduleRunRecord::from(&run))
+                .execute(connection)?;
+            diesel::select(last_insert_rowid()).get_result::<i32>(connection)
+        })?;
+        Ok(run.id(id as i64))
+    }
+
+    async fn finish_schedule_run(&self, run: &ScheduleRun) -> anyhow::Result<()> {
+        let mut connection = self.pool.get_connection()?;
+        diesel::update(schedule_runs::table.find(run.id as i32))
+            .set((
+                schedule_runs::status.eq(run.status.to_string()),
+                schedule_runs::finished_at.eq(run.finished_at.map(|time| time.naive_utc())),
+                schedule_runs::error.eq(run.error.clone()),
+            ))
+            .execute(&mut connection)?;
+        Ok(())
+    }
+
+    async fn get_schedule_runs(
+        &self,
+        id: ScheduleId,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ScheduleRun>> {
+        let mut connection = self.pool.get_connection()?;
+        let records = schedule_runs::table
+            .filter(schedule_runs::schedule_id.eq(id.id() as i32))
+            .order(schedule_runs::id.desc())
+            .limit(limit as i64)
+            .select(ScheduleRunRecord::as_select())
+            .load(&mut connection)?;
+        records.into_iter().map(ScheduleRun::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn repo_impl() -> ForgeScheduleRepository {
+        let pool = Arc::new(DatabasePool::in_memory().unwrap());
+        ForgeScheduleRepository::new(pool)
+    }
+
+    fn schedule() -> Schedule {
+        Schedule::new(
+            CronExpr::from_str("0 2 * * *").unwrap(),
+            SchedulePrompt::File(PathBuf::from("/project/audit.md")),
+            "/project",
+        )
+        .agent(AgentId::new("forge"))
+        .created_at(DateTime::from_timestamp(1_700_000_000, 0).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_schedules() {
+        let fixture = repo_impl();
+        let first = fixture.add_schedule(schedule()).await.unwrap();
+        let second = fixture
+            .add_schedule(
+                schedule()
+                    .prompt(SchedulePrompt::Text("Update docs".to_string()))
+                    .sandbox(false),
+            )
+            .await
+            .unwrap();
+
+        let removed = fixture.remove_schedule(first.id).await.unwrap();
+        let removed_again = fixture.remove_schedule(first.id).await.unwrap();
+
+        let actual = (
+            removed,
+            removed_again,
+            fixture.get_schedules().await.unwrap(),
+        );
+        let expected = (true, false, vec![second]);
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_start_and_finish_schedule_runs() {
+        let fixture = repo_impl();
+        let schedule = fixture.add_schedule(schedule()).await.unwrap();
+        let started_at = DateTime::from_timestamp(1_700_100_000, 0).unwrap();
+        let first = fixture
+            .start_schedule_run(
+                ScheduleRun::new(schedule.id, ConversationId::generate()).started_at(started_at),
+            )
+            .await
+            .unwrap();
+        let second = fixture
+            .start_schedule_run(
+                ScheduleRun::new(schedule.id, ConversationId::generate())
+                    .started_at(started_at)
+                    .sandbox("forge-schedule-1"),
+            )
+            .await
+            .unwrap();
+        let first = first
+            .status(ScheduleRunStatus::Error)
+            .finished_at(started_at)
+            .error("exit status: 1");
+        fixture.finish_schedule_run(&first).await.unwrap();
+
+        let actual = (
+            fixture.get_schedule_runs(schedule.id, 10).await.unwrap(),
+            fixture.get_schedules().await.unwrap()[0].last_run_at,
+        );
+        let expected = (vec![second, first], Some(started_at));
+        assert_eq!(actual, expected);
+    }
+}
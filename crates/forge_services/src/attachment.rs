@@ -1,32 +1,453 @@
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
 use forge_app::domain::{
-    Attachment, AttachmentContent, DirectoryEntry, FileTag, Image, LineNumbers,
+    Attachment, AttachmentContent, AttachmentTag, DirectoryEntry, Document, DocumentUnit, FileTag,
+    GitTag, Image, LineNumbers, Location, McpResourceContent, McpResourceTag, NetFetch,
+    PermissionOperation, ServerName,
 };
 use forge_app::utils::format_display_path;
 use forge_app::{
-    AttachmentService, DirectoryReaderInfra, EnvironmentInfra, FileInfoInfra, FileReaderInfra,
+    AttachmentService, CommandInfra, DirectoryReaderInfra, EnvironmentInfra, FileInfoInfra,
+    FileReaderInfra, FileWriterInfra, McpService, NetFetchService, PolicyService, ResponseContext,
+    Walker, WalkerInfra,
 };
 
+use crate::document::{self, DocumentFormat};
 use crate::range::resolve_range;
 
 #[derive(Clone)]
-pub struct ForgeChatRequest<F> {
+pub struct ForgeChatRequest<F, M, N, P> {
     infra: Arc<F>,
+    mcp_service: Arc<M>,
+    fetch_service: Arc<N>,
+    policy_service: Arc<P>,
 }
 
-impl<F: FileReaderInfra + EnvironmentInfra + FileInfoInfra + DirectoryReaderInfra>
-    ForgeChatRequest<F>
+impl<
+    F: FileReaderInfra
+        + FileWriterInfra
+        + EnvironmentInfra
+        + FileInfoInfra
+        + DirectoryReaderInfra
+        + CommandInfra
+        + WalkerInfra,
+    M: McpService,
+    N: NetFetchService,
+    P: PolicyService,
+> ForgeChatRequest<F, M, N, P>
 {
-    pub fn new(infra: Arc<F>) -> Self {
-        Self { infra }
+    pub fn new(
+        infra: Arc<F>,
+        mcp_service: Arc<M>,
+        fetch_service: Arc<N>,
+        policy_service: Arc<P>,
+    ) -> Self {
+        Self { infra, mcp_service, fetch_service, policy_service }
     }
 
-    async fn prepare_attachments(&self, paths: Vec<FileTag>) -> anyhow::Result<Vec<Attachment>> {
-        futures::future::join_all(paths.into_iter().map(|v| self.populate_attachments(v)))
-            .await
+    async fn prepare_attachments(
+        &self,
+        tags: Vec<AttachmentTag>,
+    ) -> anyhow::Result<Vec<Attachment>> {
+        let attachments = futures::future::join_all(tags.into_iter().map(|tag| async move {
+            match tag {
+                AttachmentTag::File(tag) => Ok(vec![self.populate_attachments(tag).await?]),
+                AttachmentTag::McpResource(tag) => Ok(vec![self.populate_mcp_resource(tag).await?]),
+                AttachmentTag::Git(tag) => Ok(vec![self.populate_git(tag).await?]),
+                AttachmentTag::Url(url) => Ok(vec![self.populate_url(url).await?]),
+                AttachmentTag::Glob(pattern) => self.populate_glob(pattern).await,
+            }
+        }))
+        .await
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(attachments.into_iter().flatten().collect())
+    }
+
+    /// Asks the policy service whether `operation` may proceed.
+    async fn is_allowed(&self, operation: PermissionOperation) -> anyhow::Result<bool> {
+        Ok(self
+            .policy_service
+            .check_operation_permission(&operation)
+            .await?
+            .allowed)
+    }
+
+    async fn populate_url(&self, url: String) -> anyhow::Result<Attachment> {
+        let env = self.infra.get_environment();
+        let operation = PermissionOperation::Fetch {
+            url: url.clone(),
+            cwd: env.cwd.clone(),
+            message: format!("Fetch content from URL: {url}"),
+        };
+        if !self.is_allowed(operation).await? {
+            anyhow::bail!("Fetching {url} was denied by the permission policy");
+        }
+
+        let response = self
+            .fetch_service
+            .fetch(NetFetch { url: url.clone(), ..Default::default() })
+            .await?;
+        let content_type = match response.context {
+            ResponseContext::Parsed => "text/markdown".to_string(),
+            ResponseContext::Raw => response.content_type,
+        };
+
+        //NOTE: Apply the same truncation as the fetch tool
+        let total_chars = response.content.len();
+        let full_content_path = if total_chars > env.fetch_truncation_limit {
+            let path = self
+                .infra
+                .write_temp("forge_fetch_", ".txt", &response.content)
+                .await?;
+            Some(path.to_string_lossy().to_string())
+        } else {
+            None
+        };
+        let content = response
+            .content
+            .chars()
+            .take(env.fetch_truncation_limit)
+            .collect();
+
+        Ok(Attachment {
+            content: AttachmentContent::UrlContent {
+                content,
+                content_type,
+                total_chars,
+                full_content_path,
+            },
+            path: url,
+        })
+    }
+
+    /// Attaches every readable file matching `pattern`, in walk order, until
+    /// the file count or total size limit is reached. The walker applies the
+    /// depth, file size and binary limits while the count and size limits
+    /// apply to the attached files only, so files denied by the permission
+    /// policy never count toward them. A note tells how many matching files
+    /// were omitted once a limit is reached.
+    async fn populate_glob(&self, pattern: String) -> anyhow::Result<Vec<Attachment>> {
+        let env = self.infra.get_environment();
+        let (base, relative) = split_glob(&pattern);
+        let base = env.cwd.join(base);
+        let matcher = glob::Pattern::new(&relative)?;
+        let options = glob::MatchOptions { require_literal_separator: true, ..Default::default() };
+
+        let limits = Walker::conservative();
+        let walker = Walker {
+            cwd: base.clone(),
+            max_breadth: None,
+            max_files: None,
+            max_total_size: None,
+            ..limits.clone()
+        };
+        let files = self.infra.walk(walker).await?;
+
+        let mut total_size = 0;
+        let mut omitted = 0;
+        let mut matched = Vec::new();
+        for file in files
             .into_iter()
-            .collect::<anyhow::Result<Vec<_>>>()
+            .filter(|file| !file.is_dir() && matcher.matches_with(&file.path, options))
+        {
+            if omitted > 0
+                || limits.max_files.is_some_and(|max| matched.len() >= max)
+                || limits
+                    .max_total_size
+                    .is_some_and(|max| total_size + file.size > max)
+            {
+                omitted += 1;
+                continue;
+            }
+
+            let path = base.join(&file.path);
+            let operation = PermissionOperation::Read {
+                path: path.clone(),
+                cwd: env.cwd.clone(),
+                message: format!("Read file: `{}`", format_display_path(&path, &env.cwd)),
+            };
+            if self.is_allowed(operation).await? {
+                total_size += file.size;
+                matched.push(path);
+            }
+        }
+
+        if matched.is_empty() {
+            anyhow::bail!("No readable files match '{pattern}'");
+        }
+
+        let mut attachments = futures::future::join_all(matched.into_iter().map(|path| {
+            self.populate_attachments(FileTag {
+                path: path.to_string_lossy().to_string(),
+                loc: None,
+                symbol: None,
+            })
+        }))
+        .await
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if omitted > 0 {
+            attachments.push(Attachment {
+                content: AttachmentContent::OmittedFiles { count: omitted },
+                path: pattern,
+            });
+        }
+
+        Ok(attachments)
+    }
+
+    async fn populate_mcp_resource(&self, tag: McpResourceTag) -> anyhow::Result<Attachment> {
+        let server = ServerName::from(tag.server.clone());
+        let contents = self
+            .mcp_service
+            .read_mcp_resource(&server, &tag.uri)
+            .await?;
+        let path = format!("mcp:{}/{}", tag.server, tag.uri);
+
+        // A single image blob is attached as an image, everything else as text
+        if let [McpResourceContent::Blob { mime_type: Some(mime_type), blob, .. }] =
+            contents.as_slice()
+            && mime_type.starts_with("image/")
+        {
+            return Ok(Attachment {
+                content: AttachmentContent::Image(Image::new_base64(blob.clone(), mime_type)),
+                path,
+            });
+        }
+
+        let mut mime_type = None;
+        let mut texts = Vec::new();
+        for content in contents {
+            match content {
+                McpResourceContent::Text { mime_type: mime, text, .. } => {
+                    mime_type = mime_type.or(mime);
+                    texts.push(text);
+                }
+                McpResourceContent::Blob { uri, mime_type: mime, .. } => {
+                    anyhow::bail!(
+                        "Unsupported binary MCP resource '{uri}' ({})",
+                        mime.unwrap_or_else(|| "unknown type".to_string())
+                    );
+                }
+            }
+        }
+
+        Ok(Attachment {
+            content: AttachmentContent::McpResource {
+                server: tag.server,
+                uri: tag.uri,
+                mime_type,
+                content: texts.join("\n"),
+            },
+            path,
+        })
+    }
+
+    async fn populate_git(&self, tag: GitTag) -> anyhow::Result<Attachment> {
+        let env = self.infra.get_environment();
+        // Revisions come from the prompt, so they must never be read as options
+        let revision = |rev: &str| -> anyhow::Result<String> {
+            if rev.starts_with('-') {
+                anyhow::bail!("Invalid git revision '{rev}'");
+            }
+            Ok(rev.to_string())
+        };
+        let args = match &tag {
+            GitTag::Diff { base } => vec![
+                "diff".to_string(),
+                "--no-color".to_string(),
+                "--end-of-options".to_string(),
+                revision(base.as_deref().unwrap_or("HEAD"))?,
+            ],
+            GitTag::Commit { sha } => vec![
+                "show".to_string(),
+                "--no-color".to_string(),
+                "--stat".to_string(),
+                "--patch".to_string(),
+                "--end-of-options".to_string(),
+                revision(sha)?,
+            ],
+            GitTag::Show { rev, path } => vec![
+                "show".to_string(),
+                "--no-color".to_string(),
+                "--end-of-options".to_string(),
+                revision(&format!("{rev}:{path}"))?,
+            ],
+            GitTag::Blame { path, loc } => {
+                let mut args = vec!["blame".to_string()];
+                if let Some(loc) = loc {
+                    let (start, end) = resolve_range(loc.start, loc.end, env.max_read_size);
+                    args.extend(["-L".to_string(), format!("{start},{end}")]);
+                }
+                args.extend(["--".to_string(), path.clone()]);
+                args
+            }
+        };
+
+        let output = self
+            .infra
+            .execute_program("git", args, env.cwd.clone())
+            .await?;
+        if output.exit_code != Some(0) {
+            anyhow::bail!("`{}` failed: {}", output.command, output.stderr.trim());
+        }
+
+        //NOTE: Apply the same slicing as file reads
+        let lines = output.stdout.lines().collect::<Vec<_>>();
+        let total_lines = lines.len() as u64;
+        let (start_line, end_line) = resolve_range(None, None, env.max_read_size);
+        let end_line = end_line.min(total_lines);
+        let content = lines
+            .get((start_line - 1) as usize..end_line as usize)
+            .unwrap_or_default()
+            .join("\n");
+
+        Ok(Attachment {
+            content: AttachmentContent::GitContent { content, start_line, end_line, total_lines },
+            path: tag.to_string(),
+        })
+    }
+
+    /// Extracts the selected range of a document: pages of a PDF, paragraphs
+    /// of a DOCX/ODT file, data rows of a CSV file or sheets of a
+    /// spreadsheet. Tables are rendered as markdown. Ranges and sheets are
+    /// limited to `max_read_size` pages, paragraphs or rows, and PDFs larger
+    /// than `max_image_size` are attached as their text of up to
+    /// `max_file_size` characters.
+    async fn populate_document(
+        &self,
+        path: &Path,
+        format: DocumentFormat,
+        loc: Option<&Location>,
+    ) -> anyhow::Result<AttachmentContent> {
+        let bytes = self.infra.read(path).await?;
+        let env = self.infra.get_environment();
+        let (max_read_size, max_image_size, max_file_size) =
+            (env.max_read_size, env.max_image_size, env.max_file_size);
+        let start = loc.and_then(|loc| loc.start);
+        let end = loc.and_then(|loc| loc.end);
+        let title = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        // Extraction is CPU bound and the parsers may panic on malformed input,
+        // which the blocking task turns into an error
+        let extract = move || -> anyhow::Result<AttachmentContent> {
+            let select = |start: u64, end: u64| {
+                (
+                    start.saturating_sub(1) as usize,
+                    (end + 1).saturating_sub(start) as usize,
+                )
+            };
+
+            match format {
+                DocumentFormat::Pdf => {
+                    let (start, end) = resolve_range(start, end, max_read_size);
+                    let pages = document::pdf_pages(&bytes, start, end)?;
+                    if pages.data.len() as u64 > max_image_size {
+                        return Ok(AttachmentContent::DocumentText {
+                            content: pages.text.chars().take(max_file_size as usize).collect(),
+                            unit: DocumentUnit::Page,
+                            start: pages.start,
+                            end: pages.end,
+                            total: pages.total,
+                        });
+                    }
+                    Ok(AttachmentContent::Document {
+                        document: Document::new_bytes(
+                            pages.data,
+                            "application/pdf",
+                            title,
+                            pages.text,
+                        ),
+                        start_page: pages.start,
+                        end_page: pages.end,
+                        total_pages: pages.total,
+                    })
+                }
+                DocumentFormat::Docx | DocumentFormat::Odt => {
+                    let paragraphs = document::paragraphs(&bytes, format)?;
+                    let total = paragraphs.len() as u64;
+                    let (start, end) = resolve_range(start, end, max_read_size);
+                    let (start, end) = document::clamp(start, end, total);
+                    let (skip, take) = select(start, end);
+                    let content = paragraphs
+                        .into_iter()
+                        .skip(skip)
+                        .take(take)
+                        .collect::<Vec<_>>()
+                        .join("\n\n");
+                    Ok(AttachmentContent::DocumentText {
+                        content,
+                        unit: DocumentUnit::Paragraph,
+                        start,
+                        end,
+                        total,
+                    })
+                }
+                DocumentFormat::Csv => {
+                    let mut rows = document::csv_rows(&bytes)?.into_iter();
+                    let header = rows.next().into_iter();
+                    let rows = rows.collect::<Vec<_>>();
+                    let total = rows.len() as u64;
+                    let (start, end) = resolve_range(start, end, max_read_size);
+                    let (start, end) = document::clamp(start, end, total);
+                    let (skip, take) = select(start, end);
+                    let table = header
+                        .chain(rows.into_iter().skip(skip).take(take))
+                        .collect::<Vec<_>>();
+                    Ok(AttachmentContent::DocumentText {
+                        content: document::markdown_table(&table),
+                        unit: DocumentUnit::Row,
+                        start,
+                        end,
+                        total,
+                    })
+                }
+                DocumentFormat::Spreadsheet => {
+                    let sheets = document::sheets(&bytes)?;
+                    let total = sheets.len() as u64;
+                    let (start, end) = resolve_range(start, end, max_read_size);
+                    let (start, end) = document::clamp(start, end, total);
+                    let (skip, take) = select(start, end);
+                    let content = sheets
+                        .into_iter()
+                        .skip(skip)
+                        .take(take)
+                        .map(|(name, rows)| {
+                            // The header row is kept on top of `max_read_size` data rows
+                            let omitted = rows.len().saturating_sub(max_read_size as usize + 1);
+                            let rows = &rows[..rows.len() - omitted];
+                            let table = document::markdown_table(rows);
+                            if omitted > 0 {
+                                format!("## {name}\n\n{table}\n\n({omitted} more rows omitted)")
+                            } else {
+                                format!("## {name}\n\n{table}")
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n\n");
+                    Ok(AttachmentContent::DocumentText {
+                        content,
+                        unit: DocumentUnit::Sheet,
+                        start,
+                        end,
+                        total,
+                    })
+                }
+            }
+        };
+
+        tokio::task::spawn_blocking(extract)
+            .await
+            .map_err(|error| {
+                anyhow::anyhow!("Failed to extract text from {}: {error}", path.display())
+            })?
     }
 
     async fn populate_attachments(&self, tag: FileTag) -> anyhow::Result<Attachment> {
@@ -68,6 +489,18 @@ impl<F: FileReaderInfra + EnvironmentInfra + FileInfoInfra + DirectoryReaderInfr
             });
         }
 
+        if let Some(format) = extension
+            .as_deref()
+            .and_then(DocumentFormat::from_extension)
+        {
+            return Ok(Attachment {
+                content: self
+                    .populate_document(&path, format, tag.loc.as_ref())
+                    .await?,
+                path: path.to_string_lossy().to_string(),
+            });
+        }
+
         // Determine file type (text or image with format)
         let mime_type = extension.and_then(|ext| match ext.as_str() {
             "jpeg" | "jpg" => Some("image/jpeg".to_string()),
@@ -110,12 +543,41 @@ impl<F: FileReaderInfra + EnvironmentInfra + FileInfoInfra + DirectoryReaderInfr
 }
 
 #[async_trait::async_trait]
-impl<F: FileReaderInfra + EnvironmentInfra + FileInfoInfra + DirectoryReaderInfra> AttachmentService
-    for ForgeChatRequest<F>
+impl<
+    F: FileReaderInfra
+        + FileWriterInfra
+        + EnvironmentInfra
+        + FileInfoInfra
+        + DirectoryReaderInfra
+        + CommandInfra
+        + WalkerInfra,
+    M: McpService,
+    N: NetFetchService,
+    P: PolicyService,
+> AttachmentService for ForgeChatRequest<F, M, N, P>
 {
     async fn attachments(&self, url: &str) -> anyhow::Result<Vec<Attachment>> {
-        self.prepare_attachments(Attachment::parse_all(url)).await
+        self.prepare_attachments(Attachment::parse_tags(url)).await
+    }
+}
+
+/// Splits a glob into the directory before its first wildcard component and
+/// the pattern relative to that directory.
+fn split_glob(pattern: &str) -> (PathBuf, String) {
+    let mut base = PathBuf::new();
+    let mut components = Path::new(pattern).components().peekable();
+    while let Some(component) = components.next_if(|component| {
+        !matches!(component, Component::Normal(name) if name.to_string_lossy().contains(['*', '?']))
+    }) {
+        base.push(component);
     }
+
+    let relative = components
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    (base, relative)
 }
 
 #[cfg(test)]
@@ -123,18 +585,23 @@ pub mod tests {
     use std::collections::{BTreeMap, HashMap, HashSet};
     use std::path::{Path, PathBuf};
     use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
 
     use base64::Engine;
     use bytes::Bytes;
     use forge_app::domain::{
-        AttachmentContent, CommandOutput, Environment, ToolDefinition, ToolName, ToolOutput,
+        Attachment, AttachmentContent, CommandOutput, DocumentUnit, Environment, Image, McpPrompt,
+        McpResource, McpResourceContent, McpServers, NetFetch, Permission, PermissionOperation,
+        ServerName, ToolCallFull, ToolDefinition, ToolName, ToolOutput,
     };
     use forge_app::{
         AttachmentService, CommandInfra, DirectoryReaderInfra, EnvironmentInfra,
         FileDirectoryInfra, FileInfoInfra, FileReaderInfra, FileRemoverInfra, FileWriterInfra,
-        McpClientInfra, McpServerInfra, UserInfra,
+        HttpResponse, McpClientInfra, McpServerInfra, McpService, NetFetchService, PolicyDecision,
+        PolicyService, ResponseContext, UserInfra, WalkedFile, Walker, WalkerInfra,
     };
     use forge_domain::FileInfo;
+    use pretty_assertions::assert_eq;
     use serde_json::Value;
 
     use crate::attachment::ForgeChatRequest;
@@ -406,6 +873,10 @@ pub mod tests {
                 Err(anyhow::anyhow!("File not found: {}", path.display()))
             }
         }
+
+        async fn modified(&self, _path: &Path) -> anyhow::Result<SystemTime> {
+            unimplemented!()
+        }
     }
     #[derive(Debug, Clone)]
     #[allow(dead_code)]
@@ -419,6 +890,93 @@ pub mod tests {
         async fn call(&self, _: &ToolName, _: Value) -> anyhow::Result<ToolOutput> {
             Ok(ToolOutput::default())
         }
+
+        async fn list_resources(&self) -> anyhow::Result<Vec<McpResource>> {
+            Ok(vec![])
+        }
+
+        async fn read_resource(&self, _: &str) -> anyhow::Result<Vec<McpResourceContent>> {
+            Ok(vec![])
+        }
+
+        async fn list_prompts(&self) -> anyhow::Result<Vec<McpPrompt>> {
+            Ok(vec![])
+        }
+
+        async fn get_prompt(&self, _: &str, _: BTreeMap<String, String>) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+    }
+
+    /// Serves resources keyed by `server/uri`
+    #[derive(Default)]
+    pub struct MockMcpService {
+        resources: HashMap<String, Vec<McpResourceContent>>,
+    }
+
+    impl MockMcpService {
+        fn resource(mut self, server: &str, uri: &str, contents: Vec<McpResourceContent>) -> Self {
+            self.resources.insert(format!("{server}/{uri}"), contents);
+            self
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl McpService for MockMcpService {
+        async fn get_mcp_servers(&self) -> anyhow::Result<McpServers> {
+            Ok(McpServers::default())
+        }
+
+        async fn execute_mcp(&self, _: ToolCallFull) -> anyhow::Result<ToolOutput> {
+            Ok(ToolOutput::default())
+        }
+
+        async fn reload_mcp(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn read_mcp_resource(
+            &self,
+            server: &ServerName,
+            uri: &str,
+        ) -> anyhow::Result<Vec<McpResourceContent>> {
+            self.resources
+                .get(&format!("{server}/{uri}"))
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Resource not found: {uri}"))
+        }
+
+        async fn get_mcp_prompt(
+            &self,
+            _: &ServerName,
+            _: &str,
+            _: BTreeMap<String, String>,
+        ) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        async fn get_mcp_status(&self) -> anyhow::Result<Vec<forge_app::domain::McpServerStatus>> {
+            Ok(vec![])
+        }
+
+        async fn init_mcp_auth(
+            &self,
+            _: &ServerName,
+        ) -> anyhow::Result<forge_app::domain::CodeRequest> {
+            unimplemented!()
+        }
+
+        async fn complete_mcp_auth(
+            &self,
+            _: &ServerName,
+            _: forge_app::domain::AuthContextResponse,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn logout_mcp(&self, _: &ServerName) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     #[async_trait::async_trait]
@@ -563,6 +1121,15 @@ pub mod tests {
         ) -> anyhow::Result<std::process::ExitStatus> {
             unimplemented!()
         }
+
+        async fn execute_program(
+            &self,
+            _: &str,
+            _: Vec<String>,
+            _: PathBuf,
+        ) -> anyhow::Result<CommandOutput> {
+            unimplemented!()
+        }
     }
 
     #[async_trait::async_trait]
@@ -600,6 +1167,66 @@ pub mod tests {
         }
     }
 
+    /// Serves pages keyed by URL
+    #[derive(Default)]
+    pub struct MockNetFetchService {
+        pages: HashMap<String, String>,
+    }
+
+    impl MockNetFetchService {
+        fn page(mut self, url: &str, content: &str) -> Self {
+            self.pages.insert(url.to_string(), content.to_string());
+            self
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl NetFetchService for MockNetFetchService {
+        async fn fetch(&self, request: NetFetch) -> anyhow::Result<HttpResponse> {
+            let url = request.url;
+            let content = self
+                .pages
+                .get(&url)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Failed to fetch {url} - status code 404"))?;
+            Ok(HttpResponse {
+                content,
+                code: 200,
+                context: ResponseContext::Parsed,
+                content_type: "text/html".to_string(),
+            })
+        }
+    }
+
+    /// Allows every operation except reads of the denied paths
+    #[derive(Default)]
+    pub struct MockPolicyService {
+        denied: Vec<PathBuf>,
+    }
+
+    #[async_trait::async_trait]
+    impl PolicyService for MockPolicyService {
+        async fn check_operation_permission(
+            &self,
+            operation: &PermissionOperation,
+        ) -> anyhow::Result<PolicyDecision> {
+            let allowed = match operation {
+                PermissionOperation::Read { path, .. } => !self.denied.contains(path),
+                _ => true,
+            };
+            Ok(PolicyDecision { allowed, path: None })
+        }
+
+        async fn permission(&self, operation: &PermissionOperation) -> anyhow::Result<Permission> {
+            let allowed = self.check_operation_permission(operation).await?.allowed;
+            Ok(if allowed {
+                Permission::Allow
+            } else {
+                Permission::Deny
+            })
+        }
+    }
+
     // Create a composite mock service that implements the required traits
     #[derive(Debug, Clone)]
     pub struct MockCompositeService {
@@ -674,6 +1301,10 @@ pub mod tests {
         async fn file_size(&self, path: &Path) -> anyhow::Result<u64> {
             self.file_service.file_size(path).await
         }
+
+        async fn modified(&self, path: &Path) -> anyhow::Result<SystemTime> {
+            self.file_service.modified(path).await
+        }
     }
 
     #[async_trait::async_trait]
@@ -696,11 +1327,99 @@ pub mod tests {
         }
     }
 
+    #[async_trait::async_trait]
+    impl FileWriterInfra for MockCompositeService {
+        async fn write(&self, path: &Path, contents: Bytes) -> anyhow::Result<()> {
+            self.file_service.write(path, contents).await
+        }
+
+        async fn write_temp(
+            &self,
+            prefix: &str,
+            ext: &str,
+            content: &str,
+        ) -> anyhow::Result<PathBuf> {
+            self.file_service.write_temp(prefix, ext, content).await
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl WalkerInfra for MockCompositeService {
+        async fn walk(&self, config: Walker) -> anyhow::Result<Vec<WalkedFile>> {
+            let files = self.file_service.files.lock().unwrap();
+            Ok(files
+                .iter()
+                .filter_map(|(path, content)| {
+                    let relative = path.strip_prefix(&config.cwd).ok()?;
+                    Some(WalkedFile {
+                        path: relative.to_string_lossy().to_string(),
+                        file_name: path
+                            .file_name()
+                            .map(|name| name.to_string_lossy().to_string()),
+                        size: content.len() as u64,
+                    })
+                })
+                .collect())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl CommandInfra for MockCompositeService {
+        async fn execute_command(
+            &self,
+            _command: String,
+            _working_dir: PathBuf,
+            _silent: bool,
+            _env_vars: Option<Vec<String>>,
+        ) -> anyhow::Result<CommandOutput> {
+            unimplemented!()
+        }
+
+        async fn execute_command_raw(
+            &self,
+            _command: &str,
+            _working_dir: PathBuf,
+            _env_vars: Option<Vec<String>>,
+        ) -> anyhow::Result<std::process::ExitStatus> {
+            unimplemented!()
+        }
+
+        async fn execute_program(
+            &self,
+            program: &str,
+            args: Vec<String>,
+            _working_dir: PathBuf,
+        ) -> anyhow::Result<CommandOutput> {
+            let command = format!("{program} {}", args.join(" "));
+            // Only a diff against main is known to the mock repository
+            if command == "git diff --no-color --end-of-options main" {
+                return Ok(CommandOutput {
+                    stdout: "diff --git a/src/lib.rs b/src/lib.rs\n+pub mod git;\n".to_string(),
+                    stderr: "".to_string(),
+                    command,
+                    exit_code: Some(0),
+                });
+            }
+
+            Ok(CommandOutput {
+                stdout: "".to_string(),
+                stderr: "fatal: bad revision\n".to_string(),
+                command,
+                exit_code: Some(128),
+            })
+        }
+    }
+
     #[tokio::test]
     async fn test_add_url_with_text_file() {
         // Setup
         let infra = Arc::new(MockCompositeService::new());
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with a text file path in chat message
         let url = "@[/test/file1.txt]".to_string();
@@ -722,7 +1441,12 @@ pub mod tests {
     async fn test_add_url_with_image() {
         // Setup
         let infra = Arc::new(MockCompositeService::new());
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with an image file
         let url = "@[/test/image.png]".to_string();
@@ -749,7 +1473,12 @@ pub mod tests {
     async fn test_add_url_with_jpg_image_with_spaces() {
         // Setup
         let infra = Arc::new(MockCompositeService::new());
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with an image file that has spaces in the path
         let url = "@[/test/image with spaces.jpg]".to_string();
@@ -782,7 +1511,12 @@ pub mod tests {
             "This is another text file".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with multiple files mentioned
         let url = "@[/test/file1.txt] @[/test/file2.txt] @[/test/image.png]".to_string();
@@ -816,7 +1550,12 @@ pub mod tests {
     async fn test_add_url_with_nonexistent_file() {
         // Setup
         let infra = Arc::new(MockCompositeService::new());
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with a file that doesn't exist
         let url = "@[/test/nonexistent.txt]".to_string();
@@ -833,7 +1572,12 @@ pub mod tests {
     async fn test_add_url_empty() {
         // Setup
         let infra = Arc::new(MockCompositeService::new());
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with an empty message
         let url = "".to_string();
@@ -856,7 +1600,12 @@ pub mod tests {
             "Some content".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with the file
         let url = "@[/test/unknown.xyz]".to_string();
@@ -884,7 +1633,12 @@ pub mod tests {
             "Line 1\nLine 2\nLine 3\nLine 4\nLine 5".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
         let url = "@[/test/multiline.txt]".to_string();
 
         // Execute
@@ -926,7 +1680,12 @@ pub mod tests {
             "Line 1\nLine 2\nLine 3\nLine 4\nLine 5".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test reading line 2 only
         let url = "@[/test/multiline.txt:2:2]";
@@ -954,7 +1713,12 @@ pub mod tests {
             "Line 1\nLine 2\nLine 3\nLine 4\nLine 5\nLine 6".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test reading lines 2-4
         let url = "@[/test/range_test.txt:2:4]";
@@ -982,7 +1746,12 @@ pub mod tests {
             "First\nSecond\nThird\nFourth".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test reading from start to line 2
         let url = "@[/test/start_range.txt:1:2]";
@@ -1007,7 +1776,12 @@ pub mod tests {
             "Alpha\nBeta\nGamma\nDelta\nEpsilon".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test reading from line 3 to end
         let url = "@[/test/end_range.txt:3:5]";
@@ -1032,7 +1806,12 @@ pub mod tests {
             "Only line".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test reading beyond file length
         let url = "@[/test/edge_case.txt:1:10]";
@@ -1058,7 +1837,12 @@ pub mod tests {
             "B1\nB2\nB3\nB4".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test multiple files with different ranges
         let url = "Check @[/test/file_a.txt:1:2] and @[/test/file_b.txt:3:4]";
@@ -1094,7 +1878,12 @@ pub mod tests {
             "Meta1\nMeta2\nMeta3\nMeta4\nMeta5\nMeta6\nMeta7".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test that metadata is preserved correctly with ranges
         let url = "@[/test/metadata_test.txt:3:5]";
@@ -1122,7 +1911,12 @@ pub mod tests {
             "Full1\nFull2\nFull3\nFull4\nFull5".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test full file vs ranged file to ensure they're different
         let url_full = "@[/test/comparison.txt]";
@@ -1185,7 +1979,12 @@ pub mod tests {
             .file_service
             .add_dir(PathBuf::from("/test/mydir/subdir"));
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with directory path
         let url = "@[/test/mydir]";
@@ -1226,7 +2025,12 @@ pub mod tests {
         // Add empty directory
         infra.file_service.add_dir(PathBuf::from("/test/emptydir"));
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with empty directory path
         let url = "@[/test/emptydir]";
@@ -1264,7 +2068,12 @@ pub mod tests {
             "Standalone file".to_string(),
         );
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
 
         // Test with both file and directory
         let url = "@[/test/mixdir] @[/test/standalone.txt]";
@@ -1327,7 +2136,12 @@ pub mod tests {
             .file_service
             .add_dir(PathBuf::from("/test/sortdir/berry_dir"));
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
         let url = "@[/test/sortdir]";
         let attachments = chat_request.attachments(url).await.unwrap();
 
@@ -1379,7 +2193,12 @@ pub mod tests {
             .file_service
             .add_dir(PathBuf::from("/test/onlydirs/middle_dir"));
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
         let url = "@[/test/onlydirs]";
         let attachments = chat_request.attachments(url).await.unwrap();
 
@@ -1411,7 +2230,12 @@ pub mod tests {
         infra.add_file(PathBuf::from("/test/onlyfiles/alpha.txt"), "A".to_string());
         infra.add_file(PathBuf::from("/test/onlyfiles/middle.txt"), "M".to_string());
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
         let url = "@[/test/onlyfiles]";
         let attachments = chat_request.attachments(url).await.unwrap();
 
@@ -1448,7 +2272,12 @@ pub mod tests {
         infra.add_file(PathBuf::from("/test/casetest/Zebra.txt"), "Z".to_string());
         infra.add_file(PathBuf::from("/test/casetest/apple.txt"), "A".to_string());
 
-        let chat_request = ForgeChatRequest::new(infra.clone());
+        let chat_request = ForgeChatRequest::new(
+            infra.clone(),
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
         let url = "@[/test/casetest]";
         let attachments = chat_request.attachments(url).await.unwrap();
 
@@ -1472,4 +2301,355 @@ pub mod tests {
             _ => panic!("Expected DirectoryListing attachment"),
         }
     }
+
+    #[tokio::test]
+    async fn test_add_url_with_mcp_text_resource() {
+        let infra = Arc::new(MockCompositeService::new());
+        let mcp = MockMcpService::default().resource(
+            "docs",
+            "file:///setup.md",
+            vec![McpResourceContent::Text {
+                uri: "file:///setup.md".to_string(),
+                mime_type: Some("text/markdown".to_string()),
+                text: "# Setup".to_string(),
+            }],
+        );
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(mcp),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request
+            .attachments("Read @[mcp:docs/file:///setup.md]")
+            .await
+            .unwrap();
+
+        let expected = vec![Attachment {
+            content: AttachmentContent::McpResource {
+                server: "docs".to_string(),
+                uri: "file:///setup.md".to_string(),
+                mime_type: Some("text/markdown".to_string()),
+                content: "# Setup".to_string(),
+            },
+            path: "mcp:docs/file:///setup.md".to_string(),
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_url_with_mcp_image_resource() {
+        let infra = Arc::new(MockCompositeService::new());
+        let mcp = MockMcpService::default().resource(
+            "design",
+            "figma://logo",
+            vec![McpResourceContent::Blob {
+                uri: "figma://logo".to_string(),
+                mime_type: Some("image/png".to_string()),
+                blob: "aGk=".to_string(),
+            }],
+        );
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(mcp),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request
+            .attachments("@[mcp:design/figma://logo]")
+            .await
+            .unwrap();
+
+        let expected = vec![Attachment {
+            content: AttachmentContent::Image(Image::new_base64("aGk=".to_string(), "image/png")),
+            path: "mcp:design/figma://logo".to_string(),
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_url_with_unknown_mcp_resource() {
+        let infra = Arc::new(MockCompositeService::new());
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request.attachments("@[mcp:docs/missing]").await;
+
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_add_url_with_git_diff() {
+        let infra = Arc::new(MockCompositeService::new());
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request
+            .attachments("Review @[git:diff:main]")
+            .await
+            .unwrap();
+
+        let expected = vec![Attachment {
+            content: AttachmentContent::GitContent {
+                content: "diff --git a/src/lib.rs b/src/lib.rs\n+pub mod git;".to_string(),
+                start_line: 1,
+                end_line: 2,
+                total_lines: 2,
+            },
+            path: "git:diff:main".to_string(),
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_url_with_unknown_git_revision() {
+        let infra = Arc::new(MockCompositeService::new());
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request
+            .attachments("@[git:commit:deadbeef]")
+            .await
+            .unwrap_err()
+            .to_string();
+
+        let expected = "`git show --no-color --stat --patch --end-of-options deadbeef` failed: fatal: bad revision";
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_url_with_git_option_as_revision() {
+        let infra = Arc::new(MockCompositeService::new());
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request
+            .attachments("@[git:diff:--output=/tmp/pwn]")
+            .await
+            .unwrap_err()
+            .to_string();
+
+        let expected = "Invalid git revision '--output=/tmp/pwn'";
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_url_with_web_page() {
+        let infra = Arc::new(MockCompositeService::new());
+        let fetch = MockNetFetchService::default().page("https://docs.rs/nom", "# nom");
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(fetch),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request
+            .attachments("Read @[https://docs.rs/nom]")
+            .await
+            .unwrap();
+
+        let expected = vec![Attachment {
+            content: AttachmentContent::UrlContent {
+                content: "# nom".to_string(),
+                content_type: "text/markdown".to_string(),
+                total_chars: 5,
+                full_content_path: None,
+            },
+            path: "https://docs.rs/nom".to_string(),
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_glob_expands_matching_files() {
+        let infra = Arc::new(MockCompositeService::new());
+        infra.add_file(
+            PathBuf::from("/test/migrations/001_init.sql"),
+            "CREATE TABLE users;".to_string(),
+        );
+        infra.add_file(
+            PathBuf::from("/test/migrations/nested/002_posts.sql"),
+            "CREATE TABLE posts;".to_string(),
+        );
+        infra.add_file(
+            PathBuf::from("/test/migrations/README.md"),
+            "Migrations".to_string(),
+        );
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request
+            .attachments("Review @[migrations/**/*.sql]")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|attachment| attachment.path)
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            "/test/migrations/001_init.sql".to_string(),
+            "/test/migrations/nested/002_posts.sql".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_glob_skips_denied_files() {
+        let infra = Arc::new(MockCompositeService::new());
+        infra.add_file(PathBuf::from("/test/a.sql"), "SELECT 1;".to_string());
+        infra.add_file(PathBuf::from("/test/b.sql"), "SELECT 2;".to_string());
+        let policy = MockPolicyService { denied: vec![PathBuf::from("/test/a.sql")] };
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(policy),
+        );
+
+        let actual = chat_request
+            .attachments("@[*.sql]")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|attachment| attachment.path)
+            .collect::<Vec<_>>();
+
+        let expected = vec!["/test/b.sql".to_string()];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_glob_notes_omitted_files() {
+        let infra = Arc::new(MockCompositeService::new());
+        for index in 0..102 {
+            infra.add_file(
+                PathBuf::from(format!("/test/logs/{index:03}.log")),
+                "line".to_string(),
+            );
+        }
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request.attachments("@[logs/*.log]").await.unwrap();
+
+        assert_eq!(actual.len(), 101);
+        let expected = Attachment {
+            content: AttachmentContent::OmittedFiles { count: 2 },
+            path: "logs/*.log".to_string(),
+        };
+        assert_eq!(actual.last(), Some(&expected));
+    }
+
+    #[tokio::test]
+    async fn test_add_glob_denied_files_do_not_count_toward_limits() {
+        let infra = Arc::new(MockCompositeService::new());
+        for index in 0..101 {
+            infra.add_file(
+                PathBuf::from(format!("/test/logs/{index:03}.log")),
+                "line".to_string(),
+            );
+        }
+        let policy = MockPolicyService { denied: vec![PathBuf::from("/test/logs/000.log")] };
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(policy),
+        );
+
+        let actual = chat_request.attachments("@[logs/*.log]").await.unwrap();
+
+        assert_eq!(actual.len(), 100);
+        assert!(actual.iter().all(|attachment| !matches!(
+            attachment.content,
+            AttachmentContent::OmittedFiles { .. }
+        )));
+    }
+
+    #[test]
+    fn test_split_glob() {
+        let actual = [
+            super::split_glob("src/**/*.sql"),
+            super::split_glob("*.rs"),
+            super::split_glob("/abs/dir/file?.txt"),
+        ];
+        let expected = [
+            (PathBuf::from("src"), "**/*.sql".to_string()),
+            (PathBuf::new(), "*.rs".to_string()),
+            (PathBuf::from("/abs/dir"), "file?.txt".to_string()),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_csv_renders_selected_rows_as_table() {
+        let infra = Arc::new(MockCompositeService::new());
+        infra.add_file(
+            PathBuf::from("/test/users.csv"),
+            "id,name\n1,alice\n2,bob\n3,carol\n".to_string(),
+        );
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request.attachments("@[users.csv:2:3]").await.unwrap();
+
+        let expected = vec![Attachment {
+            content: AttachmentContent::DocumentText {
+                content: "| id | name |\n| --- | --- |\n| 2 | bob |\n| 3 | carol |".to_string(),
+                unit: DocumentUnit::Row,
+                start: 2,
+                end: 3,
+                total: 3,
+            },
+            path: "/test/users.csv".to_string(),
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_add_malformed_pdf_fails() {
+        let infra = Arc::new(MockCompositeService::new());
+        infra.add_file(PathBuf::from("/test/spec.pdf"), "not a pdf".to_string());
+        let chat_request = ForgeChatRequest::new(
+            infra,
+            Arc::new(MockMcpService::default()),
+            Arc::new(MockNetFetchService::default()),
+            Arc::new(MockPolicyService::default()),
+        );
+
+        let actual = chat_request.attachments("@[spec.pdf]").await;
+
+        assert!(actual.is_err());
+    }
 }
@@ -0,0 +1,329 @@
+use std::io::{Cursor, Read};
+
+use anyhow::Context;
+use calamine::Reader as _;
+use quick_xml::events::Event;
+
+/// Document formats whose text can be extracted for attachments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentFormat {
+    Pdf,
+    Docx,
+    Odt,
+    Csv,
+    Spreadsheet,
+}
+
+impl DocumentFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "pdf" => Some(Self::Pdf),
+            "docx" => Some(Self::Docx),
+            "odt" => Some(Self::Odt),
+            "csv" => Some(Self::Csv),
+            "xlsx" | "xlsm" | "xls" | "ods" => Some(Self::Spreadsheet),
+            _ => None,
+        }
+    }
+}
+
+/// Selected pages of a PDF along with their extracted text.
+#[derive(Debug)]
+pub struct PdfPages {
+    /// PDF containing only the selected pages
+    pub data: Vec<u8>,
+    pub text: String,
+    pub start: u64,
+    pub end: u64,
+    pub total: u64,
+}
+
+/// Keeps pages `start..=end` of a PDF and extracts their text, one block per
+/// page.
+pub fn pdf_pages(bytes: &[u8], start: u64, end: u64) -> anyhow::Result<PdfPages> {
+    let mut document =
+        pdf_extract::Document::load_mem(bytes).context("Failed to parse PDF document")?;
+    let total = document.get_pages().len() as u64;
+    anyhow::ensure!(total > 0, "PDF document has no pages");
+    let (start, end) = clamp(start, end, total);
+
+    let pages = pdf_extract::extract_text_from_mem_by_pages(bytes)
+        .context("Failed to extract text from PDF document")?;
+    let text = pages
+        .iter()
+        .enumerate()
+        .skip(start.saturating_sub(1) as usize)
+        .take((end + 1).saturating_sub(start) as usize)
+        .map(|(index, page)| format!("--- Page {} ---\n{}", index + 1, page.trim()))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let data = if start == 1 && end == total {
+        bytes.to_vec()
+    } else {
+        let excluded = (1..=total as u32)
+            .filter(|page| !(start..=end).contains(&(*page as u64)))
+            .collect::<Vec<_>>();
+        document.delete_pages(&excluded);
+        document.prune_objects();
+        let mut data = Vec::new();
+        document
+            .save_to(&mut data)
+            .context("Failed to write selected PDF pages")?;
+        data
+    };
+
+    Ok(PdfPages { data, text, start, end, total })
+}
+
+/// Extracts the paragraphs of a DOCX or ODT document, skipping empty ones.
+pub fn paragraphs(bytes: &[u8], format: DocumentFormat) -> anyhow::Result<Vec<String>> {
+    let (entry, paragraph, line_break, tab) = match format {
+        DocumentFormat::Docx => ("word/document.xml", &["p"][..], "br", "tab"),
+        DocumentFormat::Odt => ("content.xml", &["p", "h"][..], "line-break", "tab"),
+        _ => anyhow::bail!("{format:?} documents have no paragraphs"),
+    };
+
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(bytes)).context("Failed to open document archive")?;
+    let mut xml = String::new();
+    archive
+        .by_name(entry)
+        .with_context(|| format!("Document is missing {entry}"))?
+        .read_to_string(&mut xml)?;
+
+    let mut reader = quick_xml::Reader::from_str(&xml);
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) | Event::Empty(element)
+                if element.local_name().as_ref() == line_break.as_bytes() =>
+            {
+                current.push('\n')
+            }
+            Event::Start(element) | Event::Empty(element)
+                if element.local_name().as_ref() == tab.as_bytes() =>
+            {
+                current.push('\t')
+            }
+            Event::End(element)
+                if paragraph
+                    .iter()
+                    .any(|name| element.local_name().as_ref() == name.as_bytes()) =>
+            {
+                let text = std::mem::take(&mut current);
+                if !text.trim().is_empty() {
+                    paragraphs.push(text.trim().to_string());
+                }
+            }
+            Event::Text(text) => current.push_str(&text.decode()?),
+            Event::CData(text) => current.push_str(&text.decode()?),
+            Event::GeneralRef(reference) => {
+                if let Some(character) = reference.resolve_char_ref()? {
+                    current.push(character);
+                } else if let Some(entity) =
+                    quick_xml::escape::resolve_predefined_entity(&reference.decode()?)
+                {
+                    current.push_str(entity);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(paragraphs)
+}
+
+/// Parses a CSV file into rows, the first of which is the header.
+pub fn csv_rows(bytes: &[u8]) -> anyhow::Result<Vec<Vec<String>>> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(bytes)
+        .records()
+        .map(|record| Ok(record?.iter().map(str::to_string).collect()))
+        .collect()
+}
+
+/// Reads every sheet of a spreadsheet as a name and its rows.
+pub fn sheets(bytes: &[u8]) -> anyhow::Result<Vec<(String, Vec<Vec<String>>)>> {
+    let mut workbook = calamine::open_workbook_auto_from_rs(Cursor::new(bytes))
+        .context("Failed to open spreadsheet")?;
+
+    Ok(workbook
+        .worksheets()
+        .into_iter()
+        .map(|(name, range)| {
+            let rows = range
+                .rows()
+                .map(|row| row.iter().map(ToString::to_string).collect())
+                .collect();
+            (name, rows)
+        })
+        .collect())
+}
+
+/// Renders rows as a markdown table using the first row as the header.
+pub fn markdown_table(rows: &[Vec<String>]) -> String {
+    let Some((header, body)) = rows.split_first() else {
+        return String::new();
+    };
+    let columns = rows.iter().map(Vec::len).max().unwrap_or_default().max(1);
+    let render = |row: &Vec<String>| {
+        let cells = (0..columns)
+            .map(|column| {
+                row.get(column)
+                    .map(|cell| cell.replace('|', "\\|").replace(['\r', '\n'], " "))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    std::iter::once(render(header))
+        .chain(std::iter::once(format!("|{}", " --- |".repeat(columns))))
+        .chain(body.iter().map(render))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Clamps a 1-based inclusive range to `1..=total`.
+pub fn clamp(start: u64, end: u64, total: u64) -> (u64, u64) {
+    let end = end.min(total);
+    (start.min(end).max(1), end)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn archive(entry: &str, xml: &str) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file(entry, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(xml.as_bytes()).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// Builds a PDF with one page per entry of `pages`, each showing its text.
+    fn pdf(pages: &[&str]) -> Vec<u8> {
+        use pdf_extract::content::{Content, Operation};
+        use pdf_extract::{Object, Stream, dictionary};
+
+        let mut document = pdf_extract::Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let font_id = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+            "Encoding" => "WinAnsiEncoding",
+        });
+        let resources_id = document.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+        let kids = pages
+            .iter()
+            .map(|text| {
+                let content = Content {
+                    operations: vec![
+                        Operation::new("BT", vec![]),
+                        Operation::new("Tf", vec!["F1".into(), 24.into()]),
+                        Operation::new("Td", vec![72.into(), 700.into()]),
+                        Operation::new("Tj", vec![Object::string_literal(*text)]),
+                        Operation::new("ET", vec![]),
+                    ],
+                };
+                let content_id =
+                    document.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+                document
+                    .add_object(dictionary! {
+                        "Type" => "Page",
+                        "Parent" => pages_id,
+                        "Contents" => content_id,
+                    })
+                    .into()
+            })
+            .collect::<Vec<Object>>();
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Count" => kids.len() as u32,
+                "Kids" => kids,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        document.trailer.set("Root", catalog_id);
+
+        let mut data = Vec::new();
+        document.save_to(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_pdf_pages_selects_range() {
+        let fixture = pdf(&["Introduction", "Design", "Appendix"]);
+
+        let actual = pdf_pages(&fixture, 2, 2).unwrap();
+
+        assert_eq!(actual.text, "--- Page 2 ---\nDesign");
+        assert_eq!((actual.start, actual.end, actual.total), (2, 2, 3));
+        let selected = pdf_extract::Document::load_mem(&actual.data).unwrap();
+        assert_eq!(selected.get_pages().len(), 1);
+    }
+
+    #[test]
+    fn test_paragraphs_docx() {
+        let fixture = archive(
+            "word/document.xml",
+            r#"<w:document xmlns:w="w"><w:body>
+                <w:p><w:r><w:t>Tom &amp; Jerry</w:t></w:r></w:p>
+                <w:p></w:p>
+                <w:p><w:r><w:t>Line one</w:t><w:br/><w:t>Line two</w:t></w:r></w:p>
+            </w:body></w:document>"#,
+        );
+
+        let actual = paragraphs(&fixture, DocumentFormat::Docx).unwrap();
+
+        let expected = vec!["Tom & Jerry".to_string(), "Line one\nLine two".to_string()];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_paragraphs_odt() {
+        let fixture = archive(
+            "content.xml",
+            r#"<office:document-content xmlns:office="o" xmlns:text="t"><office:body><office:text>
+                <text:h>Overview</text:h>
+                <text:p>First<text:tab/>paragraph</text:p>
+            </office:text></office:body></office:document-content>"#,
+        );
+
+        let actual = paragraphs(&fixture, DocumentFormat::Odt).unwrap();
+
+        let expected = vec!["Overview".to_string(), "First\tparagraph".to_string()];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        let fixture = csv_rows(b"name,notes\nalice,\"a|b\"\nbob\n").unwrap();
+
+        let actual = markdown_table(&fixture);
+
+        let expected = "| name | notes |\n| --- | --- |\n| alice | a\\|b |\n| bob |  |";
+        assert_eq!(actual, expected);
+    }
+}
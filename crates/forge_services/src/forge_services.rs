@@ -3,7 +3,7 @@ use std::sync::Arc;
 use forge_app::{
     AgentRepository, CommandInfra, DirectoryReaderInfra, EnvironmentInfra, FileDirectoryInfra,
     FileInfoInfra, FileReaderInfra, FileRemoverInfra, FileWriterInfra, HttpInfra, KVStore,
-    McpServerInfra, Services, StrategyFactory, UserInfra, WalkerInfra,
+    LspServerInfra, McpServerInfra, Services, StrategyFactory, UserInfra, WalkerInfra,
 };
 use forge_domain::{
     AppConfigRepository, ContextEngineRepository, ConversationRepository, ProviderRepository,
@@ -19,19 +19,23 @@ use crate::command::CommandLoaderService as ForgeCommandLoaderService;
 use crate::conversation::ForgeConversationService;
 use crate::discovery::ForgeDiscoveryService;
 use crate::env::ForgeEnvironmentService;
+use crate::git_context::ForgeGitContextService;
 use crate::instructions::ForgeCustomInstructionsService;
+use crate::lsp::ForgeLspService;
 use crate::mcp::{ForgeMcpManager, ForgeMcpService};
 use crate::policy::ForgePolicyService;
 use crate::provider::ForgeProviderService;
 use crate::template::ForgeTemplateService;
 use crate::tool_services::{
-    ForgeFetch, ForgeFollowup, ForgeFsCreate, ForgeFsPatch, ForgeFsRead, ForgeFsRemove,
-    ForgeFsSearch, ForgeFsUndo, ForgeImageRead, ForgePlanCreate, ForgeShell, ForgeSkillFetch,
+    ForgeAstSearch, ForgeFetch, ForgeFollowup, ForgeFsCreate, ForgeFsPatch, ForgeFsRead,
+    ForgeFsRemove, ForgeFsSearch, ForgeFsUndo, ForgeImageRead, ForgePlanCreate, ForgePlanService,
+    ForgeShell, ForgeSkillFetch, ForgeSymbols, ForgeWebSearch,
 };
 use crate::workflow::ForgeWorkflowService;
 
 type McpService<F> = ForgeMcpService<ForgeMcpManager<F>, F, <F as McpServerInfra>::Client>;
 type AuthService<F> = ForgeAuthService<F>;
+type ChatRequest<F> = ForgeChatRequest<F, McpService<F>, ForgeFetch<F>, ForgePolicyService<F>>;
 
 /// ForgeApp is the main application container that implements the App trait.
 /// It provides access to all core services required by the application.
@@ -45,6 +49,7 @@ pub struct ForgeServices<
     F: HttpInfra
         + EnvironmentInfra
         + McpServerInfra
+        + LspServerInfra
         + WalkerInfra
         + SnapshotRepository
         + ConversationRepository
@@ -61,20 +66,25 @@ pub struct ForgeServices<
     config_service: Arc<ForgeAppConfigService<F>>,
     conversation_service: Arc<ForgeConversationService<F>>,
     template_service: Arc<ForgeTemplateService<F>>,
-    attachment_service: Arc<ForgeChatRequest<F>>,
+    attachment_service: Arc<ChatRequest<F>>,
     workflow_service: Arc<ForgeWorkflowService<F>>,
     discovery_service: Arc<ForgeDiscoveryService<F>>,
     mcp_manager: Arc<ForgeMcpManager<F>>,
     file_create_service: Arc<ForgeFsCreate<F>>,
     plan_create_service: Arc<ForgePlanCreate<F>>,
+    plan_service: Arc<ForgePlanService<F>>,
     file_read_service: Arc<ForgeFsRead<F>>,
     image_read_service: Arc<ForgeImageRead<F>>,
     file_search_service: Arc<ForgeFsSearch<F>>,
+    ast_search_service: Arc<ForgeAstSearch<F>>,
+    symbol_service: Arc<ForgeSymbols<F>>,
+    lsp_service: Arc<ForgeLspService<F, ForgeWorkflowService<F>>>,
     file_remove_service: Arc<ForgeFsRemove<F>>,
     file_patch_service: Arc<ForgeFsPatch<F>>,
     file_undo_service: Arc<ForgeFsUndo<F>>,
     shell_service: Arc<ForgeShell<F>>,
-    fetch_service: Arc<ForgeFetch>,
+    fetch_service: Arc<ForgeFetch<F>>,
+    web_search_service: Arc<ForgeWebSearch<F>>,
     followup_service: Arc<ForgeFollowup<F>>,
     mcp_service: Arc<McpService<F>>,
     env_service: Arc<ForgeEnvironmentService<F>>,
@@ -82,14 +92,16 @@ pub struct ForgeServices<
     auth_service: Arc<AuthService<F>>,
     agent_registry_service: Arc<ForgeAgentRegistryService<F>>,
     command_loader_service: Arc<ForgeCommandLoaderService<F>>,
-    policy_service: ForgePolicyService<F>,
+    policy_service: Arc<ForgePolicyService<F>>,
     provider_auth_service: ForgeProviderAuthService<F>,
     codebase_service: Arc<crate::context_engine::ForgeContextEngineService<F>>,
     skill_service: Arc<ForgeSkillFetch<F>>,
+    git_context_service: Arc<ForgeGitContextService<F>>,
 }
 
 impl<
     F: McpServerInfra
+        + LspServerInfra
         + EnvironmentInfra
         + FileWriterInfra
         + FileInfoInfra
@@ -108,14 +120,23 @@ impl<
         + ContextEngineRepository
         + AgentRepository
         + SkillRepository
-        + ValidationRepository,
+        + ValidationRepository
+        + StrategyFactory,
 > ForgeServices<F>
 {
     pub fn new(infra: Arc<F>) -> Self {
         let mcp_manager = Arc::new(ForgeMcpManager::new(infra.clone()));
         let mcp_service = Arc::new(ForgeMcpService::new(mcp_manager.clone(), infra.clone()));
         let template_service = Arc::new(ForgeTemplateService::new(infra.clone()));
-        let attachment_service = Arc::new(ForgeChatRequest::new(infra.clone()));
+        let fetch_service = Arc::new(ForgeFetch::new(infra.clone()));
+        let web_search_service = Arc::new(ForgeWebSearch::new(infra.clone()));
+        let policy_service = Arc::new(ForgePolicyService::new(infra.clone()));
+        let attachment_service = Arc::new(ForgeChatRequest::new(
+            infra.clone(),
+            mcp_service.clone(),
+            fetch_service.clone(),
+            policy_service.clone(),
+        ));
         let workflow_service = Arc::new(ForgeWorkflowService::new(infra.clone()));
         let suggestion_service = Arc::new(ForgeDiscoveryService::new(infra.clone()));
         let conversation_service = Arc::new(ForgeConversationService::new(infra.clone()));
@@ -124,26 +145,32 @@ impl<
         let config_service = Arc::new(ForgeAppConfigService::new(infra.clone()));
         let file_create_service = Arc::new(ForgeFsCreate::new(infra.clone()));
         let plan_create_service = Arc::new(ForgePlanCreate::new(infra.clone()));
+        let plan_service = Arc::new(ForgePlanService::new(infra.clone()));
         let file_read_service = Arc::new(ForgeFsRead::new(infra.clone()));
         let image_read_service = Arc::new(ForgeImageRead::new(infra.clone()));
         let file_search_service = Arc::new(ForgeFsSearch::new(infra.clone()));
+        let ast_search_service = Arc::new(ForgeAstSearch::new(infra.clone()));
+        let symbol_service = Arc::new(ForgeSymbols::new(infra.clone()));
+        let lsp_service = Arc::new(ForgeLspService::new(
+            infra.clone(),
+            workflow_service.clone(),
+        ));
         let file_remove_service = Arc::new(ForgeFsRemove::new(infra.clone()));
         let file_patch_service = Arc::new(ForgeFsPatch::new(infra.clone()));
         let file_undo_service = Arc::new(ForgeFsUndo::new(infra.clone()));
         let shell_service = Arc::new(ForgeShell::new(infra.clone()));
-        let fetch_service = Arc::new(ForgeFetch::new());
         let followup_service = Arc::new(ForgeFollowup::new(infra.clone()));
         let env_service = Arc::new(ForgeEnvironmentService::new(infra.clone()));
         let custom_instructions_service =
             Arc::new(ForgeCustomInstructionsService::new(infra.clone()));
         let agent_registry_service = Arc::new(ForgeAgentRegistryService::new(infra.clone()));
         let command_loader_service = Arc::new(ForgeCommandLoaderService::new(infra.clone()));
-        let policy_service = ForgePolicyService::new(infra.clone());
         let provider_auth_service = ForgeProviderAuthService::new(infra.clone());
         let codebase_service = Arc::new(crate::context_engine::ForgeContextEngineService::new(
             infra.clone(),
         ));
         let skill_service = Arc::new(ForgeSkillFetch::new(infra.clone()));
+        let git_context_service = Arc::new(ForgeGitContextService::new(infra.clone()));
 
         Self {
             conversation_service,
@@ -154,14 +181,19 @@ impl<
             mcp_manager,
             file_create_service,
             plan_create_service,
+            plan_service,
             file_read_service,
             image_read_service,
             file_search_service,
+            ast_search_service,
+            symbol_service,
+            lsp_service,
             file_remove_service,
             file_patch_service,
             file_undo_service,
             shell_service,
             fetch_service,
+            web_search_service,
             followup_service,
             mcp_service,
             env_service,
@@ -175,6 +207,7 @@ impl<
             provider_auth_service,
             codebase_service,
             skill_service,
+            git_context_service,
         }
     }
 }
@@ -185,6 +218,7 @@ impl<
         + CommandInfra
         + UserInfra
         + McpServerInfra
+        + LspServerInfra
         + FileRemoverInfra
         + FileInfoInfra
         + FileDirectoryInfra
@@ -217,7 +251,7 @@ impl<
     fn provider_auth_service(&self) -> &Self::ProviderAuthService {
         &self.provider_auth_service
     }
-    type AttachmentService = ForgeChatRequest<F>;
+    type AttachmentService = ChatRequest<F>;
     type EnvironmentService = ForgeEnvironmentService<F>;
     type CustomInstructionsService = ForgeCustomInstructionsService<F>;
     type WorkflowService = ForgeWorkflowService<F>;
@@ -225,14 +259,19 @@ impl<
     type McpConfigManager = ForgeMcpManager<F>;
     type FsCreateService = ForgeFsCreate<F>;
     type PlanCreateService = ForgePlanCreate<F>;
+    type PlanService = ForgePlanService<F>;
     type FsPatchService = ForgeFsPatch<F>;
     type FsReadService = ForgeFsRead<F>;
     type ImageReadService = ForgeImageRead<F>;
     type FsRemoveService = ForgeFsRemove<F>;
     type FsSearchService = ForgeFsSearch<F>;
+    type AstSearchService = ForgeAstSearch<F>;
+    type SymbolService = ForgeSymbols<F>;
+    type LspService = ForgeLspService<F, ForgeWorkflowService<F>>;
     type FollowUpService = ForgeFollowup<F>;
     type FsUndoService = ForgeFsUndo<F>;
-    type NetFetchService = ForgeFetch;
+    type NetFetchService = ForgeFetch<F>;
+    type WebSearchService = ForgeWebSearch<F>;
     type ShellService = ForgeShell<F>;
     type McpService = McpService<F>;
     type AuthService = AuthService<F>;
@@ -241,6 +280,7 @@ impl<
     type PolicyService = ForgePolicyService<F>;
     type CodebaseService = crate::context_engine::ForgeContextEngineService<F>;
     type SkillFetchService = ForgeSkillFetch<F>;
+    type GitContextService = ForgeGitContextService<F>;
 
     fn provider_service(&self) -> &Self::ProviderService {
         &self.chat_service
@@ -289,6 +329,10 @@ impl<
         &self.plan_create_service
     }
 
+    fn plan_service(&self) -> &Self::PlanService {
+        &self.plan_service
+    }
+
     fn fs_patch_service(&self) -> &Self::FsPatchService {
         &self.file_patch_service
     }
@@ -305,6 +349,18 @@ impl<
         &self.file_search_service
     }
 
+    fn ast_search_service(&self) -> &Self::AstSearchService {
+        &self.ast_search_service
+    }
+
+    fn symbol_service(&self) -> &Self::SymbolService {
+        &self.symbol_service
+    }
+
+    fn lsp_service(&self) -> &Self::LspService {
+        &self.lsp_service
+    }
+
     fn follow_up_service(&self) -> &Self::FollowUpService {
         &self.followup_service
     }
@@ -317,6 +373,10 @@ impl<
         &self.fetch_service
     }
 
+    fn web_search_service(&self) -> &Self::WebSearchService {
+        &self.web_search_service
+    }
+
     fn shell_service(&self) -> &Self::ShellService {
         &self.shell_service
     }
@@ -351,4 +411,8 @@ impl<
     fn skill_fetch_service(&self) -> &Self::SkillFetchService {
         &self.skill_service
     }
+
+    fn git_context_service(&self) -> &Self::GitContextService {
+        &self.git_context_service
+    }
 }
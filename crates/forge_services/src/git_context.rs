@@ -0,0 +1,333 @@
+use std::sync::Arc;
+
+use forge_app::{CommandInfra, EnvironmentInfra, GitContextService};
+use forge_domain::{GitContext, GitContextConfig, GitUpstream};
+
+/// Collects repository state for the system prompt by running read-only git
+/// commands in the working directory.
+pub struct ForgeGitContextService<I> {
+    infra: Arc<I>,
+}
+
+impl<I: CommandInfra + EnvironmentInfra> ForgeGitContextService<I> {
+    pub fn new(infra: Arc<I>) -> Self {
+        Self { infra }
+    }
+
+    /// Runs `git <args>` silently and returns its output, or `None` when the
+    /// command exits with a non-zero status. Arguments are split on
+    /// whitespace and passed to git without a shell.
+    async fn git(&self, args: &str) -> anyhow::Result<Option<String>> {
+        let cwd = self.infra.get_environment().cwd;
+        let args = args.split_whitespace().map(str::to_string).collect();
+        let output = self.infra.execute_program("git", args, cwd).await?;
+
+        Ok((output.exit_code == Some(0)).then(|| output.stdout.trim_end().to_string()))
+    }
+
+    async fn branch(&self) -> anyhow::Result<String> {
+        if let Some(branch) = self.git("symbolic-ref --short -q HEAD").await? {
+            return Ok(branch);
+        }
+
+        Ok(self
+            .git("rev-parse --short HEAD")
+            .await?
+            .map(|sha| format!("detached at {sha}"))
+            .unwrap_or_else(|| "no commits yet".to_string()))
+    }
+
+    async fn upstream(&self) -> anyhow::Result<Option<GitUpstream>> {
+        let Some(name) = self
+            .git("rev-parse --abbrev-ref --symbolic-full-name @{upstream}")
+            .await?
+        else {
+            return Ok(None);
+        };
+        let counts = self
+            .git("rev-list --left-right --count HEAD...@{upstream}")
+            .await?
+            .unwrap_or_default();
+
+        Ok(Some(parse_divergence(name, &counts)))
+    }
+
+    /// Picks the ref the branch is compared against: the remote's default
+    /// branch when known, otherwise the upstream of the current branch.
+    async fn base(&self, upstream: Option<&GitUpstream>) -> anyhow::Result<Option<String>> {
+        Ok(self
+            .git("symbolic-ref --short -q refs/remotes/origin/HEAD")
+            .await?
+            .or_else(|| upstream.map(|upstream| upstream.name.clone())))
+    }
+
+    async fn diff_stat(&self, base: &str) -> anyhow::Result<(Vec<String>, Option<String>)> {
+        let Some(merge_base) = self.git(&format!("merge-base HEAD {base}")).await? else {
+            return Ok(Default::default());
+        };
+
+        Ok(self
+            .git(&format!("diff --no-color --stat=200 {merge_base}"))
+            .await?
+            .map(|output| parse_diff_stat(&output))
+            .unwrap_or_default())
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: CommandInfra + EnvironmentInfra> GitContextService for ForgeGitContextService<I> {
+    async fn git_context(&self, config: &GitContextConfig) -> anyhow::Result<Option<GitContext>> {
+        let inside_work_tree = self.git("rev-parse --is-inside-work-tree").await?;
+        if inside_work_tree.as_deref() != Some("true") {
+            return Ok(None);
+        }
+
+        let branch = self.branch().await?;
+        let upstream = self.upstream().await?;
+        let (staged, unstaged) = self
+            .git("--no-optional-locks status --porcelain")
+            .await?
+            .map(|output| parse_status(&output))
+            .unwrap_or_default();
+        let commits = self
+            .git(&format!(
+                "log --no-color -n{} --format=%h%x20%s",
+                config.max_commits
+            ))
+            .await?
+            .map(|output| output.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        let base = self.base(upstream.as_ref()).await?;
+        let (diff_stat, diff_summary) = match &base {
+            Some(base) => self.diff_stat(base).await?,
+            None => Default::default(),
+        };
+
+        let context = GitContext {
+            branch,
+            upstream,
+            staged,
+            unstaged,
+            commits,
+            base,
+            diff_stat,
+            diff_summary,
+            omitted: 0,
+        };
+
+        Ok(Some(context.truncate(config.max_chars)))
+    }
+}
+
+/// Splits `git status --porcelain` output into staged and unstaged changes.
+/// Untracked files are reported as unstaged.
+fn parse_status(output: &str) -> (Vec<String>, Vec<String>) {
+    let mut staged = Vec::new();
+    let mut unstaged = Vec::new();
+
+    for line in output.lines() {
+        let mut chars = line.chars();
+        let (Some(index), Some(worktree)) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        let path = chars.as_str().trim_start();
+        if path.is_empty() {
+            continue;
+        }
+
+        if index == '?' {
+            unstaged.push(format!("?? {path}"));
+            continue;
+        }
+        if index != ' ' {
+            staged.push(format!("{index} {path}"));
+        }
+        if worktree != ' ' {
+            unstaged.push(format!("{worktree} {path}"));
+        }
+    }
+
+    (staged, unstaged)
+}
+
+/// Splits `git diff --stat` output into per-file lines and the summary line.
+fn parse_diff_stat(output: &str) -> (Vec<String>, Option<String>) {
+    let mut lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    let summary = lines
+        .last()
+        .filter(|line| line.contains(" changed"))
+        .cloned();
+    if summary.is_some() {
+        lines.pop();
+    }
+
+    (lines, summary)
+}
+
+/// Parses `git rev-list --left-right --count HEAD...@{upstream}` output.
+fn parse_divergence(name: String, counts: &str) -> GitUpstream {
+    let mut counts = counts
+        .split_whitespace()
+        .map(|count| count.parse::<usize>().unwrap_or_default());
+
+    GitUpstream {
+        name,
+        ahead: counts.next().unwrap_or_default(),
+        behind: counts.next().unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+
+    use fake::{Fake, Faker};
+    use forge_domain::{CommandOutput, Environment};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        let fixture = "M  src/lib.rs\n M README.md\nMM src/main.rs\n?? notes.md\n";
+
+        let actual = parse_status(fixture);
+
+        let expected = (
+            vec!["M src/lib.rs".to_string(), "M src/main.rs".to_string()],
+            vec![
+                "M README.md".to_string(),
+                "M src/main.rs".to_string(),
+                "?? notes.md".to_string(),
+            ],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_diff_stat() {
+        let fixture = " src/lib.rs  |  2 +-\n src/main.rs | 10 ++++++++++\n 2 files changed, 11 insertions(+), 1 deletion(-)\n";
+
+        let actual = parse_diff_stat(fixture);
+
+        let expected = (
+            vec![
+                "src/lib.rs  |  2 +-".to_string(),
+                "src/main.rs | 10 ++++++++++".to_string(),
+            ],
+            Some("2 files changed, 11 insertions(+), 1 deletion(-)".to_string()),
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_divergence() {
+        let actual = parse_divergence("origin/main".to_string(), "2\t3");
+        let expected = GitUpstream { name: "origin/main".to_string(), ahead: 2, behind: 3 };
+        assert_eq!(actual, expected);
+    }
+
+    struct MockInfra {
+        cwd: PathBuf,
+    }
+
+    impl EnvironmentInfra for MockInfra {
+        fn get_environment(&self) -> Environment {
+            let env: Environment = Faker.fake();
+            env.cwd(self.cwd.clone())
+        }
+
+        fn get_env_var(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        fn get_env_vars(&self) -> BTreeMap<String, String> {
+            BTreeMap::new()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl CommandInfra for MockInfra {
+        async fn execute_command(
+            &self,
+            _command: String,
+            _working_dir: PathBuf,
+            _silent: bool,
+            _env_vars: Option<Vec<String>>,
+        ) -> anyhow::Result<CommandOutput> {
+            unimplemented!()
+        }
+
+        async fn execute_command_raw(
+            &self,
+            _command: &str,
+            _working_dir: PathBuf,
+            _env_vars: Option<Vec<String>>,
+        ) -> anyhow::Result<std::process::ExitStatus> {
+            unimplemented!()
+        }
+
+        async fn execute_program(
+            &self,
+            program: &str,
+            args: Vec<String>,
+            working_dir: PathBuf,
+        ) -> anyhow::Result<CommandOutput> {
+            let output = std::process::Command::new(program)
+                .args(&args)
+                .current_dir(working_dir)
+                .output()?;
+            Ok(CommandOutput {
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                command: args.join(" "),
+                exit_code: output.status.code(),
+            })
+        }
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args([
+                "-c",
+                "user.name=forge",
+                "-c",
+                "user.email=forge@example.com",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[tokio::test]
+    async fn test_git_context_reports_upstream() {
+        let dir = tempfile::tempdir().unwrap();
+        let origin = dir.path().join("origin");
+        std::fs::create_dir(&origin).unwrap();
+        git(&origin, &["init", "-q", "-b", "main"]);
+        git(&origin, &["commit", "-q", "--allow-empty", "-m", "first"]);
+        git(dir.path(), &["clone", "-q", "origin", "work"]);
+        let work = dir.path().join("work");
+        git(&work, &["commit", "-q", "--allow-empty", "-m", "second"]);
+        let service = ForgeGitContextService::new(Arc::new(MockInfra { cwd: work }));
+
+        let actual = service
+            .git_context(&GitContextConfig::default())
+            .await
+            .unwrap()
+            .and_then(|context| context.upstream);
+
+        let expected = Some(GitUpstream { name: "origin/main".to_string(), ahead: 1, behind: 0 });
+        assert_eq!(actual, expected);
+    }
+}
@@ -7,16 +7,20 @@ mod command;
 mod context_engine;
 mod conversation;
 mod discovery;
+mod document;
 mod env;
 mod error;
 mod forge_services;
+mod git_context;
 mod http;
 mod instructions;
+mod lsp;
 mod mcp;
 mod policy;
 mod provider;
 mod provider_auth;
 mod range;
+mod syntax;
 mod template;
 mod tool_services;
 mod utils;
@@ -29,6 +33,7 @@ pub use context_engine::*;
 pub use discovery::*;
 pub use error::*;
 pub use forge_services::*;
+pub use git_context::*;
 pub use instructions::*;
 pub use policy::*;
 pub use provider_auth::*;
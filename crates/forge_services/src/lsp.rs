@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use forge_app::domain::Diagnostic;
+use forge_app::{
+    EnvironmentInfra, FileReaderInfra, LspClientInfra, LspServerInfra, LspService, WorkflowService,
+};
+use tokio::sync::Mutex;
+
+/// Name of the configured language and root of the workspace a server runs
+/// for
+type ServerKey = (String, PathBuf);
+
+/// Collects diagnostics from the language servers configured in the
+/// workflow. A server is started per language and workspace on first use and
+/// restarted if it exits.
+pub struct ForgeLspService<F: LspServerInfra, W> {
+    infra: Arc<F>,
+    workflow_service: Arc<W>,
+    clients: Mutex<HashMap<ServerKey, Arc<F::Client>>>,
+}
+
+impl<F: LspServerInfra, W> ForgeLspService<F, W> {
+    pub fn new(infra: Arc<F>, workflow_service: Arc<W>) -> Self {
+        Self { infra, workflow_service, clients: Default::default() }
+    }
+}
+
+#[async_trait::async_trait]
+impl<F, W> LspService for ForgeLspService<F, W>
+where
+    F: LspServerInfra + EnvironmentInfra + FileReaderInfra,
+    W: WorkflowService + Send + Sync,
+{
+    async fn diagnostics(&self, path: &Path) -> anyhow::Result<Option<Vec<Diagnostic>>> {
+        let workflow = self.workflow_service.read_merged(None).await?;
+        let Some((name, config)) = workflow
+            .lsp
+            .unwrap_or_default()
+            .into_iter()
+            .find(|(_, config)| config.handles(path))
+        else {
+            return Ok(None);
+        };
+
+        let root = self.infra.get_environment().cwd;
+        let client = {
+            let mut clients = self.clients.lock().await;
+            let key = (name.clone(), root.clone());
+            match clients.get(&key) {
+                Some(client) if client.is_running() => client.clone(),
+                _ => {
+                    let language_id = config.language_id.clone().unwrap_or(name);
+                    let client = Arc::new(self.infra.start(&language_id, &config, &root).await?);
+                    clients.insert(key, client.clone());
+                    client
+                }
+            }
+        };
+
+        let content = self.infra.read_utf8(path).await?;
+        let diagnostics = client.diagnostics(path, &content, config.timeout()).await?;
+        Ok(Some(diagnostics))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use fake::{Fake, Faker};
+    use forge_app::domain::{DiagnosticSeverity, Environment, FileInfo, LspServerConfig, Workflow};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    struct MockClient {
+        language_id: String,
+    }
+
+    #[async_trait::async_trait]
+    impl LspClientInfra for MockClient {
+        async fn diagnostics(
+            &self,
+            path: &Path,
+            content: &str,
+            _timeout: Duration,
+        ) -> anyhow::Result<Vec<Diagnostic>> {
+            Ok(vec![Diagnostic {
+                path: path.display().to_string(),
+                line: 1,
+                column: 1,
+                severity: DiagnosticSeverity::Warning,
+                message: content.to_string(),
+                source: Some(self.language_id.clone()),
+                code: None,
+            }])
+        }
+
+        fn is_running(&self) -> bool {
+            true
+        }
+    }
+
+    #[derive(Default)]
+    struct MockInfra {
+        started: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl LspServerInfra for MockInfra {
+        type Client = MockClient;
+
+        async fn start(
+            &self,
+            language_id: &str,
+            _config: &LspServerConfig,
+            _root: &Path,
+        ) -> anyhow::Result<Self::Client> {
+            self.started.fetch_add(1, Ordering::SeqCst);
+            Ok(MockClient { language_id: language_id.to_string() })
+        }
+    }
+
+    impl EnvironmentInfra for MockInfra {
+        fn get_environment(&self) -> Environment {
+            Faker.fake::<Environment>().cwd(PathBuf::from("/project"))
+        }
+
+        fn get_env_var(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        fn get_env_vars(&self) -> BTreeMap<String, String> {
+            BTreeMap::new()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FileReaderInfra for MockInfra {
+        async fn read_utf8(&self, _path: &Path) -> anyhow::Result<String> {
+            Ok("fn main() {}".to_string())
+        }
+
+        async fn read(&self, _path: &Path) -> anyhow::Result<Vec<u8>> {
+            unimplemented!()
+        }
+
+        async fn range_read_utf8(
+            &self,
+            _path: &Path,
+            _start_line: u64,
+            _end_line: u64,
+        ) -> anyhow::Result<(String, FileInfo)> {
+            unimplemented!()
+        }
+    }
+
+    struct MockWorkflow;
+
+    #[async_trait::async_trait]
+    impl WorkflowService for MockWorkflow {
+        async fn resolve(&self, path: Option<PathBuf>) -> PathBuf {
+            path.unwrap_or_default()
+        }
+
+        async fn read_workflow(&self, _path: Option<&Path>) -> anyhow::Result<Workflow> {
+            let mut rust = LspServerConfig::new("rust-analyzer", vec!["rs".to_string()]);
+            rust.language_id = Some("rust".to_string());
+            Ok(Workflow::new().lsp(BTreeMap::from([("rust-analyzer".to_string(), rust)])))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_reuses_server_per_language() {
+        let infra = Arc::new(MockInfra::default());
+        let service = ForgeLspService::new(infra.clone(), Arc::new(MockWorkflow));
+
+        let first = service
+            .diagnostics(Path::new("/project/src/main.rs"))
+            .await
+            .unwrap();
+        let second = service
+            .diagnostics(Path::new("/project/src/lib.rs"))
+            .await
+            .unwrap();
+
+        let actual = (
+            first,
+            second.map(|items| items.len()),
+            infra.started.load(Ordering::SeqCst),
+        );
+        let expected = (
+            Some(vec![Diagnostic {
+                path: "/project/src/main.rs".to_string(),
+                line: 1,
+                column: 1,
+                severity: DiagnosticSeverity::Warning,
+                message: "fn main() {}".to_string(),
+                source: Some("rust".to_string()),
+                code: None,
+            }]),
+            Some(1),
+            1,
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_without_configured_server() {
+        let infra = Arc::new(MockInfra::default());
+        let service = ForgeLspService::new(infra.clone(), Arc::new(MockWorkflow));
+
+        let actual = service
+            .diagnostics(Path::new("/project/app.py"))
+            .await
+            .unwrap();
+
+        assert_eq!(actual, None);
+        assert_eq!(infra.started.load(Ordering::SeqCst), 0);
+    }
+}
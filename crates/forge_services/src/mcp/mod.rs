@@ -1,4 +1,5 @@
 mod manager;
+mod oauth;
 mod service;
 mod tool;
 
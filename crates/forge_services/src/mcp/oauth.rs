@@ -0,0 +1,560 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use bytes::Bytes;
+use forge_app::HttpInfra;
+use forge_app::domain::{McpOAuthConfig, OAuthConfig};
+use reqwest::header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderValue, WWW_AUTHENTICATE};
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+use url::Url;
+
+/// Loopback redirect used when none is configured. Nothing listens on it, so
+/// the user pastes the URL the browser was sent to (RFC 8252, section 7.3).
+const DEFAULT_REDIRECT_URI: &str = "http://127.0.0.1/callback";
+
+/// OAuth 2.0 Protected Resource Metadata (RFC 9728)
+#[derive(Debug, Deserialize)]
+struct ResourceMetadata {
+    #[serde(default)]
+    authorization_servers: Vec<Url>,
+    #[serde(default)]
+    scopes_supported: Vec<String>,
+}
+
+/// OAuth 2.0 Authorization Server Metadata (RFC 8414)
+#[derive(Debug, Deserialize)]
+struct AuthorizationServerMetadata {
+    authorization_endpoint: Url,
+    token_endpoint: Url,
+    #[serde(default)]
+    registration_endpoint: Option<Url>,
+    #[serde(default)]
+    code_challenge_methods_supported: Option<Vec<String>>,
+}
+
+/// Dynamic Client Registration response (RFC 7591)
+#[derive(Debug, Deserialize)]
+struct ClientRegistration {
+    client_id: String,
+}
+
+/// Builds the authorization code flow settings of an MCP server following
+/// the MCP authorization spec: the server's challenge and protected resource
+/// metadata point at its authorization server, whose metadata provides the
+/// endpoints, and a client is registered dynamically unless one is
+/// configured. Configured values take precedence over discovered ones.
+pub(crate) async fn discover_oauth_config<I: HttpInfra>(
+    infra: &I,
+    server: &Url,
+    overrides: &McpOAuthConfig,
+) -> anyhow::Result<OAuthConfig> {
+    let redirect_uri = overrides
+        .redirect_uri
+        .clone()
+        .unwrap_or_else(|| DEFAULT_REDIRECT_URI.to_string());
+    let mut config = OAuthConfig {
+        auth_url: server.clone(),
+        token_url: server.clone(),
+        client_id: String::new().into(),
+        scopes: overrides.scopes.clone(),
+        redirect_uri: Some(redirect_uri.clone()),
+        use_pkce: true,
+        token_refresh_url: None,
+        custom_headers: None,
+        // Binds the tokens to this server (RFC 8707)
+        extra_auth_params: Some(HashMap::from([(
+            "resource".to_string(),
+            server.to_string(),
+        )])),
+    };
+
+    if let (Some(auth_url), Some(token_url), Some(client_id)) = (
+        &overrides.auth_url,
+        &overrides.token_url,
+        &overrides.client_id,
+    ) {
+        config.auth_url = auth_url.clone();
+        config.token_url = token_url.clone();
+        config.client_id = client_id.clone().into();
+        return Ok(config);
+    }
+
+    let challenge = challenge(infra, server).await?;
+    let resource = match challenge.get("resource_metadata") {
+        Some(url) => fetch_json::<_, ResourceMetadata>(infra, &[Url::parse(url)?]).await?,
+        None => fetch_json(infra, &well_known_urls(server, "oauth-protected-resource")).await?,
+    };
+
+    // Servers predating RFC 9728 act as their own authorization server
+    let issuer = match &resource {
+        Some(resource) => resource
+            .authorization_servers
+            .first()
+            .cloned()
+            .context("Protected resource metadata lists no authorization servers")?,
+        None => server.join("/")?,
+    };
+    let metadata = match fetch_json::<_, AuthorizationServerMetadata>(
+        infra,
+        &authorization_server_metadata_urls(&issuer),
+    )
+    .await?
+    {
+        Some(metadata) => metadata,
+        None => AuthorizationServerMetadata {
+            authorization_endpoint: issuer.join("/authorize")?,
+            token_endpoint: issuer.join("/token")?,
+            registration_endpoint: Some(issuer.join("/register")?),
+            code_challenge_methods_supported: None,
+        },
+    };
+
+    if let Some(methods) = &metadata.code_challenge_methods_supported
+        && !methods.iter().any(|method| method == "S256")
+    {
+        anyhow::bail!("Authorization server {issuer} does not support PKCE with S256");
+    }
+
+    if config.scopes.is_empty() {
+        config.scopes = match challenge.get("scope") {
+            Some(scope) => scope.split_whitespace().map(str::to_string).collect(),
+            None => resource.map(|r| r.scopes_supported).unwrap_or_default(),
+        };
+    }
+
+    let client_id = match &overrides.client_id {
+        Some(client_id) => client_id.clone(),
+        None => {
+            let endpoint = metadata.registration_endpoint.as_ref().with_context(|| {
+                format!(
+                    "Authorization server {issuer} does not support dynamic client registration, set `oauth.clientId` for this server"
+                )
+            })?;
+            register_client(infra, endpoint, &redirect_uri, &config.scopes).await?
+        }
+    };
+
+    config.auth_url = overrides
+        .auth_url
+        .clone()
+        .unwrap_or(metadata.authorization_endpoint);
+    config.token_url = overrides
+        .token_url
+        .clone()
+        .unwrap_or(metadata.token_endpoint);
+    config.client_id = client_id.into();
+
+    Ok(config)
+}
+
+/// Sends an unauthenticated `initialize` request and returns the parameters
+/// of the `WWW-Authenticate` challenge the server answers it with
+async fn challenge<I: HttpInfra>(
+    infra: &I,
+    server: &Url,
+) -> anyhow::Result<HashMap<String, String>> {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(
+        ACCEPT,
+        HeaderValue::from_static("application/json, text/event-stream"),
+    );
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-06-18",
+            "capabilities": {},
+            "clientInfo": { "name": "forge", "version": env!("CARGO_PKG_VERSION") }
+        }
+    });
+
+    let response = infra
+        .http_request(
+            Method::POST,
+            server,
+            headers,
+            Some(Bytes::from(body.to_string())),
+        )
+        .await?;
+    if response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(HashMap::new());
+    }
+
+    Ok(response
+        .headers()
+        .get(WWW_AUTHENTICATE)
+        .and_then(|value| value.to_str().ok())
+        .map(challenge_params)
+        .unwrap_or_default())
+}
+
+/// Parses the auth-params of a `Bearer` challenge (RFC 6750, section 3)
+fn challenge_params(header: &str) -> HashMap<String, String> {
+    let Some((scheme, params)) = header.trim_start().split_once(' ') else {
+        return HashMap::new();
+    };
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return HashMap::new();
+    }
+
+    let mut result = HashMap::new();
+    let mut chars = params.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+        let key: String = std::iter::from_fn(|| chars.next_if(|c| *c != '=')).collect();
+        if chars.next().is_none() {
+            break;
+        }
+
+        let mut value = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => value.extend(chars.next()),
+                    c => value.push(c),
+                }
+            }
+        } else {
+            value = std::iter::from_fn(|| chars.next_if(|c| *c != ',')).collect();
+        }
+        result.insert(key.trim().to_ascii_lowercase(), value.trim().to_string());
+    }
+    result
+}
+
+/// Well-known locations of a document for `url` (RFC 8615), the one that
+/// keeps the path first
+fn well_known_urls(url: &Url, name: &str) -> Vec<Url> {
+    let path = url.path().trim_end_matches('/');
+    [
+        (!path.is_empty()).then(|| format!("/.well-known/{name}{path}")),
+        Some(format!("/.well-known/{name}")),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|location| url.join(&location).ok())
+    .collect()
+}
+
+/// Locations of the authorization server metadata of an issuer, covering both
+/// RFC 8414 and OpenID Connect Discovery
+fn authorization_server_metadata_urls(issuer: &Url) -> Vec<Url> {
+    let path = issuer.path().trim_end_matches('/');
+    let locations = if path.is_empty() {
+        vec![
+            "/.well-known/oauth-authorization-server".to_string(),
+            "/.well-known/openid-configuration".to_string(),
+        ]
+    } else {
+        vec![
+            format!("/.well-known/oauth-authorization-server{path}"),
+            format!("/.well-known/openid-configuration{path}"),
+            format!("{path}/.well-known/openid-configuration"),
+        ]
+    };
+    locations
+        .iter()
+        .filter_map(|location| issuer.join(location).ok())
+        .collect()
+}
+
+/// Returns the first document found at one of `urls`
+async fn fetch_json<I: HttpInfra, T: serde::de::DeserializeOwned>(
+    infra: &I,
+    urls: &[Url],
+) -> anyhow::Result<Option<T>> {
+    for url in urls {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        let response = infra.http_request(Method::GET, url, headers, None).await?;
+        if response.status().is_success() {
+            let document = response
+                .json()
+                .await
+                .with_context(|| format!("Invalid metadata at {url}"))?;
+            return Ok(Some(document));
+        }
+    }
+    Ok(None)
+}
+
+/// Registers Forge as a public client (RFC 7591)
+async fn register_client<I: HttpInfra>(
+    infra: &I,
+    endpoint: &Url,
+    redirect_uri: &str,
+    scopes: &[String],
+) -> anyhow::Result<String> {
+    let mut body = json!({
+        "client_name": "Forge",
+        "redirect_uris": [redirect_uri],
+        "grant_types": ["authorization_code", "refresh_token"],
+        "response_types": ["code"],
+        "token_endpoint_auth_method": "none",
+    });
+    if !scopes.is_empty() {
+        body["scope"] = scopes.join(" ").into();
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    let response = infra
+        .http_request(
+            Method::POST,
+            endpoint,
+            headers,
+            Some(Bytes::from(body.to_string())),
+        )
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        anyhow::bail!("Client registration at {endpoint} failed with {status}: {text}");
+    }
+    let registration: ClientRegistration = response
+        .json()
+        .await
+        .context("Invalid client registration response")?;
+    Ok(registration.client_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use reqwest_eventsource::EventSource;
+
+    use super::*;
+
+    struct MockInfra {
+        client: reqwest::Client,
+    }
+
+    impl MockInfra {
+        fn new() -> Self {
+            let client = reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap();
+            Self { client }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HttpInfra for MockInfra {
+        async fn http_get(
+            &self,
+            _: &Url,
+            _: Option<HeaderMap>,
+        ) -> anyhow::Result<reqwest::Response> {
+            unimplemented!()
+        }
+
+        async fn http_post(&self, _: &Url, _: Bytes) -> anyhow::Result<reqwest::Response> {
+            unimplemented!()
+        }
+
+        async fn http_delete(&self, _: &Url) -> anyhow::Result<reqwest::Response> {
+            unimplemented!()
+        }
+
+        async fn http_request(
+            &self,
+            method: Method,
+            url: &Url,
+            headers: HeaderMap,
+            body: Option<Bytes>,
+        ) -> anyhow::Result<reqwest::Response> {
+            let mut request = self.client.request(method, url.clone()).headers(headers);
+            if let Some(body) = body {
+                request = request.body(body);
+            }
+            Ok(request.send().await?)
+        }
+
+        async fn http_eventsource(
+            &self,
+            _: &Url,
+            _: Option<HeaderMap>,
+            _: Bytes,
+        ) -> anyhow::Result<EventSource> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_challenge_params() {
+        let fixture = r#"Bearer error="invalid_token", resource_metadata="https://mcp.example.com/.well-known/oauth-protected-resource", scope="read \"all\"""#;
+
+        let actual = challenge_params(fixture);
+
+        let expected = HashMap::from([
+            ("error".to_string(), "invalid_token".to_string()),
+            (
+                "resource_metadata".to_string(),
+                "https://mcp.example.com/.well-known/oauth-protected-resource".to_string(),
+            ),
+            ("scope".to_string(), r#"read "all""#.to_string()),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_challenge_params_ignores_other_schemes() {
+        let actual = challenge_params(r#"Basic realm="mcp""#);
+        let expected = HashMap::new();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_authorization_server_metadata_urls_with_path() {
+        let fixture = Url::parse("https://auth.example.com/tenant/").unwrap();
+
+        let actual: Vec<String> = authorization_server_metadata_urls(&fixture)
+            .iter()
+            .map(Url::to_string)
+            .collect();
+
+        let expected = vec![
+            "https://auth.example.com/.well-known/oauth-authorization-server/tenant",
+            "https://auth.example.com/.well-known/openid-configuration/tenant",
+            "https://auth.example.com/tenant/.well-known/openid-configuration",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_discover_oauth_config_registers_a_client() {
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+        let mcp_url = Url::parse(&format!("{base}/mcp")).unwrap();
+        let challenge = server
+            .mock("POST", "/mcp")
+            .with_status(401)
+            .with_header(
+                "www-authenticate",
+                &format!(r#"Bearer resource_metadata="{base}/meta""#),
+            )
+            .create_async()
+            .await;
+        let resource = server
+            .mock("GET", "/meta")
+            .with_body(
+                json!({
+                    "resource": mcp_url,
+                    "authorization_servers": [format!("{base}/auth")],
+                    "scopes_supported": ["files:read"]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let metadata = server
+            .mock("GET", "/.well-known/oauth-authorization-server/auth")
+            .with_body(
+                json!({
+                    "issuer": format!("{base}/auth"),
+                    "authorization_endpoint": format!("{base}/auth/authorize"),
+                    "token_endpoint": format!("{base}/auth/token"),
+                    "registration_endpoint": format!("{base}/auth/register"),
+                    "code_challenge_methods_supported": ["S256"]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let registration = server
+            .mock("POST", "/auth/register")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "redirect_uris": [DEFAULT_REDIRECT_URI],
+                "token_endpoint_auth_method": "none",
+                "scope": "files:read"
+            })))
+            .with_status(201)
+            .with_body(json!({ "client_id": "registered" }).to_string())
+            .create_async()
+            .await;
+
+        let actual = discover_oauth_config(&MockInfra::new(), &mcp_url, &McpOAuthConfig::default())
+            .await
+            .unwrap();
+
+        let expected = OAuthConfig {
+            auth_url: Url::parse(&format!("{base}/auth/authorize")).unwrap(),
+            token_url: Url::parse(&format!("{base}/auth/token")).unwrap(),
+            client_id: "registered".to_string().into(),
+            scopes: vec!["files:read".to_string()],
+            redirect_uri: Some(DEFAULT_REDIRECT_URI.to_string()),
+            use_pkce: true,
+            token_refresh_url: None,
+            custom_headers: None,
+            extra_auth_params: Some(HashMap::from([(
+                "resource".to_string(),
+                mcp_url.to_string(),
+            )])),
+        };
+        assert_eq!(actual, expected);
+        for mock in [challenge, resource, metadata, registration] {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_discover_oauth_config_falls_back_to_default_endpoints() {
+        let server = mockito::Server::new_async().await;
+        let base = server.url();
+        let mcp_url = Url::parse(&format!("{base}/mcp")).unwrap();
+        let fixture = McpOAuthConfig { client_id: Some("forge".to_string()), ..Default::default() };
+
+        let actual = discover_oauth_config(&MockInfra::new(), &mcp_url, &fixture)
+            .await
+            .unwrap();
+
+        let expected = (
+            format!("{base}/authorize"),
+            format!("{base}/token"),
+            "forge".to_string(),
+        );
+        assert_eq!(
+            (
+                actual.auth_url.to_string(),
+                actual.token_url.to_string(),
+                actual.client_id.to_string()
+            ),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_discover_oauth_config_rejects_servers_without_s256() {
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+        let mcp_url = Url::parse(&format!("{base}/mcp")).unwrap();
+        server
+            .mock("GET", "/.well-known/oauth-authorization-server")
+            .with_body(
+                json!({
+                    "authorization_endpoint": format!("{base}/authorize"),
+                    "token_endpoint": format!("{base}/token"),
+                    "code_challenge_methods_supported": ["plain"]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let actual = discover_oauth_config(&MockInfra::new(), &mcp_url, &McpOAuthConfig::default())
+            .await
+            .unwrap_err()
+            .to_string();
+
+        let expected = format!("Authorization server {base}/ does not support PKCE with S256");
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,27 +1,58 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 use forge_app::domain::{
-    McpConfig, McpServerConfig, McpServers, ServerName, ToolCallFull, ToolDefinition, ToolName,
-    ToolOutput,
+    AuthContextRequest, AuthContextResponse, AuthDetails, AuthMethod, CodeRequest, McpConfig,
+    McpConnectionState, McpHttpServer, McpPrompt, McpResource, McpResourceContent, McpServerConfig,
+    McpServerStatus, McpServers, ProviderRepository, ServerName, ToolCallFull, ToolDefinition,
+    ToolName, ToolOutput,
 };
 use forge_app::{
-    EnvironmentInfra, KVStore, McpClientInfra, McpConfigManager, McpServerInfra, McpService,
+    AuthStrategy, EnvironmentInfra, HttpInfra, KVStore, McpClientInfra, McpConfigManager,
+    McpServerInfra, McpService, StrategyFactory,
 };
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, RwLock, watch};
+use tracing::warn;
+use url::Url;
 
+use crate::mcp::oauth::discover_oauth_config;
 use crate::mcp::tool::McpExecutor;
 
-#[derive(Clone)]
+/// How long the first listing after a configuration change waits for servers
+/// to connect. Slower servers keep connecting in the background.
+const MCP_STARTUP_WAIT: Duration = Duration::from_secs(3);
+
 pub struct ForgeMcpService<M, I, C> {
     tools: Arc<RwLock<HashMap<ToolName, ToolHolder<McpExecutor<C>>>>>,
+    servers: Arc<RwLock<HashMap<ServerName, ServerHolder<C>>>>,
     failed_servers: Arc<RwLock<HashMap<ServerName, String>>>,
+    /// Servers whose connection is still in progress
+    connecting: Arc<watch::Sender<HashSet<ServerName>>>,
+    /// Incremented whenever the servers are reconnected, so that connections
+    /// started before that are discarded
+    generation: Arc<Mutex<u64>>,
     previous_config_hash: Arc<Mutex<u64>>,
     manager: Arc<M>,
     infra: Arc<I>,
 }
 
+impl<M, I, C> Clone for ForgeMcpService<M, I, C> {
+    fn clone(&self) -> Self {
+        Self {
+            tools: self.tools.clone(),
+            servers: self.servers.clone(),
+            failed_servers: self.failed_servers.clone(),
+            connecting: self.connecting.clone(),
+            generation: self.generation.clone(),
+            previous_config_hash: self.previous_config_hash.clone(),
+            manager: self.manager.clone(),
+            infra: self.infra.clone(),
+        }
+    }
+}
+
 #[derive(Clone)]
 struct ToolHolder<T> {
     definition: ToolDefinition,
@@ -29,17 +60,36 @@ struct ToolHolder<T> {
     server_name: String,
 }
 
+/// A connected server along with the resources and prompts it publishes
+struct ServerHolder<C> {
+    client: Arc<C>,
+    resources: Vec<McpResource>,
+    prompts: Vec<McpPrompt>,
+}
+
+/// A connected server along with the tools it exposes
+type Connection<C> = (ServerHolder<C>, Vec<ToolHolder<McpExecutor<C>>>);
+
 impl<M, I, C> ForgeMcpService<M, I, C>
 where
-    M: McpConfigManager,
-    I: McpServerInfra + KVStore + EnvironmentInfra,
+    M: McpConfigManager + 'static,
+    I: McpServerInfra
+        + KVStore
+        + EnvironmentInfra
+        + HttpInfra
+        + ProviderRepository
+        + StrategyFactory
+        + 'static,
     C: McpClientInfra + Clone,
     C: From<<I as McpServerInfra>::Client>,
 {
     pub fn new(manager: Arc<M>, infra: Arc<I>) -> Self {
         Self {
             tools: Default::default(),
+            servers: Default::default(),
             failed_servers: Default::default(),
+            connecting: Arc::new(watch::Sender::new(HashSet::new())),
+            generation: Default::default(),
             previous_config_hash: Arc::new(Mutex::new(Default::default())),
             manager,
             infra,
@@ -50,47 +100,162 @@ where
         *self.previous_config_hash.lock().await != config.cache_key()
     }
 
-    async fn insert_clients(&self, server_name: &ServerName, client: Arc<C>) -> anyhow::Result<()> {
-        let tools = client.list().await?;
-
-        let mut tool_map = self.tools.write().await;
+    /// Connects to a server and lists the tools, resources and prompts it
+    /// publishes.
+    async fn connect(
+        &self,
+        server_name: &ServerName,
+        config: McpServerConfig,
+    ) -> anyhow::Result<Connection<C>> {
+        let authorized = self.authorize(server_name, config.clone()).await?;
+        let env_vars = self.infra.get_env_vars();
+        let client = self.infra.connect(authorized, &env_vars).await?;
+        let client = Arc::new(C::from(client));
 
-        for mut tool in tools.into_iter() {
+        let mut tools = Vec::new();
+        for mut tool in client
+            .list()
+            .await?
+            .into_iter()
+            .filter(|tool| config.is_tool_enabled(tool.name.as_str()))
+        {
             let actual_name = tool.name.clone();
             let server = McpExecutor::new(actual_name, client.clone())?;
 
             // Generate a unique name for the tool
-            let generated_name = ToolName::new(format!(
+            tool.name = ToolName::new(format!(
                 "mcp_{server_name}_tool_{}",
                 tool.name.into_sanitized()
             ));
 
-            tool.name = generated_name.clone();
+            tools.push(ToolHolder {
+                definition: tool,
+                executable: server,
+                server_name: server_name.to_string(),
+            });
+        }
 
-            tool_map.insert(
-                generated_name,
-                ToolHolder {
-                    definition: tool,
-                    executable: server,
-                    server_name: server_name.to_string(),
-                },
-            );
+        let resources = client.list_resources().await.unwrap_or_else(|error| {
+            warn!(server = %server_name, error = ?error, "Failed to list MCP resources");
+            Vec::new()
+        });
+        let prompts = client.list_prompts().await.unwrap_or_else(|error| {
+            warn!(server = %server_name, error = ?error, "Failed to list MCP prompts");
+            Vec::new()
+        });
+
+        Ok((ServerHolder { client, resources, prompts }, tools))
+    }
+
+    /// Records the outcome of connecting to a server, unless the servers were
+    /// reconnected since `generation` started.
+    async fn register(
+        &self,
+        generation: u64,
+        server_name: ServerName,
+        result: anyhow::Result<Connection<C>>,
+    ) {
+        let current = self.generation.lock().await;
+        if *current != generation {
+            return;
         }
 
-        Ok(())
+        match result {
+            Ok((server, tools)) => {
+                self.tools.write().await.extend(
+                    tools
+                        .into_iter()
+                        .map(|tool| (tool.definition.name.clone(), tool)),
+                );
+                self.servers
+                    .write()
+                    .await
+                    .insert(server_name.clone(), server);
+            }
+            Err(error) => {
+                // Format error with full chain for detailed diagnostics
+                // Using Debug formatting with alternate flag shows the full error chain
+                self.failed_servers
+                    .write()
+                    .await
+                    .insert(server_name.clone(), format!("{error:?}"));
+            }
+        }
+
+        self.connecting.send_modify(|connecting| {
+            connecting.remove(&server_name);
+        });
     }
 
-    async fn connect(
+    /// Waits until `server` has connected or failed to, or every server when
+    /// `server` is `None`.
+    async fn wait_connected(&self, server: Option<&ServerName>) {
+        let mut receiver = self.connecting.subscribe();
+        let _ = receiver
+            .wait_for(|connecting| {
+                server.map_or(connecting.is_empty(), |s| !connecting.contains(s))
+            })
+            .await;
+    }
+
+    /// Adds the bearer token of HTTP servers the user logged in to to the
+    /// request headers, refreshing the stored credential when it is about to
+    /// expire.
+    async fn authorize(
         &self,
         server_name: &ServerName,
         config: McpServerConfig,
-    ) -> anyhow::Result<()> {
-        let env_vars = self.infra.get_env_vars();
-        let client = self.infra.connect(config, &env_vars).await?;
-        let client = Arc::new(C::from(client));
-        self.insert_clients(server_name, client).await?;
+    ) -> anyhow::Result<McpServerConfig> {
+        let McpServerConfig::Http(mut http) = config else {
+            return Ok(config);
+        };
+
+        let id = server_name.credential_id();
+        let Some(mut credential) = self.infra.get_credential(&id).await? else {
+            if http.oauth.is_some() {
+                anyhow::bail!(
+                    "MCP server requires authorization, run `forge mcp login {server_name}`"
+                );
+            }
+            return Ok(McpServerConfig::Http(http));
+        };
+
+        if credential.needs_refresh(chrono::Duration::minutes(5))
+            && let Some(oauth_config) = credential.oauth_config().cloned()
+        {
+            let strategy =
+                self.infra
+                    .create_auth_strategy(id, AuthMethod::OAuthCode(oauth_config), vec![])?;
+            credential = strategy.refresh(&credential).await.with_context(|| {
+                format!("Failed to refresh MCP credential, run `forge mcp login {server_name}`")
+            })?;
+            self.infra.upsert_credential(credential.clone()).await?;
+        }
 
-        Ok(())
+        if let AuthDetails::OAuth { tokens, .. } = &credential.auth_details {
+            http.headers.insert(
+                "Authorization".to_string(),
+                format!("Bearer {}", tokens.access_token.as_str()),
+            );
+        }
+
+        Ok(McpServerConfig::Http(http))
+    }
+
+    async fn http_server(&self, server: &ServerName) -> anyhow::Result<McpHttpServer> {
+        let config = self.manager.read_mcp_config(None).await?;
+        match config.get(server) {
+            Some(McpServerConfig::Http(http)) => Ok(http.clone()),
+            Some(_) => anyhow::bail!("MCP server '{server}' does not use HTTP transport"),
+            None => anyhow::bail!("MCP server not found: {server}"),
+        }
+    }
+
+    /// Forces the next access to reconnect all servers, picking up changed
+    /// credentials.
+    async fn reconnect(&self) -> anyhow::Result<()> {
+        *self.previous_config_hash.lock().await = Default::default();
+        self.refresh_cache().await
     }
 
     async fn init_mcp(&self) -> anyhow::Result<()> {
@@ -108,42 +273,46 @@ where
         // Update the hash with the new config
         let new_hash = mcp.cache_key();
         *self.previous_config_hash.lock().await = new_hash;
+        let generation = {
+            let mut generation = self.generation.lock().await;
+            *generation += 1;
+            *generation
+        };
         self.clear_tools().await;
 
         // Clear failed servers map before attempting new connections
         self.failed_servers.write().await.clear();
 
-        let connections: Vec<_> = mcp
+        let servers = mcp
             .mcp_servers
             .into_iter()
             .filter(|v| !v.1.is_disabled())
-            .map(|(name, server)| async move {
-                let conn = self
-                    .connect(&name, server)
+            .collect::<Vec<_>>();
+        self.connecting
+            .send_replace(servers.iter().map(|(name, _)| name.clone()).collect());
+
+        // Servers connect in the background so that a slow one does not hold up
+        // the others
+        for (name, server) in servers {
+            let service = self.clone();
+            tokio::spawn(async move {
+                let timeout = server.timeout();
+                let result = tokio::time::timeout(timeout, service.connect(&name, server))
                     .await
+                    .unwrap_or_else(|_| {
+                        Err(anyhow::anyhow!(
+                            "Timed out after {}s waiting for server to start",
+                            timeout.as_secs()
+                        ))
+                    })
                     .context(format!("Failed to initiate MCP server: {name}"));
 
-                (name, conn)
-            })
-            .collect();
-
-        let results = futures::future::join_all(connections).await;
-
-        for (server_name, result) in results {
-            match result {
-                Ok(_) => {}
-                Err(error) => {
-                    // Format error with full chain for detailed diagnostics
-                    // Using Debug formatting with alternate flag shows the full error chain
-                    let error_string = format!("{error:?}");
-                    self.failed_servers
-                        .write()
-                        .await
-                        .insert(server_name.clone(), error_string.clone());
-                }
-            }
+                service.register(generation, name, result).await;
+            });
         }
 
+        let _ = tokio::time::timeout(MCP_STARTUP_WAIT, self.wait_connected(None)).await;
+
         Ok(())
     }
 
@@ -162,16 +331,49 @@ where
 
         let failures = self.failed_servers.read().await.clone();
 
-        Ok(McpServers::new(grouped_tools, failures))
+        let servers = self.servers.read().await;
+        let resources = servers
+            .iter()
+            .filter(|(_, server)| !server.resources.is_empty())
+            .map(|(name, server)| (name.clone(), server.resources.clone()))
+            .collect::<HashMap<_, _>>();
+        let prompts = servers
+            .iter()
+            .filter(|(_, server)| !server.prompts.is_empty())
+            .map(|(name, server)| (name.clone(), server.prompts.clone()))
+            .collect::<HashMap<_, _>>();
+
+        Ok(McpServers::new(grouped_tools, failures)
+            .resources(resources)
+            .prompts(prompts))
     }
     async fn clear_tools(&self) {
-        self.tools.write().await.clear()
+        self.tools.write().await.clear();
+        self.servers.write().await.clear();
+    }
+
+    async fn client(&self, server: &ServerName) -> anyhow::Result<Arc<C>> {
+        // Ensure MCP connections are initialized before using a server
+        self.init_mcp().await?;
+        self.wait_connected(Some(server)).await;
+
+        self.servers
+            .read()
+            .await
+            .get(server)
+            .map(|holder| holder.client.clone())
+            .with_context(|| format!("MCP server not found: {server}"))
     }
 
     async fn call(&self, call: ToolCallFull) -> anyhow::Result<ToolOutput> {
         // Ensure MCP connections are initialized before calling tools
         self.init_mcp().await?;
 
+        // The tool may belong to a server that is still connecting
+        if !self.tools.read().await.contains_key(&call.name) {
+            self.wait_connected(None).await;
+        }
+
         let tools = self.tools.read().await;
 
         let tool = tools.get(&call.name).context("Tool not found")?;
@@ -189,9 +391,15 @@ where
 }
 
 #[async_trait::async_trait]
-impl<M: McpConfigManager, I: McpServerInfra + KVStore + EnvironmentInfra, C> McpService
-    for ForgeMcpService<M, I, C>
+impl<M: McpConfigManager + 'static, I, C> McpService for ForgeMcpService<M, I, C>
 where
+    I: McpServerInfra
+        + KVStore
+        + EnvironmentInfra
+        + HttpInfra
+        + ProviderRepository
+        + StrategyFactory
+        + 'static,
     C: McpClientInfra + Clone,
     C: From<<I as McpServerInfra>::Client>,
 {
@@ -209,7 +417,10 @@ where
         }
 
         let servers = self.list().await?;
-        self.infra.cache_set(&config_hash, &servers).await?;
+        // Servers still connecting would be missing from the cached listing
+        if self.connecting.borrow().is_empty() {
+            self.infra.cache_set(&config_hash, &servers).await?;
+        }
         Ok(servers)
     }
 
@@ -220,4 +431,166 @@ where
     async fn reload_mcp(&self) -> anyhow::Result<()> {
         self.refresh_cache().await
     }
+
+    async fn read_mcp_resource(
+        &self,
+        server: &ServerName,
+        uri: &str,
+    ) -> anyhow::Result<Vec<McpResourceContent>> {
+        self.client(server).await?.read_resource(uri).await
+    }
+
+    async fn get_mcp_prompt(
+        &self,
+        server: &ServerName,
+        name: &str,
+        arguments: BTreeMap<String, String>,
+    ) -> anyhow::Result<String> {
+        self.client(server).await?.get_prompt(name, arguments).await
+    }
+
+    async fn get_mcp_status(&self) -> anyhow::Result<Vec<McpServerStatus>> {
+        let config = self.manager.read_mcp_config(None).await?;
+        self.init_mcp().await?;
+        self.wait_connected(None).await;
+
+        let mut statuses = Vec::new();
+        for (name, server) in config.mcp_servers {
+            if server.is_disabled() {
+                statuses.push(McpServerStatus::new(name, McpConnectionState::Disabled));
+                continue;
+            }
+
+            let client = self
+                .servers
+                .read()
+                .await
+                .get(&name)
+                .map(|holder| holder.client.clone());
+            let Some(client) = client else {
+                let error = self
+                    .failed_servers
+                    .read()
+                    .await
+                    .get(&name)
+                    .map(|error| error_summary(error));
+                let mut status = McpServerStatus::new(name, McpConnectionState::Failed);
+                status.last_error = error;
+                statuses.push(status);
+                continue;
+            };
+
+            let tools = self
+                .tools
+                .read()
+                .await
+                .values()
+                .filter(|tool| tool.server_name == name.as_str())
+                .count();
+
+            let timeout = server.timeout();
+            let start = Instant::now();
+            let status = match tokio::time::timeout(timeout, client.list()).await {
+                Ok(Ok(_)) => McpServerStatus::new(name, McpConnectionState::Connected)
+                    .latency(start.elapsed()),
+                Ok(Err(error)) => McpServerStatus::new(name, McpConnectionState::Failed)
+                    .last_error(format!("{error:#}")),
+                Err(_) => {
+                    McpServerStatus::new(name, McpConnectionState::Failed).last_error(format!(
+                        "Timed out after {}s waiting for a response",
+                        timeout.as_secs()
+                    ))
+                }
+            };
+            statuses.push(status.tools(tools));
+        }
+
+        Ok(statuses)
+    }
+
+    async fn init_mcp_auth(&self, server: &ServerName) -> anyhow::Result<CodeRequest> {
+        let http = self.http_server(server).await?;
+        let url = Url::parse(&http.url)
+            .with_context(|| format!("Invalid URL for MCP server '{server}'"))?;
+        let overrides = http.oauth.as_deref().cloned().unwrap_or_default();
+        let oauth = discover_oauth_config(self.infra.as_ref(), &url, &overrides)
+            .await
+            .with_context(|| {
+                format!("Failed to discover how to authorize MCP server '{server}'")
+            })?;
+        let strategy = self.infra.create_auth_strategy(
+            server.credential_id(),
+            AuthMethod::OAuthCode(oauth),
+            vec![],
+        )?;
+
+        match strategy.init().await? {
+            AuthContextRequest::Code(request) => Ok(request),
+            _ => anyhow::bail!("Unexpected authorization flow for MCP server '{server}'"),
+        }
+    }
+
+    async fn complete_mcp_auth(
+        &self,
+        server: &ServerName,
+        response: AuthContextResponse,
+    ) -> anyhow::Result<()> {
+        // Reuses the discovered settings so the registered client stays the same
+        let AuthContextResponse::Code(context) = &response else {
+            anyhow::bail!("Unexpected authorization flow for MCP server '{server}'");
+        };
+        let oauth = context.request.oauth_config.clone();
+        let strategy = self.infra.create_auth_strategy(
+            server.credential_id(),
+            AuthMethod::OAuthCode(oauth),
+            vec![],
+        )?;
+        let credential = strategy.complete(response).await?;
+        self.infra.upsert_credential(credential).await?;
+
+        self.reconnect().await
+    }
+
+    async fn logout_mcp(&self, server: &ServerName) -> anyhow::Result<()> {
+        self.infra
+            .remove_credential(&server.credential_id())
+            .await?;
+
+        self.reconnect().await
+    }
+}
+
+/// Condenses a debug-formatted error chain into a single line, dropping the
+/// backtrace.
+fn error_summary(error: &str) -> String {
+    error
+        .split("Stack backtrace:")
+        .next()
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && *line != "Caused by:")
+        .map(|line| match line.split_once(": ") {
+            Some((index, cause)) if index.chars().all(|c| c.is_ascii_digit()) => cause,
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_error_summary_joins_causes_without_backtrace() {
+        let fixture = "Failed to initiate MCP server: bad\n\nCaused by:\n    0: Transport closed\n    1: Broken pipe\n\nStack backtrace:\n   0: main";
+
+        let actual = error_summary(fixture);
+
+        let expected = "Failed to initiate MCP server: bad: Transport closed: Broken pipe";
+        assert_eq!(actual, expected);
+    }
 }
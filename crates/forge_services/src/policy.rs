@@ -206,6 +206,11 @@ where
             }
         }
     }
+
+    async fn permission(&self, operation: &PermissionOperation) -> anyhow::Result<Permission> {
+        let (policies, _) = self.get_or_create_policies().await?;
+        Ok(PolicyEngine::new(&policies).can_perform(operation))
+    }
 }
 
 /// Create a policy for an operation based on its type
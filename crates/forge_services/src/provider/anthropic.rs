@@ -6,8 +6,8 @@ use forge_app::domain::{
     ChatCompletionMessage, Context, Model, ModelId, ResultStream, Transformer,
 };
 use forge_app::dto::anthropic::{
-    AuthSystemMessage, DropInvalidToolUse, EventData, ListModelResponse, ReasoningTransform,
-    Request, SetCache,
+    AuthSystemMessage, DropInvalidToolUse, EventData, ListModelResponse, NativeWebSearch,
+    ReasoningTransform, Request, SetCache,
 };
 use reqwest::Url;
 use tracing::debug;
@@ -24,6 +24,7 @@ pub struct Anthropic<T> {
     models: forge_domain::ModelSource<Url>,
     anthropic_version: String,
     use_oauth: bool,
+    native_web_search: bool,
 }
 
 impl<H: HttpClientService> Anthropic<H> {
@@ -42,9 +43,17 @@ impl<H: HttpClientService> Anthropic<H> {
             models,
             anthropic_version: version,
             use_oauth,
+            native_web_search: false,
         }
     }
 
+    /// Runs web searches with Anthropic's server-side tool instead of the
+    /// `web_search` client tool
+    pub fn native_web_search(mut self, enabled: bool) -> Self {
+        self.native_web_search = enabled;
+        self
+    }
+
     fn get_headers(&self) -> Vec<(String, String)> {
         let mut headers = vec![(
             "anthropic-version".to_string(),
@@ -87,6 +96,7 @@ impl<T: HttpClientService> Anthropic<T> {
         let request = AuthSystemMessage::default()
             .when(|_| self.use_oauth)
             .pipe(DropInvalidToolUse)
+            .pipe(NativeWebSearch.when(|_| self.native_web_search))
             .pipe(SetCache)
             .transform(request);
         let url = &self.chat_url;
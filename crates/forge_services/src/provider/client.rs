@@ -7,8 +7,8 @@ use anyhow::{Context as _, Result};
 use derive_setters::Setters;
 use forge_app::HttpClientService;
 use forge_app::domain::{
-    ChatCompletionMessage, Context, HttpConfig, Model, ModelId, ProviderResponse, ResultStream,
-    RetryConfig,
+    ChatCompletionMessage, Context, HttpConfig, Model, ModelId, ProviderResponse, ReplayMismatch,
+    ResultStream, RetryConfig,
 };
 use forge_domain::Provider;
 use reqwest::Url;
@@ -18,6 +18,7 @@ use tokio_stream::StreamExt;
 
 use crate::provider::anthropic::Anthropic;
 use crate::provider::openai::OpenAIProvider;
+use crate::provider::replay::Replay;
 use crate::provider::retry::into_retry;
 
 #[derive(Setters)]
@@ -29,6 +30,10 @@ pub struct ClientBuilder {
     pub provider: Provider<Url>,
     #[allow(dead_code)]
     pub version: String,
+    /// Whether providers with a built-in web search tool should use it
+    pub native_web_search: bool,
+    /// How replay providers handle requests that were not recorded
+    pub replay_mismatch: ReplayMismatch,
 }
 
 impl ClientBuilder {
@@ -41,6 +46,8 @@ impl ClientBuilder {
             use_hickory: false,
             provider,
             version: version.into(),
+            native_web_search: false,
+            replay_mismatch: ReplayMismatch::default(),
         }
     }
 
@@ -70,31 +77,45 @@ impl ClientBuilder {
                     .context("Anthropic provider requires credentials")?
                     .auth_details;
                 match creds {
-                    forge_domain::AuthDetails::ApiKey(api_key) => {
-                        InnerClient::Anthropic(Box::new(Anthropic::new(
+                    forge_domain::AuthDetails::ApiKey(api_key) => InnerClient::Anthropic(Box::new(
+                        Anthropic::new(
                             http.clone(),
                             api_key.as_str().to_string(),
                             url,
                             models.clone(),
                             "2023-06-01".to_string(),
                             false,
-                        )))
-                    }
+                        )
+                        .native_web_search(self.native_web_search),
+                    )),
                     forge_domain::AuthDetails::OAuth { tokens, .. } => {
-                        InnerClient::Anthropic(Box::new(Anthropic::new(
-                            http.clone(),
-                            tokens.access_token.as_str().to_string(),
-                            url,
-                            models,
-                            "2023-06-01".to_string(),
-                            true,
-                        )))
+                        InnerClient::Anthropic(Box::new(
+                            Anthropic::new(
+                                http.clone(),
+                                tokens.access_token.as_str().to_string(),
+                                url,
+                                models,
+                                "2023-06-01".to_string(),
+                                true,
+                            )
+                            .native_web_search(self.native_web_search),
+                        ))
                     }
                     _ => {
                         anyhow::bail!("Unsupported authentication method for Anthropic provider",);
                     }
                 }
             }
+
+            ProviderResponse::Replay => {
+                let path = provider.url.to_file_path().map_err(|_| {
+                    anyhow::anyhow!(
+                        "Replay provider URL must be the file:// URL of a cassette: {}",
+                        provider.url
+                    )
+                })?;
+                InnerClient::Replay(Box::new(Replay::new(path, self.replay_mismatch)))
+            }
         };
 
         Ok(Client {
@@ -124,6 +145,7 @@ impl<T> Clone for Client<T> {
 enum InnerClient<T> {
     OpenAICompat(Box<OpenAIProvider<T>>),
     Anthropic(Box<Anthropic<T>>),
+    Replay(Box<Replay>),
 }
 
 impl<T: HttpClientService> Client<T> {
@@ -136,6 +158,7 @@ impl<T: HttpClientService> Client<T> {
         let models = self.clone().retry(match self.inner.as_ref() {
             InnerClient::OpenAICompat(provider) => provider.models().await,
             InnerClient::Anthropic(provider) => provider.models().await,
+            InnerClient::Replay(provider) => provider.models().await,
         })?;
 
         // Update the cache with all fetched models
@@ -160,6 +183,7 @@ impl<T: HttpClientService> Client<T> {
         let chat_stream = self.clone().retry(match self.inner.as_ref() {
             InnerClient::OpenAICompat(provider) => provider.chat(model, context).await,
             InnerClient::Anthropic(provider) => provider.chat(model, context).await,
+            InnerClient::Replay(provider) => provider.chat(model, context).await,
         })?;
 
         let this: Client<T> = self.clone();
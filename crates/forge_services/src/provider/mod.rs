@@ -4,6 +4,7 @@ mod event;
 #[cfg(test)]
 mod mock_server;
 mod openai;
+mod replay;
 mod retry;
 mod service;
 mod utils;
@@ -0,0 +1,230 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use forge_app::domain::{
+    Cassette, ChatCompletionMessage, Context, Interaction, Model, ModelId, ReplayMismatch,
+    ResultStream,
+};
+use forge_fs::ForgeFS;
+use tokio::sync::Mutex;
+use tokio_stream::StreamExt;
+
+/// Serves chat responses from a cassette instead of calling a provider.
+/// Every recorded interaction is served at most once.
+pub struct Replay {
+    path: PathBuf,
+    mismatch: ReplayMismatch,
+    state: Mutex<Option<ReplayState>>,
+}
+
+struct ReplayState {
+    cassette: Cassette,
+    used: Vec<bool>,
+}
+
+impl Replay {
+    pub fn new(path: impl Into<PathBuf>, mismatch: ReplayMismatch) -> Self {
+        Self { path: path.into(), mismatch, state: Mutex::new(None) }
+    }
+
+    async fn with_state<A>(&self, f: impl FnOnce(&mut ReplayState) -> A) -> anyhow::Result<A> {
+        let mut state = self.state.lock().await;
+        if state.is_none() {
+            let cassette = read_cassette(&self.path)
+                .await?
+                .with_context(|| format!("Cassette not found: {}", self.path.display()))?;
+            let used = vec![false; cassette.interactions.len()];
+            *state = Some(ReplayState { cassette, used });
+        }
+        Ok(f(state.as_mut().expect("cassette is loaded")))
+    }
+
+    pub async fn chat(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let mismatch = self.mismatch;
+        let response = self
+            .with_state(|state| {
+                let index = state
+                    .cassette
+                    .find(model, &context, &state.used, mismatch)?;
+                state.used[index] = true;
+                Some(state.cassette.interactions[index].response.clone())
+            })
+            .await?
+            .with_context(|| {
+                format!(
+                    "No recorded response for request {} to model {model} in cassette {}",
+                    Interaction::request_hash(model, &context),
+                    self.path.display()
+                )
+            })?;
+
+        Ok(Box::pin(tokio_stream::iter(response.into_iter().map(Ok))))
+    }
+
+    pub async fn models(&self) -> anyhow::Result<Vec<Model>> {
+        self.with_state(|state| state.cassette.all_models()).await
+    }
+}
+
+/// Appends the interactions of a session with any provider to a cassette
+/// that can later be served by [`Replay`].
+pub struct Recorder {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl Recorder {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), lock: Mutex::new(()) }
+    }
+
+    /// Passes `stream` through, recording it once it completes. Streams that
+    /// fail are not recorded.
+    pub fn record(
+        self: Arc<Self>,
+        model: &ModelId,
+        context: Context,
+        models: Vec<Model>,
+        stream: ResultStream<ChatCompletionMessage, anyhow::Error>,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let model = model.clone();
+        let response = Arc::new(std::sync::Mutex::new(Some(Vec::new())));
+        let collected = response.clone();
+        let stream = stream?.map(move |item| {
+            let mut collected = collected.lock().unwrap();
+            match &item {
+                Ok(message) => {
+                    if let Some(messages) = collected.as_mut() {
+                        messages.push(message.clone());
+                    }
+                }
+                Err(_) => *collected = None,
+            }
+            item
+        });
+
+        let finish = futures::stream::once(async move {
+            let response = response.lock().unwrap().take();
+            if let Some(response) = response {
+                let interaction = Interaction::new(&model, &context, response);
+                if let Err(error) = self.append(interaction, models).await {
+                    tracing::warn!(error = ?error, "Failed to record provider interaction");
+                }
+            }
+        })
+        .filter_map(|_| None);
+
+        Ok(Box::pin(stream.chain(finish)))
+    }
+
+    async fn append(&self, interaction: Interaction, models: Vec<Model>) -> anyhow::Result<()> {
+        let _guard = self.lock.lock().await;
+        let mut cassette = read_cassette(&self.path).await?.unwrap_or_default();
+        for model in models {
+            if !cassette.models.iter().any(|item| item.id == model.id) {
+                cassette.models.push(model);
+            }
+        }
+        cassette.interactions.push(interaction);
+
+        if let Some(parent) = self.path.parent() {
+            ForgeFS::create_dir_all(parent).await?;
+        }
+        ForgeFS::write(&self.path, serde_json::to_string_pretty(&cassette)?).await
+    }
+}
+
+async fn read_cassette(path: &Path) -> anyhow::Result<Option<Cassette>> {
+    if !ForgeFS::exists(path) {
+        return Ok(None);
+    }
+    let content = ForgeFS::read_utf8(path).await?;
+    let cassette = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid cassette: {}", path.display()))?;
+    Ok(Some(cassette))
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_app::domain::{ContextMessage, FinishReason};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    async fn collect(
+        stream: ResultStream<ChatCompletionMessage, anyhow::Error>,
+    ) -> Vec<ChatCompletionMessage> {
+        stream
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    fn fixture_response() -> Vec<ChatCompletionMessage> {
+        vec![
+            ChatCompletionMessage::default().content_part("Hello"),
+            ChatCompletionMessage::default()
+                .content_part(" there")
+                .finish_reason(FinishReason::Stop),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_recorded_session_replays() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassettes/session.json");
+        let model = ModelId::new("gpt-4o");
+        let context = Context::default().add_message(ContextMessage::user("Hi", None));
+
+        let recorder = Arc::new(Recorder::new(&path));
+        let upstream = fixture_response().into_iter().map(Ok);
+        let recorded = collect(recorder.record(
+            &model,
+            context.clone(),
+            vec![],
+            Ok(Box::pin(tokio_stream::iter(upstream))),
+        ))
+        .await;
+
+        let replay = Replay::new(&path, ReplayMismatch::Strict);
+        let actual = collect(replay.chat(&model, context.clone()).await).await;
+
+        assert_eq!(recorded, fixture_response());
+        assert_eq!(actual, fixture_response());
+        assert!(replay.chat(&model, context).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_failed_stream_is_not_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        let model = ModelId::new("gpt-4o");
+
+        let recorder = Arc::new(Recorder::new(&path));
+        let upstream = vec![
+            Ok(ChatCompletionMessage::default().content_part("Hel")),
+            Err(anyhow::anyhow!("connection reset")),
+        ];
+        let _ = recorder
+            .record(
+                &model,
+                Context::default(),
+                vec![],
+                Ok(Box::pin(tokio_stream::iter(upstream))),
+            )
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+
+        let actual = read_cassette(&path).await.unwrap();
+        assert_eq!(actual, None);
+    }
+}
@@ -7,12 +7,15 @@ use forge_app::domain::{
     ProviderId, ResultStream, RetryConfig,
 };
 use forge_app::{EnvironmentInfra, HttpInfra, ProviderService};
-use forge_domain::{Provider, ProviderRepository};
+use forge_domain::{
+    Provider, ProviderRepository, ProviderResponse, ReplayMismatch, WebSearchBackend,
+};
 use tokio::sync::Mutex;
 use url::Url;
 
 use crate::http::HttpClient;
 use crate::provider::client::{Client, ClientBuilder};
+use crate::provider::replay::Recorder;
 #[derive(Clone)]
 pub struct ForgeProviderService<I> {
     retry_config: Arc<RetryConfig>,
@@ -20,6 +23,9 @@ pub struct ForgeProviderService<I> {
     cached_models: Arc<Mutex<HashMap<ProviderId, Vec<Model>>>>,
     version: String,
     timeout_config: HttpConfig,
+    native_web_search: bool,
+    replay_mismatch: ReplayMismatch,
+    recorder: Option<Arc<Recorder>>,
     infra: Arc<I>,
 }
 
@@ -34,6 +40,11 @@ impl<I: EnvironmentInfra + HttpInfra> ForgeProviderService<I> {
             cached_models: Arc::new(Mutex::new(HashMap::new())),
             version,
             timeout_config: env.http,
+            native_web_search: env.web_search == Some(WebSearchBackend::Native),
+            replay_mismatch: env.replay_mismatch,
+            recorder: env
+                .record_cassette
+                .map(|path| Arc::new(Recorder::new(path))),
             infra,
         }
     }
@@ -57,6 +68,8 @@ impl<I: EnvironmentInfra + HttpInfra> ForgeProviderService<I> {
             .retry_config(self.retry_config.clone())
             .timeout_config(self.timeout_config.clone())
             .use_hickory(false) // use native DNS resolver(GAI)
+            .native_web_search(self.native_web_search)
+            .replay_mismatch(self.replay_mismatch)
             .build(Arc::new(HttpClient::new(infra)))?;
 
         // Cache the new client for this provider
@@ -79,12 +92,34 @@ impl<I: EnvironmentInfra + HttpInfra + ProviderRepository> ProviderService
         request: ChatContext,
         provider: Provider<Url>,
     ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let recorder = self
+            .recorder
+            .clone()
+            .filter(|_| provider.response != Some(ProviderResponse::Replay));
+        let provider_id = provider.id.clone();
         let client = self.client(provider).await?;
 
-        client
-            .chat(model, request)
+        let Some(recorder) = recorder else {
+            return client
+                .chat(model, request)
+                .await
+                .with_context(|| format!("Failed to chat with model: {model}"));
+        };
+
+        // Model metadata shapes how requests are built, so it is recorded
+        // alongside the interactions for the replay provider to serve.
+        let models = self
+            .cached_models
+            .lock()
+            .await
+            .get(&provider_id)
+            .cloned()
+            .unwrap_or_default();
+        let stream = client
+            .chat(model, request.clone())
             .await
-            .with_context(|| format!("Failed to chat with model: {model}"))
+            .with_context(|| format!("Failed to chat with model: {model}"));
+        recorder.record(model, request, models, stream)
     }
 
     async fn models(&self, provider: Provider<Url>) -> Result<Vec<Model>> {
@@ -0,0 +1,309 @@
+use std::path::Path;
+
+use anyhow::Context;
+use forge_app::domain::SymbolKind;
+use tree_sitter::{Language, Node, Parser, Tree};
+
+/// Languages with a bundled tree-sitter grammar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyntaxLanguage {
+    Rust,
+    Python,
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Go,
+}
+
+impl SyntaxLanguage {
+    /// Detects the language of a file from its extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "js" | "mjs" | "cjs" | "jsx" => Some(Self::JavaScript),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+
+    pub fn grammar(&self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+
+    pub fn parse(&self, source: &str) -> anyhow::Result<Tree> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&self.grammar())
+            .with_context(|| format!("Failed to load the {self:?} grammar"))?;
+        parser
+            .parse(source, None)
+            .with_context(|| format!("Failed to parse {self:?} source"))
+    }
+
+    /// Returns the kind of symbol `node` defines, if it is a definition
+    pub fn definition_kind(&self, node: Node) -> Option<SymbolKind> {
+        match self {
+            Self::Rust => match node.kind() {
+                "function_item" | "function_signature_item" => {
+                    let owner = node.parent().and_then(|list| list.parent());
+                    Some(
+                        if owner
+                            .is_some_and(|owner| matches!(owner.kind(), "impl_item" | "trait_item"))
+                        {
+                            SymbolKind::Method
+                        } else {
+                            SymbolKind::Function
+                        },
+                    )
+                }
+                "struct_item" => Some(SymbolKind::Struct),
+                "enum_item" => Some(SymbolKind::Enum),
+                "union_item" => Some(SymbolKind::Union),
+                "trait_item" => Some(SymbolKind::Trait),
+                "impl_item" => Some(SymbolKind::Impl),
+                "mod_item" => Some(SymbolKind::Module),
+                "type_item" => Some(SymbolKind::Type),
+                "const_item" => Some(SymbolKind::Constant),
+                "static_item" => Some(SymbolKind::Static),
+                "macro_definition" => Some(SymbolKind::Macro),
+                _ => None,
+            },
+            Self::Python => match node.kind() {
+                "function_definition" => {
+                    let mut parent = node.parent();
+                    if parent.is_some_and(|parent| parent.kind() == "decorated_definition") {
+                        parent = parent.and_then(|parent| parent.parent());
+                    }
+                    let owner = parent
+                        .filter(|parent| parent.kind() == "block")
+                        .and_then(|block| block.parent());
+                    Some(
+                        if owner.is_some_and(|owner| owner.kind() == "class_definition") {
+                            SymbolKind::Method
+                        } else {
+                            SymbolKind::Function
+                        },
+                    )
+                }
+                "class_definition" => Some(SymbolKind::Class),
+                _ => None,
+            },
+            Self::TypeScript | Self::Tsx | Self::JavaScript => match node.kind() {
+                "function_declaration" | "generator_function_declaration" => {
+                    Some(SymbolKind::Function)
+                }
+                "variable_declarator"
+                    if node.child_by_field_name("value").is_some_and(|value| {
+                        matches!(value.kind(), "arrow_function" | "function_expression")
+                    }) =>
+                {
+                    Some(SymbolKind::Function)
+                }
+                "method_definition" => Some(SymbolKind::Method),
+                "class_declaration" | "abstract_class_declaration" => Some(SymbolKind::Class),
+                "interface_declaration" => Some(SymbolKind::Interface),
+                "type_alias_declaration" => Some(SymbolKind::Type),
+                "enum_declaration" => Some(SymbolKind::Enum),
+                "internal_module" => Some(SymbolKind::Module),
+                _ => None,
+            },
+            Self::Go => match node.kind() {
+                "function_declaration" => Some(SymbolKind::Function),
+                "method_declaration" => Some(SymbolKind::Method),
+                "type_spec" => Some(match node.child_by_field_name("type").map(|ty| ty.kind()) {
+                    Some("struct_type") => SymbolKind::Struct,
+                    Some("interface_type") => SymbolKind::Interface,
+                    _ => SymbolKind::Type,
+                }),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the display name of `node` if it defines a named symbol.
+    /// Impl blocks are named after their header, e.g. `impl Display for
+    /// Error`.
+    pub fn definition_name(&self, node: Node, source: &str) -> Option<String> {
+        match self.definition_kind(node)? {
+            SymbolKind::Impl => {
+                let ty = node_text(node.child_by_field_name("type")?, source);
+                Some(match node.child_by_field_name("trait") {
+                    Some(tr) => format!("impl {} for {ty}", node_text(tr, source)),
+                    None => format!("impl {ty}"),
+                })
+            }
+            _ => node
+                .child_by_field_name("name")
+                .map(|name| node_text(name, source).to_string()),
+        }
+    }
+
+    /// Returns the traits, interfaces or base classes a definition implements
+    /// or extends, without generic arguments or module paths.
+    pub fn implements(&self, node: Node, source: &str) -> Vec<String> {
+        let heritage = match (self, node.kind()) {
+            (Self::Rust, "impl_item") => node.child_by_field_name("trait").into_iter().collect(),
+            (Self::Python, "class_definition") => node
+                .child_by_field_name("superclasses")
+                .map(|list| named_children(list))
+                .unwrap_or_default(),
+            (
+                Self::TypeScript | Self::Tsx | Self::JavaScript,
+                "class_declaration" | "abstract_class_declaration",
+            ) => named_children(node)
+                .into_iter()
+                .filter(|child| child.kind() == "class_heritage")
+                .flat_map(|heritage| {
+                    // JavaScript puts the base class directly in the
+                    // heritage, TypeScript wraps it in clauses
+                    let clauses = named_children(heritage);
+                    if clauses
+                        .iter()
+                        .any(|clause| clause.kind().ends_with("_clause"))
+                    {
+                        clauses.into_iter().flat_map(named_children).collect()
+                    } else {
+                        clauses
+                    }
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        heritage
+            .into_iter()
+            .filter_map(|node| base_name(node, source))
+            .collect()
+    }
+
+    /// Returns the names of the definitions containing `node`, outermost
+    /// first, joined with ` > `.
+    pub fn enclosing_symbol(&self, node: Node, source: &str) -> Option<String> {
+        let mut names = Vec::new();
+        let mut current = node.parent();
+        while let Some(parent) = current {
+            names.extend(self.definition_name(parent, source));
+            current = parent.parent();
+        }
+        names.reverse();
+        (!names.is_empty()).then(|| names.join(" > "))
+    }
+}
+
+/// Returns the source text spanned by `node`
+pub fn node_text<'a>(node: Node, source: &'a str) -> &'a str {
+    &source[node.byte_range()]
+}
+
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+/// Returns the bare name of a type or path expression, e.g. `Display` for
+/// `fmt::Display` and `Repository` for `Repository<T>`
+pub fn base_name(node: Node, source: &str) -> Option<String> {
+    match node.kind() {
+        "identifier" | "type_identifier" => Some(node_text(node, source).to_string()),
+        "generic_type" => node
+            .child_by_field_name("type")
+            .or_else(|| node.child_by_field_name("name"))
+            .or_else(|| node.named_child(0))
+            .and_then(|inner| base_name(inner, source)),
+        "scoped_type_identifier" | "scoped_identifier" | "nested_type_identifier" => node
+            .child_by_field_name("name")
+            .and_then(|inner| base_name(inner, source)),
+        "member_expression" => node
+            .child_by_field_name("property")
+            .map(|property| node_text(property, source).to_string()),
+        "attribute" => node
+            .child_by_field_name("attribute")
+            .map(|attribute| node_text(attribute, source).to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn innermost_named<'a>(tree: &'a Tree, source: &str, needle: &str) -> Node<'a> {
+        let start = source.find(needle).unwrap();
+        tree.root_node()
+            .named_descendant_for_byte_range(start, start + needle.len())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_enclosing_symbol_rust() {
+        let fixture = "mod client {\n    impl Service for Client {\n        fn call(&self) {\n            self.inner.unwrap();\n        }\n    }\n}\n";
+        let tree = SyntaxLanguage::Rust.parse(fixture).unwrap();
+        let node = innermost_named(&tree, fixture, "self.inner.unwrap()");
+
+        let actual = SyntaxLanguage::Rust.enclosing_symbol(node, fixture);
+
+        let expected = Some("client > impl Service for Client > call".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_enclosing_symbol_typescript_arrow_function() {
+        let fixture =
+            "class Api {\n  load() {\n    const parse = (raw) => JSON.parse(raw);\n  }\n}\n";
+        let tree = SyntaxLanguage::TypeScript.parse(fixture).unwrap();
+        let node = innermost_named(&tree, fixture, "JSON.parse(raw)");
+
+        let actual = SyntaxLanguage::TypeScript.enclosing_symbol(node, fixture);
+
+        let expected = Some("Api > load > parse".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_implements() {
+        let fixture = "impl<T> fmt::Display for Wrapper<T> {}\n";
+        let tree = SyntaxLanguage::Rust.parse(fixture).unwrap();
+        let node = tree.root_node().named_child(0).unwrap();
+        let actual = SyntaxLanguage::Rust.implements(node, fixture);
+        assert_eq!(actual, vec!["Display".to_string()]);
+
+        let fixture = "class Store extends Base<Item> implements Repository, api.Cache {}\n";
+        let tree = SyntaxLanguage::TypeScript.parse(fixture).unwrap();
+        let node = tree.root_node().named_child(0).unwrap();
+        let actual = SyntaxLanguage::TypeScript.implements(node, fixture);
+        assert_eq!(actual, vec!["Base", "Repository", "Cache"]);
+
+        let fixture = "class Handler(base.Handler, Mixin, metaclass=Meta):\n    pass\n";
+        let tree = SyntaxLanguage::Python.parse(fixture).unwrap();
+        let node = tree.root_node().named_child(0).unwrap();
+        let actual = SyntaxLanguage::Python.implements(node, fixture);
+        assert_eq!(actual, vec!["Handler", "Mixin"]);
+    }
+
+    #[test]
+    fn test_from_path() {
+        let actual = ["a.rs", "b.py", "c.tsx", "d.mjs", "e.go", "f.md"]
+            .map(|path| SyntaxLanguage::from_path(Path::new(path)));
+        let expected = [
+            Some(SyntaxLanguage::Rust),
+            Some(SyntaxLanguage::Python),
+            Some(SyntaxLanguage::Tsx),
+            Some(SyntaxLanguage::JavaScript),
+            Some(SyntaxLanguage::Go),
+            None,
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,388 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use forge_app::domain::AstSearch;
+use forge_app::{AstMatch, AstSearchService, FileInfoInfra, FileReaderInfra, Walker, WalkerInfra};
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Node, Query, QueryCursor, Tree};
+
+use crate::syntax::SyntaxLanguage;
+use crate::utils::assert_absolute_path;
+
+/// Name of the capture reported as the match when a query defines it
+const MATCH_CAPTURE: &str = "match";
+
+/// Searches source files for nodes matching tree-sitter queries
+pub struct ForgeAstSearch<W> {
+    infra: Arc<W>,
+}
+
+/// The search and `inside` queries compiled for a single language
+struct CompiledQuery {
+    query: Query,
+    inside: Option<Query>,
+}
+
+impl CompiledQuery {
+    fn new(language: SyntaxLanguage, input: &AstSearch) -> anyhow::Result<Self> {
+        let compile = |source: &str| {
+            Query::new(&language.grammar(), source)
+                .with_context(|| format!("Invalid {language:?} query: {source}"))
+        };
+        Ok(Self {
+            query: compile(&input.query)?,
+            inside: input.inside.as_deref().map(compile).transpose()?,
+        })
+    }
+
+    /// Finds the matches in a parsed file, in source order
+    fn search(
+        &self,
+        language: SyntaxLanguage,
+        path: &Path,
+        tree: &Tree,
+        source: &str,
+    ) -> Vec<AstMatch> {
+        let scopes = self
+            .inside
+            .as_ref()
+            .map(|inside| reported_nodes(inside, tree, source));
+
+        let lines = source.lines().collect::<Vec<_>>();
+        reported_nodes(&self.query, tree, source)
+            .into_iter()
+            .filter(|node| {
+                scopes.as_ref().is_none_or(|scopes| {
+                    scopes
+                        .iter()
+                        .any(|scope| contains(&scope.byte_range(), &node.byte_range()))
+                })
+            })
+            .map(|node| {
+                let row = node.start_position().row;
+                AstMatch {
+                    path: path.to_string_lossy().to_string(),
+                    line_number: row + 1,
+                    symbol: language.enclosing_symbol(node, source),
+                    line: lines
+                        .get(row)
+                        .map(|line| line.trim())
+                        .unwrap_or_default()
+                        .to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+fn contains(outer: &Range<usize>, inner: &Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Runs `query` over the tree and returns the reported node of each match:
+/// the `@match` capture when present, otherwise the outermost capture.
+/// Nodes reported by several matches are returned once.
+fn reported_nodes<'tree>(query: &Query, tree: &'tree Tree, source: &str) -> Vec<Node<'tree>> {
+    let match_index = query.capture_index_for_name(MATCH_CAPTURE);
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, tree.root_node(), source.as_bytes());
+
+    let mut seen = HashSet::new();
+    let mut nodes = Vec::new();
+    while let Some(found) = matches.next() {
+        let node = match match_index {
+            Some(index) => found
+                .captures
+                .iter()
+                .find(|capture| capture.index == index)
+                .map(|capture| capture.node),
+            None => found
+                .captures
+                .iter()
+                .map(|capture| capture.node)
+                .min_by_key(|node| (node.start_byte(), std::cmp::Reverse(node.end_byte()))),
+        };
+        if let Some(node) = node
+            && seen.insert(node.byte_range())
+        {
+            nodes.push(node);
+        }
+    }
+
+    nodes.sort_by_key(|node| node.start_byte());
+    nodes
+}
+
+impl<W> ForgeAstSearch<W> {
+    pub fn new(infra: Arc<W>) -> Self {
+        Self { infra }
+    }
+}
+
+impl<W: WalkerInfra + FileInfoInfra> ForgeAstSearch<W> {
+    async fn retrieve_file_paths(&self, dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        if self.infra.is_file(dir).await? {
+            return Ok(vec![dir.to_path_buf()]);
+        }
+
+        let mut paths = self
+            .infra
+            .walk(Walker::unlimited().cwd(dir.to_path_buf()))
+            .await
+            .with_context(|| format!("Failed to walk directory '{}'", dir.display()))?
+            .into_iter()
+            .map(|file| dir.join(file.path))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+#[async_trait::async_trait]
+impl<W: WalkerInfra + FileReaderInfra + FileInfoInfra> AstSearchService for ForgeAstSearch<W> {
+    async fn ast_search(&self, input: AstSearch) -> anyhow::Result<Vec<AstMatch>> {
+        let path = Path::new(&input.path);
+        assert_absolute_path(path)?;
+
+        let file_pattern = input
+            .file_pattern
+            .as_deref()
+            .map(|pattern| {
+                glob::Pattern::new(pattern)
+                    .with_context(|| format!("Invalid glob pattern: {pattern}"))
+            })
+            .transpose()?;
+
+        let mut queries = HashMap::new();
+        let mut matches = Vec::new();
+        for path in self.retrieve_file_paths(path).await? {
+            let Some(language) = SyntaxLanguage::from_path(&path) else {
+                continue;
+            };
+            let name = path.file_name().and_then(|name| name.to_str());
+            if let Some(pattern) = &file_pattern
+                && !name.is_some_and(|name| pattern.matches(name))
+            {
+                continue;
+            }
+            if !self.infra.is_file(&path).await? {
+                continue;
+            }
+
+            // Queries are written against one grammar, so languages that
+            // can't compile them are skipped rather than failing the search
+            let Ok(query) = queries
+                .entry(language)
+                .or_insert_with(|| CompiledQuery::new(language, &input))
+            else {
+                continue;
+            };
+
+            let source = String::from_utf8_lossy(&self.infra.read(&path).await?).to_string();
+            let tree = language.parse(&source)?;
+            matches.extend(query.search(language, &path, &tree, &source));
+        }
+
+        // Surface the compile error when the query fits none of the searched
+        // languages, since it is most likely malformed
+        if !queries.is_empty()
+            && queries.values().all(Result::is_err)
+            && let Some(Err(error)) = queries.into_values().next()
+        {
+            return Err(error);
+        }
+
+        Ok(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use forge_app::WalkedFile;
+    use forge_domain::FileInfo;
+    use pretty_assertions::assert_eq;
+    use tokio::fs;
+
+    use super::*;
+    use crate::utils::TempDir;
+
+    struct MockInfra;
+
+    #[async_trait::async_trait]
+    impl FileReaderInfra for MockInfra {
+        async fn read_utf8(&self, _path: &Path) -> anyhow::Result<String> {
+            unimplemented!()
+        }
+
+        async fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+            Ok(fs::read(path).await?)
+        }
+
+        async fn range_read_utf8(
+            &self,
+            _path: &Path,
+            _start_line: u64,
+            _end_line: u64,
+        ) -> anyhow::Result<(String, FileInfo)> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FileInfoInfra for MockInfra {
+        async fn is_file(&self, path: &Path) -> anyhow::Result<bool> {
+            Ok(fs::metadata(path).await.is_ok_and(|meta| meta.is_file()))
+        }
+
+        async fn is_binary(&self, _path: &Path) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn exists(&self, _path: &Path) -> anyhow::Result<bool> {
+            unimplemented!()
+        }
+
+        async fn file_size(&self, _path: &Path) -> anyhow::Result<u64> {
+            unimplemented!()
+        }
+
+        async fn modified(&self, _path: &Path) -> anyhow::Result<SystemTime> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl WalkerInfra for MockInfra {
+        async fn walk(&self, config: Walker) -> anyhow::Result<Vec<WalkedFile>> {
+            let mut files = Vec::new();
+            let mut entries = fs::read_dir(&config.cwd).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                files.push(WalkedFile {
+                    path: file_name.clone(),
+                    file_name: Some(file_name),
+                    size: entry.metadata().await?.len(),
+                });
+            }
+            Ok(files)
+        }
+    }
+
+    async fn fixture_directory() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("lib.rs"),
+            "struct Client;\n\nimpl Client {\n    fn load(&self) {\n        read().unwrap();\n    }\n\n    async fn fetch(&self) {\n        get().await.unwrap();\n    }\n}\n",
+        )
+        .await
+        .unwrap();
+        fs::write(dir.path().join("app.py"), "def main():\n    pass\n")
+            .await
+            .unwrap();
+        fs::write(dir.path().join("notes.txt"), "unwrap()")
+            .await
+            .unwrap();
+        dir
+    }
+
+    fn search(dir: &TempDir, query: &str) -> AstSearch {
+        AstSearch {
+            path: dir.path().to_string_lossy().to_string(),
+            query: query.to_string(),
+            ..Default::default()
+        }
+    }
+
+    const UNWRAP_QUERY: &str = r#"(call_expression function: (field_expression field: (field_identifier) @method) (#eq? @method "unwrap")) @match"#;
+
+    #[tokio::test]
+    async fn test_ast_search_reports_enclosing_symbols() {
+        let dir = fixture_directory().await;
+        let fixture = search(&dir, UNWRAP_QUERY);
+
+        let actual = ForgeAstSearch::new(Arc::new(MockInfra))
+            .ast_search(fixture)
+            .await
+            .unwrap();
+
+        let path = dir.path().join("lib.rs").to_string_lossy().to_string();
+        let expected = vec![
+            AstMatch {
+                path: path.clone(),
+                line_number: 5,
+                symbol: Some("impl Client > load".to_string()),
+                line: "read().unwrap();".to_string(),
+            },
+            AstMatch {
+                path,
+                line_number: 9,
+                symbol: Some("impl Client > fetch".to_string()),
+                line: "get().await.unwrap();".to_string(),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_ast_search_inside() {
+        let dir = fixture_directory().await;
+        let fixture = AstSearch {
+            inside: Some(r#"(function_item (function_modifiers "async")) @match"#.to_string()),
+            ..search(&dir, UNWRAP_QUERY)
+        };
+
+        let actual = ForgeAstSearch::new(Arc::new(MockInfra))
+            .ast_search(fixture)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|matched| matched.line_number)
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, vec![9]);
+    }
+
+    #[tokio::test]
+    async fn test_ast_search_outermost_capture_without_match() {
+        let dir = fixture_directory().await;
+        let fixture = AstSearch {
+            file_pattern: Some("*.py".to_string()),
+            ..search(
+                &dir,
+                "(function_definition name: (identifier) @name) @function",
+            )
+        };
+
+        let actual = ForgeAstSearch::new(Arc::new(MockInfra))
+            .ast_search(fixture)
+            .await
+            .unwrap();
+
+        let expected = vec![AstMatch {
+            path: dir.path().join("app.py").to_string_lossy().to_string(),
+            line_number: 1,
+            symbol: None,
+            line: "def main():".to_string(),
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_ast_search_invalid_query_fails() {
+        let dir = fixture_directory().await;
+        let fixture = search(&dir, "(not_a_node_kind) @match");
+
+        let actual = ForgeAstSearch::new(Arc::new(MockInfra))
+            .ast_search(fixture)
+            .await;
+
+        assert!(actual.is_err());
+    }
+}
@@ -1,36 +1,65 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use anyhow::{Context, anyhow};
-use forge_app::{HttpResponse, NetFetchService, ResponseContext};
-use reqwest::{Client, Url};
-
-/// Retrieves content from URLs as markdown or raw text. Enables access to
-/// current online information including websites, APIs and documentation. Use
-/// for obtaining up-to-date information beyond training data, verifying facts,
-/// or retrieving specific online content. Handles HTTP/HTTPS and converts HTML
-/// to readable markdown by default. Cannot access private/restricted resources
-/// requiring authentication. Respects robots.txt and may be blocked by
-/// anti-scraping measures. For large pages, returns the first 40,000 characters
-/// and stores the complete content in a temporary file for subsequent access.
-#[derive(Debug)]
-pub struct ForgeFetch {
-    client: Client,
+use bytes::Bytes;
+use forge_app::domain::{FetchCredentials, HttpMethod, NetFetch};
+use forge_app::{
+    EnvironmentInfra, FileInfoInfra, FileReaderInfra, HttpInfra, HttpResponse, KVStore,
+    NetFetchService, ResponseContext,
+};
+use reqwest::header::{
+    AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, ETAG, HeaderMap, HeaderName, HeaderValue,
+    IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, LOCATION, PROXY_AUTHORIZATION,
+};
+use reqwest::{Method, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Maximum number of characters of an error response included in the error
+const MAX_ERROR_BODY_CHARS: usize = 1000;
+
+/// Retrieves content from URLs as markdown or raw text, sending requests
+/// through the shared HTTP client. Credentials configured for the target
+/// domain are added to the request here, so they never enter the
+/// conversation. Responses to plain GET requests that carry an ETag or
+/// Last-Modified header are cached and revalidated on subsequent fetches.
+pub struct ForgeFetch<I> {
+    infra: Arc<I>,
 }
 
-impl Default for ForgeFetch {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Identifies a cached GET response by its URL and the headers supplied with
+/// the request. Injected credentials are deliberately not part of the key.
+#[derive(Hash)]
+struct FetchCacheKey {
+    url: String,
+    headers: BTreeMap<String, String>,
 }
 
-impl ForgeFetch {
-    pub fn new() -> Self {
-        Self { client: Client::new() }
-    }
+#[derive(Serialize, Deserialize)]
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    code: u16,
+    content_type: String,
+    body: String,
 }
 
-impl ForgeFetch {
+impl<I: HttpInfra + KVStore + EnvironmentInfra + FileReaderInfra + FileInfoInfra> ForgeFetch<I> {
+    pub fn new(infra: Arc<I>) -> Self {
+        Self { infra }
+    }
+
     async fn check_robots_txt(&self, url: &Url) -> anyhow::Result<()> {
-        let robots_url = format!("{}://{}/robots.txt", url.scheme(), url.authority());
-        let robots_response = self.client.get(&robots_url).send().await;
+        let robots_url = Url::parse(&format!(
+            "{}://{}/robots.txt",
+            url.scheme(),
+            url.authority()
+        ))?;
+        let robots_response = self
+            .infra
+            .http_request(Method::GET, &robots_url, HeaderMap::new(), None)
+            .await;
 
         if let Ok(robots) = robots_response
             && robots.status().is_success()
@@ -61,42 +90,115 @@ impl ForgeFetch {
         Ok(())
     }
 
-    async fn fetch_url(&self, url: &Url, force_raw: bool) -> anyhow::Result<HttpResponse> {
+    /// Loads the credentials file, if there is one
+    async fn load_credentials(&self) -> anyhow::Result<FetchCredentials> {
+        let path = self.infra.get_environment().fetch_credentials_path();
+        if !self.infra.exists(&path).await? {
+            return Ok(FetchCredentials::default());
+        }
+
+        let content = self.infra.read_utf8(&path).await?;
+        serde_yml::from_str(&content)
+            .with_context(|| format!("Failed to parse credentials from {}", path.display()))
+    }
+
+    async fn fetch_url(&self, url: &Url, request: NetFetch) -> anyhow::Result<HttpResponse> {
         self.check_robots_txt(url).await?;
 
-        let response = self
-            .client
-            .get(url.as_str())
-            .send()
-            .await
-            .map_err(|e| anyhow!("Failed to fetch URL {url}: {e}"))?;
-        let code = response.status().as_u16();
+        let method = request.method.unwrap_or_default();
+        let requested_headers = request.headers.unwrap_or_default();
+        let cache_key = (method == HttpMethod::Get && request.body.is_none())
+            .then(|| FetchCacheKey { url: url.to_string(), headers: requested_headers.clone() });
 
-        if !response.status().is_success() {
-            return Err(anyhow!(
-                "Failed to fetch {} - status code {}",
-                url,
-                response.status()
-            ));
+        let credentials = self.load_credentials().await?;
+        let mut headers = to_header_map(requested_headers)?;
+
+        let cached = match &cache_key {
+            Some(key) => self
+                .infra
+                .cache_get::<_, CachedResponse>(key)
+                .await
+                .unwrap_or_else(|error| {
+                    warn!(error = %error, "Failed to read fetch cache");
+                    None
+                }),
+            None => None,
+        };
+        if let Some(cached) = &cached {
+            if let Some(etag) = cached.etag.as_deref().and_then(header_value) {
+                headers.insert(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = cached.last_modified.as_deref().and_then(header_value) {
+                headers.insert(IF_MODIFIED_SINCE, last_modified);
+            }
         }
 
-        let content_type = response
-            .headers()
-            .get("content-type")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("")
-            .to_string();
+        let response = self
+            .send(
+                to_method(method),
+                url,
+                headers,
+                request.body.map(Bytes::from),
+                &credentials,
+            )
+            .await?;
+        let status = response.status();
 
-        let page_raw = response
-            .text()
-            .await
-            .map_err(|e| anyhow!("Failed to read response content from {url}: {e}"))?;
+        let (code, content_type, page_raw) = match cached {
+            Some(cached) if status == StatusCode::NOT_MODIFIED => {
+                (cached.code, cached.content_type, cached.body)
+            }
+            _ => {
+                if !status.is_success() {
+                    let body = response.text().await.unwrap_or_default();
+                    let body = body.chars().take(MAX_ERROR_BODY_CHARS).collect::<String>();
+                    return Err(if body.trim().is_empty() {
+                        anyhow!("Failed to fetch {url} - status code {status}")
+                    } else {
+                        anyhow!("Failed to fetch {url} - status code {status}: {body}")
+                    });
+                }
+
+                let header = |name: HeaderName| {
+                    response
+                        .headers()
+                        .get(name)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string)
+                };
+                let content_type = header(CONTENT_TYPE).unwrap_or_default();
+                let etag = header(ETAG);
+                let last_modified = header(LAST_MODIFIED);
+
+                let page_raw = response
+                    .text()
+                    .await
+                    .map_err(|e| anyhow!("Failed to read response content from {url}: {e}"))?;
+
+                if let Some(key) = &cache_key
+                    && (etag.is_some() || last_modified.is_some())
+                {
+                    let entry = CachedResponse {
+                        etag,
+                        last_modified,
+                        code: status.as_u16(),
+                        content_type: content_type.clone(),
+                        body: page_raw.clone(),
+                    };
+                    if let Err(error) = self.infra.cache_set(key, &entry).await {
+                        warn!(error = %error, "Failed to write fetch cache");
+                    }
+                }
+
+                (status.as_u16(), content_type, page_raw)
+            }
+        };
 
         let is_page_html = page_raw[..100.min(page_raw.len())].contains("<html")
             || content_type.contains("text/html")
             || content_type.is_empty();
 
-        if is_page_html && !force_raw {
+        if is_page_html && !request.raw.unwrap_or(false) {
             let content = html2md::parse_html(&page_raw);
             Ok(HttpResponse { content, context: ResponseContext::Raw, code, content_type })
         } else {
@@ -110,11 +212,334 @@ impl ForgeFetch {
     }
 }
 
+impl<I: HttpInfra + EnvironmentInfra> ForgeFetch<I> {
+    /// Returns the headers configured for the host of `url`, resolving
+    /// environment variable references in their values.
+    fn credential_headers(
+        &self,
+        credentials: &FetchCredentials,
+        url: &Url,
+    ) -> anyhow::Result<HeaderMap> {
+        let headers = credentials
+            .headers_for(url.host_str().unwrap_or_default())
+            .into_iter()
+            .map(|(name, value)| {
+                let value = expand_env_vars(&value, |name| self.infra.get_env_var(name))
+                    .with_context(|| format!("Failed to resolve credentials for {url}"))?;
+                Ok((name, value))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        to_header_map(headers)
+    }
+
+    /// Sends a request and follows its redirects by hand, so that every hop
+    /// only gets the credentials configured for its own host. Authorization
+    /// and cookie headers supplied with the request are dropped once a
+    /// redirect leaves the original origin.
+    async fn send(
+        &self,
+        mut method: Method,
+        url: &Url,
+        mut headers: HeaderMap,
+        mut body: Option<Bytes>,
+        credentials: &FetchCredentials,
+    ) -> anyhow::Result<reqwest::Response> {
+        let max_redirects = self.infra.get_environment().http.max_redirects;
+        let mut url = url.clone();
+        for _ in 0..=max_redirects {
+            // Credentials take precedence over headers supplied with the request
+            let mut request_headers = headers.clone();
+            for (name, value) in &self.credential_headers(credentials, &url)? {
+                request_headers.insert(name.clone(), value.clone());
+            }
+
+            let response = self
+                .infra
+                .http_request(method.clone(), &url, request_headers, body.clone())
+                .await
+                .map_err(|e| anyhow!("Failed to fetch URL {url}: {e}"))?;
+            let status = response.status();
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok());
+            let (true, Some(location)) = (is_redirect(status), location) else {
+                return Ok(response);
+            };
+
+            let next = url
+                .join(location)
+                .with_context(|| format!("Invalid redirect from {url} to {location}"))?;
+            if next.origin() != url.origin() {
+                for name in [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION] {
+                    headers.remove(name);
+                }
+            }
+            if status == StatusCode::SEE_OTHER
+                || (matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND)
+                    && method == Method::POST)
+            {
+                method = Method::GET;
+                body = None;
+                headers.remove(CONTENT_TYPE);
+                headers.remove(CONTENT_LENGTH);
+            }
+            url = next;
+        }
+        Err(anyhow!("Failed to fetch URL {url}: too many redirects"))
+    }
+}
+
 #[async_trait::async_trait]
-impl NetFetchService for ForgeFetch {
-    async fn fetch(&self, url: String, raw: Option<bool>) -> anyhow::Result<HttpResponse> {
-        let url = Url::parse(&url).with_context(|| format!("Failed to parse URL: {url}"))?;
+impl<I: HttpInfra + KVStore + EnvironmentInfra + FileReaderInfra + FileInfoInfra> NetFetchService
+    for ForgeFetch<I>
+{
+    async fn fetch(&self, request: NetFetch) -> anyhow::Result<HttpResponse> {
+        let url = Url::parse(&request.url)
+            .with_context(|| format!("Failed to parse URL: {}", request.url))?;
+
+        self.fetch_url(&url, request).await
+    }
+}
+
+fn to_method(method: HttpMethod) -> Method {
+    match method {
+        HttpMethod::Get => Method::GET,
+        HttpMethod::Post => Method::POST,
+        HttpMethod::Put => Method::PUT,
+        HttpMethod::Patch => Method::PATCH,
+        HttpMethod::Delete => Method::DELETE,
+        HttpMethod::Head => Method::HEAD,
+    }
+}
+
+fn is_redirect(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::MOVED_PERMANENTLY
+            | StatusCode::FOUND
+            | StatusCode::SEE_OTHER
+            | StatusCode::TEMPORARY_REDIRECT
+            | StatusCode::PERMANENT_REDIRECT
+    )
+}
+
+fn header_value(value: &str) -> Option<HeaderValue> {
+    HeaderValue::from_str(value).ok()
+}
+
+/// Converts headers into a map in which names differing only in case are the
+/// same header, the later value replacing the earlier one.
+fn to_header_map(headers: BTreeMap<String, String>) -> anyhow::Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .with_context(|| format!("Invalid header name: {name}"))?;
+        let header_value = HeaderValue::from_str(&value)
+            .with_context(|| format!("Invalid value for header {name}"))?;
+        map.insert(header_name, header_value);
+    }
+    Ok(map)
+}
+
+/// Replaces `${NAME}` references in `value` with the variable's value.
+fn expand_env_vars(value: &str, lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<String> {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + end];
+        let variable =
+            lookup(name).with_context(|| format!("Environment variable {name} is not set"))?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&variable);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake, Faker};
+    use forge_app::domain::{DomainCredential, Environment};
+    use pretty_assertions::assert_eq;
+    use reqwest_eventsource::EventSource;
+
+    use super::*;
+
+    struct MockInfra {
+        client: reqwest::Client,
+    }
+
+    impl EnvironmentInfra for MockInfra {
+        fn get_environment(&self) -> Environment {
+            let mut env: Environment = Faker.fake();
+            env.http.max_redirects = 5;
+            env
+        }
+
+        fn get_env_var(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        fn get_env_vars(&self) -> BTreeMap<String, String> {
+            BTreeMap::new()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HttpInfra for MockInfra {
+        async fn http_get(
+            &self,
+            _: &Url,
+            _: Option<HeaderMap>,
+        ) -> anyhow::Result<reqwest::Response> {
+            unimplemented!()
+        }
+
+        async fn http_post(&self, _: &Url, _: Bytes) -> anyhow::Result<reqwest::Response> {
+            unimplemented!()
+        }
+
+        async fn http_delete(&self, _: &Url) -> anyhow::Result<reqwest::Response> {
+            unimplemented!()
+        }
+
+        async fn http_request(
+            &self,
+            method: Method,
+            url: &Url,
+            headers: HeaderMap,
+            body: Option<Bytes>,
+        ) -> anyhow::Result<reqwest::Response> {
+            let mut request = self.client.request(method, url.clone()).headers(headers);
+            if let Some(body) = body {
+                request = request.body(body);
+            }
+            Ok(request.send().await?)
+        }
+
+        async fn http_eventsource(
+            &self,
+            _: &Url,
+            _: Option<HeaderMap>,
+            _: Bytes,
+        ) -> anyhow::Result<EventSource> {
+            unimplemented!()
+        }
+    }
+
+    fn fetch() -> ForgeFetch<MockInfra> {
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        ForgeFetch { infra: Arc::new(MockInfra { client }) }
+    }
+
+    #[tokio::test]
+    async fn test_send_drops_credentials_on_redirect_to_another_host() {
+        let mut target = mockito::Server::new_async().await;
+        let target_url = format!("http://localhost:{}/page", target.socket_address().port());
+        let target_mock = target
+            .mock("GET", "/page")
+            .match_header("x-api-key", mockito::Matcher::Missing)
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_body("moved")
+            .create_async()
+            .await;
+        let mut origin = mockito::Server::new_async().await;
+        let origin_mock = origin
+            .mock("GET", "/start")
+            .match_header("x-api-key", "secret")
+            .with_status(302)
+            .with_header("location", &target_url)
+            .create_async()
+            .await;
+        let credentials = FetchCredentials {
+            credentials: vec![DomainCredential {
+                domain: "127.0.0.1".to_string(),
+                headers: BTreeMap::from([("X-Api-Key".to_string(), "secret".to_string())]),
+            }],
+        };
+        let headers = to_header_map(BTreeMap::from([(
+            "Authorization".to_string(),
+            "Bearer model".to_string(),
+        )]))
+        .unwrap();
+        let url = Url::parse(&format!("{}/start", origin.url())).unwrap();
+
+        let response = fetch()
+            .send(Method::GET, &url, headers, None, &credentials)
+            .await
+            .unwrap();
+        let actual = response.text().await.unwrap();
+
+        let expected = "moved";
+        assert_eq!(actual, expected);
+        origin_mock.assert_async().await;
+        target_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_fails_after_too_many_redirects() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/loop")
+            .with_status(307)
+            .with_header("location", "/loop")
+            .create_async()
+            .await;
+        let url = Url::parse(&format!("{}/loop", server.url())).unwrap();
+
+        let actual = fetch()
+            .send(
+                Method::GET,
+                &url,
+                HeaderMap::new(),
+                None,
+                &FetchCredentials::default(),
+            )
+            .await
+            .is_err();
+
+        let expected = true;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let lookup = |name: &str| (name == "TOKEN").then(|| "secret".to_string());
+
+        let actual = expand_env_vars("Bearer ${TOKEN}", lookup).unwrap();
+        let expected = "Bearer secret";
+        assert_eq!(actual, expected);
+
+        let actual = expand_env_vars("${MISSING}", lookup);
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_to_header_map_rejects_invalid_names() {
+        let fixture = BTreeMap::from([("Bad Header".to_string(), "value".to_string())]);
+        let actual = to_header_map(fixture);
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_to_header_map_ignores_case_of_names() {
+        let fixture = BTreeMap::from([
+            ("Authorization".to_string(), "Bearer first".to_string()),
+            ("authorization".to_string(), "Bearer second".to_string()),
+        ]);
+
+        let map = to_header_map(fixture).unwrap();
+        let actual = map.get_all(AUTHORIZATION).iter().collect::<Vec<_>>();
 
-        self.fetch_url(&url, raw.unwrap_or(false)).await
+        let expected = vec![HeaderValue::from_static("Bearer second")];
+        assert_eq!(actual, expected);
     }
 }
@@ -201,6 +201,7 @@ impl<W: WalkerInfra + FileInfoInfra> ForgeFsSearch<W> {
 #[cfg(test)]
 mod test {
     use std::sync::Arc;
+    use std::time::SystemTime;
 
     use forge_app::{WalkedFile, Walker};
     use forge_domain::FileInfo;
@@ -272,6 +273,10 @@ mod test {
         async fn file_size(&self, _path: &Path) -> anyhow::Result<u64> {
             unreachable!()
         }
+
+        async fn modified(&self, _path: &Path) -> anyhow::Result<SystemTime> {
+            unreachable!()
+        }
     }
 
     #[async_trait::async_trait]
@@ -1,3 +1,4 @@
+mod ast_search;
 mod fetch;
 mod followup;
 mod fs_create;
@@ -7,10 +8,14 @@ mod fs_remove;
 mod fs_search;
 mod fs_undo;
 mod image_read;
+mod plan;
 mod plan_create;
 mod shell;
 mod skill;
+mod symbols;
+mod web_search;
 
+pub use ast_search::*;
 pub use fetch::*;
 pub use followup::*;
 pub use fs_create::*;
@@ -20,6 +25,9 @@ pub use fs_remove::*;
 pub use fs_search::*;
 pub use fs_undo::*;
 pub use image_read::*;
+pub use plan::*;
 pub use plan_create::*;
 pub use shell::*;
 pub use skill::*;
+pub use symbols::*;
+pub use web_search::*;
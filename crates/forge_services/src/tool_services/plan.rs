@@ -0,0 +1,88 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Context;
+use bytes::Bytes;
+use forge_app::{
+    DirectoryReaderInfra, EnvironmentInfra, FileInfoInfra, FileReaderInfra, FileWriterInfra,
+    PlanService,
+};
+use forge_domain::{PlanFile, StepStatus};
+use tokio::sync::Mutex;
+
+/// Reads the markdown plans created by the `plan` tool and ticks their
+/// checkboxes.
+pub struct ForgePlanService<F> {
+    infra: Arc<F>,
+    // Serializes read-modify-write cycles so that parallel step updates do not
+    // overwrite each other
+    lock: Mutex<()>,
+}
+
+impl<F> ForgePlanService<F> {
+    pub fn new(infra: Arc<F>) -> Self {
+        Self { infra, lock: Mutex::new(()) }
+    }
+}
+
+#[async_trait::async_trait]
+impl<
+    F: FileReaderInfra
+        + FileWriterInfra
+        + FileInfoInfra
+        + DirectoryReaderInfra
+        + EnvironmentInfra
+        + Send
+        + Sync,
+> PlanService for ForgePlanService<F>
+{
+    async fn plan(&self, path: &Path) -> anyhow::Result<PlanFile> {
+        let content = self
+            .infra
+            .read_utf8(path)
+            .await
+            .with_context(|| format!("Failed to read plan file: {}", path.display()))?;
+        Ok(PlanFile::parse(path, &content))
+    }
+
+    async fn plans(&self) -> anyhow::Result<Vec<PlanFile>> {
+        let plans_dir = self.infra.get_environment().cwd.join("plans");
+        if !self.infra.exists(&plans_dir).await? {
+            return Ok(Vec::new());
+        }
+
+        let mut plans = self
+            .infra
+            .read_directory_files(&plans_dir, Some("*.md"))
+            .await?
+            .into_iter()
+            .map(|(path, content)| PlanFile::parse(path, &content))
+            .collect::<Vec<_>>();
+
+        // Plan files are prefixed with their creation date
+        plans.sort_by(|a, b| b.path.cmp(&a.path));
+        Ok(plans)
+    }
+
+    async fn update_plan_step(
+        &self,
+        path: &Path,
+        step: usize,
+        status: StepStatus,
+    ) -> anyhow::Result<PlanFile> {
+        let _guard = self.lock.lock().await;
+
+        let content = self
+            .infra
+            .read_utf8(path)
+            .await
+            .with_context(|| format!("Failed to read plan file: {}", path.display()))?;
+        let updated = PlanFile::update_step(&content, step, status)?;
+        self.infra
+            .write(path, Bytes::from(updated.clone()))
+            .await
+            .with_context(|| format!("Failed to write plan file: {}", path.display()))?;
+
+        Ok(PlanFile::parse(path, &updated))
+    }
+}
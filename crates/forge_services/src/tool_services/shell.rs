@@ -108,6 +108,15 @@ mod tests {
         ) -> anyhow::Result<std::process::ExitStatus> {
             unimplemented!()
         }
+
+        async fn execute_program(
+            &self,
+            _program: &str,
+            _args: Vec<String>,
+            _working_dir: PathBuf,
+        ) -> anyhow::Result<CommandOutput> {
+            unimplemented!()
+        }
     }
 
     impl EnvironmentInfra for MockCommandInfra {
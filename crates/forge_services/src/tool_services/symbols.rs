@@ -0,0 +1,526 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use anyhow::Context;
+use forge_app::domain::{SymbolDefinition, SymbolKind, SymbolQuery, Symbols};
+use forge_app::{
+    AstMatch, FileInfoInfra, FileReaderInfra, SymbolService, SymbolsOutput, Walker, WalkerInfra,
+    compute_hash,
+};
+use tokio::sync::Mutex;
+
+use crate::syntax::{SyntaxLanguage, base_name, node_text};
+use crate::utils::assert_absolute_path;
+
+/// Symbols extracted from a single source file
+struct IndexedFile {
+    modified: SystemTime,
+    hash: String,
+    definitions: Vec<SymbolDefinition>,
+    /// Lines each identifier is used on, excluding the names of definitions
+    references: HashMap<String, Vec<usize>>,
+}
+
+impl IndexedFile {
+    fn new(
+        language: SyntaxLanguage,
+        path: &Path,
+        source: &str,
+        modified: SystemTime,
+    ) -> anyhow::Result<Self> {
+        let tree = language.parse(source)?;
+        let lines = source.lines().collect::<Vec<_>>();
+        let mut definitions = Vec::new();
+        let mut definition_names = HashSet::new();
+        let mut references = HashMap::<String, Vec<usize>>::new();
+
+        // Visits nodes in source order, so definitions come out sorted and
+        // their name nodes are recorded before they are reached
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if let Some(kind) = language.definition_kind(node)
+                && let Some(label) = language.definition_name(node, source)
+            {
+                let name = match kind {
+                    SymbolKind::Impl => node
+                        .child_by_field_name("type")
+                        .and_then(|ty| base_name(ty, source))
+                        .unwrap_or_else(|| label.clone()),
+                    _ => label.clone(),
+                };
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    definition_names.insert(name_node.byte_range());
+                }
+                let row = node.start_position().row;
+                definitions.push(SymbolDefinition {
+                    name,
+                    kind,
+                    qualified_name: match language.enclosing_symbol(node, source) {
+                        Some(container) => format!("{container} > {label}"),
+                        None => label,
+                    },
+                    path: path.to_string_lossy().to_string(),
+                    start_line: row + 1,
+                    end_line: node.end_position().row + 1,
+                    signature: lines
+                        .get(row)
+                        .map(|line| line.trim())
+                        .unwrap_or_default()
+                        .to_string(),
+                    implements: language.implements(node, source),
+                });
+            }
+
+            if node.child_count() == 0
+                && node.kind().ends_with("identifier")
+                && !definition_names.contains(&node.byte_range())
+            {
+                let line = node.start_position().row + 1;
+                let lines = references
+                    .entry(node_text(node, source).to_string())
+                    .or_default();
+                if lines.last() != Some(&line) {
+                    lines.push(line);
+                }
+            }
+
+            let mut cursor = node.walk();
+            let children = node.children(&mut cursor).collect::<Vec<_>>();
+            stack.extend(children.into_iter().rev());
+        }
+
+        Ok(Self {
+            modified,
+            hash: compute_hash(source),
+            definitions,
+            references,
+        })
+    }
+
+    /// Returns the innermost definition containing `line`
+    fn enclosing(&self, line: usize) -> Option<&SymbolDefinition> {
+        self.definitions
+            .iter()
+            .filter(|definition| definition.contains_line(line))
+            .min_by_key(|definition| definition.end_line - definition.start_line)
+    }
+}
+
+/// Answers symbol lookups from an in-memory index of the definitions and
+/// references in source files. Files are parsed on first lookup and
+/// re-read only when their modification time changes.
+pub struct ForgeSymbols<W> {
+    infra: Arc<W>,
+    index: Mutex<HashMap<PathBuf, IndexedFile>>,
+}
+
+impl<W> ForgeSymbols<W> {
+    pub fn new(infra: Arc<W>) -> Self {
+        Self { infra, index: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl<W: WalkerInfra + FileReaderInfra + FileInfoInfra> ForgeSymbols<W> {
+    /// Re-indexes the file at `path` if it was modified and its content
+    /// changed
+    async fn update(
+        &self,
+        index: &mut HashMap<PathBuf, IndexedFile>,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        let Some(language) = SyntaxLanguage::from_path(path) else {
+            return Ok(());
+        };
+        let modified = self.infra.modified(path).await?;
+        if index
+            .get(path)
+            .is_some_and(|file| file.modified == modified)
+        {
+            return Ok(());
+        }
+
+        let source = String::from_utf8_lossy(&self.infra.read(path).await?).to_string();
+        if let Some(file) = index.get_mut(path)
+            && file.hash == compute_hash(&source)
+        {
+            file.modified = modified;
+            return Ok(());
+        }
+
+        let file = IndexedFile::new(language, path, &source, modified)
+            .with_context(|| format!("Failed to index '{}'", path.display()))?;
+        index.insert(path.to_path_buf(), file);
+        Ok(())
+    }
+
+    /// Brings the index up to date with the source files under `dir`
+    async fn refresh(
+        &self,
+        index: &mut HashMap<PathBuf, IndexedFile>,
+        dir: &Path,
+    ) -> anyhow::Result<()> {
+        let files = self
+            .infra
+            .walk(Walker::unlimited().cwd(dir.to_path_buf()))
+            .await
+            .with_context(|| format!("Failed to walk directory '{}'", dir.display()))?
+            .into_iter()
+            .filter(|file| !file.is_dir())
+            .map(|file| dir.join(file.path))
+            .filter(|path| SyntaxLanguage::from_path(path).is_some())
+            .collect::<HashSet<_>>();
+
+        index.retain(|path, _| !path.starts_with(dir) || files.contains(path));
+        for path in files {
+            if self.infra.is_file(&path).await? {
+                self.update(index, &path).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn find_references(
+        &self,
+        index: &HashMap<PathBuf, IndexedFile>,
+        dir: &Path,
+        name: &str,
+    ) -> anyhow::Result<Vec<AstMatch>> {
+        let mut files = index
+            .iter()
+            .filter(|(path, _)| path.starts_with(dir))
+            .filter_map(|(path, file)| Some((path, file, file.references.get(name)?)))
+            .collect::<Vec<_>>();
+        files.sort_by_key(|(path, ..)| *path);
+
+        let mut matches = Vec::new();
+        for (path, file, lines) in files {
+            let source = String::from_utf8_lossy(&self.infra.read(path).await?).to_string();
+            let source_lines = source.lines().collect::<Vec<_>>();
+            matches.extend(lines.iter().map(|&line| {
+                AstMatch {
+                    path: path.to_string_lossy().to_string(),
+                    line_number: line,
+                    symbol: file
+                        .enclosing(line)
+                        .map(|definition| definition.qualified_name.clone()),
+                    line: source_lines
+                        .get(line - 1)
+                        .map(|text| text.trim())
+                        .unwrap_or_default()
+                        .to_string(),
+                }
+            }));
+        }
+        Ok(matches)
+    }
+}
+
+#[async_trait::async_trait]
+impl<W: WalkerInfra + FileReaderInfra + FileInfoInfra> SymbolService for ForgeSymbols<W> {
+    async fn symbols(&self, input: Symbols) -> anyhow::Result<SymbolsOutput> {
+        let mut index = self.index.lock().await;
+
+        if input.query == SymbolQuery::Outline {
+            let path = PathBuf::from(&input.target);
+            assert_absolute_path(&path)?;
+            SyntaxLanguage::from_path(&path).with_context(|| {
+                format!(
+                    "Symbols aren't supported for '{}'. Supported files are Rust, Python, TypeScript, JavaScript and Go",
+                    path.display()
+                )
+            })?;
+            self.update(&mut index, &path).await?;
+            let definitions = index
+                .get(&path)
+                .map(|file| file.definitions.clone())
+                .unwrap_or_default();
+            return Ok(SymbolsOutput::Definitions(definitions));
+        }
+
+        let dir = PathBuf::from(
+            input
+                .path
+                .as_deref()
+                .context("A directory is required for symbol lookups")?,
+        );
+        assert_absolute_path(&dir)?;
+        self.refresh(&mut index, &dir).await?;
+
+        match input.query {
+            SymbolQuery::References => Ok(SymbolsOutput::References(
+                self.find_references(&index, &dir, &input.target).await?,
+            )),
+            _ => {
+                let mut definitions = index
+                    .iter()
+                    .filter(|(path, _)| path.starts_with(&dir))
+                    .flat_map(|(_, file)| &file.definitions)
+                    .filter(|definition| definition.is_named(&input.target))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                // Definitions of the name come before the impls and
+                // subclasses that refer to it
+                definitions.sort_by(|a, b| {
+                    let is_implementation = |definition: &SymbolDefinition| {
+                        definition.name != input.target || definition.kind == SymbolKind::Impl
+                    };
+                    (is_implementation(a), &a.path, a.start_line).cmp(&(
+                        is_implementation(b),
+                        &b.path,
+                        b.start_line,
+                    ))
+                });
+                Ok(SymbolsOutput::Definitions(definitions))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_app::WalkedFile;
+    use forge_domain::FileInfo;
+    use pretty_assertions::assert_eq;
+    use tokio::fs;
+
+    use super::*;
+    use crate::utils::TempDir;
+
+    struct MockInfra;
+
+    #[async_trait::async_trait]
+    impl FileReaderInfra for MockInfra {
+        async fn read_utf8(&self, _path: &Path) -> anyhow::Result<String> {
+            unimplemented!()
+        }
+
+        async fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+            Ok(fs::read(path).await?)
+        }
+
+        async fn range_read_utf8(
+            &self,
+            _path: &Path,
+            _start_line: u64,
+            _end_line: u64,
+        ) -> anyhow::Result<(String, FileInfo)> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FileInfoInfra for MockInfra {
+        async fn is_file(&self, path: &Path) -> anyhow::Result<bool> {
+            Ok(fs::metadata(path).await.is_ok_and(|meta| meta.is_file()))
+        }
+
+        async fn is_binary(&self, _path: &Path) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn exists(&self, _path: &Path) -> anyhow::Result<bool> {
+            unimplemented!()
+        }
+
+        async fn file_size(&self, _path: &Path) -> anyhow::Result<u64> {
+            unimplemented!()
+        }
+
+        async fn modified(&self, path: &Path) -> anyhow::Result<SystemTime> {
+            Ok(fs::metadata(path).await?.modified()?)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl WalkerInfra for MockInfra {
+        async fn walk(&self, config: Walker) -> anyhow::Result<Vec<WalkedFile>> {
+            let mut files = Vec::new();
+            let mut entries = fs::read_dir(&config.cwd).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                files.push(WalkedFile {
+                    path: file_name.clone(),
+                    file_name: Some(file_name),
+                    size: entry.metadata().await?.len(),
+                });
+            }
+            Ok(files)
+        }
+    }
+
+    async fn fixture_directory() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("repository.rs"),
+            "pub trait Repository {\n    fn load(&self) -> Store;\n}\n",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            dir.path().join("store.rs"),
+            "pub struct Store;\n\nimpl Repository for Store {\n    fn load(&self) -> Store {\n        Store\n    }\n}\n",
+        )
+        .await
+        .unwrap();
+        dir
+    }
+
+    /// Overwrites `path` and sets its modification time, since writes
+    /// within the same clock tick can leave it unchanged
+    async fn write_modified(path: PathBuf, content: &str, modified: SystemTime) {
+        fs::write(&path, content).await.unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    fn lookup(dir: &TempDir, query: SymbolQuery, target: &str) -> Symbols {
+        Symbols {
+            query,
+            target: target.to_string(),
+            path: Some(dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn summaries(output: SymbolsOutput) -> Vec<String> {
+        match output {
+            SymbolsOutput::Definitions(definitions) => definitions
+                .iter()
+                .map(|definition| {
+                    let name = Path::new(&definition.path).file_name().unwrap();
+                    format!("{}: {}", name.to_string_lossy(), definition.summary())
+                })
+                .collect(),
+            SymbolsOutput::References(references) => references
+                .iter()
+                .map(|reference| {
+                    let name = Path::new(&reference.path).file_name().unwrap();
+                    format!(
+                        "{}:{}: [{}] {}",
+                        name.to_string_lossy(),
+                        reference.line_number,
+                        reference.symbol.clone().unwrap_or_default(),
+                        reference.line
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_symbols_outline() {
+        let dir = fixture_directory().await;
+        let target = dir.path().join("store.rs").to_string_lossy().to_string();
+        let fixture = lookup(&dir, SymbolQuery::Outline, &target);
+
+        let actual = summaries(
+            ForgeSymbols::new(Arc::new(MockInfra))
+                .symbols(fixture)
+                .await
+                .unwrap(),
+        );
+
+        let expected = vec![
+            "store.rs: [struct Store, lines 1-1] pub struct Store;",
+            "store.rs: [impl impl Repository for Store, lines 3-7] impl Repository for Store {",
+            "store.rs: [method impl Repository for Store > load, lines 4-6] fn load(&self) -> Store {",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_symbols_definition_includes_implementations() {
+        let dir = fixture_directory().await;
+        let fixture = lookup(&dir, SymbolQuery::Definition, "Repository");
+
+        let actual = summaries(
+            ForgeSymbols::new(Arc::new(MockInfra))
+                .symbols(fixture)
+                .await
+                .unwrap(),
+        );
+
+        let expected = vec![
+            "repository.rs: [trait Repository, lines 1-3] pub trait Repository {",
+            "store.rs: [impl impl Repository for Store, lines 3-7] impl Repository for Store {",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_symbols_references() {
+        let dir = fixture_directory().await;
+        let fixture = lookup(&dir, SymbolQuery::References, "Store");
+
+        let actual = summaries(
+            ForgeSymbols::new(Arc::new(MockInfra))
+                .symbols(fixture)
+                .await
+                .unwrap(),
+        );
+
+        let expected = vec![
+            "repository.rs:2: [Repository > load] fn load(&self) -> Store;",
+            "store.rs:3: [impl Repository for Store] impl Repository for Store {",
+            "store.rs:4: [impl Repository for Store > load] fn load(&self) -> Store {",
+            "store.rs:5: [impl Repository for Store > load] Store",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_symbols_reindexes_changed_files() {
+        let dir = fixture_directory().await;
+        let service = ForgeSymbols::new(Arc::new(MockInfra));
+        service
+            .symbols(lookup(&dir, SymbolQuery::Definition, "Repository"))
+            .await
+            .unwrap();
+        write_modified(
+            dir.path().join("repository.rs"),
+            "pub trait Repository {}\n\npub fn open() {}\n",
+            SystemTime::now() + std::time::Duration::from_secs(1),
+        )
+        .await;
+        fs::remove_file(dir.path().join("store.rs")).await.unwrap();
+
+        let actual = summaries(
+            service
+                .symbols(lookup(&dir, SymbolQuery::Definition, "open"))
+                .await
+                .unwrap(),
+        );
+
+        let expected = vec!["repository.rs: [function open, lines 3-3] pub fn open() {}"];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_symbols_skips_files_with_unchanged_modification_time() {
+        let dir = fixture_directory().await;
+        let path = dir.path().join("repository.rs");
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        let service = ForgeSymbols::new(Arc::new(MockInfra));
+        service
+            .symbols(lookup(&dir, SymbolQuery::Definition, "Repository"))
+            .await
+            .unwrap();
+        write_modified(path, "pub fn open() {}\n", modified).await;
+
+        let actual = summaries(
+            service
+                .symbols(lookup(&dir, SymbolQuery::Definition, "open"))
+                .await
+                .unwrap(),
+        );
+
+        let expected: Vec<String> = vec![];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,312 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use bytes::Bytes;
+use forge_app::domain::{WebSearch, WebSearchBackend, WebSearchResult};
+use forge_app::{EnvironmentInfra, HttpInfra, WebSearchService};
+use reqwest::Method;
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+use serde::Deserialize;
+
+/// Maximum number of characters of an error response included in the error
+const MAX_ERROR_BODY_CHARS: usize = 1000;
+
+/// Answers web searches using the backend configured in the environment.
+/// API keys are read from the environment when a search is sent, so they
+/// never enter the conversation.
+pub struct ForgeWebSearch<I> {
+    infra: Arc<I>,
+}
+
+/// Result shape shared by the SearxNG and Tavily APIs
+#[derive(Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<SearchHit>,
+}
+
+#[derive(Deserialize)]
+struct SearchHit {
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct BraveResponse {
+    #[serde(default)]
+    web: Option<BraveWeb>,
+}
+
+#[derive(Deserialize)]
+struct BraveWeb {
+    #[serde(default)]
+    results: Vec<BraveHit>,
+}
+
+#[derive(Deserialize)]
+struct BraveHit {
+    title: String,
+    url: String,
+    #[serde(default)]
+    description: String,
+}
+
+impl<I: HttpInfra + EnvironmentInfra> ForgeWebSearch<I> {
+    pub fn new(infra: Arc<I>) -> Self {
+        Self { infra }
+    }
+
+    /// Builds a header value from the API key stored in the `name` variable,
+    /// marked sensitive so that it is never logged.
+    fn api_key(&self, name: &str, format: impl Fn(&str) -> String) -> anyhow::Result<HeaderValue> {
+        let key = self
+            .infra
+            .get_env_var(name)
+            .with_context(|| format!("Web search requires the {name} environment variable"))?;
+        let mut value = HeaderValue::from_str(&format(&key))
+            .with_context(|| format!("Invalid value for {name}"))?;
+        value.set_sensitive(true);
+        Ok(value)
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: HttpInfra + EnvironmentInfra> WebSearchService for ForgeWebSearch<I> {
+    async fn web_search(&self, request: WebSearch) -> anyhow::Result<Vec<WebSearchResult>> {
+        let backend = self.infra.get_environment().web_search.context(
+            "Web search is not configured. Set FORGE_WEB_SEARCH to searxng, brave, tavily or native to enable it",
+        )?;
+        let max_results = request.max_results();
+        let url = backend.search_url(&request.query, max_results).context(
+            "Native web search is run by the provider and isn't available for this model",
+        )?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        let (method, body) = match backend {
+            WebSearchBackend::Brave => {
+                let token = self.api_key("BRAVE_API_KEY", str::to_string)?;
+                headers.insert("X-Subscription-Token", token);
+                (Method::GET, None)
+            }
+            WebSearchBackend::Tavily => {
+                let token = self.api_key("TAVILY_API_KEY", |key| format!("Bearer {key}"))?;
+                headers.insert(AUTHORIZATION, token);
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                let body =
+                    serde_json::json!({ "query": request.query, "max_results": max_results });
+                (Method::POST, Some(Bytes::from(serde_json::to_vec(&body)?)))
+            }
+            WebSearchBackend::Searxng { .. } | WebSearchBackend::Native => (Method::GET, None),
+        };
+
+        let response = self
+            .infra
+            .http_request(method, &url, headers, body)
+            .await
+            .context("Failed to send web search request")?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .context("Failed to read web search response")?;
+        if !status.is_success() {
+            let body = text.chars().take(MAX_ERROR_BODY_CHARS).collect::<String>();
+            anyhow::bail!("Web search failed with status {status}: {body}");
+        }
+
+        let mut results = parse_results(&backend, &text)?;
+        results.truncate(max_results);
+        Ok(results)
+    }
+}
+
+/// Parses a search response into results, skipping hits without a URL.
+fn parse_results(backend: &WebSearchBackend, body: &str) -> anyhow::Result<Vec<WebSearchResult>> {
+    let results = match backend {
+        WebSearchBackend::Brave => serde_json::from_str::<BraveResponse>(body)
+            .context("Failed to parse Brave search response")?
+            .web
+            .map(|web| web.results)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|hit| WebSearchResult { title: hit.title, url: hit.url, snippet: hit.description })
+            .collect::<Vec<_>>(),
+        _ => serde_json::from_str::<SearchResponse>(body)
+            .context("Failed to parse web search response")?
+            .results
+            .into_iter()
+            .map(|hit| WebSearchResult { title: hit.title, url: hit.url, snippet: hit.content })
+            .collect(),
+    };
+
+    Ok(results
+        .into_iter()
+        .filter(|result| !result.url.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use fake::{Fake, Faker};
+    use forge_app::domain::Environment;
+    use pretty_assertions::assert_eq;
+    use reqwest::Response;
+    use reqwest_eventsource::EventSource;
+    use url::Url;
+
+    use super::*;
+
+    struct MockInfra {
+        backend: Option<WebSearchBackend>,
+        client: reqwest::Client,
+    }
+
+    impl EnvironmentInfra for MockInfra {
+        fn get_environment(&self) -> Environment {
+            let mut env: Environment = Faker.fake();
+            env.web_search = self.backend.clone();
+            env
+        }
+
+        fn get_env_var(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        fn get_env_vars(&self) -> BTreeMap<String, String> {
+            BTreeMap::new()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HttpInfra for MockInfra {
+        async fn http_get(&self, _: &Url, _: Option<HeaderMap>) -> anyhow::Result<Response> {
+            unimplemented!()
+        }
+
+        async fn http_post(&self, _: &Url, _: Bytes) -> anyhow::Result<Response> {
+            unimplemented!()
+        }
+
+        async fn http_delete(&self, _: &Url) -> anyhow::Result<Response> {
+            unimplemented!()
+        }
+
+        async fn http_request(
+            &self,
+            method: Method,
+            url: &Url,
+            headers: HeaderMap,
+            body: Option<Bytes>,
+        ) -> anyhow::Result<Response> {
+            let mut request = self.client.request(method, url.clone()).headers(headers);
+            if let Some(body) = body {
+                request = request.body(body);
+            }
+            Ok(request.send().await?)
+        }
+
+        async fn http_eventsource(
+            &self,
+            _: &Url,
+            _: Option<HeaderMap>,
+            _: Bytes,
+        ) -> anyhow::Result<EventSource> {
+            unimplemented!()
+        }
+    }
+
+    fn search(backend: Option<WebSearchBackend>) -> ForgeWebSearch<MockInfra> {
+        ForgeWebSearch::new(Arc::new(MockInfra {
+            backend,
+            client: reqwest::Client::new(),
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_web_search_searxng() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/search")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("q".into(), "rust async".into()),
+                mockito::Matcher::UrlEncoded("format".into(), "json".into()),
+            ]))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"query": "rust async", "results": [
+                    {"title": "Async Book", "url": "https://rust-lang.github.io/async-book/", "content": "Asynchronous programming in Rust"},
+                    {"title": "Tokio", "url": "https://tokio.rs/", "content": "An asynchronous runtime"},
+                    {"title": "Untitled", "url": ""}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+        let backend = WebSearchBackend::Searxng { url: Url::parse(&server.url()).unwrap() };
+        let fixture = WebSearch { query: "rust async".to_string(), max_results: Some(5) };
+
+        let actual = search(Some(backend)).web_search(fixture).await.unwrap();
+
+        let expected = vec![
+            WebSearchResult {
+                title: "Async Book".to_string(),
+                url: "https://rust-lang.github.io/async-book/".to_string(),
+                snippet: "Asynchronous programming in Rust".to_string(),
+            },
+            WebSearchResult {
+                title: "Tokio".to_string(),
+                url: "https://tokio.rs/".to_string(),
+                snippet: "An asynchronous runtime".to_string(),
+            },
+        ];
+        assert_eq!(actual, expected);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_web_search_limits_results() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/search")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                r#"{"results": [
+                    {"title": "One", "url": "https://one.example", "content": ""},
+                    {"title": "Two", "url": "https://two.example", "content": ""}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+        let backend = WebSearchBackend::Searxng { url: Url::parse(&server.url()).unwrap() };
+        let fixture = WebSearch { query: "example".to_string(), max_results: Some(1) };
+
+        let actual = search(Some(backend)).web_search(fixture).await.unwrap();
+
+        assert_eq!(actual.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_web_search_unconfigured_fails() {
+        let fixture = WebSearch { query: "rust".to_string(), max_results: None };
+        let actual = search(None).web_search(fixture).await;
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_parse_results_brave() {
+        let fixture = r#"{"web": {"results": [{"title": "Rust", "url": "https://www.rust-lang.org/", "description": "A language empowering everyone"}]}}"#;
+
+        let actual = parse_results(&WebSearchBackend::Brave, fixture).unwrap();
+
+        let expected = vec![WebSearchResult {
+            title: "Rust".to_string(),
+            url: "https://www.rust-lang.org/".to_string(),
+            snippet: "A language empowering everyone".to_string(),
+        }];
+        assert_eq!(actual, expected);
+    }
+}
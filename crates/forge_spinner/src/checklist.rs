@@ -0,0 +1,75 @@
+use colored::Colorize;
+
+/// Status of an item of the checklist drawn above the spinner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecklistStatus {
+    Pending,
+    Active,
+    Done,
+}
+
+/// An item of the checklist drawn above the spinner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub status: ChecklistStatus,
+}
+
+impl ChecklistItem {
+    pub fn new(text: impl ToString, status: ChecklistStatus) -> Self {
+        Self { text: text.to_string(), status }
+    }
+}
+
+/// Renders one line per item. Text that would wrap past `width` columns is
+/// cut, so that the spinner can move the cursor back over exactly one line
+/// per item when redrawing.
+pub fn render_checklist(items: &[ChecklistItem], width: usize) -> Vec<String> {
+    // Indent, marker and the space after it
+    let max_chars = width.saturating_sub(5).max(1);
+    items
+        .iter()
+        .map(|item| {
+            let text = item.text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let text = if text.chars().count() > max_chars {
+                let cut = text
+                    .chars()
+                    .take(max_chars.saturating_sub(1))
+                    .collect::<String>();
+                format!("{}…", cut.trim_end())
+            } else {
+                text
+            };
+            match item.status {
+                ChecklistStatus::Pending => format!("  {} {}", "○".dimmed(), text),
+                ChecklistStatus::Active => format!("  {} {}", "◐".yellow(), text.bold()),
+                ChecklistStatus::Done => format!("  {} {}", "✓".green(), text.dimmed()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_render_checklist_cuts_long_items() {
+        colored::control::set_override(false);
+        let fixture = vec![
+            ChecklistItem::new("Add the parser", ChecklistStatus::Done),
+            ChecklistItem::new("Wire the tool\ninto the executor", ChecklistStatus::Active),
+            ChecklistItem::new("Document it", ChecklistStatus::Pending),
+        ];
+
+        let actual = render_checklist(&fixture, 20);
+        let expected = vec![
+            "  ✓ Add the parser".to_string(),
+            "  ◐ Wire the tool…".to_string(),
+            "  ○ Document it".to_string(),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,5 +1,6 @@
 use std::io::{self, Write};
 use std::sync::mpsc;
+use std::thread::JoinHandle;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -7,7 +8,6 @@ use crossterm::event::{self, Event, KeyCode, KeyModifiers};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use rand::seq::IndexedRandom;
 use tokio::sync::broadcast;
-use tokio::task::JoinHandle;
 
 /// Render the spinner line consistently with styling and flush.
 fn render_spinner_line(frame: &str, status: &str, seconds: u64) {
@@ -20,16 +20,44 @@ fn render_spinner_line(frame: &str, status: &str, seconds: u64) {
     let _ = io::stdout().flush();
 }
 
+/// Clears the spinner line and the `drawn` checklist lines above it, leaving
+/// the cursor where the checklist started.
+fn clear_checklist(drawn: usize) {
+    eprint!("\r\x1b[2K");
+    if drawn > 0 {
+        eprint!("\x1b[{drawn}A\x1b[J");
+    }
+}
+
+/// Draws the checklist and returns the number of lines it takes, leaving the
+/// cursor at the start of the line below it.
+fn draw_checklist(items: &[ChecklistItem]) -> usize {
+    if items.is_empty() {
+        return 0;
+    }
+    let width = crossterm::terminal::size()
+        .map(|(columns, _)| columns as usize)
+        .unwrap_or(80);
+    let lines = render_checklist(items, width);
+    for line in &lines {
+        eprint!("\r\x1b[2K{line}\r\n");
+    }
+    lines.len()
+}
+
 /// Commands for the spinner background thread
 enum Cmd {
     Start(String),
     Write(String),
+    Checklist(Vec<ChecklistItem>),
     Hide,
     Show,
     Stop(mpsc::Sender<()>),
 }
 
+mod checklist;
 mod progress_bar;
+pub use checklist::{ChecklistItem, ChecklistStatus, render_checklist};
 pub use progress_bar::*;
 
 /// Manages spinner functionality for the UI
@@ -52,7 +80,9 @@ impl SpinnerManager {
         let (tx, rx) = mpsc::channel::<Cmd>();
         let (ctrl_c_tx, ctrl_c_rx) = broadcast::channel(1);
 
-        let handle = tokio::spawn(async move {
+        // The loop blocks on the command channel, so it runs on its own thread
+        // instead of occupying a runtime worker
+        let handle = std::thread::spawn(move || {
             let spinner_frames: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
             let mut idx: usize = 0;
             let tick = Duration::from_millis(60);
@@ -61,6 +91,10 @@ impl SpinnerManager {
             let mut status_text = String::new();
             let mut active = false;
             let mut hidden = false;
+            // The checklist is kept across runs and drawn above the spinner
+            // while it is visible
+            let mut checklist: Vec<ChecklistItem> = Vec::new();
+            let mut drawn: usize = 0;
 
             loop {
                 let cmd = if active && !hidden {
@@ -82,13 +116,16 @@ impl SpinnerManager {
                         let _ = enable_raw_mode();
                         // Hide cursor and draw initial spinner line
                         eprintln!("\x1b[?25l");
+                        drawn = draw_checklist(&checklist);
                         render_spinner_line(spinner_frames[idx], &status_text, 0);
                     }
                     Ok(Cmd::Write(s)) => {
                         if active {
-                            eprint!("\r\x1b[2K");
+                            clear_checklist(drawn);
+                            drawn = 0;
                             println!("{}", s);
                             if !hidden {
+                                drawn = draw_checklist(&checklist);
                                 let elapsed = start_time.elapsed().as_secs();
                                 render_spinner_line(spinner_frames[idx], &status_text, elapsed);
                             } else {
@@ -98,9 +135,19 @@ impl SpinnerManager {
                             println!("{}", s);
                         }
                     }
+                    Ok(Cmd::Checklist(items)) => {
+                        checklist = items;
+                        if active && !hidden {
+                            clear_checklist(drawn);
+                            drawn = draw_checklist(&checklist);
+                            let elapsed = start_time.elapsed().as_secs();
+                            render_spinner_line(spinner_frames[idx], &status_text, elapsed);
+                        }
+                    }
                     Ok(Cmd::Hide) => {
                         if active && !hidden {
-                            eprint!("\r\x1b[2K");
+                            clear_checklist(drawn);
+                            drawn = 0;
                             eprint!("\x1b[?25h");
                             let _ = io::stdout().flush();
                             let _ = disable_raw_mode();
@@ -111,6 +158,7 @@ impl SpinnerManager {
                         if active && hidden {
                             let _ = enable_raw_mode();
                             eprint!("\n\n\x1b[?25l");
+                            drawn = draw_checklist(&checklist);
                             let elapsed = start_time.elapsed().as_secs();
                             render_spinner_line(spinner_frames[idx], &status_text, elapsed);
                             hidden = false;
@@ -118,7 +166,8 @@ impl SpinnerManager {
                     }
                     Ok(Cmd::Stop(tx)) => {
                         if active {
-                            eprint!("\r\x1b[2K");
+                            clear_checklist(drawn);
+                            drawn = 0;
                             eprint!("\x1b[?25h");
                             let _ = io::stdout().flush();
                             let _ = disable_raw_mode();
@@ -228,6 +277,15 @@ impl SpinnerManager {
         Ok(())
     }
 
+    /// Replaces the checklist drawn above the spinner. An empty list removes
+    /// it.
+    pub fn set_checklist(&mut self, items: Vec<ChecklistItem>) -> Result<()> {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(Cmd::Checklist(items));
+        }
+        Ok(())
+    }
+
     pub fn ewrite_ln(&mut self, message: impl ToString) -> Result<()> {
         self.hide()?;
         eprintln!("{}", message.to_string());